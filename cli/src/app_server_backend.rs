@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::path::Path;
 use std::path::PathBuf;
 use std::process::Stdio;
@@ -20,7 +21,10 @@ use crate::app_server_protocol::JSONRPCError;
 use crate::app_server_protocol::JSONRPCErrorError;
 use crate::app_server_protocol::JSONRPCMessage;
 use crate::app_server_protocol::JSONRPCResponse;
+use crate::app_server_protocol::McpServerElicitationAction;
+use crate::app_server_protocol::McpServerElicitationRequestResponse;
 use crate::app_server_protocol::RequestId;
+use crate::app_server_protocol::SandboxPolicy;
 use crate::app_server_protocol::ServerRequest;
 use crate::app_server_protocol::ThreadResumeParams;
 use crate::app_server_protocol::ThreadResumeResponse;
@@ -28,22 +32,45 @@ use crate::app_server_protocol::ThreadRollbackParams;
 use crate::app_server_protocol::ThreadRollbackResponse;
 use crate::app_server_protocol::ThreadStartParams;
 use crate::app_server_protocol::ThreadStartResponse;
+use crate::app_server_protocol::ToolRequestUserInputParams;
+use crate::app_server_protocol::ToolRequestUserInputResponse;
+use crate::app_server_protocol::TurnInterruptParams;
+use crate::app_server_protocol::TurnInterruptResponse;
 use crate::app_server_protocol::TurnStartParams;
 use crate::app_server_protocol::TurnStartResponse;
 use crate::app_server_protocol::UserInput as ApiUserInput;
+use crate::approval_policy::ApprovalPolicy;
+use crate::approval_policy::ApprovalSubject;
+use crate::approval_policy::ApprovalVerdict;
+use crate::codex_overrides::CodexOverrides;
+use crate::command_guard::BlockedCommand;
+use crate::command_guard::CommandGuard;
 use crate::potter_stream_recovery::ContinueRetryDecision;
 use crate::potter_stream_recovery::ContinueRetryPlan;
 use crate::potter_stream_recovery::PotterStreamRecovery;
+use crate::potter_stream_recovery::RestartDecision;
+use crate::sandbox_policy::WorkspaceWriteSettings;
+use crate::user_input_policy::UserInputPolicy;
 use anyhow::Context;
 use codex_protocol::ThreadId;
 use codex_protocol::openai_models::ReasoningEffort;
+use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterApprovalDecidedBy;
+use codex_protocol::protocol::PotterApprovalDecision;
+use codex_protocol::protocol::PotterElicitationDecision;
+use codex_protocol::protocol::PotterPromptAnswer;
+use codex_protocol::protocol::PotterPromptAnsweredBy;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::PotterRoundTimeout;
 use codex_protocol::protocol::ReviewDecision;
 use codex_protocol::protocol::SessionConfiguredEvent;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputResponse;
 use codex_protocol::user_input::UserInput as CodexUserInput;
 use tokio::io::AsyncBufReadExt;
 use tokio::io::AsyncReadExt;
@@ -60,7 +87,113 @@ use tokio::sync::mpsc::unbounded_channel;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum RecoveryAction {
-    RetryContinue { attempt: u32 },
+    RetryContinue {
+        attempt: u32,
+    },
+    /// Respawn the app-server, which died mid-turn, and continue the thread on the new process.
+    RestartAppServer,
+    /// Interrupt the running turn, which started a command the guard blocks.
+    InterruptBlockedTurn,
+    /// Interrupt the running turn, whose context window crossed the `--context-checkpoint`
+    /// threshold.
+    InterruptForContextCheckpoint,
+    /// Ask the agent to save its progress, after the turn was interrupted for a context checkpoint.
+    StartContextCheckpointTurn,
+}
+
+/// Progress of a user interrupt (`Op::Interrupt`) of the running turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TurnInterrupt {
+    None,
+    /// `turn/interrupt` was sent; waiting for the turn to abort.
+    Requested,
+    /// The turn was aborted; the round stays open until the UI sends a new instruction
+    /// (`Op::UserInput`) or finishes the round (`Op::PotterFinishRound`).
+    AwaitingDecision,
+}
+
+/// Which kind of server request an approval answers, which decides the response shape.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ApprovalRequestKind {
+    CommandExecution,
+    FileChange,
+    ApplyPatch,
+    ExecCommand,
+}
+
+/// An approval request waiting for the user (`Op::PotterApprovalResponse`).
+struct PendingApproval {
+    request_id: RequestId,
+    kind: ApprovalRequestKind,
+    subject: ApprovalSubject,
+}
+
+/// Approval requests of this app-server connection.
+#[derive(Default)]
+struct ApprovalContext {
+    policy: ApprovalPolicy,
+    /// Subjects announced by `ExecApprovalRequest`/`ApplyPatchApprovalRequest` events, by call
+    /// id, for requests whose params do not carry them.
+    subjects_by_call_id: HashMap<String, ApprovalSubject>,
+    /// Requests waiting for the user, by the id sent in `PotterApprovalRequest`.
+    pending: HashMap<String, PendingApproval>,
+    next_pending_id: u64,
+}
+
+/// A `request_user_input` call waiting for the user (`Op::PotterUserInputResponse`).
+struct PendingUserInput {
+    request_id: RequestId,
+    questions: Vec<RequestUserInputQuestion>,
+}
+
+/// An MCP server elicitation waiting for the user (`Op::PotterElicitationResponse`).
+struct PendingElicitation {
+    request_id: RequestId,
+    server_name: String,
+    message: String,
+}
+
+/// Question prompts and elicitations of this app-server connection.
+#[derive(Default)]
+struct PromptContext {
+    policy: UserInputPolicy,
+    /// Prompts waiting for the user, by the id sent in `PotterUserInputRequest`.
+    pending_user_input: HashMap<String, PendingUserInput>,
+    /// Elicitations waiting for the user, by the id sent in `PotterElicitationRequest`.
+    pending_elicitations: HashMap<String, PendingElicitation>,
+    next_pending_id: u64,
+}
+
+/// Command guard state of this app-server connection.
+#[derive(Default)]
+struct CommandGuardContext {
+    guard: CommandGuard,
+    /// Directory the agent works in; `rm -rf` must stay inside it.
+    workdir: PathBuf,
+    /// The blocked command of the running turn, until the turn ends and the round finishes.
+    blocked: Option<BlockedCommand>,
+}
+
+/// Progress of a context checkpoint (`--context-checkpoint`) in this round.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+enum ContextCheckpointState {
+    #[default]
+    None,
+    /// The threshold was crossed and `turn/interrupt` is on its way.
+    Interrupting,
+    /// The checkpoint turn runs; the round finishes as `ContextExhausted` when it ends.
+    Checkpointing,
+}
+
+/// Context window and token usage of the running thread.
+#[derive(Default)]
+struct ContextWindowContext {
+    checkpoint: Option<ContextCheckpoint>,
+    /// Size of the model's context window, from `TurnStarted` or `TokenCount`.
+    window: Option<i64>,
+    /// Tokens the thread used so far, from `TokenCount`.
+    total_tokens: i64,
+    state: ContextCheckpointState,
 }
 
 struct StreamRecoveryContext {
@@ -70,6 +203,66 @@ struct StreamRecoveryContext {
     has_sent_turn_start: bool,
     has_finished_round: bool,
     last_turn_start_was_recovery_continue: bool,
+    /// Turn id from the latest `TurnStarted` event, cleared when the turn ends.
+    active_turn_id: Option<String>,
+    turn_interrupt: TurnInterrupt,
+    /// The user continued this round with a new instruction after an interrupt.
+    continued_after_interrupt: bool,
+    approvals: ApprovalContext,
+    prompts: PromptContext,
+    command_guard: CommandGuardContext,
+    turn_settings: TurnSettings,
+    context_window: ContextWindowContext,
+    thread_filter: ThreadFilter,
+}
+
+impl StreamRecoveryContext {
+    /// Whether a running turn is up to the app-server, so silence means it hangs rather than
+    /// waits for the user.
+    fn is_waiting_on_app_server(&self) -> bool {
+        self.active_turn_id.is_some()
+            && !self.has_finished_round
+            && self.approvals.pending.is_empty()
+            && self.prompts.pending_user_input.is_empty()
+            && self.prompts.pending_elicitations.is_empty()
+    }
+}
+
+/// Which thread's `codex/event/*` notifications this round forwards.
+#[derive(Default)]
+struct ThreadFilter {
+    /// The process served earlier rounds (`--reuse-app-server`), whose threads may still send
+    /// late events.
+    reused_process: bool,
+    /// The thread started or resumed for this round, once the app-server answered.
+    thread_id: Option<String>,
+}
+
+impl ThreadFilter {
+    /// Whether a notification names (`conversationId`) a thread other than this round's. Only a
+    /// reused process serves more than one thread, so nothing is filtered otherwise.
+    fn is_other_thread(&self, params: &serde_json::Value) -> bool {
+        if !self.reused_process {
+            return false;
+        }
+        let Some(event_thread_id) = params
+            .get("conversationId")
+            .and_then(serde_json::Value::as_str)
+        else {
+            return false;
+        };
+        self.thread_id.as_deref() != Some(event_thread_id)
+    }
+}
+
+/// Settings sent with every `turn/start` of this app-server connection.
+#[derive(Default)]
+struct TurnSettings {
+    /// Reasoning effort (`--effort`).
+    effort: Option<ReasoningEffort>,
+    /// `workspace-write` refinements (`--writable-root`, `--network`, `[sandbox]`) merged into the
+    /// thread's sandbox, once the thread is started.
+    sandbox_policy: Option<SandboxPolicy>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -96,9 +289,34 @@ impl AppServerLaunchConfig {
             bypass_approvals_and_sandbox: false,
         }
     }
+
+    /// Use `mode` when the user did not pick a sandbox (`--sandbox default`) or bypass it.
+    pub fn with_default_sandbox(
+        self,
+        mode: Option<crate::app_server_protocol::SandboxMode>,
+    ) -> Self {
+        if self.bypass_approvals_and_sandbox || self.spawn_sandbox.is_some() {
+            return self;
+        }
+        Self {
+            spawn_sandbox: mode,
+            thread_sandbox: mode,
+            ..self
+        }
+    }
+
+    /// Read-only launch for planning rounds (`--approve-plan`), regardless of `--sandbox` or
+    /// `--yolo`.
+    pub fn read_only() -> Self {
+        Self {
+            spawn_sandbox: Some(crate::app_server_protocol::SandboxMode::ReadOnly),
+            thread_sandbox: Some(crate::app_server_protocol::SandboxMode::ReadOnly),
+            bypass_approvals_and_sandbox: false,
+        }
+    }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct AppServerBackendConfig {
     pub codex_bin: String,
     pub developer_instructions: Option<String>,
@@ -106,6 +324,355 @@ pub struct AppServerBackendConfig {
     pub codex_home: Option<PathBuf>,
     pub thread_cwd: Option<PathBuf>,
     pub resume_thread_id: Option<ThreadId>,
+    /// Decides the approval requests of the thread (`[approval]` in `config.toml`).
+    pub approval_policy: ApprovalPolicy,
+    /// Answers the agent's questions and MCP elicitations (`[user_input]`).
+    pub user_input_policy: UserInputPolicy,
+    /// Commands that interrupt the turn when the agent runs them (`[command_guard]`).
+    pub command_guard: CommandGuard,
+    /// Model, profile, effort and `-c` overrides for the thread and its turns.
+    pub codex_overrides: CodexOverrides,
+    /// Refinements of the thread's `workspace-write` sandbox sent with every turn
+    /// (`--writable-root`, `--network`, `[sandbox]`).
+    pub workspace_write: WorkspaceWriteSettings,
+    /// Process kept alive across the rounds of a session (`--reuse-app-server`); `None` spawns a
+    /// process for this round only.
+    pub shared_app_server: Option<SharedAppServer>,
+    /// Save progress before the context window overflows (`--context-checkpoint`).
+    pub context_checkpoint: Option<ContextCheckpoint>,
+    /// Watchdog limits that kill the app-server and finish the round as `TimedOut`.
+    pub timeouts: RoundTimeouts,
+}
+
+/// Watchdog limits of a round (`--idle-timeout`, `--round-timeout`, and what is left of
+/// `--max-duration` / `--max-tokens`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RoundTimeouts {
+    /// Longest silence of the app-server while a turn runs and no one is asked anything.
+    pub idle: Option<std::time::Duration>,
+    /// Longest wall-clock time of the round, whatever it waits for.
+    pub round: Option<std::time::Duration>,
+    /// Wall-clock time left of the session budget when the round starts.
+    pub budget_duration: Option<std::time::Duration>,
+    /// Tokens the round's thread may use before the session budget runs out.
+    pub budget_tokens: Option<u64>,
+}
+
+impl RoundTimeouts {
+    /// The earliest limit to trip, and when, for a round started at `round_started_at` whose
+    /// app-server was last heard from at `last_activity` and whose thread used `thread_tokens`.
+    fn next_timeout(
+        &self,
+        round_started_at: tokio::time::Instant,
+        last_activity: tokio::time::Instant,
+        waiting_on_app_server: bool,
+        thread_tokens: u64,
+    ) -> Option<(tokio::time::Instant, PotterRoundTimeout)> {
+        // A spent token budget trips right away.
+        if self
+            .budget_tokens
+            .is_some_and(|limit| thread_tokens >= limit)
+        {
+            return Some((round_started_at, PotterRoundTimeout::Budget));
+        }
+        let idle = self.idle.filter(|_| waiting_on_app_server).map(|limit| {
+            (
+                last_activity + limit,
+                PotterRoundTimeout::Idle {
+                    limit_secs: limit.as_secs(),
+                },
+            )
+        });
+        let round = self.round.map(|limit| {
+            (
+                round_started_at + limit,
+                PotterRoundTimeout::Round {
+                    limit_secs: limit.as_secs(),
+                },
+            )
+        });
+        let budget = self
+            .budget_duration
+            .map(|left| (round_started_at + left, PotterRoundTimeout::Budget));
+        [idle, round, budget]
+            .into_iter()
+            .flatten()
+            .min_by_key(|(deadline, _)| *deadline)
+    }
+}
+
+/// When and how to checkpoint a round whose context window is nearly full.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContextCheckpoint {
+    /// Interrupt the turn once at most this percentage of the context window is left.
+    pub percent_left: i64,
+    /// Follow-up prompt asking the agent to record its progress in the progress file.
+    pub prompt: String,
+}
+
+/// A `codex app-server` process shared by the rounds of one session (`--reuse-app-server`).
+///
+/// Each round still starts its own thread, so rounds keep a fresh context; only process startup
+/// (initialize, MCP servers, skills) is saved. An idle process that exited or closed its stdout
+/// between rounds is replaced by a fresh one.
+#[derive(Clone, Default)]
+pub struct SharedAppServer(Arc<tokio::sync::Mutex<Option<AppServerProcess>>>);
+
+impl std::fmt::Debug for SharedAppServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("SharedAppServer").finish_non_exhaustive()
+    }
+}
+
+impl SharedAppServer {
+    /// Take the idle process if it runs with `launch` and `codex_home` and is still usable.
+    async fn take(
+        &self,
+        launch: AppServerLaunchConfig,
+        codex_home: Option<&Path>,
+    ) -> Option<AppServerProcess> {
+        let mut process = self.0.lock().await.take()?;
+        // A process launched differently (e.g. the read-only planning round) is shut down.
+        if process.launch != launch || process.codex_home.as_deref() != codex_home {
+            return None;
+        }
+        if !process.is_idle_and_alive().await {
+            return None;
+        }
+        process.stderr.clear();
+        Some(process)
+    }
+
+    async fn put(&self, process: AppServerProcess) {
+        *self.0.lock().await = Some(process);
+    }
+}
+
+/// A spawned and initialized `codex app-server` connection.
+struct AppServerProcess {
+    /// Always `Some` until dropped.
+    child: Option<Child>,
+    /// `None` once closed to ask the app-server to exit.
+    stdin: Option<ChildStdin>,
+    lines: tokio::io::Lines<BufReader<ChildStdout>>,
+    next_id: i64,
+    launch: AppServerLaunchConfig,
+    codex_home: Option<PathBuf>,
+    stderr: StderrCapture,
+}
+
+impl AppServerProcess {
+    async fn spawn(
+        codex_bin: &str,
+        launch: AppServerLaunchConfig,
+        codex_home: Option<PathBuf>,
+    ) -> anyhow::Result<Self> {
+        let (child, stdin, stdout, stderr) =
+            spawn_app_server(codex_bin, launch, codex_home.as_deref()).await?;
+        Ok(Self {
+            child: Some(child),
+            stdin: Some(stdin),
+            lines: BufReader::new(stdout).lines(),
+            next_id: 1,
+            launch,
+            codex_home,
+            stderr: StderrCapture::spawn(stderr),
+        })
+    }
+
+    /// Whether the process still runs with its stdout open. Output that arrived between rounds
+    /// (late notifications of the previous round's thread) is discarded.
+    async fn is_idle_and_alive(&mut self) -> bool {
+        let Some(child) = self.child.as_mut() else {
+            return false;
+        };
+        if !matches!(child.try_wait(), Ok(None)) {
+            return false;
+        }
+        loop {
+            match tokio::time::timeout(std::time::Duration::ZERO, self.lines.next_line()).await {
+                Ok(Ok(Some(_))) => continue,
+                Ok(Ok(None) | Err(_)) => return false,
+                Err(_) => return true,
+            }
+        }
+    }
+
+    /// Describe how the process went away after its stdout closed: exit status and the last
+    /// lines of stderr.
+    async fn exit_message(&mut self) -> String {
+        const WAIT_FOR_EXIT: std::time::Duration = std::time::Duration::from_secs(2);
+        let status = match self.child.as_mut() {
+            Some(child) => match tokio::time::timeout(WAIT_FOR_EXIT, child.wait()).await {
+                Ok(Ok(status)) => status.to_string(),
+                Ok(Err(err)) => format!("wait failed: {err}"),
+                Err(_) => {
+                    let _ = child.kill().await;
+                    "stdout closed".to_string()
+                }
+            },
+            None => "stdout closed".to_string(),
+        };
+        // Let the stderr drain catch up with what the process wrote before exiting.
+        let _ = tokio::time::timeout(WAIT_FOR_EXIT, &mut self.stderr.task).await;
+
+        let mut message = format!("codex app-server exited unexpectedly ({status})");
+        for line in self.stderr.last_lines(5) {
+            message.push('\n');
+            message.push_str(&line);
+        }
+        message
+    }
+}
+
+impl Drop for AppServerProcess {
+    /// Close stdin so the app-server exits on its own (shutting down its MCP servers), and kill
+    /// it if it is still running a few seconds later.
+    fn drop(&mut self) {
+        self.stdin.take();
+        let Some(mut child) = self.child.take() else {
+            return;
+        };
+        if !matches!(child.try_wait(), Ok(None)) {
+            return;
+        }
+        if let Ok(runtime) = tokio::runtime::Handle::try_current() {
+            runtime.spawn(async move {
+                let exited =
+                    tokio::time::timeout(std::time::Duration::from_secs(5), child.wait()).await;
+                if exited.is_err() {
+                    let _ = child.kill().await;
+                }
+            });
+        }
+    }
+}
+
+/// The first 32 KiB of the app-server's stderr, added to error messages, and its last 4 KiB,
+/// reported when the process dies.
+struct StderrCapture {
+    bytes: Arc<Mutex<Vec<u8>>>,
+    truncated: Arc<AtomicBool>,
+    tail: Arc<Mutex<Vec<u8>>>,
+    task: tokio::task::JoinHandle<std::io::Result<()>>,
+}
+
+impl StderrCapture {
+    fn spawn(stderr: ChildStderr) -> Self {
+        let bytes = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let truncated = Arc::new(AtomicBool::new(false));
+        let tail = Arc::new(Mutex::new(Vec::<u8>::new()));
+        let task = {
+            let bytes = bytes.clone();
+            let truncated = truncated.clone();
+            let tail = tail.clone();
+            tokio::spawn(async move {
+                const LIMIT_BYTES: usize = 32 * 1024;
+                const TAIL_BYTES: usize = 4 * 1024;
+                let mut stderr = stderr;
+                let mut buf = [0u8; 4096];
+
+                loop {
+                    let n = stderr.read(&mut buf).await?;
+                    if n == 0 {
+                        break;
+                    }
+
+                    {
+                        let mut tail = match tail.lock() {
+                            Ok(guard) => guard,
+                            Err(err) => err.into_inner(),
+                        };
+                        tail.extend_from_slice(&buf[..n]);
+                        let excess = tail.len().saturating_sub(TAIL_BYTES);
+                        tail.drain(..excess);
+                    }
+
+                    let mut capture = match bytes.lock() {
+                        Ok(guard) => guard,
+                        Err(err) => err.into_inner(),
+                    };
+                    let remaining = LIMIT_BYTES.saturating_sub(capture.len());
+                    if remaining == 0 {
+                        truncated.store(true, Ordering::Relaxed);
+                        continue;
+                    }
+
+                    let take = remaining.min(n);
+                    capture.extend_from_slice(&buf[..take]);
+                    if take < n {
+                        truncated.store(true, Ordering::Relaxed);
+                    }
+                }
+
+                Ok::<(), std::io::Error>(())
+            })
+        };
+        Self {
+            bytes,
+            truncated,
+            tail,
+            task,
+        }
+    }
+
+    /// The last `count` non-empty lines written to stderr.
+    fn last_lines(&self, count: usize) -> Vec<String> {
+        let tail = match self.tail.lock() {
+            Ok(guard) => guard,
+            Err(err) => err.into_inner(),
+        };
+        let tail = String::from_utf8_lossy(&tail);
+        let lines = tail
+            .lines()
+            .map(str::trim_end)
+            .filter(|line| !line.is_empty())
+            .collect::<Vec<_>>();
+        lines[lines.len().saturating_sub(count)..]
+            .iter()
+            .map(|line| line.to_string())
+            .collect()
+    }
+
+    /// Forget what earlier rounds wrote, so errors show the current round's stderr.
+    fn clear(&self) {
+        match self.bytes.lock() {
+            Ok(mut guard) => guard.clear(),
+            Err(err) => err.into_inner().clear(),
+        }
+        match self.tail.lock() {
+            Ok(mut guard) => guard.clear(),
+            Err(err) => err.into_inner().clear(),
+        }
+        self.truncated.store(false, Ordering::Relaxed);
+    }
+
+    fn annotate(&self, err: anyhow::Error) -> anyhow::Error {
+        let stderr = {
+            let capture = match self.bytes.lock() {
+                Ok(guard) => guard,
+                Err(err) => err.into_inner(),
+            };
+            String::from_utf8_lossy(&capture).to_string()
+        };
+
+        let stderr = stderr.trim_end_matches(['\n', '\r']).to_string();
+        if stderr.is_empty() {
+            return err;
+        }
+
+        let mut message = String::new();
+        message.push_str(&err.to_string());
+        message.push_str("\n\n");
+        message.push_str("app-server stderr:");
+        message.push('\n');
+        message.push_str(&stderr);
+        if self.truncated.load(Ordering::Relaxed) {
+            message.push('\n');
+            message.push_str("[stderr truncated]");
+        }
+        anyhow::Error::msg(message)
+    }
 }
 
 pub async fn run_app_server_backend(
@@ -147,50 +714,41 @@ async fn run_app_server_backend_inner(
         codex_home,
         thread_cwd,
         resume_thread_id,
+        approval_policy,
+        user_input_policy,
+        command_guard,
+        codex_overrides,
+        workspace_write,
+        shared_app_server,
+        context_checkpoint,
+        timeouts,
     } = config;
-    let (mut child, stdin, stdout, stderr) =
-        spawn_app_server(&codex_bin, launch, codex_home.as_deref()).await?;
-    let stderr_capture = Arc::new(Mutex::new(Vec::<u8>::new()));
-    let stderr_truncated = Arc::new(AtomicBool::new(false));
-    let stderr_task = {
-        let stderr_capture = stderr_capture.clone();
-        let stderr_truncated = stderr_truncated.clone();
-        tokio::spawn(async move {
-            const LIMIT_BYTES: usize = 32 * 1024;
-            let mut stderr = stderr;
-            let mut buf = [0u8; 4096];
-
-            loop {
-                let n = stderr.read(&mut buf).await?;
-                if n == 0 {
-                    break;
-                }
-
-                let mut capture = match stderr_capture.lock() {
-                    Ok(guard) => guard,
-                    Err(err) => err.into_inner(),
-                };
-                let remaining = LIMIT_BYTES.saturating_sub(capture.len());
-                if remaining == 0 {
-                    stderr_truncated.store(true, Ordering::Relaxed);
-                    continue;
-                }
-
-                let take = remaining.min(n);
-                capture.extend_from_slice(&buf[..take]);
-                if take < n {
-                    stderr_truncated.store(true, Ordering::Relaxed);
-                }
-            }
-
-            Ok::<(), std::io::Error>(())
-        })
+    let round_started_at = tokio::time::Instant::now();
+    // Without rules the agent need not ask at all, which keeps the thread's behavior unchanged.
+    let ask_for_approval =
+        if launch.bypass_approvals_and_sandbox || approval_policy.accepts_everything() {
+            crate::app_server_protocol::AskForApproval::Never
+        } else {
+            crate::app_server_protocol::AskForApproval::UnlessTrusted
+        };
+    let reused_process = match &shared_app_server {
+        Some(shared) => shared.take(launch, codex_home.as_deref()).await,
+        None => None,
+    };
+    let reused = reused_process.is_some();
+    let mut process = match reused_process {
+        Some(process) => process,
+        None => AppServerProcess::spawn(&codex_bin, launch, codex_home.clone()).await?,
     };
-
-    let mut stdin = Some(stdin);
-    let mut lines = BufReader::new(stdout).lines();
-    let mut next_id: i64 = 1;
     let mut shutdown_requested = false;
+    let mut keep_process = false;
+    // Set while a process that died mid-turn waits to be respawned (`RestartAppServer`).
+    let mut app_server_down = false;
+    // Ops the UI sent while the process was down, handled once it is back.
+    let mut deferred_ops = Vec::new();
+    // Set once a watchdog limit killed the process; the round is over and nothing is respawned.
+    let mut timed_out = false;
+    let mut last_activity = tokio::time::Instant::now();
     let (recovery_action_tx, mut recovery_action_rx) = unbounded_channel::<RecoveryAction>();
     let mut recovery = StreamRecoveryContext {
         stream_recovery: PotterStreamRecovery::new(),
@@ -199,33 +757,71 @@ async fn run_app_server_backend_inner(
         has_sent_turn_start: false,
         has_finished_round: false,
         last_turn_start_was_recovery_continue: false,
+        active_turn_id: None,
+        turn_interrupt: TurnInterrupt::None,
+        continued_after_interrupt: false,
+        approvals: ApprovalContext {
+            policy: approval_policy,
+            ..ApprovalContext::default()
+        },
+        prompts: PromptContext {
+            policy: user_input_policy,
+            ..PromptContext::default()
+        },
+        command_guard: CommandGuardContext {
+            guard: command_guard,
+            workdir: match &thread_cwd {
+                Some(cwd) => cwd.clone(),
+                None => std::env::current_dir().context("resolve current directory")?,
+            },
+            blocked: None,
+        },
+        turn_settings: TurnSettings {
+            effort: codex_overrides.effort,
+            sandbox_policy: None,
+        },
+        context_window: ContextWindowContext {
+            checkpoint: context_checkpoint,
+            ..ContextWindowContext::default()
+        },
+        thread_filter: ThreadFilter {
+            reused_process: reused,
+            thread_id: None,
+        },
     };
 
     let result = async {
-        initialize_app_server(
-            stdin
-                .as_mut()
-                .context("codex app-server stdin unavailable")?,
-            &mut lines,
-            &mut next_id,
-            &mut recovery,
-            event_tx,
-        )
-        .await?;
+        if !reused {
+            initialize_app_server(
+                process
+                    .stdin
+                    .as_mut()
+                    .context("codex app-server stdin unavailable")?,
+                &mut process.lines,
+                &mut process.next_id,
+                &mut recovery,
+                event_tx,
+            )
+            .await?;
+        }
 
         let thread_start_or_resume = match resume_thread_id {
             Some(thread_id) => ThreadStartOrResume::Resume(
                 thread_resume(
-                    stdin
+                    process
+                        .stdin
                         .as_mut()
                         .context("codex app-server stdin unavailable")?,
-                    &mut lines,
-                    &mut next_id,
+                    &mut process.lines,
+                    &mut process.next_id,
                     ThreadResumeSettings {
                         thread_id,
-                        developer_instructions,
+                        developer_instructions: developer_instructions.clone(),
                         sandbox_mode: launch.thread_sandbox,
-                        cwd: thread_cwd,
+                        approval_policy: ask_for_approval,
+                        cwd: thread_cwd.clone(),
+                        model: codex_overrides.model.clone(),
+                        config: codex_overrides.thread_config(),
                     },
                     &mut recovery,
                     event_tx,
@@ -234,15 +830,19 @@ async fn run_app_server_backend_inner(
             ),
             None => ThreadStartOrResume::Start(
                 thread_start(
-                    stdin
+                    process
+                        .stdin
                         .as_mut()
                         .context("codex app-server stdin unavailable")?,
-                    &mut lines,
-                    &mut next_id,
+                    &mut process.lines,
+                    &mut process.next_id,
                     ThreadStartSettings {
-                        developer_instructions,
+                        developer_instructions: developer_instructions.clone(),
                         sandbox_mode: launch.thread_sandbox,
-                        cwd: thread_cwd,
+                        approval_policy: ask_for_approval,
+                        cwd: thread_cwd.clone(),
+                        model: codex_overrides.model.clone(),
+                        config: codex_overrides.thread_config(),
                     },
                     &mut recovery,
                     event_tx,
@@ -252,6 +852,9 @@ async fn run_app_server_backend_inner(
         };
 
         let thread_id = thread_start_or_resume.thread_id().to_string();
+        recovery.thread_filter.thread_id = Some(thread_id.clone());
+        recovery.turn_settings.sandbox_policy =
+            workspace_write.turn_policy(launch, thread_start_or_resume.sandbox());
 
         let session_configured = synthesize_session_configured(&thread_start_or_resume)?;
         let _ = event_tx.send(Event {
@@ -260,15 +863,43 @@ async fn run_app_server_backend_inner(
         });
 
         loop {
+            let watchdog = if recovery.has_finished_round {
+                None
+            } else {
+                timeouts.next_timeout(
+                    round_started_at,
+                    last_activity,
+                    !app_server_down && recovery.is_waiting_on_app_server(),
+                    u64::try_from(recovery.context_window.total_tokens).unwrap_or(0),
+                )
+            };
             tokio::select! {
                 maybe_op = op_rx.recv(), if !shutdown_requested => {
+                    last_activity = tokio::time::Instant::now();
                     let Some(op) = maybe_op else {
                         shutdown_requested = true;
-                        stdin.take();
+                        if app_server_down {
+                            break;
+                        }
+                        // An idle process is kept for the next round; one with a running turn
+                        // (the user quit mid-turn) is shut down like without reuse.
+                        if shared_app_server.is_some() && recovery.active_turn_id.is_none() {
+                            keep_process = true;
+                            break;
+                        }
+                        process.stdin.take();
                         continue;
                     };
+                    if app_server_down {
+                        deferred_ops.push(op);
+                        continue;
+                    }
                     if matches!(op, Op::UserInput { .. }) {
                         let was_in_retry_streak = recovery.stream_recovery.is_in_retry_streak();
+                        if recovery.turn_interrupt == TurnInterrupt::AwaitingDecision {
+                            recovery.turn_interrupt = TurnInterrupt::None;
+                            recovery.continued_after_interrupt = true;
+                        }
                         recovery.has_sent_turn_start = true;
                         recovery.last_turn_start_was_recovery_continue = false;
                         recovery.pending_continue_retry = None;
@@ -283,25 +914,25 @@ async fn run_app_server_backend_inner(
                     handle_op(
                         &thread_id,
                         op,
-                        stdin.as_mut().context("codex app-server stdin unavailable")?,
-                        &mut lines,
-                        &mut next_id,
+                        process.stdin.as_mut().context("codex app-server stdin unavailable")?,
+                        &mut process.lines,
+                        &mut process.next_id,
                         &mut recovery,
                         event_tx,
                     )
                     .await?;
                 }
-                maybe_action = recovery_action_rx.recv(), if !shutdown_requested => {
+                maybe_action = recovery_action_rx.recv(), if !shutdown_requested && !timed_out => {
+                    last_activity = tokio::time::Instant::now();
                     let Some(action) = maybe_action else {
                         continue;
                     };
 
-                    if !recovery.stream_recovery.is_in_retry_streak() {
-                        continue;
-                    }
-
                     match action {
                         RecoveryAction::RetryContinue { attempt } => {
+                            if !recovery.stream_recovery.is_in_retry_streak() {
+                                continue;
+                            }
                             recovery.has_sent_turn_start = true;
                             if attempt >= 2 && recovery.last_turn_start_was_recovery_continue {
                                 // Remove the previous automatic `Continue` turn from the thread
@@ -312,9 +943,12 @@ async fn run_app_server_backend_inner(
                                 // scheduled only after the previous turn has ended.
                                 thread_rollback(
                                     &thread_id,
-                                    stdin.as_mut().context("codex app-server stdin unavailable")?,
-                                    &mut lines,
-                                    &mut next_id,
+                                    process
+                                        .stdin
+                                        .as_mut()
+                                        .context("codex app-server stdin unavailable")?,
+                                    &mut process.lines,
+                                    &mut process.next_id,
                                     &mut recovery,
                                     event_tx,
                                 )
@@ -323,16 +957,125 @@ async fn run_app_server_backend_inner(
                             recovery.last_turn_start_was_recovery_continue = true;
                             handle_op(
                                 &thread_id,
-                                Op::UserInput {
-                                    items: vec![CodexUserInput::Text {
-                                        text: String::from("Continue"),
-                                        text_elements: Vec::new(),
-                                    }],
-                                    final_output_json_schema: None,
+                                continue_op(),
+                                process
+                                    .stdin
+                                    .as_mut()
+                                    .context("codex app-server stdin unavailable")?,
+                                &mut process.lines,
+                                &mut process.next_id,
+                                &mut recovery,
+                                event_tx,
+                            )
+                            .await?;
+                        }
+                        RecoveryAction::RestartAppServer => {
+                            process = respawn_app_server(
+                                &codex_bin,
+                                launch,
+                                codex_home.clone(),
+                                ThreadResumeSettings {
+                                    thread_id: ThreadId::from_string(&thread_id)
+                                        .context("parse thread id")?,
+                                    developer_instructions: developer_instructions.clone(),
+                                    sandbox_mode: launch.thread_sandbox,
+                                    approval_policy: ask_for_approval,
+                                    cwd: thread_cwd.clone(),
+                                    model: codex_overrides.model.clone(),
+                                    config: codex_overrides.thread_config(),
                                 },
-                                stdin.as_mut().context("codex app-server stdin unavailable")?,
-                                &mut lines,
-                                &mut next_id,
+                                &mut recovery,
+                                event_tx,
+                            )
+                            .await?;
+                            app_server_down = false;
+
+                            // Requests and the turn of the dead process are gone with it.
+                            recovery.active_turn_id = None;
+                            recovery.pending_continue_retry = None;
+                            recovery.approvals.subjects_by_call_id.clear();
+                            recovery.approvals.pending.clear();
+                            recovery.prompts.pending_user_input.clear();
+                            recovery.prompts.pending_elicitations.clear();
+
+                            // The interrupted turn may have done work, so unlike stream retries
+                            // it is not rolled back.
+                            recovery.has_sent_turn_start = true;
+                            recovery.last_turn_start_was_recovery_continue = true;
+                            handle_op(
+                                &thread_id,
+                                continue_op(),
+                                process
+                                    .stdin
+                                    .as_mut()
+                                    .context("codex app-server stdin unavailable")?,
+                                &mut process.lines,
+                                &mut process.next_id,
+                                &mut recovery,
+                                event_tx,
+                            )
+                            .await?;
+                            for op in std::mem::take(&mut deferred_ops) {
+                                handle_op(
+                                    &thread_id,
+                                    op,
+                                    process
+                                        .stdin
+                                        .as_mut()
+                                        .context("codex app-server stdin unavailable")?,
+                                    &mut process.lines,
+                                    &mut process.next_id,
+                                    &mut recovery,
+                                    event_tx,
+                                )
+                                .await?;
+                            }
+                        }
+                        RecoveryAction::InterruptBlockedTurn
+                        | RecoveryAction::InterruptForContextCheckpoint => {
+                            // The turn may have ended on its own in the meantime; the round then
+                            // finished already (as blocked, or as completed before a checkpoint
+                            // was needed).
+                            let Some(turn_id) = recovery.active_turn_id.clone() else {
+                                continue;
+                            };
+                            send_turn_interrupt(
+                                &thread_id,
+                                turn_id,
+                                process
+                                    .stdin
+                                    .as_mut()
+                                    .context("codex app-server stdin unavailable")?,
+                                &mut process.lines,
+                                &mut process.next_id,
+                                &mut recovery,
+                                event_tx,
+                            )
+                            .await?;
+                        }
+                        RecoveryAction::StartContextCheckpointTurn => {
+                            let checkpoint = &recovery.context_window.checkpoint;
+                            let Some(checkpoint) = checkpoint.as_ref() else {
+                                continue;
+                            };
+                            let op = Op::UserInput {
+                                items: vec![CodexUserInput::Text {
+                                    text: checkpoint.prompt.clone(),
+                                    text_elements: Vec::new(),
+                                }],
+                                final_output_json_schema: None,
+                            };
+                            recovery.has_sent_turn_start = true;
+                            recovery.last_turn_start_was_recovery_continue = false;
+                            handle_op(
+                                &thread_id,
+                                op,
+                                process
+                                    .stdin
+                                    .as_mut()
+                                    .context("codex app-server stdin unavailable")?,
+                                &mut process.lines,
+                                &mut process.next_id,
                                 &mut recovery,
                                 event_tx,
                             )
@@ -340,62 +1083,115 @@ async fn run_app_server_backend_inner(
                         }
                     }
                 }
-                maybe_line = lines.next_line() => {
-                    let Some(line) = maybe_line? else {
-                        break;
+                maybe_line = process.lines.next_line(), if !app_server_down => {
+                    last_activity = tokio::time::Instant::now();
+                    let line = match maybe_line {
+                        Ok(Some(line)) => line,
+                        // The process died mid-turn (crash, OOM kill): respawn it and continue
+                        // the thread, as after a stream disconnect.
+                        Ok(None) | Err(_)
+                            if !shutdown_requested
+                                && recovery.has_sent_turn_start
+                                && !recovery.has_finished_round =>
+                        {
+                            let error_message = process.exit_message().await;
+                            match recovery.stream_recovery.plan_restart() {
+                                RestartDecision::Retry(plan) => {
+                                    let _ = event_tx.send(Event {
+                                        id: "".to_string(),
+                                        msg: EventMsg::PotterStreamRecoveryUpdate {
+                                            attempt: plan.attempt,
+                                            max_attempts: plan.max_attempts,
+                                            error_message,
+                                        },
+                                    });
+                                    app_server_down = true;
+                                    schedule_recovery_action(
+                                        &recovery.recovery_action_tx,
+                                        RecoveryAction::RestartAppServer,
+                                        plan.backoff,
+                                    );
+                                }
+                                RestartDecision::GiveUp {
+                                    attempts,
+                                    max_attempts,
+                                } => {
+                                    let _ = event_tx.send(Event {
+                                        id: "".to_string(),
+                                        msg: EventMsg::PotterStreamRecoveryGaveUp {
+                                            error_message: error_message.clone(),
+                                            attempts,
+                                            max_attempts,
+                                        },
+                                    });
+                                    anyhow::bail!(
+                                        "{error_message} (recovery gave up after {attempts}/{max_attempts} retries)"
+                                    );
+                                }
+                            }
+                            continue;
+                        }
+                        Ok(None) => break,
+                        Err(err) => return Err(err.into()),
                     };
                     let msg: JSONRPCMessage = serde_json::from_str(&line)
                         .with_context(|| format!("failed to decode app-server message: {line}"))?;
                     handle_app_server_message(
                         msg,
-                        &mut stdin,
+                        &mut process.stdin,
                         &mut recovery,
                         event_tx,
                     )
                     .await?;
                 }
+                _ = tokio::time::sleep_until(
+                    watchdog.map_or_else(tokio::time::Instant::now, |(deadline, _)| deadline),
+                ), if watchdog.is_some() => {
+                    let Some((_, timeout)) = watchdog else {
+                        continue;
+                    };
+                    // A hung app-server may never answer `turn/interrupt`, so it is killed; the
+                    // round runner decides whether the round runs again in a fresh process.
+                    if let Some(child) = process.child.as_mut() {
+                        let _ = child.kill().await;
+                    }
+                    process.stdin.take();
+                    timed_out = true;
+                    app_server_down = true;
+                    deferred_ops.clear();
+                    finish_round(
+                        &mut recovery,
+                        event_tx,
+                        String::new(),
+                        PotterRoundOutcome::TimedOut { timeout },
+                    );
+                }
             }
         }
 
-        let _ = child.wait().await;
+        if !keep_process && let Some(child) = process.child.as_mut() {
+            let _ = child.wait().await;
+        }
         Ok::<(), anyhow::Error>(())
     }
     .await;
 
-    if result.is_err() {
+    if let Err(err) = result {
         // Do not await the drain task on failure: the child might keep running and we'd hang while
         // waiting for stderr to close. We already captured enough to provide context.
-        stderr_task.abort();
-    } else {
-        let _ = stderr_task.await;
+        process.stderr.task.abort();
+        return Err(process.stderr.annotate(err));
+    }
+    // Children of the killed process (a hung command, an MCP server) may still hold its stderr.
+    if timed_out {
+        process.stderr.task.abort();
+    }
+    match &shared_app_server {
+        Some(shared) if keep_process => shared.put(process).await,
+        _ => {
+            let _ = (&mut process.stderr.task).await;
+        }
     }
-
-    result.map_err(|err| {
-        let stderr = {
-            let capture = match stderr_capture.lock() {
-                Ok(guard) => guard,
-                Err(err) => err.into_inner(),
-            };
-            String::from_utf8_lossy(&capture).to_string()
-        };
-
-        let stderr = stderr.trim_end_matches(['\n', '\r']).to_string();
-        if stderr.is_empty() {
-            return err;
-        }
-
-        let mut message = String::new();
-        message.push_str(&err.to_string());
-        message.push_str("\n\n");
-        message.push_str("app-server stderr:");
-        message.push('\n');
-        message.push_str(&stderr);
-        if stderr_truncated.load(Ordering::Relaxed) {
-            message.push('\n');
-            message.push_str("[stderr truncated]");
-        }
-        anyhow::Error::msg(message)
-    })?;
 
     // If the backend finishes while the UI still expects it to be alive, ensure the UI can exit.
     if !shutdown_requested {
@@ -485,17 +1281,67 @@ async fn initialize_app_server(
     Ok(())
 }
 
+/// Spawn a fresh app-server after the previous one died mid-round and resume the round's thread
+/// on it.
+async fn respawn_app_server(
+    codex_bin: &str,
+    launch: AppServerLaunchConfig,
+    codex_home: Option<PathBuf>,
+    settings: ThreadResumeSettings,
+    recovery: &mut StreamRecoveryContext,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<AppServerProcess> {
+    let mut process = AppServerProcess::spawn(codex_bin, launch, codex_home)
+        .await
+        .context("respawn codex app-server")?;
+    let result = async {
+        let stdin = process
+            .stdin
+            .as_mut()
+            .context("codex app-server stdin unavailable")?;
+        initialize_app_server(
+            stdin,
+            &mut process.lines,
+            &mut process.next_id,
+            recovery,
+            event_tx,
+        )
+        .await?;
+        thread_resume(
+            stdin,
+            &mut process.lines,
+            &mut process.next_id,
+            settings,
+            recovery,
+            event_tx,
+        )
+        .await
+        .context("resume thread on respawned codex app-server")
+    }
+    .await;
+    match result {
+        Ok(_) => Ok(process),
+        Err(err) => Err(process.stderr.annotate(err)),
+    }
+}
+
 struct ThreadStartSettings {
     developer_instructions: Option<String>,
     sandbox_mode: Option<crate::app_server_protocol::SandboxMode>,
+    approval_policy: crate::app_server_protocol::AskForApproval,
     cwd: Option<PathBuf>,
+    model: Option<String>,
+    config: Option<HashMap<String, serde_json::Value>>,
 }
 
 struct ThreadResumeSettings {
     thread_id: ThreadId,
     developer_instructions: Option<String>,
     sandbox_mode: Option<crate::app_server_protocol::SandboxMode>,
+    approval_policy: crate::app_server_protocol::AskForApproval,
     cwd: Option<PathBuf>,
+    model: Option<String>,
+    config: Option<HashMap<String, serde_json::Value>>,
 }
 
 async fn thread_start(
@@ -509,18 +1355,21 @@ async fn thread_start(
     let ThreadStartSettings {
         developer_instructions,
         sandbox_mode,
+        approval_policy,
         cwd,
+        model,
+        config,
     } = settings;
     let request_id = next_request_id(next_id);
     let request = ClientRequest::ThreadStart {
         request_id: request_id.clone(),
         params: ThreadStartParams {
-            model: None,
+            model,
             model_provider: None,
             cwd: cwd.map(|cwd| cwd.to_string_lossy().to_string()),
-            approval_policy: Some(crate::app_server_protocol::AskForApproval::Never),
+            approval_policy: Some(approval_policy),
             sandbox: sandbox_mode,
-            config: None,
+            config,
             base_instructions: None,
             developer_instructions,
             experimental_raw_events: false,
@@ -543,7 +1392,10 @@ async fn thread_resume(
         thread_id,
         developer_instructions,
         sandbox_mode,
+        approval_policy,
         cwd,
+        model,
+        config,
     } = settings;
 
     let request_id = next_request_id(next_id);
@@ -551,12 +1403,12 @@ async fn thread_resume(
         request_id: request_id.clone(),
         params: ThreadResumeParams {
             thread_id: thread_id.to_string(),
-            model: None,
+            model,
             model_provider: None,
             cwd: cwd.map(|cwd| cwd.to_string_lossy().to_string()),
-            approval_policy: Some(crate::app_server_protocol::AskForApproval::Never),
+            approval_policy: Some(approval_policy),
             sandbox: sandbox_mode,
-            config: None,
+            config,
             base_instructions: None,
             developer_instructions,
         },
@@ -614,9 +1466,12 @@ async fn handle_op(
                     input,
                     cwd: None,
                     approval_policy: None,
-                    sandbox_policy: None,
+                    sandbox_policy: recovery.turn_settings.sandbox_policy.clone(),
                     model: None,
-                    effort: None,
+                    effort: recovery
+                        .turn_settings
+                        .effort
+                        .map(|effort| serde_json::json!(effort)),
                     summary: None,
                     output_schema: final_output_json_schema,
                     collaboration_mode: None,
@@ -630,10 +1485,67 @@ async fn handle_op(
             Ok(())
         }
         Op::Interrupt => {
-            // The single-turn TUI runner does not track the active turn id, so we cannot call
-            // turn/interrupt. Ignore and let the session complete naturally.
+            let Some(turn_id) = recovery.active_turn_id.clone() else {
+                // No turn is running (it has not started yet or already ended), so there is
+                // nothing to interrupt: end the round instead.
+                finish_round(
+                    recovery,
+                    event_tx,
+                    String::new(),
+                    PotterRoundOutcome::UserRequested,
+                );
+                return Ok(());
+            };
+            recovery.turn_interrupt = TurnInterrupt::Requested;
+            send_turn_interrupt(
+                thread_id, turn_id, stdin, lines, next_id, recovery, event_tx,
+            )
+            .await
+        }
+        Op::PotterFinishRound { outcome } => {
+            finish_round(recovery, event_tx, String::new(), outcome);
+            Ok(())
+        }
+        Op::PotterApprovalResponse { id, decision } => {
+            let Some(pending) = recovery.approvals.pending.remove(&id) else {
+                return Ok(());
+            };
+            send_approval_response(stdin, pending.request_id, pending.kind, decision).await?;
+            send_approval_decided(
+                event_tx,
+                pending.subject,
+                decision,
+                PotterApprovalDecidedBy::User,
+            );
             Ok(())
         }
+        Op::PotterUserInputResponse { id, response } => {
+            let Some(pending) = recovery.prompts.pending_user_input.remove(&id) else {
+                return Ok(());
+            };
+            send_user_input_response(
+                stdin,
+                pending.request_id,
+                &pending.questions,
+                response,
+                PotterPromptAnsweredBy::User,
+                event_tx,
+            )
+            .await
+        }
+        Op::PotterElicitationResponse { id, decision } => {
+            let Some(pending) = recovery.prompts.pending_elicitations.remove(&id) else {
+                return Ok(());
+            };
+            send_elicitation_response(
+                stdin,
+                pending,
+                decision,
+                PotterPromptAnsweredBy::User,
+                event_tx,
+            )
+            .await
+        }
         Op::GetHistoryEntryRequest { .. } => {
             // The prompt screen does not support fetching persisted prompt history from the
             // backend. Ignore the request so the UI can stay simple.
@@ -642,6 +1554,32 @@ async fn handle_op(
     }
 }
 
+async fn send_turn_interrupt(
+    thread_id: &str,
+    turn_id: String,
+    stdin: &mut ChildStdin,
+    lines: &mut tokio::io::Lines<BufReader<ChildStdout>>,
+    next_id: &mut i64,
+    recovery: &mut StreamRecoveryContext,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let request_id = next_request_id(next_id);
+    let request = ClientRequest::TurnInterrupt {
+        request_id: request_id.clone(),
+        params: TurnInterruptParams {
+            thread_id: thread_id.to_string(),
+            turn_id: turn_id.clone(),
+        },
+    };
+    send_message(stdin, &request).await?;
+    let response = read_until_response(stdin, lines, request_id, recovery, event_tx)
+        .await
+        .with_context(|| format!("turn/interrupt turn_id={turn_id}"))?;
+    let _parsed: TurnInterruptResponse =
+        serde_json::from_value(response.result).context("decode turn/interrupt response")?;
+    Ok(())
+}
+
 async fn handle_app_server_message(
     msg: JSONRPCMessage,
     stdin: &mut Option<ChildStdin>,
@@ -659,7 +1597,16 @@ async fn handle_app_server_message(
         }
         JSONRPCMessage::Request(request) => {
             if let Some(stdin) = stdin.as_mut() {
-                handle_server_request(stdin, request).await?;
+                handle_server_request(
+                    stdin,
+                    request,
+                    &mut recovery.approvals,
+                    &mut recovery.prompts,
+                    &mut recovery.command_guard,
+                    &recovery.recovery_action_tx,
+                    event_tx,
+                )
+                .await?;
             }
         }
         JSONRPCMessage::Response(_) | JSONRPCMessage::Error(_) => {}
@@ -680,6 +1627,10 @@ fn handle_codex_event_notification(
     let Some(params) = params else {
         return Ok(());
     };
+    // A late event of a previous round's thread on a reused process.
+    if recovery.thread_filter.is_other_thread(&params) {
+        return Ok(());
+    }
 
     let event: Event = serde_json::from_value(params)?;
     handle_codex_event(event, recovery, event_tx);
@@ -729,7 +1680,10 @@ fn handle_codex_event(
             // and then issue the planned automatic `Continue`.
             should_forward = false;
         } else if recovery.pending_continue_retry.is_none()
-            && let Some(decision) = recovery.stream_recovery.plan_retry(err)
+            && let Some(decision) = recovery
+                .stream_recovery
+                .plan_usage_limit_wait(err, unix_now())
+                .or_else(|| recovery.stream_recovery.plan_retry(err))
         {
             match decision {
                 ContinueRetryDecision::Retry(plan) => {
@@ -743,6 +1697,20 @@ fn handle_codex_event(
                     });
                     recovery.pending_continue_retry = Some(plan);
                 }
+                ContinueRetryDecision::WaitForUsageLimit(plan) => {
+                    let _ = event_tx.send(Event {
+                        id: event.id.clone(),
+                        msg: EventMsg::PotterUsageLimitWait {
+                            resumes_at: plan.resumes_at,
+                            error_message: err.message.clone(),
+                        },
+                    });
+                    recovery.pending_continue_retry = Some(ContinueRetryPlan {
+                        attempt: plan.attempt,
+                        max_attempts: plan.max_attempts,
+                        backoff: plan.wait,
+                    });
+                }
                 ContinueRetryDecision::GiveUp {
                     attempts,
                     max_attempts,
@@ -768,25 +1736,135 @@ fn handle_codex_event(
         }
     }
 
-    if matches!(event.msg, EventMsg::TurnAborted(_)) {
+    // An overflowing context window cannot be recovered within the thread: the round ends and the
+    // session continues in a fresh one.
+    if recovery.has_sent_turn_start
+        && let EventMsg::Error(err) = &event.msg
+        && err.codex_error_info == Some(CodexErrorInfo::ContextWindowExceeded)
+    {
+        should_forward = false;
+        recovery.pending_continue_retry = None;
+        round_outcome = Some(PotterRoundOutcome::ContextExhausted);
+    }
+
+    match &event.msg {
+        EventMsg::TurnStarted(ev) if !ev.turn_id.is_empty() => {
+            recovery.active_turn_id = Some(ev.turn_id.clone());
+            recovery.context_window.window =
+                ev.model_context_window.or(recovery.context_window.window);
+        }
+        EventMsg::TokenCount(ev) => {
+            if let Some(info) = &ev.info {
+                recovery.context_window.window =
+                    info.model_context_window.or(recovery.context_window.window);
+                recovery.context_window.total_tokens = info.total_token_usage.total_tokens;
+                check_context_window(recovery, &info.last_token_usage, event_tx, &event_id);
+            }
+        }
+        EventMsg::TurnComplete(_) => {
+            recovery.active_turn_id = None;
+            recovery.approvals.subjects_by_call_id.clear();
+        }
+        EventMsg::TurnAborted(_) => {
+            recovery.active_turn_id = None;
+            recovery.pending_continue_retry = None;
+            recovery.approvals.subjects_by_call_id.clear();
+        }
+        EventMsg::ExecApprovalRequest(ev) => {
+            recovery.approvals.subjects_by_call_id.insert(
+                ev.call_id.clone(),
+                ApprovalSubject {
+                    command: Some(ev.command.clone()),
+                    paths: Vec::new(),
+                    reason: ev.reason.clone(),
+                },
+            );
+        }
+        EventMsg::ApplyPatchApprovalRequest(ev) => {
+            recovery.approvals.subjects_by_call_id.insert(
+                ev.call_id.clone(),
+                ApprovalSubject {
+                    command: None,
+                    paths: ev.changes.keys().cloned().collect(),
+                    reason: ev.reason.clone(),
+                },
+            );
+        }
+        EventMsg::ExecCommandBegin(ev) if recovery.command_guard.blocked.is_none() => {
+            let guard = &recovery.command_guard;
+            if let Some(blocked) =
+                guard
+                    .guard
+                    .check(&ev.command, &ev.parsed_cmd, &ev.cwd, &guard.workdir)
+            {
+                recovery.command_guard.blocked = Some(blocked);
+                let _ = recovery
+                    .recovery_action_tx
+                    .send(RecoveryAction::InterruptBlockedTurn);
+            }
+        }
+        _ => {}
+    }
+
+    // However a turn that ran a blocked command ends, it ends the round; a user interrupt racing
+    // with the guard's does not get to ask how to go on.
+    if matches!(
+        &event.msg,
+        EventMsg::TurnComplete(_) | EventMsg::TurnAborted(_)
+    ) && let Some(blocked) = recovery.command_guard.blocked.take()
+    {
         recovery.pending_continue_retry = None;
+        recovery.turn_interrupt = TurnInterrupt::None;
+        round_outcome = Some(PotterRoundOutcome::CommandBlocked {
+            command: blocked.command,
+            rule: blocked.rule,
+        });
+    }
+
+    // A turn the user interrupted leaves the round open: the UI asks how to go on and either
+    // sends a new instruction or finishes the round via `Op::PotterFinishRound`.
+    let is_user_interrupt = recovery.turn_interrupt == TurnInterrupt::Requested
+        && matches!(
+            &event.msg,
+            EventMsg::TurnAborted(ev)
+                if ev.reason == codex_protocol::protocol::TurnAbortReason::Interrupted
+        );
+    if is_user_interrupt {
+        recovery.turn_interrupt = TurnInterrupt::AwaitingDecision;
+        // The user decides how the round goes on; a later turn may checkpoint again.
+        if recovery.context_window.state == ContextCheckpointState::Interrupting {
+            recovery.context_window.state = ContextCheckpointState::None;
+        }
+    }
+
+    // A turn interrupted for a context checkpoint is followed by the checkpoint turn in the same
+    // round.
+    let is_context_checkpoint_interrupt = !is_user_interrupt
+        && round_outcome.is_none()
+        && recovery.context_window.state == ContextCheckpointState::Interrupting
+        && matches!(
+            &event.msg,
+            EventMsg::TurnAborted(ev)
+                if ev.reason == codex_protocol::protocol::TurnAbortReason::Interrupted
+        );
+    if is_context_checkpoint_interrupt {
+        recovery.context_window.state = ContextCheckpointState::Checkpointing;
+        should_forward = false;
+        let _ = recovery
+            .recovery_action_tx
+            .send(RecoveryAction::StartContextCheckpointTurn);
     }
 
     if matches!(event.msg, EventMsg::TurnComplete(_))
         && let Some(plan) = recovery.pending_continue_retry.take()
     {
-        let tx = recovery.recovery_action_tx.clone();
-        let action = RecoveryAction::RetryContinue {
-            attempt: plan.attempt,
-        };
-        if plan.backoff.is_zero() {
-            let _ = tx.send(action);
-        } else {
-            tokio::spawn(async move {
-                tokio::time::sleep(plan.backoff).await;
-                let _ = tx.send(action);
-            });
-        }
+        schedule_recovery_action(
+            &recovery.recovery_action_tx,
+            RecoveryAction::RetryContinue {
+                attempt: plan.attempt,
+            },
+            plan.backoff,
+        );
     }
 
     if should_suppress_turn_complete {
@@ -795,14 +1873,22 @@ fn handle_codex_event(
 
     if round_outcome.is_none() {
         round_outcome = match &event.msg {
-            EventMsg::TurnComplete(_) if !should_suppress_turn_complete => {
-                Some(PotterRoundOutcome::Completed)
-            }
+            EventMsg::TurnComplete(_) if !should_suppress_turn_complete => Some(
+                if recovery.context_window.state == ContextCheckpointState::Checkpointing {
+                    PotterRoundOutcome::ContextExhausted
+                } else if recovery.continued_after_interrupt {
+                    PotterRoundOutcome::InterruptedThenContinued
+                } else {
+                    PotterRoundOutcome::Completed
+                },
+            ),
             EventMsg::TurnAborted(ev)
-                if !matches!(
-                    ev.reason,
-                    codex_protocol::protocol::TurnAbortReason::Replaced
-                ) =>
+                if !is_user_interrupt
+                    && !is_context_checkpoint_interrupt
+                    && !matches!(
+                        ev.reason,
+                        codex_protocol::protocol::TurnAbortReason::Replaced
+                    ) =>
             {
                 Some(PotterRoundOutcome::UserRequested)
             }
@@ -817,20 +1903,109 @@ fn handle_codex_event(
         let _ = event_tx.send(event);
     }
 
-    if !recovery.has_finished_round
-        && let Some(outcome) = round_outcome
+    if let Some(outcome) = round_outcome {
+        finish_round(recovery, event_tx, event_id, outcome);
+    }
+}
+
+/// Start a context checkpoint once the running turn has at most `--context-checkpoint` percent of
+/// its context window left.
+fn check_context_window(
+    recovery: &mut StreamRecoveryContext,
+    last_token_usage: &TokenUsage,
+    event_tx: &UnboundedSender<Event>,
+    event_id: &str,
+) {
+    let context = &recovery.context_window;
+    let (Some(checkpoint), Some(window)) = (context.checkpoint.as_ref(), context.window) else {
+        return;
+    };
+    // Turns already on their way out (interrupted, blocked, or retrying) are left alone.
+    if context.state != ContextCheckpointState::None
+        || recovery.active_turn_id.is_none()
+        || recovery.has_finished_round
+        || recovery.turn_interrupt != TurnInterrupt::None
+        || recovery.command_guard.blocked.is_some()
+        || recovery.pending_continue_retry.is_some()
     {
-        recovery.has_finished_round = true;
-        let _ = event_tx.send(Event {
-            id: event_id,
-            msg: EventMsg::PotterRoundFinished { outcome },
+        return;
+    }
+    let percent_left = last_token_usage.percent_of_context_window_remaining(window);
+    if percent_left > checkpoint.percent_left {
+        return;
+    }
+
+    recovery.context_window.state = ContextCheckpointState::Interrupting;
+    let _ = event_tx.send(Event {
+        id: event_id.to_string(),
+        msg: EventMsg::PotterContextCheckpoint { percent_left },
+    });
+    let _ = recovery
+        .recovery_action_tx
+        .send(RecoveryAction::InterruptForContextCheckpoint);
+}
+
+fn unix_now() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map_or(0, |elapsed| {
+            i64::try_from(elapsed.as_secs()).unwrap_or(i64::MAX)
+        })
+}
+
+/// Send `action` to the backend loop once `backoff` has passed.
+fn schedule_recovery_action(
+    tx: &UnboundedSender<RecoveryAction>,
+    action: RecoveryAction,
+    backoff: std::time::Duration,
+) {
+    if backoff.is_zero() {
+        let _ = tx.send(action);
+    } else {
+        let tx = tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(backoff).await;
+            let _ = tx.send(action);
         });
     }
 }
 
+/// The automatic `Continue` prompt of stream recovery.
+fn continue_op() -> Op {
+    Op::UserInput {
+        items: vec![CodexUserInput::Text {
+            text: String::from("Continue"),
+            text_elements: Vec::new(),
+        }],
+        final_output_json_schema: None,
+    }
+}
+
+/// Emit the round's `PotterRoundFinished` marker, unless the round already finished.
+fn finish_round(
+    recovery: &mut StreamRecoveryContext,
+    event_tx: &UnboundedSender<Event>,
+    event_id: String,
+    outcome: PotterRoundOutcome,
+) {
+    if recovery.has_finished_round {
+        return;
+    }
+    recovery.has_finished_round = true;
+    let _ = event_tx.send(Event {
+        id: event_id,
+        msg: EventMsg::PotterRoundFinished { outcome },
+    });
+}
+
 async fn handle_server_request(
     stdin: &mut ChildStdin,
     request: crate::app_server_protocol::JSONRPCRequest,
+    approvals: &mut ApprovalContext,
+    prompts: &mut PromptContext,
+    command_guard: &mut CommandGuardContext,
+    recovery_action_tx: &UnboundedSender<RecoveryAction>,
+    event_tx: &UnboundedSender<Event>,
 ) -> anyhow::Result<()> {
     let request_id = request.id.clone();
     let method = request.method.clone();
@@ -854,36 +2029,311 @@ async fn handle_server_request(
         }
     };
 
-    match server_request {
-        ServerRequest::CommandExecution { .. } => {
+    let (kind, params) = match server_request {
+        ServerRequest::CommandExecution { params, .. } => {
+            (ApprovalRequestKind::CommandExecution, params)
+        }
+        ServerRequest::FileChange { params, .. } => (ApprovalRequestKind::FileChange, params),
+        ServerRequest::ApplyPatch { params, .. } => (ApprovalRequestKind::ApplyPatch, params),
+        ServerRequest::ExecCommand { params, .. } => (ApprovalRequestKind::ExecCommand, params),
+        ServerRequest::ToolRequestUserInput { params, .. } => {
+            return handle_user_input_request(stdin, request_id, params, prompts, event_tx).await;
+        }
+        ServerRequest::McpServerElicitation { params, .. } => {
+            return handle_elicitation_request(stdin, request_id, params, prompts, event_tx).await;
+        }
+    };
+    let subject = ApprovalSubject::from_request_params(params.as_ref());
+    let subject = match ApprovalSubject::call_id(params.as_ref())
+        .and_then(|call_id| approvals.subjects_by_call_id.remove(&call_id))
+    {
+        Some(known) => subject.or_from(known),
+        None => subject,
+    };
+
+    // A command the guard forbids is denied before it runs, whatever the approval rules say, and
+    // its turn is stopped like one that started such a command.
+    if let Some(command) = subject.command.as_deref() {
+        let cwd = params
+            .as_ref()
+            .and_then(|params| params.get("cwd"))
+            .and_then(serde_json::Value::as_str)
+            .map_or_else(|| command_guard.workdir.clone(), PathBuf::from);
+        if let Some(blocked) = command_guard
+            .guard
+            .check(command, &[], &cwd, &command_guard.workdir)
+        {
+            let decided_by = PotterApprovalDecidedBy::CommandGuard {
+                rule: blocked.rule.clone(),
+            };
+            send_approval_response(stdin, request_id, kind, PotterApprovalDecision::Deny).await?;
+            send_approval_decided(event_tx, subject, PotterApprovalDecision::Deny, decided_by);
+            if command_guard.blocked.is_none() {
+                command_guard.blocked = Some(blocked);
+                let _ = recovery_action_tx.send(RecoveryAction::InterruptBlockedTurn);
+            }
+            return Ok(());
+        }
+    }
+
+    match approvals.policy.decide(&subject) {
+        ApprovalVerdict::Decided {
+            decision,
+            decided_by,
+        } => {
+            send_approval_response(stdin, request_id, kind, decision).await?;
+            send_approval_decided(event_tx, subject, decision, decided_by);
+        }
+        ApprovalVerdict::AskUser => {
+            // Answered later, when the UI sends `Op::PotterApprovalResponse`.
+            approvals.next_pending_id += 1;
+            let id = format!("approval-{}", approvals.next_pending_id);
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg: EventMsg::PotterApprovalRequest {
+                    id: id.clone(),
+                    command: subject.command.clone(),
+                    paths: subject.paths.clone(),
+                    reason: subject.reason.clone(),
+                },
+            });
+            approvals.pending.insert(
+                id,
+                PendingApproval {
+                    request_id,
+                    kind,
+                    subject,
+                },
+            );
+        }
+    }
+
+    Ok(())
+}
+
+async fn send_approval_response(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    kind: ApprovalRequestKind,
+    decision: PotterApprovalDecision,
+) -> anyhow::Result<()> {
+    let accept = decision == PotterApprovalDecision::Accept;
+    let review_decision = if accept {
+        ReviewDecision::Approved
+    } else {
+        ReviewDecision::Denied
+    };
+    match kind {
+        ApprovalRequestKind::CommandExecution => {
             let response = CommandExecutionRequestApprovalResponse {
-                decision: CommandExecutionApprovalDecision::Accept,
+                decision: if accept {
+                    CommandExecutionApprovalDecision::Accept
+                } else {
+                    CommandExecutionApprovalDecision::Decline
+                },
             };
-            send_response(stdin, request_id, response).await?;
+            send_response(stdin, request_id, response).await
         }
-        ServerRequest::FileChange { .. } => {
+        ApprovalRequestKind::FileChange => {
             let response = FileChangeRequestApprovalResponse {
-                decision: FileChangeApprovalDecision::Accept,
+                decision: if accept {
+                    FileChangeApprovalDecision::Accept
+                } else {
+                    FileChangeApprovalDecision::Decline
+                },
             };
-            send_response(stdin, request_id, response).await?;
+            send_response(stdin, request_id, response).await
         }
-        ServerRequest::ApplyPatch { .. } => {
+        ApprovalRequestKind::ApplyPatch => {
             let response = ApplyPatchApprovalResponse {
-                decision: ReviewDecision::Approved,
+                decision: review_decision,
             };
-            send_response(stdin, request_id, response).await?;
+            send_response(stdin, request_id, response).await
         }
-        ServerRequest::ExecCommand { .. } => {
+        ApprovalRequestKind::ExecCommand => {
             let response = ExecCommandApprovalResponse {
-                decision: ReviewDecision::Approved,
+                decision: review_decision,
             };
-            send_response(stdin, request_id, response).await?;
+            send_response(stdin, request_id, response).await
         }
     }
+}
+
+async fn handle_user_input_request(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    params: ToolRequestUserInputParams,
+    prompts: &mut PromptContext,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    if !prompts.policy.asks_user() {
+        let response = prompts.policy.answer(&params.questions);
+        return send_user_input_response(
+            stdin,
+            request_id,
+            &params.questions,
+            response,
+            PotterPromptAnsweredBy::Headless,
+            event_tx,
+        )
+        .await;
+    }
+
+    // Answered later, when the UI sends `Op::PotterUserInputResponse`.
+    prompts.next_pending_id += 1;
+    let id = format!("user-input-{}", prompts.next_pending_id);
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterUserInputRequest {
+            id: id.clone(),
+            questions: params.questions.clone(),
+        },
+    });
+    prompts.pending_user_input.insert(
+        id,
+        PendingUserInput {
+            request_id,
+            questions: params.questions,
+        },
+    );
+    Ok(())
+}
+
+async fn send_user_input_response(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    questions: &[RequestUserInputQuestion],
+    response: RequestUserInputResponse,
+    answered_by: PotterPromptAnsweredBy,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let answers = crate::user_input_policy::transcript_answers(questions, &response);
+    send_response(
+        stdin,
+        request_id,
+        ToolRequestUserInputResponse {
+            answers: response.answers,
+        },
+    )
+    .await?;
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterPromptAnswered {
+            answers,
+            answered_by,
+        },
+    });
+    Ok(())
+}
+
+async fn handle_elicitation_request(
+    stdin: &mut ChildStdin,
+    request_id: RequestId,
+    params: Option<serde_json::Value>,
+    prompts: &mut PromptContext,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let (server_name, message) = elicitation_prompt(params.as_ref());
+    let pending = PendingElicitation {
+        request_id,
+        server_name,
+        message,
+    };
+    if !prompts.policy.asks_user() {
+        let decision = prompts.policy.decide_elicitation();
+        return send_elicitation_response(
+            stdin,
+            pending,
+            decision,
+            PotterPromptAnsweredBy::Headless,
+            event_tx,
+        )
+        .await;
+    }
+
+    // Answered later, when the UI sends `Op::PotterElicitationResponse`.
+    prompts.next_pending_id += 1;
+    let id = format!("elicitation-{}", prompts.next_pending_id);
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterElicitationRequest {
+            id: id.clone(),
+            server_name: pending.server_name.clone(),
+            message: pending.message.clone(),
+        },
+    });
+    prompts.pending_elicitations.insert(id, pending);
+    Ok(())
+}
+
+/// Server name and message of an elicitation request; the message may sit at the top level or in
+/// a nested `request` object depending on the app-server version.
+fn elicitation_prompt(params: Option<&serde_json::Value>) -> (String, String) {
+    let read = |value: Option<&serde_json::Value>, key: &str| {
+        value
+            .and_then(|value| value.get(key))
+            .and_then(serde_json::Value::as_str)
+            .map(ToString::to_string)
+    };
+    let server_name = read(params, "serverName").unwrap_or_else(|| "MCP server".to_string());
+    let message = read(params, "message")
+        .or_else(|| read(params.and_then(|params| params.get("request")), "message"))
+        .unwrap_or_default();
+    (server_name, message)
+}
 
+async fn send_elicitation_response(
+    stdin: &mut ChildStdin,
+    pending: PendingElicitation,
+    decision: PotterElicitationDecision,
+    answered_by: PotterPromptAnsweredBy,
+    event_tx: &UnboundedSender<Event>,
+) -> anyhow::Result<()> {
+    let (action, answer) = match decision {
+        PotterElicitationDecision::Accept => (McpServerElicitationAction::Accept, "accepted"),
+        PotterElicitationDecision::Decline => (McpServerElicitationAction::Decline, "declined"),
+        PotterElicitationDecision::Cancel => (McpServerElicitationAction::Cancel, "cancelled"),
+    };
+    send_response(
+        stdin,
+        pending.request_id,
+        McpServerElicitationRequestResponse {
+            action,
+            content: None,
+        },
+    )
+    .await?;
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterPromptAnswered {
+            answers: vec![PotterPromptAnswer {
+                question: format!("{}: {}", pending.server_name, pending.message),
+                answer: answer.to_string(),
+            }],
+            answered_by,
+        },
+    });
     Ok(())
 }
 
+/// Report an answered approval request, so the control plane can record it.
+fn send_approval_decided(
+    event_tx: &UnboundedSender<Event>,
+    subject: ApprovalSubject,
+    decision: PotterApprovalDecision,
+    decided_by: PotterApprovalDecidedBy,
+) {
+    let _ = event_tx.send(Event {
+        id: "".to_string(),
+        msg: EventMsg::PotterApprovalDecided {
+            command: subject.command,
+            paths: subject.paths,
+            decision,
+            decided_by,
+        },
+    });
+}
+
 async fn send_message<T>(stdin: &mut ChildStdin, message: &T) -> anyhow::Result<()>
 where
     T: serde::Serialize,
@@ -956,7 +2406,16 @@ async fn read_until_response_or_error(
                 )?;
             }
             JSONRPCMessage::Request(request) => {
-                handle_server_request(stdin, request).await?;
+                handle_server_request(
+                    stdin,
+                    request,
+                    &mut recovery.approvals,
+                    &mut recovery.prompts,
+                    &mut recovery.command_guard,
+                    &recovery.recovery_action_tx,
+                    event_tx,
+                )
+                .await?;
             }
             _ => {}
         }
@@ -1024,6 +2483,14 @@ impl ThreadStartOrResume {
         }
     }
 
+    /// The thread's effective sandbox, from Codex's config and the `thread/start` overrides.
+    fn sandbox(&self) -> &SandboxPolicy {
+        match self {
+            ThreadStartOrResume::Start(resp) => &resp.sandbox,
+            ThreadStartOrResume::Resume(resp) => &resp.sandbox,
+        }
+    }
+
     fn rollout_path(&self) -> &Path {
         match self {
             ThreadStartOrResume::Start(resp) => resp.thread.path.as_path(),
@@ -1044,7 +2511,10 @@ mod stream_recovery_tests {
     use codex_protocol::protocol::AgentMessageDeltaEvent;
     use codex_protocol::protocol::CodexErrorInfo;
     use codex_protocol::protocol::ThreadRolledBackEvent;
+    use codex_protocol::protocol::TurnAbortReason;
+    use codex_protocol::protocol::TurnAbortedEvent;
     use codex_protocol::protocol::TurnCompleteEvent;
+    use codex_protocol::protocol::TurnStartedEvent;
     use pretty_assertions::assert_eq;
 
     fn retryable_error_event() -> ErrorEvent {
@@ -1068,6 +2538,15 @@ mod stream_recovery_tests {
             has_sent_turn_start: true,
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
         };
 
         handle_codex_event(
@@ -1149,6 +2628,90 @@ mod stream_recovery_tests {
         assert!(matches!(forwarded.msg, EventMsg::AgentMessageDelta(_)));
     }
 
+    #[test]
+    fn reused_process_drops_events_of_other_threads() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, _action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            has_sent_turn_start: false,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter {
+                reused_process: true,
+                thread_id: None,
+            },
+        };
+        let delta = |conversation_id: Option<&str>, text: &str| {
+            let mut params = serde_json::json!({
+                "id": "1",
+                "msg": { "type": "agent_message_delta", "delta": text },
+            });
+            if let Some(conversation_id) = conversation_id {
+                params["conversationId"] = conversation_id.into();
+            }
+            Some(params)
+        };
+        let forwarded_deltas = |event_rx: &mut UnboundedReceiver<Event>| {
+            std::iter::from_fn(|| event_rx.try_recv().ok())
+                .filter_map(|event| match event.msg {
+                    EventMsg::AgentMessageDelta(ev) => Some(ev.delta),
+                    _ => None,
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Before this round's thread is known, every named thread is a previous one.
+        handle_codex_event_notification(
+            "codex/event/agent_message_delta",
+            delta(Some("thread-1"), "late"),
+            &mut recovery,
+            &event_tx,
+        )
+        .expect("handle notification");
+        assert_eq!(forwarded_deltas(&mut event_rx), Vec::<String>::new());
+
+        recovery.thread_filter.thread_id = Some("thread-2".to_string());
+        for (conversation_id, text) in [
+            (Some("thread-1"), "late"),
+            (Some("thread-2"), "current"),
+            (None, "unnamed"),
+        ] {
+            handle_codex_event_notification(
+                "codex/event/agent_message_delta",
+                delta(conversation_id, text),
+                &mut recovery,
+                &event_tx,
+            )
+            .expect("handle notification");
+        }
+        assert_eq!(
+            forwarded_deltas(&mut event_rx),
+            vec!["current".to_string(), "unnamed".to_string()]
+        );
+
+        // A process that only ever served this round forwards everything.
+        recovery.thread_filter.reused_process = false;
+        handle_codex_event_notification(
+            "codex/event/agent_message_delta",
+            delta(Some("thread-1"), "other"),
+            &mut recovery,
+            &event_tx,
+        )
+        .expect("handle notification");
+        assert_eq!(forwarded_deltas(&mut event_rx), vec!["other".to_string()]);
+    }
+
     #[test]
     fn stream_recovery_ignores_retryable_error_before_first_turn_start() {
         let (event_tx, mut event_rx) = unbounded_channel::<Event>();
@@ -1160,6 +2723,15 @@ mod stream_recovery_tests {
             has_sent_turn_start: false,
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
         };
 
         handle_codex_event(
@@ -1195,6 +2767,15 @@ mod stream_recovery_tests {
             has_sent_turn_start: true,
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
         };
 
         let err = retryable_error_event();
@@ -1255,6 +2836,15 @@ mod stream_recovery_tests {
             has_sent_turn_start: true,
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
         };
 
         handle_codex_event(
@@ -1297,6 +2887,15 @@ mod stream_recovery_tests {
             has_sent_turn_start: true,
             has_finished_round: false,
             last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
         };
 
         handle_codex_event(
@@ -1318,6 +2917,409 @@ mod stream_recovery_tests {
         );
         assert!(!recovery.has_finished_round, "round should continue");
     }
+
+    #[test]
+    fn user_interrupt_keeps_round_open_until_continued_turn_completes() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, _action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            has_sent_turn_start: true,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            active_turn_id: None,
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
+        };
+
+        handle_codex_event(
+            Event {
+                id: "turn-started".into(),
+                msg: EventMsg::TurnStarted(TurnStartedEvent {
+                    turn_id: "turn-1".to_string(),
+                    model_context_window: None,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert_eq!(recovery.active_turn_id.as_deref(), Some("turn-1"));
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TurnStarted(_)
+        ));
+
+        recovery.turn_interrupt = TurnInterrupt::Requested;
+        handle_codex_event(
+            Event {
+                id: "turn-aborted".into(),
+                msg: EventMsg::TurnAborted(TurnAbortedEvent {
+                    turn_id: Some("turn-1".to_string()),
+                    reason: TurnAbortReason::Interrupted,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TurnAborted(_)
+        ));
+        assert!(
+            event_rx.try_recv().is_err(),
+            "expected the round to stay open after a user interrupt"
+        );
+        assert_eq!(recovery.turn_interrupt, TurnInterrupt::AwaitingDecision);
+        assert_eq!(recovery.active_turn_id, None);
+
+        // The UI continued the round with a new instruction.
+        recovery.turn_interrupt = TurnInterrupt::None;
+        recovery.continued_after_interrupt = true;
+        handle_codex_event(
+            Event {
+                id: "turn-complete".into(),
+                msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: "turn-2".to_string(),
+                    last_agent_message: None,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TurnComplete(_)
+        ));
+        let round_finished = event_rx.try_recv().expect("expected round finished marker");
+        assert!(matches!(
+            round_finished.msg,
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::InterruptedThenContinued
+            }
+        ));
+    }
+
+    #[test]
+    fn blocked_command_interrupts_turn_and_finishes_round() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, mut action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            has_sent_turn_start: true,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            active_turn_id: Some("turn-1".to_string()),
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext {
+                workdir: PathBuf::from("/repo"),
+                ..CommandGuardContext::default()
+            },
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
+        };
+
+        handle_codex_event(
+            Event {
+                id: "exec-begin".into(),
+                msg: EventMsg::ExecCommandBegin(codex_protocol::protocol::ExecCommandBeginEvent {
+                    call_id: "call-1".to_string(),
+                    process_id: None,
+                    turn_id: "turn-1".to_string(),
+                    command: vec![
+                        "/bin/bash".to_string(),
+                        "-lc".to_string(),
+                        "git push origin main".to_string(),
+                    ],
+                    cwd: PathBuf::from("/repo"),
+                    parsed_cmd: Vec::new(),
+                    source: Default::default(),
+                    interaction_input: None,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::ExecCommandBegin(_)
+        ));
+        assert_eq!(
+            action_rx.try_recv().expect("interrupt scheduled"),
+            RecoveryAction::InterruptBlockedTurn
+        );
+        assert!(event_rx.try_recv().is_err());
+
+        handle_codex_event(
+            Event {
+                id: "turn-aborted".into(),
+                msg: EventMsg::TurnAborted(TurnAbortedEvent {
+                    turn_id: Some("turn-1".to_string()),
+                    reason: TurnAbortReason::Interrupted,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TurnAborted(_)
+        ));
+        let EventMsg::PotterRoundFinished { outcome } = event_rx.try_recv().expect("event").msg
+        else {
+            panic!("expected round finished marker");
+        };
+        assert_eq!(
+            outcome,
+            PotterRoundOutcome::CommandBlocked {
+                command: vec![
+                    "git".to_string(),
+                    "push".to_string(),
+                    "origin".to_string(),
+                    "main".to_string(),
+                ],
+                rule: "builtin: git push".to_string(),
+            }
+        );
+        assert_eq!(recovery.command_guard.blocked, None);
+    }
+
+    fn token_count_event(total_tokens: i64) -> Event {
+        Event {
+            id: "token-count".into(),
+            msg: EventMsg::TokenCount(codex_protocol::protocol::TokenCountEvent {
+                info: Some(codex_protocol::protocol::TokenUsageInfo {
+                    total_token_usage: TokenUsage {
+                        total_tokens,
+                        ..TokenUsage::default()
+                    },
+                    last_token_usage: TokenUsage {
+                        total_tokens,
+                        ..TokenUsage::default()
+                    },
+                    model_context_window: Some(112_000),
+                }),
+                rate_limits: None,
+            }),
+        }
+    }
+
+    #[test]
+    fn context_checkpoint_interrupts_turn_and_asks_to_save_progress() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, mut action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            has_sent_turn_start: true,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            active_turn_id: Some("turn-1".to_string()),
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext {
+                checkpoint: Some(ContextCheckpoint {
+                    percent_left: 10,
+                    prompt: "Save your progress".to_string(),
+                }),
+                ..ContextWindowContext::default()
+            },
+            thread_filter: ThreadFilter::default(),
+        };
+
+        // 62% left: nothing happens.
+        handle_codex_event(token_count_event(50_000), &mut recovery, &event_tx);
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TokenCount(_)
+        ));
+        assert!(action_rx.try_recv().is_err());
+
+        // 8% left: the turn is interrupted, once.
+        handle_codex_event(token_count_event(104_000), &mut recovery, &event_tx);
+        assert!(matches!(
+            event_rx.try_recv().expect("checkpoint notice").msg,
+            EventMsg::PotterContextCheckpoint { percent_left: 8 }
+        ));
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TokenCount(_)
+        ));
+        assert_eq!(
+            action_rx.try_recv().expect("interrupt scheduled"),
+            RecoveryAction::InterruptForContextCheckpoint
+        );
+        handle_codex_event(token_count_event(105_000), &mut recovery, &event_tx);
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TokenCount(_)
+        ));
+        assert!(action_rx.try_recv().is_err());
+
+        // The interrupted turn does not end the round; the checkpoint turn follows.
+        handle_codex_event(
+            Event {
+                id: "turn-aborted".into(),
+                msg: EventMsg::TurnAborted(TurnAbortedEvent {
+                    turn_id: Some("turn-1".to_string()),
+                    reason: TurnAbortReason::Interrupted,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(event_rx.try_recv().is_err());
+        assert_eq!(
+            action_rx.try_recv().expect("checkpoint turn scheduled"),
+            RecoveryAction::StartContextCheckpointTurn
+        );
+        assert!(!recovery.has_finished_round);
+
+        handle_codex_event(
+            Event {
+                id: "turn-complete".into(),
+                msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                    turn_id: "turn-2".to_string(),
+                    last_agent_message: None,
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("forwarded").msg,
+            EventMsg::TurnComplete(_)
+        ));
+        assert!(matches!(
+            event_rx.try_recv().expect("round finished marker").msg,
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::ContextExhausted
+            }
+        ));
+    }
+
+    #[test]
+    fn context_window_exceeded_finishes_round_as_context_exhausted() {
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (action_tx, mut action_rx) = unbounded_channel::<RecoveryAction>();
+        let mut recovery = StreamRecoveryContext {
+            stream_recovery: PotterStreamRecovery::new(),
+            recovery_action_tx: action_tx,
+            pending_continue_retry: None,
+            has_sent_turn_start: true,
+            has_finished_round: false,
+            last_turn_start_was_recovery_continue: false,
+            active_turn_id: Some("turn-1".to_string()),
+            turn_interrupt: TurnInterrupt::None,
+            continued_after_interrupt: false,
+            approvals: ApprovalContext::default(),
+            prompts: PromptContext::default(),
+            command_guard: CommandGuardContext::default(),
+            turn_settings: TurnSettings::default(),
+            context_window: ContextWindowContext::default(),
+            thread_filter: ThreadFilter::default(),
+        };
+
+        handle_codex_event(
+            Event {
+                id: "err".into(),
+                msg: EventMsg::Error(ErrorEvent {
+                    message: "Codex ran out of room in the model's context window.".to_string(),
+                    codex_error_info: Some(CodexErrorInfo::ContextWindowExceeded),
+                }),
+            },
+            &mut recovery,
+            &event_tx,
+        );
+        assert!(matches!(
+            event_rx.try_recv().expect("round finished marker").msg,
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::ContextExhausted
+            }
+        ));
+        assert!(event_rx.try_recv().is_err());
+        assert!(action_rx.try_recv().is_err());
+    }
+
+    #[test]
+    fn round_timeouts_pick_the_earliest_limit_that_applies() {
+        let started = tokio::time::Instant::now();
+        let last_activity = started + std::time::Duration::from_secs(50);
+        let timeouts = RoundTimeouts {
+            idle: Some(std::time::Duration::from_secs(60)),
+            round: Some(std::time::Duration::from_secs(100)),
+            ..RoundTimeouts::default()
+        };
+
+        assert_eq!(
+            timeouts.next_timeout(started, last_activity, true, 0),
+            Some((
+                started + std::time::Duration::from_secs(100),
+                PotterRoundTimeout::Round { limit_secs: 100 }
+            ))
+        );
+        assert_eq!(
+            timeouts.next_timeout(started, started, true, 0),
+            Some((
+                started + std::time::Duration::from_secs(60),
+                PotterRoundTimeout::Idle { limit_secs: 60 }
+            ))
+        );
+        // The idle limit does not apply while the turn waits for the user.
+        assert_eq!(
+            RoundTimeouts {
+                round: None,
+                ..timeouts
+            }
+            .next_timeout(started, started, false, 0),
+            None
+        );
+        assert_eq!(
+            RoundTimeouts::default().next_timeout(started, started, true, 0),
+            None
+        );
+    }
+
+    #[test]
+    fn round_timeouts_stop_the_round_when_the_session_budget_runs_out() {
+        let started = tokio::time::Instant::now();
+        let timeouts = RoundTimeouts {
+            round: Some(std::time::Duration::from_secs(100)),
+            budget_duration: Some(std::time::Duration::from_secs(30)),
+            budget_tokens: Some(1_000),
+            ..RoundTimeouts::default()
+        };
+
+        assert_eq!(
+            timeouts.next_timeout(started, started, true, 999),
+            Some((
+                started + std::time::Duration::from_secs(30),
+                PotterRoundTimeout::Budget
+            ))
+        );
+        assert_eq!(
+            timeouts.next_timeout(started, started, true, 1_000),
+            Some((started, PotterRoundTimeout::Budget))
+        );
+    }
 }
 
 #[cfg(test)]
@@ -1329,6 +3331,33 @@ mod tests {
     use tokio::time::Duration;
     use tokio::time::timeout;
 
+    #[test]
+    fn with_default_sandbox_only_fills_in_unset_sandbox() {
+        use crate::app_server_protocol::SandboxMode;
+
+        let unset = AppServerLaunchConfig::from_cli(crate::CliSandbox::Default, false);
+        assert_eq!(
+            unset.with_default_sandbox(Some(SandboxMode::WorkspaceWrite)),
+            AppServerLaunchConfig {
+                spawn_sandbox: Some(SandboxMode::WorkspaceWrite),
+                thread_sandbox: Some(SandboxMode::WorkspaceWrite),
+                bypass_approvals_and_sandbox: false,
+            }
+        );
+
+        let explicit = AppServerLaunchConfig::from_cli(crate::CliSandbox::DangerFullAccess, false);
+        assert_eq!(
+            explicit.with_default_sandbox(Some(SandboxMode::WorkspaceWrite)),
+            explicit
+        );
+
+        let bypass = AppServerLaunchConfig::from_cli(crate::CliSandbox::Default, true);
+        assert_eq!(
+            bypass.with_default_sandbox(Some(SandboxMode::WorkspaceWrite)),
+            bypass
+        );
+    }
+
     #[cfg(unix)]
     #[tokio::test]
     async fn backend_allows_another_turn_after_turn_complete() {
@@ -1404,6 +3433,14 @@ done
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -1548,12 +3585,579 @@ echo "$turn_start" | grep -q '"text":"Continue"' || {{
 touch "$MARKER"
 echo '{{"id":6,"result":{{}}}}'
 
-# Wait for the client to close stdin to request shutdown.
+# Wait for the client to close stdin to request shutdown.
+while IFS= read -r _line; do
+  :
+done
+"#,
+            marker = marker.display()
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let (event_tx, _event_rx) = unbounded_channel::<Event>();
+        let (fatal_exit_tx, _fatal_exit_rx) = unbounded_channel::<String>();
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let backend = tokio::spawn(async move {
+            run_app_server_backend_inner(
+                AppServerBackendConfig {
+                    codex_bin: codex_bin.display().to_string(),
+                    developer_instructions: None,
+                    launch: AppServerLaunchConfig {
+                        spawn_sandbox: None,
+                        thread_sandbox: None,
+                        bypass_approvals_and_sandbox: false,
+                    },
+                    codex_home: None,
+                    thread_cwd: None,
+                    resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
+                },
+                &mut op_rx,
+                &event_tx,
+                &fatal_exit_tx,
+            )
+            .await
+        });
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send user input");
+
+        timeout(Duration::from_secs(10), async {
+            while !marker.exists() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for dummy server marker");
+
+        drop(op_tx);
+
+        timeout(Duration::from_secs(5), backend)
+            .await
+            .expect("backend timed out")
+            .expect("backend panicked")
+            .expect("backend failed");
+
+        assert!(
+            marker.exists(),
+            "dummy server did not observe rollback+continue"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_respawns_crashed_app_server_and_resumes_thread() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let spawns = temp.path().join("spawns");
+        let marker = temp.path().join("saw-resume-then-continue");
+
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+SPAWNS="{spawns}"
+MARKER="{marker}"
+THREAD='{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}'
+
+echo spawned >> "$SPAWNS"
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+if [[ "$(wc -l < "$SPAWNS")" -eq 1 ]]; then
+  # thread/start request
+  IFS= read -r _line
+  echo '{{"id":2,"result":'"$THREAD"'}}'
+
+  # turn/start request, then crash mid-turn
+  IFS= read -r _line
+  echo '{{"id":3,"result":{{}}}}'
+  echo "fatal: out of memory" >&2
+  exit 137
+fi
+
+# thread/resume request for the same thread
+IFS= read -r resume
+echo "$resume" | grep -q '"method":"thread/resume"' || {{
+  echo "expected thread/resume, got: $resume" >&2
+  exit 1
+}}
+echo "$resume" | grep -q '"threadId":"00000000-0000-0000-0000-000000000000"' || {{
+  echo "expected the crashed thread id, got: $resume" >&2
+  exit 1
+}}
+echo '{{"id":2,"result":'"$THREAD"'}}'
+
+# turn/start request (automatic Continue)
+IFS= read -r turn_start
+echo "$turn_start" | grep -q '"text":"Continue"' || {{
+  echo "expected Continue prompt, got: $turn_start" >&2
+  exit 1
+}}
+touch "$MARKER"
+echo '{{"id":3,"result":{{}}}}'
+
+# Wait for the client to close stdin to request shutdown.
+while IFS= read -r _line; do
+  :
+done
+"#,
+            spawns = spawns.display(),
+            marker = marker.display()
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (fatal_exit_tx, mut fatal_exit_rx) = unbounded_channel::<String>();
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let backend = tokio::spawn(async move {
+            run_app_server_backend_inner(
+                AppServerBackendConfig {
+                    codex_bin: codex_bin.display().to_string(),
+                    developer_instructions: None,
+                    launch: AppServerLaunchConfig {
+                        spawn_sandbox: None,
+                        thread_sandbox: None,
+                        bypass_approvals_and_sandbox: false,
+                    },
+                    codex_home: None,
+                    thread_cwd: None,
+                    resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
+                },
+                &mut op_rx,
+                &event_tx,
+                &fatal_exit_tx,
+            )
+            .await
+        });
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send user input");
+
+        timeout(Duration::from_secs(10), async {
+            while !marker.exists() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for dummy server marker");
+
+        drop(op_tx);
+
+        timeout(Duration::from_secs(5), backend)
+            .await
+            .expect("backend timed out")
+            .expect("backend panicked")
+            .expect("backend failed");
+
+        assert!(fatal_exit_rx.try_recv().is_err());
+        let mut update = None;
+        while let Ok(event) = event_rx.try_recv() {
+            if let EventMsg::PotterStreamRecoveryUpdate {
+                attempt,
+                error_message,
+                ..
+            } = event.msg
+            {
+                update = Some((attempt, error_message));
+            }
+        }
+        let (attempt, error_message) = update.expect("expected PotterStreamRecoveryUpdate");
+        assert_eq!(attempt, 1);
+        assert!(
+            error_message.contains("codex app-server exited unexpectedly")
+                && error_message.contains("fatal: out of memory"),
+            "unexpected error message: {error_message}"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_kills_silent_app_server_after_idle_timeout() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+
+        let script = r#"#!/usr/bin/env bash
+set -euo pipefail
+
+# initialize request
+IFS= read -r _line
+echo '{"id":1,"result":{}}'
+
+# initialized notification
+IFS= read -r _line
+
+# thread/start request
+IFS= read -r _line
+echo '{"id":2,"result":{"thread":{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{"type":"readOnly"},"reasoningEffort":null}}'
+
+# turn/start request, then hang mid-turn like a stuck command
+IFS= read -r _line
+echo '{"id":3,"result":{}}'
+echo '{"method":"codex/event/task_started","params":{"id":"1","msg":{"type":"task_started","turn_id":"turn-1","model_context_window":null}}}'
+sleep 30
+"#;
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (fatal_exit_tx, mut fatal_exit_rx) = unbounded_channel::<String>();
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let backend = tokio::spawn(async move {
+            run_app_server_backend_inner(
+                AppServerBackendConfig {
+                    codex_bin: codex_bin.display().to_string(),
+                    developer_instructions: None,
+                    launch: AppServerLaunchConfig {
+                        spawn_sandbox: None,
+                        thread_sandbox: None,
+                        bypass_approvals_and_sandbox: false,
+                    },
+                    codex_home: None,
+                    thread_cwd: None,
+                    resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts {
+                        idle: Some(Duration::from_secs(1)),
+                        ..RoundTimeouts::default()
+                    },
+                },
+                &mut op_rx,
+                &event_tx,
+                &fatal_exit_tx,
+            )
+            .await
+        });
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send user input");
+
+        let outcome = timeout(Duration::from_secs(10), async {
+            loop {
+                let event = event_rx.recv().await.expect("backend event");
+                if let EventMsg::PotterRoundFinished { outcome } = event.msg {
+                    return outcome;
+                }
+            }
+        })
+        .await
+        .expect("timed out waiting for the watchdog");
+        assert_eq!(
+            outcome,
+            PotterRoundOutcome::TimedOut {
+                timeout: PotterRoundTimeout::Idle { limit_secs: 1 },
+            }
+        );
+
+        drop(op_tx);
+
+        timeout(Duration::from_secs(5), backend)
+            .await
+            .expect("backend timed out")
+            .expect("backend panicked")
+            .expect("backend failed");
+        assert!(fatal_exit_rx.try_recv().is_err());
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_user_input_cancels_pending_stream_recovery_continue() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let marker = temp.path().join("saw-manual-input-without-auto-continue");
+
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+MARKER="{marker}"
+
+if [[ "${{1:-}}" != "app-server" ]]; then
+  echo "expected app-server, got: $*" >&2
+  exit 1
+fi
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+# thread/start request
+IFS= read -r _line
+echo '{{"id":2,"result":{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}}}'
+
+# first turn/start request
+IFS= read -r _line
+echo '{{"id":3,"result":{{}}}}'
+
+# signal a retryable stream error, followed by an empty completion
+echo '{{"method":"codex/event/test","params":{{"id":"err-1","msg":{{"type":"error","message":"stream disconnected before completion: error sending request for url (...)"}}}}}}'
+echo '{{"method":"codex/event/test","params":{{"id":"tc-1","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
+
+# second turn/start request (first automatic Continue)
+IFS= read -r turn_start
+echo "$turn_start" | grep -q '"method":"turn/start"' || {{
+  echo "expected turn/start, got: $turn_start" >&2
+  exit 1
+}}
+echo "$turn_start" | grep -q '"text":"Continue"' || {{
+  echo "expected Continue prompt, got: $turn_start" >&2
+  exit 1
+}}
+echo '{{"id":4,"result":{{}}}}'
+
+# signal another retryable stream error (attempt 2), which would normally schedule a rollback+Continue
+echo '{{"method":"codex/event/test","params":{{"id":"err-2","msg":{{"type":"error","message":"stream disconnected before completion: error sending request for url (...)"}}}}}}'
+echo '{{"method":"codex/event/test","params":{{"id":"tc-2","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
+
+# third turn/start request (manual user input should cancel the pending automatic Continue)
+IFS= read -r turn_start
+echo "$turn_start" | grep -q '"method":"turn/start"' || {{
+  if echo "$turn_start" | grep -q '"method":"thread/rollback"'; then
+    echo "unexpected thread/rollback before manual input: $turn_start" >&2
+  else
+    echo "expected manual turn/start, got: $turn_start" >&2
+  fi
+  exit 1
+}}
+echo "$turn_start" | grep -q '"text":"manual"' || {{
+  echo "expected manual prompt, got: $turn_start" >&2
+  exit 1
+}}
+echo '{{"id":5,"result":{{}}}}'
+
+# Ensure the pending retry action does not fire after the manual input.
+if IFS= read -r -t 2 unexpected; then
+  echo "expected no further requests, got: $unexpected" >&2
+  exit 1
+fi
+
+touch "$MARKER"
+
+# Wait for the client to close stdin to request shutdown.
+while IFS= read -r _line; do
+  :
+done
+"#,
+            marker = marker.display(),
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
+        let (fatal_exit_tx, _fatal_exit_rx) = unbounded_channel::<String>();
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let backend = tokio::spawn(async move {
+            run_app_server_backend_inner(
+                AppServerBackendConfig {
+                    codex_bin: codex_bin.display().to_string(),
+                    developer_instructions: None,
+                    launch: AppServerLaunchConfig {
+                        spawn_sandbox: None,
+                        thread_sandbox: None,
+                        bypass_approvals_and_sandbox: false,
+                    },
+                    codex_home: None,
+                    thread_cwd: None,
+                    resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
+                },
+                &mut op_rx,
+                &event_tx,
+                &fatal_exit_tx,
+            )
+            .await
+        });
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send user input");
+
+        let saw_second_attempt = timeout(Duration::from_secs(5), async {
+            while let Some(event) = event_rx.recv().await {
+                if let EventMsg::PotterStreamRecoveryUpdate { attempt, .. } = event.msg
+                    && attempt == 2
+                {
+                    return true;
+                }
+            }
+            false
+        })
+        .await;
+        assert_eq!(
+            saw_second_attempt,
+            Ok(true),
+            "did not observe stream recovery attempt 2"
+        );
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "manual".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send manual input");
+
+        timeout(Duration::from_secs(5), async {
+            while !marker.exists() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for dummy server marker");
+
+        drop(op_tx);
+
+        timeout(Duration::from_secs(5), backend)
+            .await
+            .expect("backend timed out")
+            .expect("backend panicked")
+            .expect("backend failed");
+
+        assert!(
+            marker.exists(),
+            "dummy server did not observe manual input without auto continue"
+        );
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_answers_user_input_request_headless() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let response_path = temp.path().join("user-input-response.json");
+
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+RESPONSE="{response}"
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+# thread/start request
+IFS= read -r _line
+echo '{{"id":2,"result":{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}}}'
+
+# turn/start request
+IFS= read -r _line
+echo '{{"id":3,"result":{{}}}}'
+
+# ask a question and record the answer
+echo '{{"id":"req-1","method":"item/tool/requestUserInput","params":{{"threadId":"thread-1","turnId":"turn-1","itemId":"call-1","questions":[{{"id":"scope","header":"Scope","question":"Which crate?","options":[{{"label":"cli","description":"The binary"}}]}}]}}}}'
+IFS= read -r line
+printf '%s\n' "$line" > "$RESPONSE"
+
+echo '{{"method":"codex/event/test","params":{{"id":"1","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
+
 while IFS= read -r _line; do
   :
 done
 "#,
-            marker = marker.display()
+            response = response_path.display()
         );
 
         std::fs::write(&codex_bin, script).expect("write dummy codex");
@@ -1563,7 +4167,7 @@ done
         perms.set_mode(0o755);
         std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
 
-        let (event_tx, _event_rx) = unbounded_channel::<Event>();
+        let (event_tx, mut event_rx) = unbounded_channel::<Event>();
         let (fatal_exit_tx, _fatal_exit_rx) = unbounded_channel::<String>();
 
         let (op_tx, mut op_rx) = unbounded_channel::<Op>();
@@ -1580,6 +4184,14 @@ done
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -1598,47 +4210,64 @@ done
             })
             .expect("send user input");
 
-        timeout(Duration::from_secs(10), async {
-            while !marker.exists() {
-                tokio::time::sleep(Duration::from_millis(10)).await;
+        let answered = timeout(Duration::from_secs(5), async {
+            while let Some(event) = event_rx.recv().await {
+                if let EventMsg::PotterPromptAnswered {
+                    answers,
+                    answered_by,
+                } = event.msg
+                {
+                    return Some((answers, answered_by));
+                }
             }
+            None
         })
         .await
-        .expect("timed out waiting for dummy server marker");
+        .expect("timed out waiting for PotterPromptAnswered");
+        let Some((answers, answered_by)) = answered else {
+            panic!("event channel closed before PotterPromptAnswered");
+        };
+        assert_eq!(answered_by, PotterPromptAnsweredBy::Headless);
+        assert_eq!(
+            answers,
+            vec![PotterPromptAnswer {
+                question: "Which crate?".to_string(),
+                answer: codex_protocol::request_user_input::DECIDE_YOURSELF_ANSWER.to_string(),
+            }]
+        );
 
         drop(op_tx);
-
         timeout(Duration::from_secs(5), backend)
             .await
             .expect("backend timed out")
             .expect("backend panicked")
             .expect("backend failed");
 
-        assert!(
-            marker.exists(),
-            "dummy server did not observe rollback+continue"
+        let response: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(&response_path).expect("read recorded response"),
+        )
+        .expect("decode recorded response");
+        assert_eq!(response["id"], "req-1");
+        assert_eq!(
+            response["result"]["answers"]["scope"]["answers"][0],
+            codex_protocol::request_user_input::DECIDE_YOURSELF_ANSWER
         );
     }
 
     #[cfg(unix)]
     #[tokio::test]
-    async fn backend_user_input_cancels_pending_stream_recovery_continue() {
+    async fn backend_denies_guarded_command_at_its_approval_request() {
         use std::os::unix::fs::PermissionsExt;
 
         let temp = tempfile::tempdir().expect("tempdir");
         let codex_bin = temp.path().join("dummy-codex");
-        let marker = temp.path().join("saw-manual-input-without-auto-continue");
+        let response_path = temp.path().join("approval-response.json");
 
         let script = format!(
             r#"#!/usr/bin/env bash
 set -euo pipefail
 
-MARKER="{marker}"
-
-if [[ "${{1:-}}" != "app-server" ]]; then
-  echo "expected app-server, got: $*" >&2
-  exit 1
-fi
+RESPONSE="{response}"
 
 # initialize request
 IFS= read -r _line
@@ -1651,60 +4280,22 @@ IFS= read -r _line
 IFS= read -r _line
 echo '{{"id":2,"result":{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}}}'
 
-# first turn/start request
+# turn/start request
 IFS= read -r _line
 echo '{{"id":3,"result":{{}}}}'
 
-# signal a retryable stream error, followed by an empty completion
-echo '{{"method":"codex/event/test","params":{{"id":"err-1","msg":{{"type":"error","message":"stream disconnected before completion: error sending request for url (...)"}}}}}}'
-echo '{{"method":"codex/event/test","params":{{"id":"tc-1","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
-
-# second turn/start request (first automatic Continue)
-IFS= read -r turn_start
-echo "$turn_start" | grep -q '"method":"turn/start"' || {{
-  echo "expected turn/start, got: $turn_start" >&2
-  exit 1
-}}
-echo "$turn_start" | grep -q '"text":"Continue"' || {{
-  echo "expected Continue prompt, got: $turn_start" >&2
-  exit 1
-}}
-echo '{{"id":4,"result":{{}}}}'
-
-# signal another retryable stream error (attempt 2), which would normally schedule a rollback+Continue
-echo '{{"method":"codex/event/test","params":{{"id":"err-2","msg":{{"type":"error","message":"stream disconnected before completion: error sending request for url (...)"}}}}}}'
-echo '{{"method":"codex/event/test","params":{{"id":"tc-2","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
-
-# third turn/start request (manual user input should cancel the pending automatic Continue)
-IFS= read -r turn_start
-echo "$turn_start" | grep -q '"method":"turn/start"' || {{
-  if echo "$turn_start" | grep -q '"method":"thread/rollback"'; then
-    echo "unexpected thread/rollback before manual input: $turn_start" >&2
-  else
-    echo "expected manual turn/start, got: $turn_start" >&2
-  fi
-  exit 1
-}}
-echo "$turn_start" | grep -q '"text":"manual"' || {{
-  echo "expected manual prompt, got: $turn_start" >&2
-  exit 1
-}}
-echo '{{"id":5,"result":{{}}}}'
-
-# Ensure the pending retry action does not fire after the manual input.
-if IFS= read -r -t 2 unexpected; then
-  echo "expected no further requests, got: $unexpected" >&2
-  exit 1
-fi
+# ask to run a forbidden command and record the answer
+echo '{{"id":"req-1","method":"item/commandExecution/requestApproval","params":{{"threadId":"thread-1","turnId":"turn-1","itemId":"call-1","command":"git push origin main","cwd":"/repo"}}}}'
+IFS= read -r line
+printf '%s\n' "$line" > "$RESPONSE"
 
-touch "$MARKER"
+echo '{{"method":"codex/event/test","params":{{"id":"1","msg":{{"type":"turn_complete","last_agent_message":null}}}}}}'
 
-# Wait for the client to close stdin to request shutdown.
 while IFS= read -r _line; do
   :
 done
 "#,
-            marker = marker.display(),
+            response = response_path.display()
         );
 
         std::fs::write(&codex_bin, script).expect("write dummy codex");
@@ -1731,6 +4322,14 @@ done
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -1749,53 +4348,60 @@ done
             })
             .expect("send user input");
 
-        let saw_second_attempt = timeout(Duration::from_secs(5), async {
+        let events = timeout(Duration::from_secs(5), async {
+            let mut decided = None;
             while let Some(event) = event_rx.recv().await {
-                if let EventMsg::PotterStreamRecoveryUpdate { attempt, .. } = event.msg
-                    && attempt == 2
-                {
-                    return true;
+                match event.msg {
+                    EventMsg::PotterApprovalRequest { .. } => {
+                        panic!("a guarded command must not reach the approval rules")
+                    }
+                    EventMsg::PotterApprovalDecided {
+                        decision,
+                        decided_by,
+                        ..
+                    } => decided = Some((decision, decided_by)),
+                    EventMsg::PotterRoundFinished { outcome } => return (decided, Some(outcome)),
+                    _ => {}
                 }
             }
-            false
+            (decided, None)
         })
-        .await;
+        .await
+        .expect("timed out waiting for PotterRoundFinished");
         assert_eq!(
-            saw_second_attempt,
-            Ok(true),
-            "did not observe stream recovery attempt 2"
+            events,
+            (
+                Some((
+                    PotterApprovalDecision::Deny,
+                    PotterApprovalDecidedBy::CommandGuard {
+                        rule: "builtin: git push".to_string(),
+                    }
+                )),
+                Some(PotterRoundOutcome::CommandBlocked {
+                    command: vec![
+                        "git".to_string(),
+                        "push".to_string(),
+                        "origin".to_string(),
+                        "main".to_string(),
+                    ],
+                    rule: "builtin: git push".to_string(),
+                })
+            )
         );
 
-        op_tx
-            .send(Op::UserInput {
-                items: vec![UserInput::Text {
-                    text: "manual".to_string(),
-                    text_elements: Vec::new(),
-                }],
-                final_output_json_schema: None,
-            })
-            .expect("send manual input");
-
-        timeout(Duration::from_secs(5), async {
-            while !marker.exists() {
-                tokio::time::sleep(Duration::from_millis(10)).await;
-            }
-        })
-        .await
-        .expect("timed out waiting for dummy server marker");
-
         drop(op_tx);
-
         timeout(Duration::from_secs(5), backend)
             .await
             .expect("backend timed out")
             .expect("backend panicked")
             .expect("backend failed");
 
-        assert!(
-            marker.exists(),
-            "dummy server did not observe manual input without auto continue"
-        );
+        let response: serde_json::Value = serde_json::from_str(
+            &std::fs::read_to_string(&response_path).expect("read recorded response"),
+        )
+        .expect("decode recorded response");
+        assert_eq!(response["id"], "req-1");
+        assert_eq!(response["result"]["decision"], "decline");
     }
 
     #[cfg(unix)]
@@ -1879,6 +4485,14 @@ touch "$MARKER"
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -1979,6 +4593,14 @@ touch "$MARKER"
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2064,6 +4686,14 @@ touch "$MARKER"
                     codex_home: None,
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2158,6 +4788,14 @@ touch "$MARKER"
                     codex_home: Some(codex_home),
                     thread_cwd: None,
                     resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides::default(),
+                    workspace_write: WorkspaceWriteSettings::default(),
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
                 },
                 &mut op_rx,
                 &event_tx,
@@ -2170,4 +4808,229 @@ touch "$MARKER"
 
         assert!(marker.exists(), "dummy server did not observe stdin EOF");
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn backend_merges_sandbox_settings_into_the_thread_sandbox() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let marker = temp.path().join("saw-turn-start");
+
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+MARKER="{marker}"
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+# thread/start request
+IFS= read -r thread_start
+for expected in '"model":"o3"' '"profile":"work"' '"model_verbosity":"low"' '"sandbox_workspace_write.network_access":true'; do
+  echo "$thread_start" | grep -Fq "$expected" || {{
+    echo "expected $expected in thread/start, got: $thread_start" >&2
+    exit 1
+  }}
+done
+# Codex applied the network access override to the thread's sandbox.
+echo '{{"id":2,"result":{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"o3","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"workspaceWrite","writableRoots":["/repo/shared"],"networkAccess":true}},"reasoningEffort":"high"}}}}'
+
+# turn/start request: `--writable-root` is added to the thread's sandbox, which keeps its network
+# access.
+IFS= read -r turn_start
+for expected in '"effort":"high"' '"type":"workspaceWrite"' '"writableRoots":["/repo/shared","/repo/cache"]' '"networkAccess":true'; do
+  echo "$turn_start" | grep -Fq "$expected" || {{
+    echo "expected $expected in turn/start, got: $turn_start" >&2
+    exit 1
+  }}
+done
+touch "$MARKER"
+echo '{{"id":3,"result":{{}}}}'
+
+# Wait for the client to close stdin to request shutdown.
+while IFS= read -r _line; do
+  :
+done
+"#,
+            marker = marker.display()
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let (event_tx, _event_rx) = unbounded_channel::<Event>();
+        let (fatal_exit_tx, _fatal_exit_rx) = unbounded_channel::<String>();
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let backend = tokio::spawn(async move {
+            run_app_server_backend_inner(
+                AppServerBackendConfig {
+                    codex_bin: codex_bin.display().to_string(),
+                    developer_instructions: None,
+                    launch: AppServerLaunchConfig {
+                        spawn_sandbox: None,
+                        thread_sandbox: None,
+                        bypass_approvals_and_sandbox: false,
+                    },
+                    codex_home: None,
+                    thread_cwd: None,
+                    resume_thread_id: None,
+                    approval_policy: ApprovalPolicy::default(),
+                    user_input_policy: UserInputPolicy::default(),
+                    command_guard: CommandGuard::default(),
+                    codex_overrides: CodexOverrides {
+                        model: Some("o3".to_string()),
+                        profile: Some("work".to_string()),
+                        effort: Some(ReasoningEffort::High),
+                        config: std::collections::BTreeMap::from([
+                            ("model_verbosity".to_string(), serde_json::json!("low")),
+                            (
+                                "sandbox_workspace_write.network_access".to_string(),
+                                serde_json::json!(true),
+                            ),
+                        ]),
+                        phases: std::collections::BTreeMap::new(),
+                    },
+                    workspace_write: WorkspaceWriteSettings {
+                        writable_roots: vec![PathBuf::from("/repo/cache")],
+                        ..WorkspaceWriteSettings::default()
+                    },
+                    shared_app_server: None,
+                    context_checkpoint: None,
+                    timeouts: RoundTimeouts::default(),
+                },
+                &mut op_rx,
+                &event_tx,
+                &fatal_exit_tx,
+            )
+            .await
+        });
+
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: "hello".to_string(),
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .expect("send user input");
+
+        timeout(Duration::from_secs(5), async {
+            while !marker.exists() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .expect("timed out waiting for dummy server marker");
+
+        drop(op_tx);
+
+        timeout(Duration::from_secs(5), backend)
+            .await
+            .expect("backend timed out")
+            .expect("backend panicked")
+            .expect("backend failed");
+    }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn shared_app_server_is_reused_across_rounds() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let codex_bin = temp.path().join("dummy-codex");
+        let spawns = temp.path().join("spawns");
+
+        let script = format!(
+            r#"#!/usr/bin/env bash
+set -euo pipefail
+
+echo spawned >> "{spawns}"
+
+# initialize request
+IFS= read -r _line
+echo '{{"id":1,"result":{{}}}}'
+
+# initialized notification
+IFS= read -r _line
+
+# One thread/start request per round until the client closes stdin.
+id=2
+while IFS= read -r thread_start; do
+  echo "$thread_start" | grep -q '"method":"thread/start"' || {{
+    echo "expected thread/start, got: $thread_start" >&2
+    exit 1
+  }}
+  echo '{{"id":'"$id"',"result":{{"thread":{{"id":"00000000-0000-0000-0000-000000000000","path":"rollout.jsonl"}},"model":"test-model","modelProvider":"test-provider","cwd":"project","approvalPolicy":"never","sandbox":{{"type":"readOnly"}},"reasoningEffort":null}}}}'
+  id=$((id + 1))
+done
+"#,
+            spawns = spawns.display()
+        );
+
+        std::fs::write(&codex_bin, script).expect("write dummy codex");
+        let mut perms = std::fs::metadata(&codex_bin)
+            .expect("stat dummy codex")
+            .permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&codex_bin, perms).expect("chmod dummy codex");
+
+        let shared = SharedAppServer::default();
+        for _round in 0..2 {
+            let (event_tx, _event_rx) = unbounded_channel::<Event>();
+            let (fatal_exit_tx, mut fatal_exit_rx) = unbounded_channel::<String>();
+            let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+            drop(op_tx);
+
+            timeout(
+                Duration::from_secs(5),
+                run_app_server_backend_inner(
+                    AppServerBackendConfig {
+                        codex_bin: codex_bin.display().to_string(),
+                        developer_instructions: None,
+                        launch: AppServerLaunchConfig {
+                            spawn_sandbox: None,
+                            thread_sandbox: None,
+                            bypass_approvals_and_sandbox: false,
+                        },
+                        codex_home: None,
+                        thread_cwd: None,
+                        resume_thread_id: None,
+                        approval_policy: ApprovalPolicy::default(),
+                        user_input_policy: UserInputPolicy::default(),
+                        command_guard: CommandGuard::default(),
+                        codex_overrides: CodexOverrides::default(),
+                        workspace_write: WorkspaceWriteSettings::default(),
+                        shared_app_server: Some(shared.clone()),
+                        context_checkpoint: None,
+                        timeouts: RoundTimeouts::default(),
+                    },
+                    &mut op_rx,
+                    &event_tx,
+                    &fatal_exit_tx,
+                ),
+            )
+            .await
+            .expect("backend timed out")
+            .expect("backend failed");
+
+            assert!(fatal_exit_rx.try_recv().is_err());
+        }
+
+        assert!(shared.0.lock().await.is_some());
+        let spawns = std::fs::read_to_string(&spawns).expect("read spawns");
+        assert_eq!(spawns.lines().count(), 1);
+    }
 }
@@ -44,6 +44,13 @@ pub enum ClientRequest {
         request_id: RequestId,
         params: v2::TurnStartParams,
     },
+
+    #[serde(rename = "turn/interrupt")]
+    TurnInterrupt {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: v2::TurnInterruptParams,
+    },
 }
 
 /// Notification from the client to the server.
@@ -86,6 +93,21 @@ pub enum ServerRequest {
         #[serde(default)]
         params: Option<serde_json::Value>,
     },
+
+    #[serde(rename = "item/tool/requestUserInput")]
+    ToolRequestUserInput {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        params: v2::ToolRequestUserInputParams,
+    },
+
+    #[serde(rename = "mcpServer/elicitation/request")]
+    McpServerElicitation {
+        #[serde(rename = "id")]
+        request_id: RequestId,
+        #[serde(default)]
+        params: Option<serde_json::Value>,
+    },
 }
 
 impl TryFrom<JSONRPCRequest> for ServerRequest {
@@ -102,6 +124,7 @@ mod tests {
     use super::v2::ThreadResumeParams;
     use super::v2::ThreadRollbackParams;
     use super::v2::ThreadStartParams;
+    use super::v2::TurnInterruptParams;
     use super::v2::TurnStartParams;
     use super::*;
 
@@ -265,6 +288,54 @@ mod tests {
         assert_eq!(value["params"]["numTurns"], 1);
     }
 
+    #[test]
+    fn serialize_turn_interrupt_includes_turn_id() {
+        let request = ClientRequest::TurnInterrupt {
+            request_id: RequestId::Integer(5),
+            params: TurnInterruptParams {
+                thread_id: "thread-1".to_string(),
+                turn_id: "turn-2".to_string(),
+            },
+        };
+
+        let value = serde_json::to_value(&request).expect("serialize request");
+        assert_eq!(value["method"], "turn/interrupt");
+        assert_eq!(value["id"], 5);
+        assert_eq!(value["params"]["threadId"], "thread-1");
+        assert_eq!(value["params"]["turnId"], "turn-2");
+    }
+
+    #[test]
+    fn parse_tool_request_user_input_request() {
+        let request: JSONRPCRequest = serde_json::from_value(serde_json::json!({
+            "id": 7,
+            "method": "item/tool/requestUserInput",
+            "params": {
+                "threadId": "thread-1",
+                "turnId": "turn-1",
+                "itemId": "call-1",
+                "questions": [{
+                    "id": "scope",
+                    "header": "Scope",
+                    "question": "Which crate?",
+                    "isOther": true,
+                    "options": [{"label": "cli", "description": "The binary"}]
+                }]
+            }
+        }))
+        .expect("json-rpc request");
+
+        let ServerRequest::ToolRequestUserInput { request_id, params } =
+            ServerRequest::try_from(request).expect("server request")
+        else {
+            panic!("expected a requestUserInput request");
+        };
+        assert_eq!(request_id, RequestId::Integer(7));
+        assert_eq!(params.item_id, "call-1");
+        assert_eq!(params.questions.len(), 1);
+        assert!(params.questions[0].is_other);
+    }
+
     #[test]
     fn serialize_initialize_request() {
         let request = ClientRequest::Initialize {
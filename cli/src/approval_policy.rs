@@ -0,0 +1,683 @@
+//! Policy for the approval requests `codex app-server` sends before it runs a command or applies
+//! a file change.
+//!
+//! Rules live in the `[approval]` table of `~/.codexpotter/config.toml` and
+//! `<workdir>/.codexpotter/config.toml` (read through `crate::config::LayeredConfig`); home rules
+//! are checked before repository rules and the first matching rule decides. A rule matches a
+//! command by argv prefix (`command_prefix`) or regex (`command_regex`), or a file change by path
+//! glob (`path`), and decides `accept`, `deny` or `ask`. `ask` is answered by the user in the TUI,
+//! and by `approval.headless` when no one is there to ask. Without any configuration every request
+//! is accepted.
+//!
+//! The agent can write the repository file, so it may only narrow what is accepted: `accept`
+//! (in a rule or `default`) and `headless` are rejected there.
+//!
+//! ```toml
+//! [approval]
+//! default = "accept"
+//! headless = "deny"
+//!
+//! [[approval.rules]]
+//! command_prefix = ["git", "push"]
+//! decision = "deny"
+//!
+//! [[approval.rules]]
+//! path = "**/*.lock"
+//! decision = "ask"
+//! ```
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_protocol::protocol::PotterApprovalDecidedBy;
+use codex_protocol::protocol::PotterApprovalDecision;
+use globset::GlobBuilder;
+use globset::GlobMatcher;
+use regex_automata::meta::Regex;
+use toml_edit::Item as TomlItem;
+use toml_edit::TableLike;
+
+use crate::config::ConfigSource;
+use crate::config::LayeredConfig;
+use crate::config::read_str;
+use crate::config::reject_in_repository;
+
+/// What a rule (or `approval.default`) decides.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalRuleDecision {
+    Accept,
+    Deny,
+    /// Ask the user; answered by `approval.headless` without the TUI.
+    Ask,
+}
+
+impl ApprovalRuleDecision {
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "accept" => Ok(Self::Accept),
+            "deny" => Ok(Self::Deny),
+            "ask" => Ok(Self::Ask),
+            other => anyhow::bail!("unknown decision {other:?} (expected accept, deny or ask)"),
+        }
+    }
+}
+
+/// What an approval request asks about.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ApprovalSubject {
+    /// Command argv, for command execution requests.
+    pub command: Option<Vec<String>>,
+    /// Changed paths, for file change requests.
+    pub paths: Vec<PathBuf>,
+    /// Why the agent asks, when it says so.
+    pub reason: Option<String>,
+}
+
+impl ApprovalSubject {
+    /// Read the subject out of the params of an app-server approval request.
+    ///
+    /// Both the v2 (`item/*/requestApproval`) and v1 (`execCommandApproval`,
+    /// `applyPatchApproval`) shapes are accepted; fields the request does not carry stay empty.
+    pub fn from_request_params(params: Option<&serde_json::Value>) -> Self {
+        let Some(params) = params else {
+            return Self::default();
+        };
+        let command = match params.get("command") {
+            Some(serde_json::Value::Array(argv)) => Some(
+                argv.iter()
+                    .filter_map(|arg| arg.as_str().map(ToString::to_string))
+                    .collect(),
+            ),
+            Some(serde_json::Value::String(command)) => {
+                Some(shlex::split(command).unwrap_or_else(|| vec![command.clone()]))
+            }
+            _ => None,
+        };
+        let paths = ["fileChanges", "changes"]
+            .iter()
+            .find_map(|key| params.get(*key).and_then(serde_json::Value::as_object))
+            .map(|changes| changes.keys().map(PathBuf::from).collect())
+            .unwrap_or_default();
+        let reason = params
+            .get("reason")
+            .and_then(serde_json::Value::as_str)
+            .map(ToString::to_string);
+        Self {
+            command,
+            paths,
+            reason,
+        }
+    }
+
+    /// Id of the command or patch call the request belongs to.
+    pub fn call_id(params: Option<&serde_json::Value>) -> Option<String> {
+        let params = params?;
+        ["callId", "itemId"]
+            .iter()
+            .find_map(|key| params.get(*key).and_then(serde_json::Value::as_str))
+            .map(ToString::to_string)
+    }
+
+    /// Fill in whatever this subject lacks from `known` (announced by an approval request event).
+    pub fn or_from(mut self, known: ApprovalSubject) -> Self {
+        if self.command.is_none() {
+            self.command = known.command;
+        }
+        if self.paths.is_empty() {
+            self.paths = known.paths;
+        }
+        if self.reason.is_none() {
+            self.reason = known.reason;
+        }
+        self
+    }
+}
+
+#[derive(Debug, Clone)]
+enum RuleMatcher {
+    CommandPrefix(Vec<String>),
+    CommandRegex(Regex),
+    Path(GlobMatcher),
+}
+
+#[derive(Debug, Clone)]
+struct ApprovalRule {
+    matcher: RuleMatcher,
+    decision: ApprovalRuleDecision,
+    /// Human-readable form of the rule, recorded with the decisions it makes.
+    description: String,
+}
+
+impl ApprovalRule {
+    fn matches(&self, subject: &ApprovalSubject, workdir: &Path) -> bool {
+        match &self.matcher {
+            RuleMatcher::CommandPrefix(prefix) => subject.command.as_deref().is_some_and(|argv| {
+                argv.starts_with(prefix)
+                    || script_argv(argv).is_some_and(|script| script.starts_with(prefix))
+            }),
+            RuleMatcher::CommandRegex(regex) => subject.command.as_deref().is_some_and(|argv| {
+                regex.is_match(&argv.join(" "))
+                    || shell_script(argv).is_some_and(|script| regex.is_match(script))
+            }),
+            RuleMatcher::Path(glob) => subject.paths.iter().any(|path| {
+                glob.is_match(path)
+                    || path
+                        .strip_prefix(workdir)
+                        .is_ok_and(|relative| glob.is_match(relative))
+            }),
+        }
+    }
+}
+
+/// The script of `bash -lc <script>` style commands (which is how the agent runs most commands).
+pub(crate) fn shell_script(argv: &[String]) -> Option<&str> {
+    let [shell, flag, script] = argv else {
+        return None;
+    };
+    let shell = Path::new(shell).file_name()?.to_str()?;
+    (matches!(shell, "bash" | "sh" | "zsh") && matches!(flag.as_str(), "-c" | "-lc"))
+        .then_some(script.as_str())
+}
+
+fn script_argv(argv: &[String]) -> Option<Vec<String>> {
+    shell_script(argv).and_then(shlex::split)
+}
+
+/// How the policy answered a request.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApprovalVerdict {
+    Decided {
+        decision: PotterApprovalDecision,
+        decided_by: PotterApprovalDecidedBy,
+    },
+    /// Ask the user in the TUI.
+    AskUser,
+}
+
+/// Approval rules of one `codex-potter` invocation.
+#[derive(Debug, Clone)]
+pub struct ApprovalPolicy {
+    rules: Vec<ApprovalRule>,
+    default: ApprovalRuleDecision,
+    headless: PotterApprovalDecision,
+    /// Whether `ask` can be answered by the user (the TUI is running).
+    interactive: bool,
+    workdir: PathBuf,
+}
+
+impl Default for ApprovalPolicy {
+    /// Accept everything (no configuration).
+    fn default() -> Self {
+        Self {
+            rules: Vec::new(),
+            default: ApprovalRuleDecision::Accept,
+            headless: PotterApprovalDecision::Deny,
+            interactive: false,
+            workdir: PathBuf::new(),
+        }
+    }
+}
+
+impl ApprovalPolicy {
+    /// Load the rules for `workdir` from the `[approval]` tables of `config`.
+    ///
+    /// Home rules come first, so a repository rule or default can never override the user's own
+    /// rules, and repository rules cannot accept anything the home file would not.
+    pub fn load(config: &LayeredConfig, workdir: &Path, interactive: bool) -> anyhow::Result<Self> {
+        let mut policy = Self {
+            interactive,
+            workdir: workdir.to_path_buf(),
+            ..Self::default()
+        };
+        let mut default = None;
+        let mut headless = None;
+        for layer in config.tables("approval", |source, table| {
+            reject_in_repository(source, "approval", table, &["headless"])?;
+            let layer = parse_approval_config(table)?;
+            if source == ConfigSource::Repository {
+                if layer.default == Some(ApprovalRuleDecision::Accept) {
+                    anyhow::bail!(
+                        "`approval.default = \"accept\"` is only read from ~/.codexpotter/config.toml, not the repository config"
+                    );
+                }
+                if let Some(idx) = layer
+                    .rules
+                    .iter()
+                    .position(|rule| rule.decision == ApprovalRuleDecision::Accept)
+                {
+                    anyhow::bail!(
+                        "`approval.rules[{idx}]` accepts requests, which only ~/.codexpotter/config.toml can do, not the repository config"
+                    );
+                }
+            }
+            Ok(layer)
+        })? {
+            policy.rules.extend(layer.rules);
+            default = default.or(layer.default);
+            headless = headless.or(layer.headless);
+        }
+        policy.default = default.unwrap_or(ApprovalRuleDecision::Accept);
+        policy.headless = headless.unwrap_or(PotterApprovalDecision::Deny);
+        Ok(policy)
+    }
+
+    /// The same rules, answering `ask` with `approval.headless` (`exec`, `--json`, `--parallel`).
+    pub fn headless(self) -> Self {
+        Self {
+            interactive: false,
+            ..self
+        }
+    }
+
+    /// Whether every request would be accepted anyway, so the agent need not ask at all.
+    pub fn accepts_everything(&self) -> bool {
+        self.rules.is_empty() && self.default == ApprovalRuleDecision::Accept
+    }
+
+    /// Decide a request about `subject`.
+    pub fn decide(&self, subject: &ApprovalSubject) -> ApprovalVerdict {
+        let (decision, decided_by) = match self
+            .rules
+            .iter()
+            .find(|rule| rule.matches(subject, &self.workdir))
+        {
+            Some(rule) => (
+                rule.decision,
+                PotterApprovalDecidedBy::Rule {
+                    rule: rule.description.clone(),
+                },
+            ),
+            None => (self.default, PotterApprovalDecidedBy::Default),
+        };
+        match decision {
+            ApprovalRuleDecision::Accept => ApprovalVerdict::Decided {
+                decision: PotterApprovalDecision::Accept,
+                decided_by,
+            },
+            ApprovalRuleDecision::Deny => ApprovalVerdict::Decided {
+                decision: PotterApprovalDecision::Deny,
+                decided_by,
+            },
+            ApprovalRuleDecision::Ask if self.interactive => ApprovalVerdict::AskUser,
+            ApprovalRuleDecision::Ask => ApprovalVerdict::Decided {
+                decision: self.headless,
+                decided_by: PotterApprovalDecidedBy::Headless,
+            },
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+struct ApprovalConfig {
+    rules: Vec<ApprovalRule>,
+    default: Option<ApprovalRuleDecision>,
+    headless: Option<PotterApprovalDecision>,
+}
+
+fn parse_approval_config(approval: &dyn TableLike) -> anyhow::Result<ApprovalConfig> {
+    let default = read_str(approval, "default")?
+        .map(ApprovalRuleDecision::parse)
+        .transpose()
+        .context("approval.default")?;
+    let headless = read_str(approval, "headless")?
+        .map(|value| -> anyhow::Result<PotterApprovalDecision> {
+            match ApprovalRuleDecision::parse(value)? {
+                ApprovalRuleDecision::Accept => Ok(PotterApprovalDecision::Accept),
+                ApprovalRuleDecision::Deny => Ok(PotterApprovalDecision::Deny),
+                ApprovalRuleDecision::Ask => anyhow::bail!("cannot ask without the TUI"),
+            }
+        })
+        .transpose()
+        .context("approval.headless")?;
+
+    let mut rules = Vec::new();
+    match approval.get("rules") {
+        None => {}
+        Some(TomlItem::ArrayOfTables(tables)) => {
+            for (idx, table) in tables.iter().enumerate() {
+                rules.push(parse_rule(table).with_context(|| format!("approval.rules[{idx}]"))?);
+            }
+        }
+        Some(item) => {
+            let array = item
+                .as_array()
+                .context("`approval.rules` must be an array of tables")?;
+            for (idx, value) in array.iter().enumerate() {
+                let table = value
+                    .as_inline_table()
+                    .with_context(|| format!("approval.rules[{idx}] must be a table"))?;
+                rules.push(parse_rule(table).with_context(|| format!("approval.rules[{idx}]"))?);
+            }
+        }
+    }
+
+    Ok(ApprovalConfig {
+        rules,
+        default,
+        headless,
+    })
+}
+
+fn parse_rule(table: &dyn TableLike) -> anyhow::Result<ApprovalRule> {
+    let decision =
+        ApprovalRuleDecision::parse(read_str(table, "decision")?.context("missing `decision`")?)?;
+
+    let mut matchers = Vec::new();
+    if let Some(item) = table.get("command_prefix") {
+        let prefix = item
+            .as_array()
+            .and_then(|array| {
+                array
+                    .iter()
+                    .map(|arg| arg.as_str().map(ToString::to_string))
+                    .collect::<Option<Vec<_>>>()
+            })
+            .filter(|prefix| !prefix.is_empty())
+            .context("`command_prefix` must be a non-empty array of strings")?;
+        let description = format!("command_prefix = {prefix:?}");
+        matchers.push((RuleMatcher::CommandPrefix(prefix), description));
+    }
+    if let Some(pattern) = read_str(table, "command_regex")? {
+        let regex =
+            Regex::new(pattern).with_context(|| format!("invalid `command_regex` {pattern:?}"))?;
+        matchers.push((
+            RuleMatcher::CommandRegex(regex),
+            format!("command_regex = {pattern:?}"),
+        ));
+    }
+    if let Some(pattern) = read_str(table, "path")? {
+        let glob = GlobBuilder::new(pattern)
+            .literal_separator(true)
+            .build()
+            .with_context(|| format!("invalid `path` glob {pattern:?}"))?;
+        matchers.push((
+            RuleMatcher::Path(glob.compile_matcher()),
+            format!("path = {pattern:?}"),
+        ));
+    }
+
+    let (Some((matcher, description)), true) = (matchers.pop(), matchers.is_empty()) else {
+        anyhow::bail!("a rule needs exactly one of `command_prefix`, `command_regex` or `path`");
+    };
+    Ok(ApprovalRule {
+        matcher,
+        decision,
+        description,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn load(layers: &[(ConfigSource, &str)], interactive: bool) -> anyhow::Result<ApprovalPolicy> {
+        let config = LayeredConfig::from_toml(layers)?;
+        ApprovalPolicy::load(&config, Path::new("/repo"), interactive)
+    }
+
+    fn policy(content: &str, interactive: bool) -> ApprovalPolicy {
+        load(&[(ConfigSource::Home, content)], interactive).expect("load policy")
+    }
+
+    fn command(argv: &[&str]) -> ApprovalSubject {
+        ApprovalSubject {
+            command: Some(argv.iter().map(ToString::to_string).collect()),
+            ..Default::default()
+        }
+    }
+
+    fn paths(paths: &[&str]) -> ApprovalSubject {
+        ApprovalSubject {
+            paths: paths.iter().map(PathBuf::from).collect(),
+            ..Default::default()
+        }
+    }
+
+    fn decided(decision: PotterApprovalDecision, rule: Option<&str>) -> ApprovalVerdict {
+        ApprovalVerdict::Decided {
+            decision,
+            decided_by: match rule {
+                Some(rule) => PotterApprovalDecidedBy::Rule {
+                    rule: rule.to_string(),
+                },
+                None => PotterApprovalDecidedBy::Default,
+            },
+        }
+    }
+
+    #[test]
+    fn missing_config_accepts_everything() {
+        let policy = ApprovalPolicy::load(&LayeredConfig::default(), Path::new("/repo"), true)
+            .expect("load policy");
+        assert!(policy.accepts_everything());
+        assert_eq!(
+            policy.decide(&command(&["rm", "-rf", "/"])),
+            decided(PotterApprovalDecision::Accept, None)
+        );
+    }
+
+    #[test]
+    fn first_matching_rule_decides() {
+        let policy = policy(
+            r#"
+check_for_update_on_startup = false
+
+[approval]
+default = "ask"
+
+[[approval.rules]]
+command_prefix = ["git", "push"]
+decision = "deny"
+
+[[approval.rules]]
+command_regex = "^cargo (build|test)\\b"
+decision = "accept"
+
+[[approval.rules]]
+path = "**/*.lock"
+decision = "deny"
+"#,
+            true,
+        );
+
+        assert!(!policy.accepts_everything());
+        assert_eq!(
+            policy.decide(&command(&["git", "push", "origin", "main"])),
+            decided(
+                PotterApprovalDecision::Deny,
+                Some(r#"command_prefix = ["git", "push"]"#)
+            )
+        );
+        // Shell wrappers are looked through.
+        assert_eq!(
+            policy.decide(&command(&["/bin/bash", "-lc", "git push --force"])),
+            decided(
+                PotterApprovalDecision::Deny,
+                Some(r#"command_prefix = ["git", "push"]"#)
+            )
+        );
+        assert_eq!(
+            policy.decide(&command(&["bash", "-lc", "cargo test -p codex-potter-cli"])),
+            decided(
+                PotterApprovalDecision::Accept,
+                Some(r#"command_regex = "^cargo (build|test)\\b""#)
+            )
+        );
+        // Paths match relative to the workdir, and only with `**/` across directories.
+        assert_eq!(
+            policy.decide(&paths(&["src/main.rs", "/repo/sub/Cargo.lock"])),
+            decided(PotterApprovalDecision::Deny, Some(r#"path = "**/*.lock""#))
+        );
+        assert_eq!(
+            policy.decide(&command(&["git", "status"])),
+            ApprovalVerdict::AskUser
+        );
+    }
+
+    #[test]
+    fn ask_falls_back_to_headless_decision() {
+        let content = "[approval]\ndefault = \"ask\"\nheadless = \"accept\"\n";
+        assert_eq!(
+            policy(content, false).decide(&command(&["ls"])),
+            ApprovalVerdict::Decided {
+                decision: PotterApprovalDecision::Accept,
+                decided_by: PotterApprovalDecidedBy::Headless,
+            }
+        );
+        assert_eq!(
+            policy(content, true).headless().decide(&command(&["ls"])),
+            ApprovalVerdict::Decided {
+                decision: PotterApprovalDecision::Accept,
+                decided_by: PotterApprovalDecidedBy::Headless,
+            }
+        );
+        assert_eq!(
+            policy("[approval]\ndefault = \"ask\"\n", false).decide(&command(&["ls"])),
+            ApprovalVerdict::Decided {
+                decision: PotterApprovalDecision::Deny,
+                decided_by: PotterApprovalDecidedBy::Headless,
+            }
+        );
+    }
+
+    #[test]
+    fn home_rules_come_before_repository_rules() {
+        let policy = load(
+            &[
+                (
+                    ConfigSource::Home,
+                    "[approval]\ndefault = \"deny\"\n\n[[approval.rules]]\ncommand_prefix = [\"git\"]\ndecision = \"accept\"\n",
+                ),
+                (
+                    ConfigSource::Repository,
+                    "[approval]\ndefault = \"ask\"\n\n[[approval.rules]]\ncommand_prefix = [\"git\", \"push\"]\ndecision = \"deny\"\n\n[[approval.rules]]\ncommand_prefix = [\"curl\"]\ndecision = \"deny\"\n",
+                ),
+            ],
+            true,
+        )
+        .expect("load policy");
+        // A repository rule cannot override a home rule.
+        assert_eq!(
+            policy.decide(&command(&["git", "push"])),
+            decided(
+                PotterApprovalDecision::Accept,
+                Some(r#"command_prefix = ["git"]"#)
+            )
+        );
+        // Nor can the repository `default`, but its rules apply where home has none.
+        assert_eq!(
+            policy.decide(&command(&["ls"])),
+            decided(PotterApprovalDecision::Deny, None)
+        );
+        assert_eq!(
+            policy.decide(&command(&["curl", "example.com"])),
+            decided(
+                PotterApprovalDecision::Deny,
+                Some(r#"command_prefix = ["curl"]"#)
+            )
+        );
+    }
+
+    #[test]
+    fn repository_config_cannot_accept_requests() {
+        for (content, key) in [
+            (
+                "[[approval.rules]]\ncommand_regex = \".*\"\ndecision = \"accept\"\n",
+                "`approval.rules[0]`",
+            ),
+            ("[approval]\ndefault = \"accept\"\n", "`approval.default"),
+            ("[approval]\nheadless = \"accept\"\n", "`approval.headless`"),
+        ] {
+            let err = load(
+                &[
+                    (ConfigSource::Home, "[approval]\ndefault = \"deny\"\n"),
+                    (ConfigSource::Repository, content),
+                ],
+                false,
+            )
+            .expect_err("repository accept is rejected");
+            assert!(
+                format!("{err:#}").contains(key),
+                "unexpected error for {content:?}: {err:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        for content in [
+            "approval = 1\n",
+            "[approval]\ndefault = \"maybe\"\n",
+            "[approval]\nheadless = \"ask\"\n",
+            "[[approval.rules]]\ndecision = \"deny\"\n",
+            "[[approval.rules]]\ncommand_prefix = []\ndecision = \"deny\"\n",
+            "[[approval.rules]]\ncommand_regex = \"(\"\ndecision = \"deny\"\n",
+            "[[approval.rules]]\npath = \"*.rs\"\ncommand_prefix = [\"ls\"]\ndecision = \"deny\"\n",
+        ] {
+            assert!(
+                load(&[(ConfigSource::Home, content)], true).is_err(),
+                "expected an error for:\n{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn subject_is_read_from_v1_and_v2_params() {
+        let v1_exec = serde_json::json!({
+            "conversationId": "thread",
+            "callId": "call-1",
+            "command": ["git", "push"],
+            "cwd": "/repo",
+            "reason": "needs network",
+        });
+        assert_eq!(
+            ApprovalSubject::from_request_params(Some(&v1_exec)),
+            ApprovalSubject {
+                command: Some(vec!["git".to_string(), "push".to_string()]),
+                paths: Vec::new(),
+                reason: Some("needs network".to_string()),
+            }
+        );
+        assert_eq!(
+            ApprovalSubject::call_id(Some(&v1_exec)).as_deref(),
+            Some("call-1")
+        );
+
+        let v1_patch = serde_json::json!({
+            "callId": "call-2",
+            "fileChanges": { "src/lib.rs": { "type": "update" } },
+        });
+        assert_eq!(
+            ApprovalSubject::from_request_params(Some(&v1_patch)).paths,
+            vec![PathBuf::from("src/lib.rs")]
+        );
+
+        let v2_command = serde_json::json!({
+            "threadId": "thread",
+            "turnId": "turn",
+            "itemId": "call-3",
+            "command": "cargo test --all",
+        });
+        let subject = ApprovalSubject::from_request_params(Some(&v2_command));
+        assert_eq!(
+            subject.command,
+            Some(vec![
+                "cargo".to_string(),
+                "test".to_string(),
+                "--all".to_string()
+            ])
+        );
+        assert_eq!(
+            ApprovalSubject::call_id(Some(&v2_command)).as_deref(),
+            Some("call-3")
+        );
+
+        let v2_file_change = serde_json::json!({ "itemId": "call-4" });
+        assert_eq!(
+            ApprovalSubject::from_request_params(Some(&v2_file_change)).or_from(paths(&["a.rs"])),
+            paths(&["a.rs"])
+        );
+    }
+}
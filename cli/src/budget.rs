@@ -0,0 +1,282 @@
+//! Session-wide budgets (`--max-duration`, `--max-tokens`).
+//!
+//! Usage is derived from the `round_usage` lines in `potter-rollout.jsonl`, so a resumed project
+//! continues with whatever budget is left instead of starting over. A round is given what is left
+//! as watchdog limits (`RoundTimeouts`), so one that outlasts the budget is stopped; the budgets are
+//! checked against the recorded usage when a round finishes.
+
+use std::time::Duration;
+
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
+
+use crate::potter_rollout::PotterRolloutLine;
+
+/// Limits applied across all rounds of a project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SessionBudget {
+    pub max_duration: Option<Duration>,
+    pub max_tokens: Option<u64>,
+}
+
+/// Resources consumed by the recorded rounds of a project.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct BudgetUsage {
+    pub elapsed: Duration,
+    pub tokens: u64,
+}
+
+impl SessionBudget {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_duration.is_none() && self.max_tokens.is_none()
+    }
+
+    /// The first exhausted budget for `usage`, if any.
+    pub fn exhausted(&self, usage: BudgetUsage) -> Option<PotterBudgetExhaustedReason> {
+        if let Some(limit) = self.max_duration
+            && usage.elapsed >= limit
+        {
+            return Some(PotterBudgetExhaustedReason::MaxDuration {
+                limit_secs: limit.as_secs(),
+                elapsed_secs: usage.elapsed.as_secs(),
+            });
+        }
+        if let Some(limit) = self.max_tokens
+            && usage.tokens >= limit
+        {
+            return Some(PotterBudgetExhaustedReason::MaxTokens {
+                limit,
+                used: usage.tokens,
+            });
+        }
+        None
+    }
+
+    /// What is left of each budget after `usage`.
+    pub fn remaining(&self, usage: BudgetUsage) -> SessionBudget {
+        SessionBudget {
+            max_duration: self
+                .max_duration
+                .map(|limit| limit.saturating_sub(usage.elapsed)),
+            max_tokens: self
+                .max_tokens
+                .map(|limit| limit.saturating_sub(usage.tokens)),
+        }
+    }
+}
+
+/// Sum the `round_usage` entries of already parsed `potter-rollout.jsonl` lines.
+pub fn usage_from_lines(lines: &[PotterRolloutLine]) -> BudgetUsage {
+    lines
+        .iter()
+        .fold(BudgetUsage::default(), |usage, line| match line {
+            PotterRolloutLine::RoundUsage {
+                duration_secs,
+                token_usage,
+            } => BudgetUsage {
+                elapsed: usage.elapsed + Duration::from_secs(*duration_secs),
+                tokens: usage
+                    .tokens
+                    .saturating_add(u64::try_from(token_usage.total_tokens).unwrap_or(0)),
+            },
+            _ => usage,
+        })
+}
+
+/// Parse a duration such as `90s`, `45m`, `2h`, `1h30m`, or a bare number of seconds.
+pub fn parse_duration(raw: &str) -> Result<Duration, String> {
+    let raw = raw.trim();
+    if let Ok(secs) = raw.parse::<u64>() {
+        return positive_duration(secs, raw);
+    }
+
+    let mut total_secs = 0u64;
+    let mut digits = String::new();
+    for ch in raw.chars() {
+        if ch.is_ascii_digit() {
+            digits.push(ch);
+            continue;
+        }
+        let unit_secs = match ch {
+            's' => 1,
+            'm' => 60,
+            'h' => 60 * 60,
+            'd' => 24 * 60 * 60,
+            _ => return Err(format!("invalid duration `{raw}`: unknown unit `{ch}`")),
+        };
+        let value = digits
+            .parse::<u64>()
+            .map_err(|_| format!("invalid duration `{raw}`: expected a number before `{ch}`"))?;
+        total_secs = total_secs.saturating_add(value.saturating_mul(unit_secs));
+        digits.clear();
+    }
+    if !digits.is_empty() {
+        return Err(format!(
+            "invalid duration `{raw}`: missing unit after `{digits}`"
+        ));
+    }
+    positive_duration(total_secs, raw)
+}
+
+fn positive_duration(secs: u64, raw: &str) -> Result<Duration, String> {
+    if secs == 0 {
+        return Err(format!(
+            "invalid duration `{raw}`: must be greater than zero"
+        ));
+    }
+    Ok(Duration::from_secs(secs))
+}
+
+/// Parse a token count such as `500000`, `500k`, `5M`, or `1.5M`.
+pub fn parse_token_count(raw: &str) -> Result<u64, String> {
+    let raw = raw.trim();
+    let (number, multiplier) = match raw.char_indices().last() {
+        Some((idx, 'k' | 'K')) => (&raw[..idx], 1_000.0),
+        Some((idx, 'm' | 'M')) => (&raw[..idx], 1_000_000.0),
+        Some((idx, 'b' | 'B')) => (&raw[..idx], 1_000_000_000.0),
+        _ => (raw, 1.0),
+    };
+    let value = number
+        .parse::<f64>()
+        .map_err(|_| format!("invalid token count `{raw}`"))?;
+    let tokens = (value * multiplier).round();
+    if !tokens.is_finite() || tokens < 1.0 {
+        return Err(format!(
+            "invalid token count `{raw}`: must be greater than zero"
+        ));
+    }
+    Ok(tokens as u64)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::TokenUsage;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_duration_accepts_units_and_bare_seconds() {
+        assert_eq!(parse_duration("90"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("45s"), Ok(Duration::from_secs(45)));
+        assert_eq!(parse_duration("2h"), Ok(Duration::from_secs(2 * 3600)));
+        assert_eq!(parse_duration("1h30m"), Ok(Duration::from_secs(5400)));
+        assert_eq!(parse_duration("1d"), Ok(Duration::from_secs(86400)));
+        assert!(parse_duration("0").is_err());
+        assert!(parse_duration("2x").is_err());
+        assert!(parse_duration("1h30").is_err());
+        assert!(parse_duration("h").is_err());
+    }
+
+    #[test]
+    fn parse_token_count_accepts_suffixes() {
+        assert_eq!(parse_token_count("500000"), Ok(500_000));
+        assert_eq!(parse_token_count("500k"), Ok(500_000));
+        assert_eq!(parse_token_count("5M"), Ok(5_000_000));
+        assert_eq!(parse_token_count("1.5m"), Ok(1_500_000));
+        assert!(parse_token_count("0").is_err());
+        assert!(parse_token_count("lots").is_err());
+    }
+
+    #[test]
+    fn exhausted_reports_the_first_exceeded_limit() {
+        let budget = SessionBudget {
+            max_duration: Some(Duration::from_secs(60)),
+            max_tokens: Some(1_000),
+        };
+        assert_eq!(
+            budget.exhausted(BudgetUsage {
+                elapsed: Duration::from_secs(59),
+                tokens: 999,
+            }),
+            None
+        );
+        assert_eq!(
+            budget.exhausted(BudgetUsage {
+                elapsed: Duration::from_secs(10),
+                tokens: 1_200,
+            }),
+            Some(PotterBudgetExhaustedReason::MaxTokens {
+                limit: 1_000,
+                used: 1_200,
+            })
+        );
+        assert_eq!(
+            budget.exhausted(BudgetUsage {
+                elapsed: Duration::from_secs(61),
+                tokens: 1_200,
+            }),
+            Some(PotterBudgetExhaustedReason::MaxDuration {
+                limit_secs: 60,
+                elapsed_secs: 61,
+            })
+        );
+        assert_eq!(
+            SessionBudget::default().exhausted(BudgetUsage::default()),
+            None
+        );
+    }
+
+    #[test]
+    fn remaining_subtracts_usage_from_each_limit() {
+        let budget = SessionBudget {
+            max_duration: Some(Duration::from_secs(60)),
+            max_tokens: None,
+        };
+        assert_eq!(
+            budget.remaining(BudgetUsage {
+                elapsed: Duration::from_secs(45),
+                tokens: 1_200,
+            }),
+            SessionBudget {
+                max_duration: Some(Duration::from_secs(15)),
+                max_tokens: None,
+            }
+        );
+        assert_eq!(
+            SessionBudget {
+                max_duration: None,
+                max_tokens: Some(1_000),
+            }
+            .remaining(BudgetUsage {
+                elapsed: Duration::from_secs(45),
+                tokens: 1_200,
+            }),
+            SessionBudget {
+                max_duration: None,
+                max_tokens: Some(0),
+            }
+        );
+    }
+
+    #[test]
+    fn usage_from_lines_sums_round_usage() {
+        let round = |duration_secs, total_tokens| PotterRolloutLine::RoundUsage {
+            duration_secs,
+            token_usage: TokenUsage {
+                total_tokens,
+                ..TokenUsage::default()
+            },
+        };
+        let lines = vec![
+            PotterRolloutLine::RoundStarted {
+                current: 1,
+                total: 2,
+                planning: false,
+            },
+            round(30, 1_000),
+            PotterRolloutLine::RoundStarted {
+                current: 2,
+                total: 2,
+                planning: false,
+            },
+            round(45, 2_500),
+        ];
+
+        assert_eq!(
+            usage_from_lines(&lines),
+            BudgetUsage {
+                elapsed: Duration::from_secs(75),
+                tokens: 3_500,
+            }
+        );
+    }
+}
@@ -0,0 +1,567 @@
+//! Codex settings forwarded to every app-server thread: `--model`, `--profile`, `--effort`, and
+//! repeatable `-c key=value` overrides.
+//!
+//! The same settings can live in the `[codex]` table of `~/.codexpotter/config.toml` and
+//! `<workdir>/.codexpotter/config.toml`. Command-line flags win over the home file, which wins over
+//! the repository file; `-c` overrides are merged key by key. The agent can write the repository
+//! file, and most Codex settings (`notify`, `mcp_servers`, `approval_policy`, the sandbox, a
+//! `profile` of `~/.codex/config.toml`) would let it run commands outside its sandbox, so the
+//! repository's `[codex.config]` only accepts the model and output keys of
+//! `REPOSITORY_CONFIG_KEYS`, and its `[codex]` table cannot set `profile`.
+//!
+//! ```toml
+//! [codex]
+//! model = "gpt-5.2-codex"
+//! profile = "work"
+//! model_reasoning_effort = "high"
+//!
+//! [codex.config]
+//! model_verbosity = "low"
+//! sandbox_workspace_write.network_access = true
+//!
+//! # Per progress-file phase (planning, execution, review); unset values fall back to the above.
+//! [codex.phases.planning]
+//! model = "gpt-5.2"
+//! model_reasoning_effort = "xhigh"
+//! ```
+
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::path::Path;
+
+use anyhow::Context;
+use codex_protocol::openai_models::ReasoningEffort;
+use serde_json::Value as JsonValue;
+use toml_edit::DocumentMut;
+use toml_edit::Item as TomlItem;
+use toml_edit::TableLike;
+
+use crate::config::ConfigSource;
+use crate::config::LayeredConfig;
+use crate::config::read_str;
+use crate::config::reject_in_repository;
+use crate::project::ProgressPhase;
+
+/// The only `[codex.config]` keys the repository file may set: they change the model and its
+/// output, not what the agent may run.
+const REPOSITORY_CONFIG_KEYS: &[&str] = &[
+    "model",
+    "model_reasoning_effort",
+    "model_reasoning_summary",
+    "model_verbosity",
+    "hide_agent_reasoning",
+    "show_raw_agent_reasoning",
+];
+
+/// Codex model and config overrides of one `codex-potter` invocation.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct CodexOverrides {
+    pub model: Option<String>,
+    /// Codex config profile (`[profiles.<name>]` in Codex's `config.toml`).
+    pub profile: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+    /// Dotted config keys and their values, as with `codex -c key=value`.
+    pub config: BTreeMap<String, JsonValue>,
+    /// Model and effort of rounds in a given progress-file phase (`[codex.phases.<phase>]`).
+    pub phases: BTreeMap<ProgressPhase, PhaseModel>,
+}
+
+/// Model and reasoning effort of one progress-file phase.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PhaseModel {
+    pub model: Option<String>,
+    pub effort: Option<ReasoningEffort>,
+}
+
+impl CodexOverrides {
+    /// Layer `cli` on top of the `[codex]` tables of `config`.
+    pub fn load(config: &LayeredConfig, cli: CodexOverrides) -> anyhow::Result<Self> {
+        let mut merged = cli;
+        for layer in config.tables("codex", |source, table| {
+            reject_in_repository(source, "codex", table, &["profile"])?;
+            let layer = parse_codex_config(table)?;
+            if source == ConfigSource::Repository
+                && let Some(key) = layer
+                    .config
+                    .keys()
+                    .find(|key| !REPOSITORY_CONFIG_KEYS.contains(&key.as_str()))
+            {
+                anyhow::bail!(
+                    "`codex.config.{key}` is only read from ~/.codexpotter/config.toml, not the repository config"
+                );
+            }
+            Ok(layer)
+        })? {
+            merged.model = merged.model.or(layer.model);
+            merged.profile = merged.profile.or(layer.profile);
+            merged.effort = merged.effort.or(layer.effort);
+            for (key, value) in layer.config {
+                merged.config.entry(key).or_insert(value);
+            }
+            for (phase, layer) in layer.phases {
+                let phase = merged.phases.entry(phase).or_default();
+                phase.model = phase.model.take().or(layer.model);
+                phase.effort = phase.effort.or(layer.effort);
+            }
+        }
+        Ok(merged)
+    }
+
+    /// The overrides of a round in `phase`: the phase's model and effort replace the general ones.
+    pub fn for_phase(&self, phase: ProgressPhase) -> Self {
+        let mut overrides = self.clone();
+        if let Some(phase) = self.phases.get(&phase) {
+            if let Some(model) = &phase.model {
+                overrides.model = Some(model.clone());
+            }
+            overrides.effort = phase.effort.or(overrides.effort);
+        }
+        overrides
+    }
+
+    /// The overrides of a round for the progress file at `progress_file`.
+    ///
+    /// The file is only read when `[codex.phases]` is configured; when it cannot be read, the
+    /// round falls back to the general model.
+    pub fn for_progress_file(&self, progress_file: &Path) -> Self {
+        if self.phases.is_empty() {
+            return self.clone();
+        }
+        match crate::progress_file::ProgressFile::read(progress_file) {
+            Ok(progress) => self.for_phase(progress.phase()),
+            Err(_) => self.clone(),
+        }
+    }
+
+    /// `config` of `thread/start` / `thread/resume`: the `-c` overrides plus the profile.
+    pub fn thread_config(&self) -> Option<HashMap<String, JsonValue>> {
+        let mut config = self
+            .config
+            .iter()
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect::<HashMap<_, _>>();
+        if let Some(profile) = &self.profile {
+            config.insert("profile".to_string(), JsonValue::String(profile.clone()));
+        }
+        (!config.is_empty()).then_some(config)
+    }
+
+    /// The model, profile and effort these overrides select, including `-c model=...` and friends,
+    /// for the startup banner.
+    pub fn model_overrides(&self) -> codex_tui::CodexModelOverrides {
+        let config_str = |key: &str| {
+            self.config
+                .get(key)
+                .and_then(JsonValue::as_str)
+                .map(ToString::to_string)
+        };
+        codex_tui::CodexModelOverrides {
+            model: self.model.clone().or_else(|| config_str("model")),
+            profile: self.profile.clone().or_else(|| config_str("profile")),
+            reasoning_effort: self.effort.or_else(|| {
+                config_str("model_reasoning_effort")
+                    .and_then(|effort| parse_reasoning_effort(&effort).ok())
+            }),
+        }
+    }
+}
+
+/// Parse a `-c key=value` flag. The value is parsed as TOML and falls back to a plain string, as
+/// `codex -c` does.
+pub fn parse_config_override(raw: &str) -> Result<(String, JsonValue), String> {
+    let Some((key, value)) = raw.split_once('=') else {
+        return Err(format!("expected key=value, got {raw:?}"));
+    };
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(format!("missing key in {raw:?}"));
+    }
+    let value = value.trim();
+    let parsed = format!("value = {value}")
+        .parse::<DocumentMut>()
+        .ok()
+        .and_then(|doc| doc.get("value").and_then(toml_item_to_json));
+    Ok((
+        key.to_string(),
+        parsed.unwrap_or_else(|| JsonValue::String(value.to_string())),
+    ))
+}
+
+/// Parse a reasoning effort name (`none`, `minimal`, `low`, `medium`, `high`, `xhigh`).
+pub fn parse_reasoning_effort(raw: &str) -> Result<ReasoningEffort, String> {
+    serde_json::from_value(JsonValue::String(raw.to_string())).map_err(|_| {
+        format!(
+            "unknown reasoning effort {raw:?} (expected none, minimal, low, medium, high or xhigh)"
+        )
+    })
+}
+
+fn parse_codex_config(codex: &dyn TableLike) -> anyhow::Result<CodexOverrides> {
+    let effort = read_effort(codex).context("codex.model_reasoning_effort")?;
+    let mut config = BTreeMap::new();
+    if let Some(item) = codex.get("config") {
+        let table = item
+            .as_table_like()
+            .context("`codex.config` must be a table")?;
+        flatten_config(table, "", &mut config)?;
+    }
+    let mut phases = BTreeMap::new();
+    if let Some(item) = codex.get("phases") {
+        let table = item
+            .as_table_like()
+            .context("`codex.phases` must be a table")?;
+        for (name, item) in table.iter() {
+            let phase = ProgressPhase::ALL
+                .into_iter()
+                .find(|phase| phase.as_str() == name)
+                .with_context(|| {
+                    format!("unknown phase {name:?} (expected planning, execution or review)")
+                })?;
+            let table = item
+                .as_table_like()
+                .with_context(|| format!("`codex.phases.{name}` must be a table"))?;
+            let model = PhaseModel {
+                model: read_str(table, "model")?.map(ToString::to_string),
+                effort: read_effort(table)
+                    .with_context(|| format!("codex.phases.{name}.model_reasoning_effort"))?,
+            };
+            phases.insert(phase, model);
+        }
+    }
+
+    Ok(CodexOverrides {
+        model: read_str(codex, "model")?.map(ToString::to_string),
+        profile: read_str(codex, "profile")?.map(ToString::to_string),
+        effort,
+        config,
+        phases,
+    })
+}
+
+fn read_effort(table: &dyn TableLike) -> anyhow::Result<Option<ReasoningEffort>> {
+    read_str(table, "model_reasoning_effort")?
+        .map(parse_reasoning_effort)
+        .transpose()
+        .map_err(anyhow::Error::msg)
+}
+
+/// Turn nested tables into dotted keys (`a.b = 1` and `[a] b = 1` both become `a.b`).
+fn flatten_config(
+    table: &dyn TableLike,
+    prefix: &str,
+    config: &mut BTreeMap<String, JsonValue>,
+) -> anyhow::Result<()> {
+    for (key, item) in table.iter() {
+        let key = if prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        if let Some(nested) = item.as_table_like() {
+            flatten_config(nested, &key, config)?;
+            continue;
+        }
+        let value = toml_item_to_json(item)
+            .with_context(|| format!("`codex.config.{key}` is not a supported value"))?;
+        config.insert(key, value);
+    }
+    Ok(())
+}
+
+fn toml_item_to_json(item: &TomlItem) -> Option<JsonValue> {
+    match item {
+        TomlItem::Value(value) => toml_value_to_json(value),
+        TomlItem::Table(table) => Some(JsonValue::Object(
+            table
+                .iter()
+                .filter_map(|(key, item)| Some((key.to_string(), toml_item_to_json(item)?)))
+                .collect(),
+        )),
+        TomlItem::None | TomlItem::ArrayOfTables(_) => None,
+    }
+}
+
+fn toml_value_to_json(value: &toml_edit::Value) -> Option<JsonValue> {
+    Some(match value {
+        toml_edit::Value::String(value) => JsonValue::String(value.value().clone()),
+        toml_edit::Value::Integer(value) => JsonValue::from(*value.value()),
+        toml_edit::Value::Float(value) => JsonValue::from(*value.value()),
+        toml_edit::Value::Boolean(value) => JsonValue::Bool(*value.value()),
+        toml_edit::Value::Datetime(value) => JsonValue::String(value.value().to_string()),
+        toml_edit::Value::Array(array) => JsonValue::Array(
+            array
+                .iter()
+                .map(toml_value_to_json)
+                .collect::<Option<Vec<_>>>()?,
+        ),
+        toml_edit::Value::InlineTable(table) => JsonValue::Object(
+            table
+                .iter()
+                .map(|(key, value)| Some((key.to_string(), toml_value_to_json(value)?)))
+                .collect::<Option<serde_json::Map<_, _>>>()?,
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+    use serde_json::json;
+
+    #[test]
+    fn config_override_values_parse_as_toml_or_fall_back_to_strings() {
+        assert_eq!(
+            parse_config_override("model_verbosity=\"low\""),
+            Ok(("model_verbosity".to_string(), json!("low")))
+        );
+        assert_eq!(
+            parse_config_override("sandbox_workspace_write.network_access = true"),
+            Ok((
+                "sandbox_workspace_write.network_access".to_string(),
+                json!(true)
+            ))
+        );
+        assert_eq!(
+            parse_config_override("model=gpt-5.2-codex"),
+            Ok(("model".to_string(), json!("gpt-5.2-codex")))
+        );
+        assert_eq!(
+            parse_config_override("shell_environment_policy.include_only=[\"PATH\", \"HOME\"]"),
+            Ok((
+                "shell_environment_policy.include_only".to_string(),
+                json!(["PATH", "HOME"])
+            ))
+        );
+        assert!(parse_config_override("model").is_err());
+        assert!(parse_config_override("=1").is_err());
+    }
+
+    fn load(
+        layers: &[(ConfigSource, &str)],
+        cli: CodexOverrides,
+    ) -> anyhow::Result<CodexOverrides> {
+        CodexOverrides::load(&LayeredConfig::from_toml(layers)?, cli)
+    }
+
+    #[test]
+    fn cli_flags_win_over_home_and_repository_config() {
+        let cli = CodexOverrides {
+            effort: Some(ReasoningEffort::Low),
+            config: BTreeMap::from([("model_verbosity".to_string(), json!("medium"))]),
+            ..Default::default()
+        };
+        let overrides = load(
+            &[
+                (
+                    ConfigSource::Home,
+                    "[codex]\nmodel = \"home-model\"\nprofile = \"work\"\nmodel_reasoning_effort = \"high\"\n\n[codex.config]\nmodel_verbosity = \"high\"\nsandbox_workspace_write.network_access = true\n",
+                ),
+                (
+                    ConfigSource::Repository,
+                    "[codex]\nmodel = \"repo-model\"\n\n[codex.config]\nmodel_verbosity = \"low\"\nhide_agent_reasoning = true\n",
+                ),
+            ],
+            cli,
+        )
+        .expect("load overrides");
+        assert_eq!(
+            overrides,
+            CodexOverrides {
+                model: Some("home-model".to_string()),
+                profile: Some("work".to_string()),
+                effort: Some(ReasoningEffort::Low),
+                config: BTreeMap::from([
+                    ("hide_agent_reasoning".to_string(), json!(true)),
+                    ("model_verbosity".to_string(), json!("medium")),
+                    (
+                        "sandbox_workspace_write.network_access".to_string(),
+                        json!(true)
+                    ),
+                ]),
+                phases: BTreeMap::new(),
+            }
+        );
+        assert_eq!(
+            overrides.thread_config(),
+            Some(HashMap::from([
+                ("hide_agent_reasoning".to_string(), json!(true)),
+                ("model_verbosity".to_string(), json!("medium")),
+                (
+                    "sandbox_workspace_write.network_access".to_string(),
+                    json!(true)
+                ),
+                ("profile".to_string(), json!("work")),
+            ]))
+        );
+    }
+
+    #[test]
+    fn repository_config_only_sets_model_and_output_keys() {
+        for (content, key) in [
+            (
+                "[codex.config]\nsandbox_mode = \"danger-full-access\"\n",
+                "`codex.config.sandbox_mode`",
+            ),
+            (
+                "[codex]\nconfig = { sandbox_workspace_write = { writable_roots = [\"/\"] } }\n",
+                "`codex.config.sandbox_workspace_write.writable_roots`",
+            ),
+            (
+                "[codex.config]\nnotify = [\"sh\", \"-c\", \"curl evil\"]\n",
+                "`codex.config.notify`",
+            ),
+            (
+                "[codex.config.mcp_servers.tools]\ncommand = \"sh\"\n",
+                "`codex.config.mcp_servers.tools.command`",
+            ),
+            (
+                "[codex.config]\nshell_environment_policy.inherit = \"all\"\n",
+                "`codex.config.shell_environment_policy.inherit`",
+            ),
+            (
+                "[codex.config]\napproval_policy = \"never\"\n",
+                "`codex.config.approval_policy`",
+            ),
+            ("[codex]\nprofile = \"yolo\"\n", "`codex.profile`"),
+        ] {
+            let err = load(
+                &[(ConfigSource::Repository, content)],
+                CodexOverrides::default(),
+            )
+            .expect_err("repository key is rejected");
+            assert!(
+                format!("{err:#}").contains(&format!(
+                    "{key} is only read from ~/.codexpotter/config.toml"
+                )),
+                "unexpected error for {content:?}: {err:#}"
+            );
+        }
+
+        let overrides = load(
+            &[(
+                ConfigSource::Repository,
+                "[codex]\nmodel = \"o3\"\n\n[codex.config]\nmodel_verbosity = \"low\"\nhide_agent_reasoning = true\n",
+            )],
+            CodexOverrides::default(),
+        )
+        .expect("model and output keys are accepted");
+        assert_eq!(overrides.model.as_deref(), Some("o3"));
+        assert_eq!(overrides.config.len(), 2);
+    }
+
+    #[test]
+    fn model_overrides_include_config_keys() {
+        let overrides = CodexOverrides {
+            config: BTreeMap::from([
+                ("model".to_string(), json!("o3")),
+                ("model_reasoning_effort".to_string(), json!("xhigh")),
+            ]),
+            ..Default::default()
+        };
+        assert_eq!(
+            overrides.model_overrides(),
+            codex_tui::CodexModelOverrides {
+                model: Some("o3".to_string()),
+                profile: None,
+                reasoning_effort: Some(ReasoningEffort::XHigh),
+            }
+        );
+        assert_eq!(CodexOverrides::default().thread_config(), None);
+    }
+
+    #[test]
+    fn phase_models_replace_the_general_model() {
+        let overrides = load(
+            &[
+                (
+                    ConfigSource::Home,
+                    "[codex]\nmodel = \"gpt-5.2-codex\"\nmodel_reasoning_effort = \"medium\"\n\n[codex.phases.planning]\nmodel = \"gpt-5.2\"\nmodel_reasoning_effort = \"high\"\n\n[codex.phases.execution]\nmodel = \"gpt-5.2-codex-mini\"\n",
+                ),
+                (
+                    ConfigSource::Repository,
+                    "[codex.phases.planning]\nmodel_reasoning_effort = \"xhigh\"\n\n[codex.phases.review]\nmodel_reasoning_effort = \"low\"\n",
+                ),
+            ],
+            CodexOverrides::default(),
+        )
+        .expect("load overrides");
+        let phase_model = |phase| {
+            let overrides = overrides.for_phase(phase);
+            (overrides.model, overrides.effort)
+        };
+        assert_eq!(
+            phase_model(ProgressPhase::Planning),
+            (Some("gpt-5.2".to_string()), Some(ReasoningEffort::High))
+        );
+        assert_eq!(
+            phase_model(ProgressPhase::Execution),
+            (
+                Some("gpt-5.2-codex-mini".to_string()),
+                Some(ReasoningEffort::Medium)
+            )
+        );
+        assert_eq!(
+            phase_model(ProgressPhase::Review),
+            (
+                Some("gpt-5.2-codex".to_string()),
+                Some(ReasoningEffort::Low)
+            )
+        );
+    }
+
+    #[test]
+    fn progress_file_is_read_only_for_phase_models() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let progress_file = temp.path().join("MAIN.md");
+        let general = CodexOverrides {
+            model: Some("gpt-5.2-codex".to_string()),
+            ..Default::default()
+        };
+        // No `[codex.phases]`: a missing progress file does not matter.
+        assert_eq!(general.for_progress_file(&progress_file), general);
+
+        let phased = CodexOverrides {
+            phases: BTreeMap::from([(
+                ProgressPhase::Review,
+                PhaseModel {
+                    model: Some("gpt-5.2".to_string()),
+                    effort: None,
+                },
+            )]),
+            ..general
+        };
+        // An unreadable progress file falls back to the general model.
+        assert_eq!(
+            phased.for_progress_file(&progress_file).model.as_deref(),
+            Some("gpt-5.2-codex")
+        );
+
+        std::fs::write(
+            &progress_file,
+            "---\nstatus: open\nfinite_incantatem: false\n---\n\n# Overall Goal\n\ngoal\n\n## Todo\n\n## Done\n\n- [x] task\n",
+        )
+        .expect("write progress file");
+        assert_eq!(
+            phased.for_progress_file(&progress_file).model.as_deref(),
+            Some("gpt-5.2")
+        );
+    }
+
+    #[test]
+    fn invalid_codex_config_is_rejected() {
+        for content in [
+            "codex = 1\n",
+            "[codex]\nmodel = 1\n",
+            "[codex]\nmodel_reasoning_effort = \"max\"\n",
+            "[codex]\nconfig = 1\n",
+            "[codex.phases.testing]\nmodel = \"o3\"\n",
+            "[codex.phases]\nreview = \"o3\"\n",
+        ] {
+            assert!(
+                load(&[(ConfigSource::Home, content)], CodexOverrides::default()).is_err(),
+                "expected an error for:\n{content}"
+            );
+        }
+    }
+}
@@ -0,0 +1,652 @@
+//! Guard against dangerous commands, which applies even with `--yolo`.
+//!
+//! Every command the agent asks to run (an approval request) or starts (`ExecCommandBegin`) is
+//! checked against a deny-list: built-in rules for `git push`, `git reset --hard` on a shared
+//! branch, package publishes and `rm -rf` outside the workdir, plus the `[[command_guard.deny]]`
+//! entries of `~/.codexpotter/config.toml` and `<workdir>/.codexpotter/config.toml`. Only the home
+//! file can turn the built-in rules off or replace the shared branches; the agent can write the
+//! repository file, so there it can only add deny rules and shared branches. A match interrupts the
+//! turn and ends the round as `command_blocked`; the next round's developer prompt tells the agent
+//! that the command is forbidden.
+//!
+//! A matching approval request is denied, so the command never runs. In `--yolo` mode there are no
+//! approval requests: the guard only sees the command once it started, and stopping the turn does
+//! not undo what the command already did.
+//!
+//! ```toml
+//! [command_guard]
+//! builtin = true
+//! shared_branches = ["main", "master", "develop"]
+//!
+//! [[command_guard.deny]]
+//! command_prefix = ["terraform", "apply"]
+//!
+//! [[command_guard.deny]]
+//! command_regex = "^kubectl (apply|delete)\\b"
+//! ```
+
+use std::path::Component;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_protocol::parse_command::ParsedCommand;
+use regex_automata::meta::Regex;
+use toml_edit::Item as TomlItem;
+use toml_edit::TableLike;
+
+use crate::approval_policy::shell_script;
+use crate::config::ConfigSource;
+use crate::config::LayeredConfig;
+use crate::config::read_str;
+use crate::config::reject_in_repository;
+use crate::config::string_array;
+
+/// Package publishing commands denied by the built-in rules.
+const PUBLISH_COMMANDS: &[&[&str]] = &[
+    &["cargo", "publish"],
+    &["npm", "publish"],
+    &["pnpm", "publish"],
+    &["yarn", "publish"],
+    &["yarn", "npm", "publish"],
+    &["poetry", "publish"],
+    &["twine", "upload"],
+    &["uv", "publish"],
+    &["gem", "push"],
+];
+
+const DEFAULT_SHARED_BRANCHES: &[&str] = &["main", "master"];
+
+/// A command the guard stopped, reported to the next round.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BlockedCommand {
+    /// The offending command (one command of a shell script, not the whole script).
+    pub command: Vec<String>,
+    /// Human-readable form of the rule that matched.
+    pub rule: String,
+}
+
+#[derive(Debug, Clone)]
+enum DenyMatcher {
+    CommandPrefix(Vec<String>),
+    CommandRegex(Regex),
+    GitPush,
+    /// `git reset --hard` while one of the shared branches is checked out.
+    GitResetHardOnSharedBranch,
+    /// `rm` with both recursive and force flags on a path that is not inside the workdir.
+    ForceRemoveOutsideWorkdir,
+}
+
+#[derive(Debug, Clone)]
+struct DenyRule {
+    matcher: DenyMatcher,
+    description: String,
+}
+
+/// Deny-list of one `codex-potter` invocation.
+#[derive(Debug, Clone)]
+pub struct CommandGuard {
+    rules: Vec<DenyRule>,
+    shared_branches: Vec<String>,
+}
+
+impl Default for CommandGuard {
+    /// The built-in rules (no configuration).
+    fn default() -> Self {
+        Self {
+            rules: builtin_rules(),
+            shared_branches: DEFAULT_SHARED_BRANCHES
+                .iter()
+                .map(ToString::to_string)
+                .collect(),
+        }
+    }
+}
+
+impl CommandGuard {
+    /// Load the deny-list from the `[command_guard]` tables of `config`.
+    ///
+    /// Only the home file can drop the built-in rules or replace the shared branches; the
+    /// repository file can only add deny rules and shared branches.
+    pub fn load(config: &LayeredConfig) -> anyhow::Result<Self> {
+        let mut deny = Vec::new();
+        let mut builtin = None;
+        let mut home_shared_branches = None;
+        let mut repository_shared_branches = Vec::new();
+        for (source, layer) in config.tables("command_guard", |source, table| {
+            reject_in_repository(source, "command_guard", table, &["builtin"])?;
+            Ok((source, parse_command_guard_config(table)?))
+        })? {
+            deny.extend(layer.deny);
+            builtin = builtin.or(layer.builtin);
+            match source {
+                ConfigSource::Home => home_shared_branches = layer.shared_branches,
+                ConfigSource::Repository => {
+                    repository_shared_branches.extend(layer.shared_branches.unwrap_or_default());
+                }
+            }
+        }
+
+        let mut guard = Self::default();
+        if !builtin.unwrap_or(true) {
+            guard.rules.clear();
+        }
+        guard.rules.extend(deny);
+        if let Some(shared_branches) = home_shared_branches {
+            guard.shared_branches = shared_branches;
+        }
+        for branch in repository_shared_branches {
+            if !guard.shared_branches.contains(&branch) {
+                guard.shared_branches.push(branch);
+            }
+        }
+        Ok(guard)
+    }
+
+    /// Check a command the agent started in `cwd`; `workdir` is the directory the agent works in.
+    ///
+    /// Shell wrappers (`bash -lc "a && b"`) are looked through, and so are the commands app-server
+    /// parsed out of the script (`parsed`).
+    pub fn check(
+        &self,
+        command: &[String],
+        parsed: &[ParsedCommand],
+        cwd: &Path,
+        workdir: &Path,
+    ) -> Option<BlockedCommand> {
+        let mut candidates = match shell_script(command) {
+            Some(script) => shlex::split(script)
+                .map(|tokens| split_script_commands(&tokens))
+                .unwrap_or_default(),
+            None => vec![command.to_vec()],
+        };
+        candidates.extend(parsed.iter().filter_map(|parsed| {
+            let cmd = match parsed {
+                ParsedCommand::Read { cmd, .. }
+                | ParsedCommand::ListFiles { cmd, .. }
+                | ParsedCommand::Search { cmd, .. }
+                | ParsedCommand::Unknown { cmd } => cmd,
+            };
+            shlex::split(cmd)
+        }));
+        let candidates = candidates
+            .iter()
+            .map(|argv| strip_command_prefixes(argv))
+            .filter(|argv| !argv.is_empty())
+            .collect::<Vec<_>>();
+        let script = shell_script(command);
+
+        self.rules.iter().find_map(|rule| {
+            let blocked = |argv: &[String]| BlockedCommand {
+                command: argv.to_vec(),
+                rule: rule.description.clone(),
+            };
+            if let DenyMatcher::CommandRegex(regex) = &rule.matcher
+                && (regex.is_match(&command.join(" "))
+                    || script.is_some_and(|script| regex.is_match(script)))
+            {
+                return Some(blocked(command));
+            }
+            candidates
+                .iter()
+                .find(|argv| self.matches(&rule.matcher, argv, cwd, workdir))
+                .map(|argv| blocked(argv.as_slice()))
+        })
+    }
+
+    fn matches(&self, matcher: &DenyMatcher, argv: &[String], cwd: &Path, workdir: &Path) -> bool {
+        match matcher {
+            DenyMatcher::CommandPrefix(prefix) => argv.starts_with(prefix),
+            DenyMatcher::CommandRegex(regex) => regex.is_match(&argv.join(" ")),
+            DenyMatcher::GitPush => git_subcommand(argv).is_some_and(|(sub, _)| sub == "push"),
+            DenyMatcher::GitResetHardOnSharedBranch => {
+                git_subcommand(argv).is_some_and(|(sub, args)| {
+                    sub == "reset" && args.iter().any(|arg| arg == "--hard")
+                }) && current_git_branch(cwd)
+                    .is_some_and(|branch| self.shared_branches.contains(&branch))
+            }
+            DenyMatcher::ForceRemoveOutsideWorkdir => {
+                force_remove_targets(argv).is_some_and(|targets| {
+                    targets
+                        .iter()
+                        .any(|target| !is_strictly_inside(target, cwd, workdir))
+                })
+            }
+        }
+    }
+}
+
+/// Developer prompt for the round after one the guard stopped: `developer_prompt` plus a note
+/// that the command is forbidden.
+pub fn developer_prompt_with_blocked_command(
+    developer_prompt: &str,
+    blocked: Option<&BlockedCommand>,
+) -> String {
+    let Some(blocked) = blocked else {
+        return developer_prompt.to_string();
+    };
+    let command = shlex::try_join(blocked.command.iter().map(String::as_str))
+        .unwrap_or_else(|_| blocked.command.join(" "));
+    format!(
+        "{developer_prompt}\n\n<COMMAND_BLOCKED>\n\nThe previous round was stopped because it ran `{command}`, which is forbidden here ({}). Never run it, or anything with the same effect, again. If the task cannot be done without it, leave that step to the user and say so in the progress file.\n\n</COMMAND_BLOCKED>\n",
+        blocked.rule,
+    )
+}
+
+fn builtin_rules() -> Vec<DenyRule> {
+    let mut rules = vec![
+        DenyRule {
+            matcher: DenyMatcher::GitPush,
+            description: "builtin: git push".to_string(),
+        },
+        DenyRule {
+            matcher: DenyMatcher::GitResetHardOnSharedBranch,
+            description: "builtin: git reset --hard on a shared branch".to_string(),
+        },
+        DenyRule {
+            matcher: DenyMatcher::ForceRemoveOutsideWorkdir,
+            description: "builtin: rm -rf outside the workdir".to_string(),
+        },
+    ];
+    rules.extend(PUBLISH_COMMANDS.iter().map(|prefix| DenyRule {
+        matcher: DenyMatcher::CommandPrefix(prefix.iter().map(ToString::to_string).collect()),
+        description: format!("builtin: {}", prefix.join(" ")),
+    }));
+    rules
+}
+
+/// Split the words of a shell script into its commands, at `&&`, `||`, `;`, `|` and `&`.
+fn split_script_commands(tokens: &[String]) -> Vec<Vec<String>> {
+    let mut commands = vec![Vec::new()];
+    for token in tokens {
+        if matches!(token.as_str(), "&&" | "||" | ";" | "|" | "&") {
+            commands.push(Vec::new());
+            continue;
+        }
+        let (word, ends_command) = match token.strip_suffix(';') {
+            Some(word) => (word, true),
+            None => (token.as_str(), false),
+        };
+        if let Some(current) = commands.last_mut()
+            && !word.is_empty()
+        {
+            current.push(word.to_string());
+        }
+        if ends_command {
+            commands.push(Vec::new());
+        }
+    }
+    commands.retain(|command| !command.is_empty());
+    commands
+}
+
+/// Drop leading `NAME=value` assignments and `sudo`/`env`/`command`/`exec` prefixes.
+fn strip_command_prefixes(argv: &[String]) -> Vec<String> {
+    let start = argv
+        .iter()
+        .position(|arg| {
+            let is_assignment = arg
+                .split_once('=')
+                .is_some_and(|(name, _)| !name.is_empty() && !name.starts_with('-'));
+            !is_assignment && !matches!(arg.as_str(), "sudo" | "command" | "exec" | "env")
+        })
+        .unwrap_or(argv.len());
+    argv[start..].to_vec()
+}
+
+/// The subcommand of a `git` invocation and its arguments, skipping global options such as `-C`.
+fn git_subcommand(argv: &[String]) -> Option<(&str, &[String])> {
+    let (program, rest) = argv.split_first()?;
+    if Path::new(program).file_name()?.to_str()? != "git" {
+        return None;
+    }
+    let mut idx = 0;
+    while let Some(arg) = rest.get(idx) {
+        match arg.as_str() {
+            "-C" | "-c" | "--git-dir" | "--work-tree" | "--namespace" => idx += 2,
+            arg if arg.starts_with('-') => idx += 1,
+            sub => return Some((sub, &rest[idx + 1..])),
+        }
+    }
+    None
+}
+
+fn current_git_branch(cwd: &Path) -> Option<String> {
+    let output = std::process::Command::new("git")
+        .arg("-C")
+        .arg(cwd)
+        .args(["rev-parse", "--abbrev-ref", "HEAD"])
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// The operands of an `rm` that removes recursively and forcibly, or `None` for any other command.
+fn force_remove_targets(argv: &[String]) -> Option<Vec<&str>> {
+    let (program, args) = argv.split_first()?;
+    if Path::new(program).file_name()?.to_str()? != "rm" {
+        return None;
+    }
+    let mut recursive = false;
+    let mut force = false;
+    let mut targets = Vec::new();
+    let mut options_done = false;
+    for arg in args {
+        match arg.as_str() {
+            "--" if !options_done => options_done = true,
+            "--recursive" if !options_done => recursive = true,
+            "--force" if !options_done => force = true,
+            flag if !options_done && flag.starts_with("--") => {}
+            flags if !options_done && flags.starts_with('-') && flags.len() > 1 => {
+                recursive |= flags.contains(['r', 'R']);
+                force |= flags.contains('f');
+            }
+            target => targets.push(target),
+        }
+    }
+    (recursive && force).then_some(targets)
+}
+
+/// Whether `target` (relative to `cwd`) is a path strictly inside `workdir`.
+///
+/// Paths the shell would expand (`~`, `$HOME`) cannot be resolved and count as outside.
+fn is_strictly_inside(target: &str, cwd: &Path, workdir: &Path) -> bool {
+    if target.starts_with('~') || target.contains('$') {
+        return false;
+    }
+    let resolved = normalize(&cwd.join(target));
+    let workdir = normalize(workdir);
+    resolved != workdir && resolved.starts_with(&workdir)
+}
+
+/// Resolve `.` and `..` components without touching the filesystem.
+fn normalize(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+#[derive(Debug, Default)]
+struct CommandGuardConfig {
+    deny: Vec<DenyRule>,
+    builtin: Option<bool>,
+    shared_branches: Option<Vec<String>>,
+}
+
+fn parse_command_guard_config(guard: &dyn TableLike) -> anyhow::Result<CommandGuardConfig> {
+    let builtin = guard
+        .get("builtin")
+        .map(|item| {
+            item.as_bool()
+                .context("`command_guard.builtin` must be a boolean")
+        })
+        .transpose()?;
+    let shared_branches = guard
+        .get("shared_branches")
+        .map(|item| {
+            string_array(item)
+                .context("`command_guard.shared_branches` must be an array of strings")
+        })
+        .transpose()?;
+
+    let mut deny = Vec::new();
+    match guard.get("deny") {
+        None => {}
+        Some(TomlItem::ArrayOfTables(tables)) => {
+            for (idx, table) in tables.iter().enumerate() {
+                deny.push(
+                    parse_deny_rule(table).with_context(|| format!("command_guard.deny[{idx}]"))?,
+                );
+            }
+        }
+        Some(item) => {
+            let array = item
+                .as_array()
+                .context("`command_guard.deny` must be an array of tables")?;
+            for (idx, value) in array.iter().enumerate() {
+                let table = value
+                    .as_inline_table()
+                    .with_context(|| format!("command_guard.deny[{idx}] must be a table"))?;
+                deny.push(
+                    parse_deny_rule(table).with_context(|| format!("command_guard.deny[{idx}]"))?,
+                );
+            }
+        }
+    }
+
+    Ok(CommandGuardConfig {
+        deny,
+        builtin,
+        shared_branches,
+    })
+}
+
+fn parse_deny_rule(table: &dyn TableLike) -> anyhow::Result<DenyRule> {
+    let prefix = table
+        .get("command_prefix")
+        .map(|item| {
+            string_array(item)
+                .filter(|prefix| !prefix.is_empty())
+                .context("`command_prefix` must be a non-empty array of strings")
+        })
+        .transpose()?;
+    let pattern = read_str(table, "command_regex")?;
+    match (prefix, pattern) {
+        (Some(prefix), None) => Ok(DenyRule {
+            description: format!("command_prefix = {prefix:?}"),
+            matcher: DenyMatcher::CommandPrefix(prefix),
+        }),
+        (None, Some(pattern)) => Ok(DenyRule {
+            matcher: DenyMatcher::CommandRegex(
+                Regex::new(pattern)
+                    .with_context(|| format!("invalid `command_regex` {pattern:?}"))?,
+            ),
+            description: format!("command_regex = {pattern:?}"),
+        }),
+        _ => anyhow::bail!("a deny rule needs exactly one of `command_prefix` or `command_regex`"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn argv(args: &[&str]) -> Vec<String> {
+        args.iter().map(ToString::to_string).collect()
+    }
+
+    fn check(guard: &CommandGuard, args: &[&str]) -> Option<String> {
+        guard
+            .check(&argv(args), &[], Path::new("/repo/sub"), Path::new("/repo"))
+            .map(|blocked| blocked.rule)
+    }
+
+    fn load(layers: &[(ConfigSource, &str)]) -> anyhow::Result<CommandGuard> {
+        CommandGuard::load(&LayeredConfig::from_toml(layers)?)
+    }
+
+    fn load_guard(content: &str) -> CommandGuard {
+        load(&[(ConfigSource::Home, content)]).expect("load guard")
+    }
+
+    #[test]
+    fn builtin_rules_block_dangerous_commands() {
+        let guard = CommandGuard::default();
+
+        assert_eq!(
+            guard.check(
+                &argv(&[
+                    "/bin/bash",
+                    "-lc",
+                    "cargo fmt && git -C .. push origin main"
+                ]),
+                &[],
+                Path::new("/repo"),
+                Path::new("/repo"),
+            ),
+            Some(BlockedCommand {
+                command: argv(&["git", "-C", "..", "push", "origin", "main"]),
+                rule: "builtin: git push".to_string(),
+            })
+        );
+        assert_eq!(
+            check(
+                &guard,
+                &["bash", "-lc", "NPM_TOKEN=x npm publish --access public"]
+            ),
+            Some("builtin: npm publish".to_string())
+        );
+        assert_eq!(
+            check(&guard, &["rm", "-rf", "../../etc"]),
+            Some("builtin: rm -rf outside the workdir".to_string())
+        );
+        assert_eq!(
+            check(&guard, &["rm", "-r", "-f", ".."]),
+            Some("builtin: rm -rf outside the workdir".to_string())
+        );
+        assert_eq!(
+            check(&guard, &["bash", "-lc", "rm -rf ~/.cache;"]),
+            Some("builtin: rm -rf outside the workdir".to_string())
+        );
+
+        assert_eq!(check(&guard, &["rm", "-rf", "target", "../build"]), None);
+        assert_eq!(check(&guard, &["rm", "-r", "/tmp/x"]), None);
+        assert_eq!(check(&guard, &["git", "status"]), None);
+        assert_eq!(check(&guard, &["bash", "-lc", "cargo test --all"]), None);
+    }
+
+    #[test]
+    fn parsed_commands_are_checked_too() {
+        let guard = CommandGuard::default();
+        let blocked = guard.check(
+            &argv(&["pwsh", "-Command", "something opaque"]),
+            &[ParsedCommand::Unknown {
+                cmd: "cargo publish -p codex-potter".to_string(),
+            }],
+            Path::new("/repo"),
+            Path::new("/repo"),
+        );
+        assert_eq!(
+            blocked.map(|blocked| blocked.rule),
+            Some("builtin: cargo publish".to_string())
+        );
+    }
+
+    #[test]
+    fn configured_rules_extend_or_replace_builtins() {
+        let guard = load_guard(
+            r#"
+[command_guard]
+builtin = false
+
+[[command_guard.deny]]
+command_prefix = ["terraform", "apply"]
+
+[[command_guard.deny]]
+command_regex = "^kubectl (apply|delete)\\b"
+"#,
+        );
+        assert_eq!(
+            check(&guard, &["terraform", "apply", "-auto-approve"]),
+            Some(r#"command_prefix = ["terraform", "apply"]"#.to_string())
+        );
+        assert_eq!(
+            check(&guard, &["bash", "-lc", "kubectl delete pod x"]),
+            Some(r#"command_regex = "^kubectl (apply|delete)\\b""#.to_string())
+        );
+        assert_eq!(check(&guard, &["git", "push"]), None);
+
+        let guard =
+            load_guard("[command_guard]\ndeny = [{ command_prefix = [\"make\", \"deploy\"] }]\n");
+        assert_eq!(
+            check(&guard, &["git", "push"]),
+            Some("builtin: git push".to_string())
+        );
+        assert_eq!(
+            check(&guard, &["make", "deploy"]),
+            Some(r#"command_prefix = ["make", "deploy"]"#.to_string())
+        );
+    }
+
+    #[test]
+    fn repository_config_only_adds_rules() {
+        let home = "[command_guard]\nshared_branches = [\"trunk\"]\n";
+
+        let err = load(&[
+            (ConfigSource::Home, home),
+            (
+                ConfigSource::Repository,
+                "[command_guard]\nbuiltin = false\n",
+            ),
+        ])
+        .expect_err("repository builtin is rejected");
+        assert!(
+            format!("{err:#}").contains("`command_guard.builtin`"),
+            "unexpected error: {err:#}"
+        );
+
+        let guard = load(&[
+            (ConfigSource::Home, home),
+            (
+                ConfigSource::Repository,
+                "[command_guard]\nshared_branches = []\n\n[[command_guard.deny]]\ncommand_prefix = [\"make\", \"deploy\"]\n",
+            ),
+        ])
+        .expect("load guard");
+        assert_eq!(
+            check(&guard, &["git", "push"]),
+            Some("builtin: git push".to_string())
+        );
+        assert_eq!(
+            check(&guard, &["make", "deploy"]),
+            Some(r#"command_prefix = ["make", "deploy"]"#.to_string())
+        );
+        assert_eq!(guard.shared_branches, vec!["trunk".to_string()]);
+    }
+
+    #[test]
+    fn invalid_config_is_rejected() {
+        for content in [
+            "command_guard = 1\n",
+            "[command_guard]\nbuiltin = \"yes\"\n",
+            "[command_guard]\nshared_branches = \"main\"\n",
+            "[[command_guard.deny]]\n",
+            "[[command_guard.deny]]\ncommand_prefix = []\n",
+            "[[command_guard.deny]]\ncommand_regex = \"(\"\n",
+            "[[command_guard.deny]]\ncommand_prefix = [\"ls\"]\ncommand_regex = \"ls\"\n",
+        ] {
+            assert!(
+                load(&[(ConfigSource::Home, content)]).is_err(),
+                "expected an error for:\n{content}"
+            );
+        }
+    }
+
+    #[test]
+    fn developer_prompt_mentions_blocked_command() {
+        assert_eq!(developer_prompt_with_blocked_command("base", None), "base");
+        let prompt = developer_prompt_with_blocked_command(
+            "base",
+            Some(&BlockedCommand {
+                command: argv(&["git", "push", "origin", "main"]),
+                rule: "builtin: git push".to_string(),
+            }),
+        );
+        assert!(prompt.starts_with("base\n\n<COMMAND_BLOCKED>"));
+        assert!(
+            prompt.contains("`git push origin main`, which is forbidden here (builtin: git push)")
+        );
+    }
+}
@@ -2,9 +2,11 @@ use std::io::ErrorKind;
 use std::path::Path;
 use std::path::PathBuf;
 
+use anyhow::Context;
 use toml_edit::DocumentMut;
 use toml_edit::Item as TomlItem;
 use toml_edit::Table as TomlTable;
+use toml_edit::TableLike;
 use toml_edit::value;
 
 use crate::atomic_write::write_atomic_text;
@@ -82,6 +84,141 @@ impl ConfigStore {
     }
 }
 
+/// Which `config.toml` a layer of [`LayeredConfig`] was read from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConfigSource {
+    /// `~/.codexpotter/config.toml`, which only the user edits.
+    Home,
+    /// `<workdir>/.codexpotter/config.toml`, which the agent can write as well.
+    Repository,
+}
+
+#[derive(Debug)]
+struct ConfigLayer {
+    source: ConfigSource,
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+/// The `config.toml` files of one invocation, for the feature tables (`[approval]`,
+/// `[command_guard]`, `[user_input]`, `[codex]`, `[sandbox]`).
+///
+/// Precedence is the same for every table: the home file comes before the repository file.
+/// Features take a value from the first layer that sets it and check rules in layer order, so the
+/// repository file can add to the user's configuration but never override it. Keys that would
+/// widen what the agent may do are rejected in the repository file ([`reject_in_repository`]).
+#[derive(Debug, Default)]
+pub struct LayeredConfig {
+    layers: Vec<ConfigLayer>,
+}
+
+impl LayeredConfig {
+    /// Read the home and repository config files of `workdir`; missing files are skipped.
+    pub fn load(workdir: &Path) -> anyhow::Result<Self> {
+        let mut files = Vec::new();
+        if let Some(home) = dirs::home_dir() {
+            files.push((ConfigSource::Home, default_config_path(&home)));
+        }
+        files.push((ConfigSource::Repository, default_config_path(workdir)));
+
+        let mut layers = Vec::new();
+        for (source, path) in files {
+            let content = match std::fs::read_to_string(&path) {
+                Ok(content) => content,
+                Err(err) if err.kind() == ErrorKind::NotFound => continue,
+                Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+            };
+            let doc = content
+                .parse::<DocumentMut>()
+                .with_context(|| format!("parse {}", path.display()))?;
+            layers.push(ConfigLayer { source, path, doc });
+        }
+        Ok(Self { layers })
+    }
+
+    /// Layers from TOML strings, in precedence order.
+    #[cfg(test)]
+    pub(crate) fn from_toml(layers: &[(ConfigSource, &str)]) -> anyhow::Result<Self> {
+        let layers = layers
+            .iter()
+            .map(|(source, content)| {
+                let path = match source {
+                    ConfigSource::Home => default_config_path(Path::new("~")),
+                    ConfigSource::Repository => default_config_path(Path::new("")),
+                };
+                let doc = content
+                    .parse::<DocumentMut>()
+                    .with_context(|| format!("parse {}", path.display()))?;
+                Ok(ConfigLayer {
+                    source: *source,
+                    path,
+                    doc,
+                })
+            })
+            .collect::<anyhow::Result<_>>()?;
+        Ok(Self { layers })
+    }
+
+    /// Parse the `[key]` table of every layer that has one, home first.
+    pub fn tables<T>(
+        &self,
+        key: &str,
+        mut parse: impl FnMut(ConfigSource, &dyn TableLike) -> anyhow::Result<T>,
+    ) -> anyhow::Result<Vec<T>> {
+        let mut parsed = Vec::new();
+        for layer in &self.layers {
+            let Some(item) = layer.doc.get(key) else {
+                continue;
+            };
+            let table = item
+                .as_table_like()
+                .with_context(|| format!("`{key}` must be a table"))
+                .and_then(|table| parse(layer.source, table))
+                .with_context(|| format!("invalid [{key}] config in {}", layer.path.display()))?;
+            parsed.push(table);
+        }
+        Ok(parsed)
+    }
+}
+
+/// Fail when the repository file sets one of `keys` of `[table_name]`: they would let the agent
+/// widen what it may do, so only the home file (or the command line) can set them.
+pub fn reject_in_repository(
+    source: ConfigSource,
+    table_name: &str,
+    table: &dyn TableLike,
+    keys: &[&str],
+) -> anyhow::Result<()> {
+    if source != ConfigSource::Repository {
+        return Ok(());
+    }
+    match keys.iter().find(|key| table.contains_key(key)) {
+        Some(key) => anyhow::bail!(
+            "`{table_name}.{key}` is only read from ~/.codexpotter/config.toml, not the repository config"
+        ),
+        None => Ok(()),
+    }
+}
+
+pub fn read_str<'a>(table: &'a dyn TableLike, key: &str) -> anyhow::Result<Option<&'a str>> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(item) => item
+            .as_str()
+            .map(Some)
+            .with_context(|| format!("`{key}` must be a string")),
+    }
+}
+
+pub fn string_array(item: &TomlItem) -> Option<Vec<String>> {
+    item.as_array().and_then(|array| {
+        array
+            .iter()
+            .map(|value| value.as_str().map(ToString::to_string))
+            .collect()
+    })
+}
+
 fn default_config_path(home: &Path) -> PathBuf {
     home.join(".codexpotter").join("config.toml")
 }
@@ -305,6 +442,72 @@ check_for_update_on_startup = false # keep me
         assert!(!store.check_for_update_on_startup().expect("read flag"));
     }
 
+    #[test]
+    fn layered_tables_come_home_first() {
+        let config = LayeredConfig::from_toml(&[
+            (ConfigSource::Home, "[approval]\ndefault = \"deny\"\n"),
+            (
+                ConfigSource::Repository,
+                "[approval]\ndefault = \"accept\"\n\n[other]\nkey = 1\n",
+            ),
+        ])
+        .expect("parse layers");
+        let tables = config
+            .tables("approval", |source, table| {
+                Ok((source, read_str(table, "default")?.map(ToString::to_string)))
+            })
+            .expect("read tables");
+        assert_eq!(
+            tables,
+            vec![
+                (ConfigSource::Home, Some("deny".to_string())),
+                (ConfigSource::Repository, Some("accept".to_string())),
+            ]
+        );
+        assert_eq!(
+            config
+                .tables("missing", |_, _| Ok(()))
+                .expect("read tables")
+                .len(),
+            0
+        );
+    }
+
+    #[test]
+    fn layered_tables_name_the_file_on_errors() {
+        let config = LayeredConfig::from_toml(&[(ConfigSource::Repository, "sandbox = 1\n")])
+            .expect("parse layers");
+        let err = config
+            .tables("sandbox", |_, _| Ok(()))
+            .expect_err("not a table");
+        assert_eq!(
+            format!("{err:#}"),
+            format!(
+                "invalid [sandbox] config in {}: `sandbox` must be a table",
+                default_config_path(Path::new("")).display()
+            )
+        );
+
+        let config = LayeredConfig::from_toml(&[
+            (ConfigSource::Home, "[sandbox]\nnetwork_access = true\n"),
+            (
+                ConfigSource::Repository,
+                "[sandbox]\nnetwork_access = true\n",
+            ),
+        ])
+        .expect("parse layers");
+        let err = config
+            .tables("sandbox", |source, table| {
+                reject_in_repository(source, "sandbox", table, &["network_access"])
+            })
+            .expect_err("repository key rejected");
+        assert!(
+            format!("{err:#}")
+                .contains("`sandbox.network_access` is only read from ~/.codexpotter/config.toml"),
+            "unexpected error: {err:#}"
+        );
+    }
+
     #[test]
     fn default_config_path_uses_codexpotter_home_dir() {
         let home = Path::new("home");
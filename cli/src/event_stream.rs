@@ -0,0 +1,141 @@
+//! Machine-readable JSONL event stream (`--json`).
+//!
+//! Every `EventMsg` the round runner forwards to the UI (including the synthesized `Potter*`
+//! markers) is also written as one JSON object per line, wrapped in a small stable envelope:
+//!
+//! ```json
+//! {"session_id":"2026/02/01/1","round":1,"timestamp":"2026-02-01T12:00:00.000Z","event":{"type":"agent_message","message":"..."}}
+//! ```
+//!
+//! `session_id` is the project path accepted by `codex-potter resume`, so it stays stable across
+//! resumed runs of the same project.
+
+use std::io::Write;
+use std::sync::Arc;
+use std::sync::Mutex;
+
+use anyhow::Context;
+use chrono::SecondsFormat;
+use chrono::Utc;
+use codex_protocol::protocol::EventMsg;
+use serde::Serialize;
+
+#[derive(Debug, Serialize)]
+struct JsonEventEnvelope<'a> {
+    session_id: &'a str,
+    round: u32,
+    timestamp: String,
+    event: &'a EventMsg,
+}
+
+/// Shared JSONL writer, bound to one CodexPotter session.
+#[derive(Clone)]
+pub struct JsonEventStream {
+    out: Arc<Mutex<Box<dyn Write + Send>>>,
+    session_id: Arc<str>,
+}
+
+impl std::fmt::Debug for JsonEventStream {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("JsonEventStream")
+            .field("session_id", &self.session_id)
+            .finish_non_exhaustive()
+    }
+}
+
+impl JsonEventStream {
+    pub fn new(out: Box<dyn Write + Send>) -> Self {
+        Self {
+            out: Arc::new(Mutex::new(out)),
+            session_id: Arc::from(""),
+        }
+    }
+
+    pub fn stdout() -> Self {
+        Self::new(Box::new(std::io::stdout()))
+    }
+
+    /// Return a stream that shares the same writer but tags events with `session_id`.
+    pub fn for_session(&self, session_id: &str) -> Self {
+        Self {
+            out: Arc::clone(&self.out),
+            session_id: Arc::from(session_id),
+        }
+    }
+
+    /// Write one event line. Each line is flushed so consumers can follow the stream live.
+    pub fn emit(&self, round: u32, event: &EventMsg) -> anyhow::Result<()> {
+        let envelope = JsonEventEnvelope {
+            session_id: &self.session_id,
+            round,
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            event,
+        };
+        let mut json = serde_json::to_string(&envelope).context("serialize json event")?;
+        json.push('\n');
+
+        let mut out = self
+            .out
+            .lock()
+            .map_err(|_| anyhow::anyhow!("json event stream lock poisoned"))?;
+        out.write_all(json.as_bytes()).context("write json event")?;
+        out.flush().context("flush json event")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    #[derive(Clone, Default)]
+    struct SharedBuf(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().expect("lock").extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn emit_wraps_event_in_stable_envelope() {
+        let buf = SharedBuf::default();
+        let stream = JsonEventStream::new(Box::new(buf.clone())).for_session("2026/02/01/1");
+
+        stream
+            .emit(
+                2,
+                &EventMsg::PotterRoundStarted {
+                    current: 2,
+                    total: 10,
+                },
+            )
+            .expect("emit");
+
+        let output = String::from_utf8(buf.0.lock().expect("lock").clone()).expect("utf8");
+        assert!(output.ends_with('\n'));
+        let mut value: serde_json::Value = serde_json::from_str(output.trim_end()).expect("json");
+        let timestamp = value
+            .as_object_mut()
+            .and_then(|obj| obj.remove("timestamp"))
+            .expect("timestamp");
+        assert!(
+            chrono::DateTime::parse_from_rfc3339(timestamp.as_str().expect("timestamp str"))
+                .is_ok()
+        );
+        assert_eq!(
+            value,
+            serde_json::json!({
+                "session_id": "2026/02/01/1",
+                "round": 2,
+                "event": {"type": "potter_round_started", "current": 2, "total": 10},
+            })
+        );
+    }
+}
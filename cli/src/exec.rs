@@ -0,0 +1,698 @@
+//! Headless `codex-potter exec` frontend.
+//!
+//! `exec` runs the same multi-round loop as the interactive UI, but instead of driving the
+//! ratatui renderer it writes a plain, line-oriented transcript to a writer (stdout in practice).
+//! It never reads from the terminal, so it works without a TTY (CI jobs, other agents, pipes).
+
+use std::io::Write;
+use std::path::Path;
+
+use anyhow::Context;
+use codex_protocol::ThreadId;
+use codex_protocol::plan_tool::StepStatus;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterApprovalDecidedBy;
+use codex_protocol::protocol::PotterApprovalDecision;
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
+use codex_protocol::protocol::PotterPromptAnsweredBy;
+use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::PotterRoundTimeout;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::user_input::UserInput;
+use codex_tui::AppExitInfo;
+use codex_tui::ExitReason;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+
+/// Resolve the `exec` goal from either the positional prompt or `--prompt-file`.
+pub fn resolve_exec_prompt(
+    prompt: Option<&str>,
+    prompt_file: Option<&Path>,
+) -> anyhow::Result<String> {
+    let prompt = match (prompt, prompt_file) {
+        (Some(prompt), None) => prompt.to_string(),
+        (None, Some(path)) => std::fs::read_to_string(path)
+            .with_context(|| format!("read prompt file {}", path.display()))?,
+        (Some(_), Some(_)) => anyhow::bail!("pass either a prompt or --prompt-file, not both"),
+        (None, None) => anyhow::bail!("missing prompt: pass a prompt or --prompt-file"),
+    };
+
+    let prompt = prompt.trim();
+    if prompt.is_empty() {
+        anyhow::bail!("prompt is empty");
+    }
+    Ok(prompt.to_string())
+}
+
+/// Round frontend that prints a line-oriented transcript instead of rendering a TUI.
+pub struct ExecRoundUi<W: Write> {
+    out: W,
+    token_usage: TokenUsage,
+    thread_id: Option<ThreadId>,
+}
+
+impl<W: Write> ExecRoundUi<W> {
+    pub fn new(out: W) -> Self {
+        Self {
+            out,
+            token_usage: TokenUsage::default(),
+            thread_id: None,
+        }
+    }
+
+    fn write_event(&mut self, msg: &EventMsg) -> anyhow::Result<()> {
+        match msg {
+            EventMsg::SessionConfigured(cfg) => {
+                self.thread_id = Some(cfg.session_id);
+            }
+            EventMsg::TokenCount(ev) => {
+                if let Some(info) = &ev.info {
+                    self.token_usage = info.total_token_usage.clone();
+                }
+            }
+            _ => {}
+        }
+
+        for line in format_event_lines(msg) {
+            writeln!(self.out, "{line}").context("write exec output")?;
+        }
+        self.out.flush().context("flush exec output")
+    }
+}
+
+impl<W: Write> crate::round_runner::PotterRoundUi for ExecRoundUi<W> {
+    async fn render_turn(
+        &mut self,
+        prompt: String,
+        _pad_before_first_cell: bool,
+        op_tx: UnboundedSender<Op>,
+        mut event_rx: UnboundedReceiver<Event>,
+        mut fatal_exit_rx: UnboundedReceiver<String>,
+    ) -> anyhow::Result<AppExitInfo> {
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: prompt,
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .map_err(|err| anyhow::Error::msg(err.to_string()))?;
+
+        let exit_reason = loop {
+            tokio::select! {
+                maybe_event = event_rx.recv() => {
+                    let Some(event) = maybe_event else {
+                        break ExitReason::Fatal("Backend disconnected".to_string());
+                    };
+                    self.write_event(&event.msg)?;
+                    if let EventMsg::PotterRoundFinished { outcome } = &event.msg {
+                        break exit_reason_from_outcome(outcome);
+                    }
+                }
+                maybe_fatal = fatal_exit_rx.recv() => {
+                    let Some(message) = maybe_fatal else {
+                        continue;
+                    };
+                    writeln!(self.out, "[fatal] {message}").context("write exec output")?;
+                    break ExitReason::Fatal(message);
+                }
+            }
+        };
+
+        Ok(AppExitInfo {
+            token_usage: self.token_usage.clone(),
+            thread_id: self.thread_id,
+            exit_reason,
+        })
+    }
+}
+
+impl<W: Write> crate::resume::PotterResumeUi for ExecRoundUi<W> {
+    fn clear(&mut self) -> anyhow::Result<()> {
+        Ok(())
+    }
+
+    /// Headless runs cannot ask, so always take the first (default) action.
+    async fn prompt_action_picker(
+        &mut self,
+        actions: Vec<String>,
+    ) -> anyhow::Result<Option<usize>> {
+        let Some(action) = actions.first() else {
+            return Ok(None);
+        };
+        writeln!(self.out, "[resume] {action}").context("write exec output")?;
+        Ok(Some(0))
+    }
+}
+
+pub fn exit_reason_from_outcome(outcome: &PotterRoundOutcome) -> ExitReason {
+    match outcome {
+        PotterRoundOutcome::Completed
+        | PotterRoundOutcome::InterruptedThenContinued
+        | PotterRoundOutcome::InterruptedNextRound
+        | PotterRoundOutcome::CommandBlocked { .. }
+        | PotterRoundOutcome::ContextExhausted
+        | PotterRoundOutcome::TimedOut { .. } => ExitReason::Completed,
+        PotterRoundOutcome::UserRequested | PotterRoundOutcome::InterruptedEndSession => {
+            ExitReason::UserRequested
+        }
+        PotterRoundOutcome::TaskFailed { message } => ExitReason::TaskFailed(message.clone()),
+        PotterRoundOutcome::Fatal { message } => ExitReason::Fatal(message.clone()),
+    }
+}
+
+/// Format one event as zero or more transcript lines.
+///
+/// Streaming deltas and reasoning are skipped; the final `AgentMessage` carries the full text.
+fn format_event_lines(msg: &EventMsg) -> Vec<String> {
+    match msg {
+        EventMsg::PotterSessionStarted {
+            working_dir,
+            user_prompt_file,
+            ..
+        } => vec![format!(
+            "[session] started in {} (progress file: {})",
+            working_dir.display(),
+            user_prompt_file.display()
+        )],
+        EventMsg::PotterRoundStarted { current, total } => {
+            vec![format!("[round] {current}/{total}")]
+        }
+        EventMsg::SessionConfigured(cfg) => {
+            vec![format!(
+                "[thread] {} (model: {})",
+                cfg.session_id, cfg.model
+            )]
+        }
+        EventMsg::AgentMessage(ev) => vec![format!("[codex] {}", ev.message.trim_end())],
+        EventMsg::ExecCommandEnd(ev) => vec![format!(
+            "[exec] {} (exit {}, {:.1}s)",
+            display_command(&ev.command),
+            ev.exit_code,
+            ev.duration.as_secs_f64()
+        )],
+        EventMsg::PatchApplyEnd(ev) => {
+            let mut paths = ev
+                .changes
+                .keys()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>();
+            paths.sort();
+            let status = if ev.success { "applied" } else { "failed" };
+            vec![format!("[patch] {status}: {}", paths.join(", "))]
+        }
+        EventMsg::WebSearchEnd(ev) => vec![format!("[search] {}", ev.query)],
+        EventMsg::PlanUpdate(args) => {
+            let mut lines = vec!["[plan]".to_string()];
+            lines.extend(args.plan.iter().map(|item| {
+                let mark = match item.status {
+                    StepStatus::Pending => "[ ]",
+                    StepStatus::InProgress => "[~]",
+                    StepStatus::Completed => "[x]",
+                };
+                format!("  {mark} {}", item.step)
+            }));
+            lines
+        }
+        EventMsg::ContextCompacted(_) => vec!["[info] context compacted".to_string()],
+        EventMsg::Warning(ev) => vec![format!("[warning] {}", ev.message)],
+        EventMsg::DeprecationNotice(ev) => vec![format!("[warning] {}", ev.summary)],
+        EventMsg::StreamError(ev) => vec![format!("[warning] {}", ev.message)],
+        EventMsg::Error(ev) => vec![format!("[error] {}", ev.message)],
+        EventMsg::TurnAborted(ev) => vec![format!("[aborted] {:?}", ev.reason)],
+        EventMsg::PotterStreamRecoveryUpdate {
+            attempt,
+            max_attempts,
+            error_message,
+        } => vec![format!(
+            "[retry] attempt {attempt}/{max_attempts}: {error_message}"
+        )],
+        EventMsg::PotterUsageLimitWait {
+            resumes_at,
+            error_message,
+        } => {
+            let resumes_at = chrono::DateTime::from_timestamp(*resumes_at, 0)
+                .map(|at| at.format("%Y-%m-%dT%H:%M:%SZ").to_string())
+                .unwrap_or_else(|| resumes_at.to_string());
+            vec![format!(
+                "[retry] usage limit reached, continuing at {resumes_at}: {error_message}"
+            )]
+        }
+        EventMsg::PotterContextCheckpoint { percent_left } => vec![format!(
+            "[context] {percent_left}% of the context window left, asking the agent to save its progress"
+        )],
+        EventMsg::PotterStreamRecoveryRecovered => vec!["[retry] recovered".to_string()],
+        EventMsg::PotterStreamRecoveryGaveUp {
+            error_message,
+            attempts,
+            max_attempts,
+        } => vec![format!(
+            "[retry] gave up after {attempts}/{max_attempts} attempts: {error_message}"
+        )],
+        EventMsg::PotterRoundVerified {
+            command,
+            success,
+            exit_code,
+            duration,
+            output_tail,
+        } => {
+            let status = if *success { "passed" } else { "failed" };
+            let exit = match exit_code {
+                Some(code) => format!("exit {code}"),
+                None => "no exit code".to_string(),
+            };
+            let mut lines = vec![format!(
+                "[verify] {status}: {command} ({exit}, {}s)",
+                duration.as_secs()
+            )];
+            if !success {
+                lines.extend(output_tail.lines().map(|line| format!("  {line}")));
+            }
+            lines
+        }
+        EventMsg::PotterProgressFileIssues {
+            repaired,
+            unresolved,
+        } => repaired
+            .iter()
+            .map(|issue| format!("[progress] repaired: {issue}"))
+            .chain(
+                unresolved
+                    .iter()
+                    .map(|issue| format!("[progress] invalid, left for the next round: {issue}")),
+            )
+            .collect(),
+        EventMsg::PotterRoundUsage {
+            duration,
+            token_usage,
+            session_token_usage,
+        } => vec![format!(
+            "[round] usage: {} tokens in {}s (input {}, cached {}, output {}, reasoning {}); session total {} tokens",
+            token_usage.total_tokens,
+            duration.as_secs(),
+            token_usage.input_tokens,
+            token_usage.cached_input_tokens,
+            token_usage.output_tokens,
+            token_usage.reasoning_output_tokens,
+            session_token_usage.total_tokens
+        )],
+        EventMsg::PotterBudgetExhausted { reason } => {
+            let reason = match reason {
+                PotterBudgetExhaustedReason::MaxDuration {
+                    limit_secs,
+                    elapsed_secs,
+                } => format!("time budget exhausted ({elapsed_secs}s of {limit_secs}s)"),
+                PotterBudgetExhaustedReason::MaxTokens { limit, used } => {
+                    format!("token budget exhausted ({used} of {limit} tokens)")
+                }
+            };
+            vec![format!("[budget] stopped: {reason}")]
+        }
+        EventMsg::PotterSessionStalled { rounds } => {
+            vec![format!(
+                "[session] stalled: no progress in the last {rounds} round(s)"
+            )]
+        }
+        EventMsg::PotterApprovalDecided {
+            command,
+            paths,
+            decision,
+            decided_by,
+        } => {
+            let subject = match command {
+                Some(command) => display_command(command),
+                None => paths
+                    .iter()
+                    .map(|path| path.display().to_string())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+            };
+            let decision = match decision {
+                PotterApprovalDecision::Accept => "accepted",
+                PotterApprovalDecision::Deny => "denied",
+            };
+            let decided_by = match decided_by {
+                PotterApprovalDecidedBy::Rule { rule } => format!("rule {rule}"),
+                PotterApprovalDecidedBy::Default => "approval.default".to_string(),
+                PotterApprovalDecidedBy::User => "user".to_string(),
+                PotterApprovalDecidedBy::Headless => "approval.headless".to_string(),
+                PotterApprovalDecidedBy::CommandGuard { rule } => format!("command guard: {rule}"),
+            };
+            vec![format!("[approval] {decision}: {subject} ({decided_by})")]
+        }
+        EventMsg::PotterPromptAnswered {
+            answers,
+            answered_by,
+        } => {
+            let answered_by = match answered_by {
+                PotterPromptAnsweredBy::User => "user",
+                PotterPromptAnsweredBy::Headless => "user_input.headless",
+            };
+            answers
+                .iter()
+                .map(|answer| {
+                    format!(
+                        "[input] {} -> {} ({answered_by})",
+                        answer.question, answer.answer
+                    )
+                })
+                .collect()
+        }
+        EventMsg::PotterRoundFinished { outcome } => {
+            let outcome = match outcome {
+                PotterRoundOutcome::Completed => "completed".to_string(),
+                PotterRoundOutcome::UserRequested => "interrupted".to_string(),
+                PotterRoundOutcome::InterruptedThenContinued => {
+                    "completed (continued after interrupt)".to_string()
+                }
+                PotterRoundOutcome::InterruptedNextRound => {
+                    "interrupted, moving to the next round".to_string()
+                }
+                PotterRoundOutcome::InterruptedEndSession => {
+                    "interrupted, ending the session".to_string()
+                }
+                PotterRoundOutcome::CommandBlocked { command, rule } => format!(
+                    "command blocked: {} ({rule}), moving to the next round",
+                    display_command(command)
+                ),
+                PotterRoundOutcome::ContextExhausted => {
+                    "context window exhausted, continuing in a fresh round".to_string()
+                }
+                PotterRoundOutcome::TimedOut { timeout } => match timeout {
+                    PotterRoundTimeout::Idle { limit_secs } => {
+                        format!("timed out: no events for {limit_secs}s, app-server killed")
+                    }
+                    PotterRoundTimeout::Round { limit_secs } => {
+                        format!("timed out: round ran for {limit_secs}s, app-server killed")
+                    }
+                    PotterRoundTimeout::Budget => {
+                        "stopped: session budget ran out, app-server killed".to_string()
+                    }
+                },
+                PotterRoundOutcome::TaskFailed { message } => format!("task failed: {message}"),
+                PotterRoundOutcome::Fatal { message } => format!("fatal: {message}"),
+            };
+            vec![format!("[round] finished: {outcome}")]
+        }
+        EventMsg::PotterSessionSucceeded {
+            rounds,
+            duration,
+            user_prompt_file,
+            git_commit_start,
+            git_commit_end,
+            round_token_usage,
+        } => {
+            let mut lines = vec![format!(
+                "[session] succeeded after {rounds} round(s) in {}s ({}, {git_commit_start}..{git_commit_end})",
+                duration.as_secs(),
+                user_prompt_file.display()
+            )];
+            if !round_token_usage.is_empty() {
+                let total = crate::potter_rollout::sum_token_usage(round_token_usage);
+                let per_round = round_token_usage
+                    .iter()
+                    .map(|usage| usage.total_tokens.to_string())
+                    .collect::<Vec<_>>()
+                    .join(", ");
+                lines.push(format!(
+                    "[session] tokens: {} total (per round: {per_round})",
+                    total.total_tokens
+                ));
+            }
+            lines
+        }
+        _ => Vec::new(),
+    }
+}
+
+fn display_command(command: &[String]) -> String {
+    match command {
+        [shell, flag, script] if (flag == "-lc" || flag == "-c") && shell.ends_with("sh") => {
+            script.clone()
+        }
+        _ => command.join(" "),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::path::PathBuf;
+
+    use codex_protocol::plan_tool::PlanItemArg;
+    use codex_protocol::plan_tool::UpdatePlanArgs;
+    use codex_protocol::protocol::PotterPromptAnswer;
+    use pretty_assertions::assert_eq;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    #[test]
+    fn resolve_exec_prompt_reads_prompt_file_and_trims() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("goal.md");
+        std::fs::write(&path, "\n  Fix the flaky test\n\n").expect("write prompt file");
+
+        assert_eq!(
+            resolve_exec_prompt(None, Some(&path)).expect("resolve prompt"),
+            "Fix the flaky test"
+        );
+    }
+
+    #[test]
+    fn resolve_exec_prompt_rejects_empty_prompt() {
+        assert!(resolve_exec_prompt(Some("   \n"), None).is_err());
+        assert!(resolve_exec_prompt(None, None).is_err());
+    }
+
+    #[test]
+    fn format_event_lines_renders_plan_and_commands() {
+        let plan = EventMsg::PlanUpdate(UpdatePlanArgs {
+            explanation: None,
+            plan: vec![
+                PlanItemArg {
+                    step: "Read code".to_string(),
+                    status: StepStatus::Completed,
+                },
+                PlanItemArg {
+                    step: "Write tests".to_string(),
+                    status: StepStatus::InProgress,
+                },
+            ],
+        });
+        assert_eq!(
+            format_event_lines(&plan),
+            vec![
+                "[plan]".to_string(),
+                "  [x] Read code".to_string(),
+                "  [~] Write tests".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterRoundVerified {
+                command: "cargo test".to_string(),
+                success: false,
+                exit_code: Some(101),
+                duration: std::time::Duration::from_secs(3),
+                output_tail: "test foo ... FAILED".to_string(),
+            }),
+            vec![
+                "[verify] failed: cargo test (exit 101, 3s)".to_string(),
+                "  test foo ... FAILED".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterApprovalDecided {
+                command: Some(vec![
+                    "bash".to_string(),
+                    "-lc".to_string(),
+                    "git push".to_string(),
+                ]),
+                paths: Vec::new(),
+                decision: PotterApprovalDecision::Deny,
+                decided_by: PotterApprovalDecidedBy::Rule {
+                    rule: r#"command_prefix = ["git", "push"]"#.to_string(),
+                },
+            }),
+            vec![
+                r#"[approval] denied: git push (rule command_prefix = ["git", "push"])"#
+                    .to_string()
+            ]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterPromptAnswered {
+                answers: vec![PotterPromptAnswer {
+                    question: "Which crate?".to_string(),
+                    answer: "cli".to_string(),
+                }],
+                answered_by: PotterPromptAnsweredBy::Headless,
+            }),
+            vec!["[input] Which crate? -> cli (user_input.headless)".to_string()]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterUsageLimitWait {
+                resumes_at: 1_790_000_000,
+                error_message: "You've hit your usage limit.".to_string(),
+            }),
+            vec![
+                "[retry] usage limit reached, continuing at 2026-09-21T14:13:20Z: You've hit your usage limit."
+                    .to_string()
+            ]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::CommandBlocked {
+                    command: vec!["git".to_string(), "push".to_string()],
+                    rule: "builtin: git push".to_string(),
+                },
+            }),
+            vec![
+                "[round] finished: command blocked: git push (builtin: git push), moving to the next round"
+                    .to_string()
+            ]
+        );
+
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterContextCheckpoint { percent_left: 8 }),
+            vec![
+                "[context] 8% of the context window left, asking the agent to save its progress"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::ContextExhausted,
+            }),
+            vec![
+                "[round] finished: context window exhausted, continuing in a fresh round"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::TimedOut {
+                    timeout: PotterRoundTimeout::Idle { limit_secs: 1800 },
+                },
+            }),
+            vec!["[round] finished: timed out: no events for 1800s, app-server killed".to_string()]
+        );
+
+        let usage = |input_tokens, output_tokens| TokenUsage {
+            input_tokens,
+            cached_input_tokens: input_tokens / 2,
+            output_tokens,
+            reasoning_output_tokens: output_tokens / 2,
+            total_tokens: input_tokens + output_tokens,
+        };
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterRoundUsage {
+                duration: std::time::Duration::from_secs(42),
+                token_usage: usage(1_000, 200),
+                session_token_usage: usage(3_000, 600),
+            }),
+            vec![
+                "[round] usage: 1200 tokens in 42s (input 1000, cached 500, output 200, reasoning 100); session total 3600 tokens"
+                    .to_string(),
+            ]
+        );
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterSessionSucceeded {
+                rounds: 2,
+                duration: std::time::Duration::from_secs(90),
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/1/MAIN.md"),
+                git_commit_start: "abc".to_string(),
+                git_commit_end: "def".to_string(),
+                round_token_usage: vec![usage(1_000, 200), usage(2_000, 400)],
+            }),
+            vec![
+                "[session] succeeded after 2 round(s) in 90s (.codexpotter/projects/2026/02/01/1/MAIN.md, abc..def)".to_string(),
+                "[session] tokens: 3600 total (per round: 1200, 2400)".to_string(),
+            ]
+        );
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterSessionStalled { rounds: 3 }),
+            vec!["[session] stalled: no progress in the last 3 round(s)".to_string()]
+        );
+        assert_eq!(
+            format_event_lines(&EventMsg::PotterProgressFileIssues {
+                repaired: vec!["missing the `## Done` section".to_string()],
+                unresolved: vec!["the front matter has no `status` field".to_string()],
+            }),
+            vec![
+                "[progress] repaired: missing the `## Done` section".to_string(),
+                "[progress] invalid, left for the next round: the front matter has no `status` field".to_string(),
+            ]
+        );
+
+        assert_eq!(
+            display_command(&[
+                "bash".to_string(),
+                "-lc".to_string(),
+                "cargo test".to_string()
+            ]),
+            "cargo test"
+        );
+        assert_eq!(
+            display_command(&["git".to_string(), "status".to_string()]),
+            "git status"
+        );
+    }
+
+    #[tokio::test]
+    async fn render_turn_submits_prompt_and_stops_at_round_finished() {
+        use crate::round_runner::PotterRoundUi;
+
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let (event_tx, event_rx) = unbounded_channel::<Event>();
+        let (_fatal_tx, fatal_rx) = unbounded_channel::<String>();
+
+        for msg in [
+            EventMsg::PotterRoundStarted {
+                current: 1,
+                total: 3,
+            },
+            EventMsg::AgentMessage(codex_protocol::protocol::AgentMessageEvent {
+                message: "All done.".to_string(),
+            }),
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+            },
+        ] {
+            event_tx
+                .send(Event {
+                    id: String::new(),
+                    msg,
+                })
+                .expect("send event");
+        }
+
+        let mut out = Vec::new();
+        let exit_info = ExecRoundUi::new(&mut out)
+            .render_turn("Continue".to_string(), false, op_tx, event_rx, fatal_rx)
+            .await
+            .expect("render turn");
+
+        assert!(matches!(exit_info.exit_reason, ExitReason::Completed));
+        let Some(Op::UserInput { items, .. }) = op_rx.recv().await else {
+            panic!("expected user input op");
+        };
+        assert_eq!(
+            items,
+            vec![UserInput::Text {
+                text: "Continue".to_string(),
+                text_elements: Vec::new(),
+            }]
+        );
+        assert_eq!(
+            String::from_utf8(out).expect("utf8"),
+            "[round] 1/3\n[codex] All done.\n[round] finished: completed\n"
+        );
+    }
+}
@@ -1,33 +1,50 @@
 mod app_server_backend;
 mod app_server_protocol;
+mod approval_policy;
 mod atomic_write;
+mod budget;
 mod codex_compat;
+mod codex_overrides;
+mod command_guard;
 mod config;
+mod event_stream;
+mod exec;
 mod global_gitignore;
+mod parallel;
 mod path_utils;
+mod plan_approval;
 mod potter_rollout;
 mod potter_rollout_resume_index;
 mod potter_stream_recovery;
+mod progress_file;
 mod project;
 mod prompt_queue;
+mod prompt_templates;
 mod resume;
 mod resume_picker_index;
 mod round_runner;
+mod sandbox_policy;
+mod stall;
 mod startup;
+#[cfg(test)]
+mod test_git;
+mod user_input_policy;
+mod verify;
+mod workflow;
+mod worktree;
 
 use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
-use std::time::Instant;
 
 use anyhow::Context;
-use chrono::Local;
 use clap::CommandFactory;
 use clap::FromArgMatches;
 use clap::Parser;
 use clap::Subcommand;
 use clap::ValueEnum;
-use codex_tui::ExitReason;
+
+use crate::round_runner::PotterSessionOutcome;
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
 #[clap(rename_all = "kebab-case")]
@@ -39,6 +56,23 @@ enum CliSandbox {
     DangerFullAccess,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+enum CliNetwork {
+    On,
+    Off,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+enum CliOnTimeout {
+    /// Run the round again in a fresh app-server process.
+    #[default]
+    Retry,
+    /// End the session.
+    Stop,
+}
+
 impl CliSandbox {
     fn as_protocol(self) -> Option<crate::app_server_protocol::SandboxMode> {
         match self {
@@ -61,7 +95,7 @@ struct Cli {
     #[arg(long, env = "CODEX_BIN", default_value = "codex", global = true)]
     codex_bin: String,
 
-    /// Number of turns to run (each turn starts a fresh `codex app-server`; must be >= 1).
+    /// Number of rounds to run (each round starts a fresh thread; must be >= 1).
     ///
     /// For `resume`, this controls how many rounds are run when the last recorded round is
     /// complete. If the last recorded round is unfinished, the remaining budget is derived from
@@ -76,6 +110,77 @@ struct Cli {
     #[arg(long = "sandbox", value_enum, default_value_t, global = true)]
     sandbox: CliSandbox,
 
+    /// Extra directory the agent may write to in `workspace-write` mode (repeatable).
+    ///
+    /// Relative paths are resolved against the working directory. `--sandbox default` becomes
+    /// `workspace-write` when this (or another `workspace-write` setting) is given.
+    #[arg(long = "writable-root", value_name = "PATH", global = true)]
+    writable_roots: Vec<PathBuf>,
+
+    /// Allow or block network access in `workspace-write` mode.
+    #[arg(long, value_enum, global = true)]
+    network: Option<CliNetwork>,
+
+    /// Do not make `$TMPDIR` writable in `workspace-write` mode.
+    #[arg(long, global = true)]
+    exclude_tmpdir_env_var: bool,
+
+    /// Do not make `/tmp` writable in `workspace-write` mode.
+    #[arg(long, global = true)]
+    exclude_slash_tmp: bool,
+
+    /// Keep one `codex app-server` process alive for all rounds of a session.
+    ///
+    /// Each round still starts a fresh thread; only process startup (MCP servers, skills) is
+    /// saved. A process that exits between rounds is respawned.
+    #[arg(long, global = true)]
+    reuse_app_server: bool,
+
+    /// Checkpoint a round once at most this percentage of its context window is left.
+    ///
+    /// The turn is interrupted and the agent saves its progress in the progress file; the session
+    /// then continues in a fresh round that does not count against `--rounds`. `0` disables the
+    /// checkpoint, leaving only the fresh round after the context window overflows.
+    #[arg(
+        long,
+        value_name = "PERCENT_LEFT",
+        default_value = "10",
+        value_parser = clap::value_parser!(u8).range(0..=100),
+        global = true
+    )]
+    context_checkpoint: u8,
+
+    /// Stop a session after this many completed rounds in a row made no progress.
+    ///
+    /// A round makes no progress when the git tree, the progress file, and its Todo/Done counts are
+    /// the same as after the previous round. `0` disables stall detection.
+    #[arg(long, value_name = "N", default_value = "3", global = true)]
+    max_stalled_rounds: u32,
+
+    /// Kill a round whose running turn produced no events for this long (e.g. `30m`).
+    ///
+    /// Time spent waiting for the user (approvals, questions) does not count.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = crate::budget::parse_duration,
+        global = true
+    )]
+    idle_timeout: Option<std::time::Duration>,
+
+    /// Kill a round that has run for this long (e.g. `2h`).
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = crate::budget::parse_duration,
+        global = true
+    )]
+    round_timeout: Option<std::time::Duration>,
+
+    /// What to do after `--idle-timeout` or `--round-timeout` killed a round.
+    #[arg(long, value_enum, default_value_t, global = true)]
+    on_timeout: CliOnTimeout,
+
     /// Pass Codex's bypass flag when launching `codex app-server`.
     ///
     /// Alias: `--yolo`.
@@ -86,6 +191,105 @@ struct Cli {
     )]
     dangerously_bypass_approvals_and_sandbox: bool,
 
+    /// Print every event as one JSON object per line (JSONL) to stdout.
+    ///
+    /// Only supported by the headless commands: `exec` and `resume <PROJECT_PATH>`. The plain
+    /// transcript is written to stderr instead.
+    #[arg(long, global = true)]
+    json: bool,
+
+    /// Run up to N prompts concurrently, each in its own git worktree with its own
+    /// `codex app-server`.
+    ///
+    /// Prompts queued while sessions are running start as soon as a slot frees up. Use PgUp/PgDn
+    /// to switch between the running sessions' transcripts. Interactive mode only.
+    #[arg(long, value_name = "N")]
+    parallel: Option<NonZeroUsize>,
+
+    /// Shell command to run after every completed round (e.g. `cargo test`).
+    ///
+    /// A session only succeeds when the progress file is marked `finite_incantatem: true` and
+    /// this command exits successfully. Failing output is fed back into the next round.
+    #[arg(long = "verify", value_name = "COMMAND", global = true)]
+    verify_command: Option<String>,
+
+    /// Fail a `--verify` run that takes longer than this (e.g. `10m`) and kill the command.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        default_value = "30m",
+        value_parser = crate::budget::parse_duration,
+        global = true
+    )]
+    verify_timeout: std::time::Duration,
+
+    /// Stop the session once the rounds together have run this long (e.g. `90m`, `2h`, `1h30m`).
+    ///
+    /// A round still running when it runs out is stopped. `resume` counts the time recorded by
+    /// earlier runs.
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = crate::budget::parse_duration,
+        global = true
+    )]
+    max_duration: Option<std::time::Duration>,
+
+    /// Stop the session once the rounds together have used this many tokens (e.g. `500k`, `5M`).
+    ///
+    /// A round still running when they run out is stopped. `resume` counts the tokens recorded by
+    /// earlier runs.
+    #[arg(
+        long,
+        value_name = "TOKENS",
+        value_parser = crate::budget::parse_token_count,
+        global = true
+    )]
+    max_tokens: Option<u64>,
+
+    /// Model for Codex to use (e.g. `gpt-5.2-codex`), overriding Codex's own config.
+    #[arg(long, global = true)]
+    model: Option<String>,
+
+    /// Codex config profile to apply (`[profiles.<name>]` in Codex's `config.toml`).
+    #[arg(long, global = true)]
+    profile: Option<String>,
+
+    /// Reasoning effort for every turn: `none`, `minimal`, `low`, `medium`, `high`, or `xhigh`.
+    #[arg(
+        long,
+        value_name = "EFFORT",
+        value_parser = crate::codex_overrides::parse_reasoning_effort,
+        global = true
+    )]
+    effort: Option<codex_protocol::openai_models::ReasoningEffort>,
+
+    /// Override a Codex config value, as with `codex -c key=value` (repeatable).
+    ///
+    /// The value is parsed as TOML and falls back to a plain string.
+    #[arg(
+        short = 'c',
+        long = "config",
+        value_name = "KEY=VALUE",
+        value_parser = crate::codex_overrides::parse_config_override,
+        global = true
+    )]
+    config_overrides: Vec<(String, serde_json::Value)>,
+
+    /// Workflow for new projects (default: `implement`).
+    ///
+    /// Picks the developer prompt, progress file template, default sandbox, and which repository
+    /// changes are allowed. `resume` uses the workflow recorded in the progress file.
+    #[arg(long, value_enum, global = true)]
+    workflow: Option<crate::workflow::Workflow>,
+
+    /// Start with a read-only planning round and wait for the plan to be approved.
+    ///
+    /// The plan is shown for approval, editing in `$EDITOR`, or rejection before any
+    /// write-capable round starts. Interactive mode only (not with `--parallel`).
+    #[arg(long)]
+    approve_plan: bool,
+
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -97,6 +301,19 @@ enum CliCommand {
         /// Project path to resolve to a unique `MAIN.md`. If omitted, open a picker UI.
         project_path: Option<PathBuf>,
     },
+
+    /// Run a new project headlessly and print a line-oriented transcript to stdout.
+    ///
+    /// Does not require a TTY, which makes it suitable for CI jobs and other agents.
+    Exec {
+        /// The project goal. Mutually exclusive with `--prompt-file`.
+        #[arg(required_unless_present = "prompt_file")]
+        prompt: Option<String>,
+
+        /// Read the project goal from a file.
+        #[arg(long, value_name = "FILE", conflicts_with = "prompt")]
+        prompt_file: Option<PathBuf>,
+    },
 }
 
 fn parse_cli() -> Cli {
@@ -109,9 +326,30 @@ fn parse_cli() -> Cli {
 #[tokio::main(flavor = "multi_thread")]
 async fn main() -> anyhow::Result<()> {
     let cli = parse_cli();
+    if cli.json && !is_headless_command(cli.command.as_ref()) {
+        eprintln!("error: --json requires `exec` or `resume <PROJECT_PATH>`");
+        std::process::exit(2);
+    }
+    if cli.parallel.is_some() && cli.command.is_some() {
+        eprintln!("error: --parallel is only supported when starting new projects interactively");
+        std::process::exit(2);
+    }
+    if cli.approve_plan && (cli.command.is_some() || cli.parallel.is_some()) {
+        eprintln!(
+            "error: --approve-plan is only supported when starting a single new project interactively"
+        );
+        std::process::exit(2);
+    }
+    if cli.workflow.is_some() && matches!(cli.command, Some(CliCommand::Resume { .. })) {
+        eprintln!(
+            "error: --workflow only applies to new projects; resume uses the workflow recorded in the progress file"
+        );
+        std::process::exit(2);
+    }
+    let workflow = cli.workflow.unwrap_or_default();
     let bypass = cli.dangerously_bypass_approvals_and_sandbox;
     let sandbox = cli.sandbox;
-    let mut resume_note_project_path: Option<String> = None;
+    let mut resume_note_project_paths: Vec<String> = Vec::new();
 
     let check_for_update_on_startup = crate::config::ConfigStore::new_default()
         .and_then(|store| store.check_for_update_on_startup())
@@ -126,9 +364,95 @@ async fn main() -> anyhow::Result<()> {
     };
 
     let workdir = std::env::current_dir().context("resolve current directory")?;
+    if cli.parallel.is_some()
+        && let Err(err) = crate::worktree::ensure_worktree_support(&workdir)
+    {
+        eprintln!("error: --parallel requires a git repository with a commit: {err:#}");
+        std::process::exit(2);
+    }
 
     let backend_launch = app_server_backend::AppServerLaunchConfig::from_cli(sandbox, bypass);
-    let turn_prompt = crate::project::fixed_prompt().trim_end().to_string();
+    let prompt_templates = match crate::prompt_templates::PromptTemplates::load(&workdir, workflow)
+    {
+        Ok(templates) => templates,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
+
+    let layered_config = match crate::config::LayeredConfig::load(&workdir) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
+
+    // `exec` and `--json` run without the TUI, so `ask` approval rules use `approval.headless`
+    // and the agent's questions are answered by `user_input.headless`.
+    let interactive = !cli.json && !matches!(cli.command, Some(CliCommand::Exec { .. }));
+    let approval_policy = match crate::approval_policy::ApprovalPolicy::load(
+        &layered_config,
+        &workdir,
+        interactive,
+    ) {
+        Ok(policy) => policy,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
+    let user_input_policy =
+        match crate::user_input_policy::UserInputPolicy::load(&layered_config, interactive) {
+            Ok(policy) => policy,
+            Err(err) => {
+                eprintln!("error: {err:#}");
+                std::process::exit(2);
+            }
+        };
+    let command_guard = match crate::command_guard::CommandGuard::load(&layered_config) {
+        Ok(guard) => guard,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
+
+    let codex_overrides = match crate::codex_overrides::CodexOverrides::load(
+        &layered_config,
+        crate::codex_overrides::CodexOverrides {
+            model: cli.model.clone(),
+            profile: cli.profile.clone(),
+            effort: cli.effort,
+            config: cli.config_overrides.iter().cloned().collect(),
+            // Phase models are configured in `[codex.phases]` only.
+            phases: Default::default(),
+        },
+    ) {
+        Ok(overrides) => overrides,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
+
+    let workspace_write = match crate::sandbox_policy::WorkspaceWriteSettings::load(
+        &layered_config,
+        &workdir,
+        crate::sandbox_policy::WorkspaceWriteSettings {
+            writable_roots: cli.writable_roots.clone(),
+            network_access: cli.network.map(|network| network == CliNetwork::On),
+            exclude_tmpdir_env_var: cli.exclude_tmpdir_env_var.then_some(true),
+            exclude_slash_tmp: cli.exclude_slash_tmp.then_some(true),
+        },
+    ) {
+        Ok(settings) => settings,
+        Err(err) => {
+            eprintln!("error: {err:#}");
+            std::process::exit(2);
+        }
+    };
 
     let codex_compat_home = match crate::codex_compat::ensure_default_codex_compat_home() {
         Ok(home) => home,
@@ -138,9 +462,82 @@ async fn main() -> anyhow::Result<()> {
         }
     };
 
+    let session_settings = crate::round_runner::PotterSessionSettings {
+        codex_bin,
+        backend_launch,
+        codex_compat_home,
+        workdir: workdir.clone(),
+        workflow,
+        prompt_templates,
+        rounds: cli.rounds,
+        event_stream: cli.json.then(crate::event_stream::JsonEventStream::stdout),
+        isolate_in_worktree: false,
+        verify_command: cli.verify_command.clone(),
+        verify_timeout: cli.verify_timeout,
+        budget: crate::budget::SessionBudget {
+            max_duration: cli.max_duration,
+            max_tokens: cli.max_tokens,
+        },
+        approve_plan: cli.approve_plan,
+        approval_policy,
+        user_input_policy,
+        command_guard,
+        codex_overrides,
+        workspace_write,
+        reuse_app_server: cli.reuse_app_server,
+        context_checkpoint: (cli.context_checkpoint > 0).then_some(cli.context_checkpoint),
+        max_stalled_rounds: (cli.max_stalled_rounds > 0).then_some(cli.max_stalled_rounds),
+        timeouts: crate::app_server_backend::RoundTimeouts {
+            idle: cli.idle_timeout,
+            round: cli.round_timeout,
+            ..crate::app_server_backend::RoundTimeouts::default()
+        },
+        on_timeout: match cli.on_timeout {
+            CliOnTimeout::Retry => crate::round_runner::OnTimeout::RetryRound,
+            CliOnTimeout::Stop => crate::round_runner::OnTimeout::EndSession,
+        },
+    };
+
+    if cli.json
+        && let Some(CliCommand::Resume {
+            project_path: Some(project_path),
+        }) = cli.command.as_ref()
+    {
+        let mut ui = crate::exec::ExecRoundUi::new(std::io::stderr());
+        let resume_exit =
+            crate::resume::run_resume(&mut ui, &workdir, project_path, &session_settings)
+                .await
+                .context("resume project")?;
+        exit_with_code(resume_exit.exit_code());
+    }
+
+    if let Some(CliCommand::Exec {
+        prompt,
+        prompt_file,
+    }) = cli.command.as_ref()
+    {
+        let user_prompt =
+            crate::exec::resolve_exec_prompt(prompt.as_deref(), prompt_file.as_deref())?;
+        // With `--json`, stdout is reserved for the JSONL stream.
+        let transcript: Box<dyn std::io::Write> = if cli.json {
+            Box::new(std::io::stderr())
+        } else {
+            Box::new(std::io::stdout())
+        };
+        let mut ui = crate::exec::ExecRoundUi::new(transcript);
+        let session_result =
+            crate::round_runner::run_potter_session(&mut ui, &session_settings, &user_prompt)
+                .await?;
+        if session_result.outcome == PotterSessionOutcome::UserCancelled && !cli.json {
+            print_resume_note(&resume_project_path_for_note(&session_result.project_dir));
+        }
+        exit_with_code(session_result.outcome.exit_code());
+    }
+
     let mut ui = codex_tui::CodexPotterTui::new()?;
 
     ui.set_check_for_update_on_startup(check_for_update_on_startup);
+    ui.set_codex_model_overrides(session_settings.codex_overrides.model_overrides());
     if let Some(update_action) = ui.prompt_update_if_needed().await? {
         drop(ui);
         run_update_action(update_action)?;
@@ -167,24 +564,14 @@ async fn main() -> anyhow::Result<()> {
         };
 
         if let Some(project_path) = project_path {
-            let resume_exit = crate::resume::run_resume(
-                &mut ui,
-                &workdir,
-                &project_path,
-                codex_bin.clone(),
-                backend_launch,
-                codex_compat_home.clone(),
-                cli.rounds,
-            )
-            .await
-            .context("resume project")?;
-            if resume_exit == crate::resume::ResumeExit::FatalExitRequested {
-                // `std::process::exit` skips destructors, so explicitly drop the UI to restore terminal
-                // state before exiting.
-                drop(ui);
-                std::process::exit(1);
-            }
-            return Ok(());
+            let resume_exit =
+                crate::resume::run_resume(&mut ui, &workdir, &project_path, &session_settings)
+                    .await
+                    .context("resume project")?;
+            // `std::process::exit` skips destructors, so explicitly drop the UI to restore terminal
+            // state before exiting.
+            drop(ui);
+            exit_with_code(resume_exit.exit_code());
         }
     }
 
@@ -196,6 +583,7 @@ async fn main() -> anyhow::Result<()> {
     ui.clear()?;
 
     let mut pending_user_prompts = prompt_queue::PromptQueue::new(user_prompt);
+    let mut last_outcome: Option<PotterSessionOutcome> = None;
 
     'session: loop {
         let next_prompt = pending_user_prompts.pop_next_prompt(|| ui.pop_queued_user_prompt());
@@ -214,90 +602,72 @@ async fn main() -> anyhow::Result<()> {
             }
         };
 
-        let init = crate::project::init_project(&workdir, &user_prompt, Local::now())
-            .context("initialize .codexpotter project")?;
-        let project_started_at = Instant::now();
-        let project_dir = init
-            .progress_file_rel
-            .parent()
-            .context("derive CodexPotter project dir from progress file path")?
-            .to_path_buf();
-        let project_dir_abs = workdir.join(&project_dir);
-        let potter_rollout_path = crate::potter_rollout::potter_rollout_path(&project_dir_abs);
-        let user_prompt_file = init.progress_file_rel.clone();
-        let developer_prompt = crate::project::render_developer_prompt(&init.progress_file_rel);
-
-        let round_context = crate::round_runner::PotterRoundContext {
-            codex_bin: codex_bin.clone(),
-            developer_prompt: developer_prompt.clone(),
-            backend_launch,
-            codex_compat_home: codex_compat_home.clone(),
-            thread_cwd: Some(workdir.clone()),
-            turn_prompt: turn_prompt.clone(),
-            workdir: workdir.clone(),
-            progress_file_rel: init.progress_file_rel.clone(),
-            user_prompt_file: user_prompt_file.clone(),
-            git_commit_start: init.git_commit_start.clone(),
-            potter_rollout_path: potter_rollout_path.clone(),
-            project_started_at,
-        };
-
-        for round_index in 0..cli.rounds.get() {
-            let total_rounds = u32::try_from(cli.rounds.get()).unwrap_or(u32::MAX);
-            let current_round = u32::try_from(round_index.saturating_add(1)).unwrap_or(u32::MAX);
-            let session_started = if round_index == 0 {
-                Some(crate::round_runner::PotterSessionStartedInfo {
-                    user_message: Some(user_prompt.clone()),
-                    working_dir: workdir.clone(),
-                    project_dir: project_dir.clone(),
-                    user_prompt_file: user_prompt_file.clone(),
-                })
-            } else {
-                None
-            };
-
-            let round_result = crate::round_runner::run_potter_round(
+        if let Some(parallel) = cli.parallel {
+            let parallel_result = crate::parallel::run_parallel_sessions(
                 &mut ui,
-                &round_context,
-                crate::round_runner::PotterRoundOptions {
-                    pad_before_first_cell: round_index != 0,
-                    session_started,
-                    round_current: current_round,
-                    round_total: total_rounds,
-                    session_succeeded_rounds: current_round,
-                },
+                &session_settings,
+                user_prompt,
+                parallel,
             )
             .await?;
-
-            match &round_result.exit_reason {
-                ExitReason::UserRequested => {
-                    resume_note_project_path = Some(
-                        derive_resume_project_path_from_project_dir(&project_dir)
-                            .unwrap_or_else(|| project_dir.to_string_lossy().to_string()),
-                    );
-                    break 'session;
-                }
-                ExitReason::TaskFailed(_) => break,
-                ExitReason::Fatal(_) => {
-                    // `std::process::exit` skips destructors, so explicitly drop the UI to restore
-                    // terminal state before exiting.
-                    drop(ui);
-                    std::process::exit(1);
+            last_outcome = parallel_result.last_outcome.or(last_outcome);
+            if parallel_result.user_cancelled {
+                for project_dir in &parallel_result.cancelled_project_dirs {
+                    resume_note_project_paths.push(resume_project_path_for_note(project_dir));
                 }
-                ExitReason::Completed => {}
+                break 'session;
+            }
+            continue 'session;
+        }
+
+        let session_result =
+            crate::round_runner::run_potter_session(&mut ui, &session_settings, &user_prompt)
+                .await?;
+
+        last_outcome = Some(session_result.outcome);
+        match session_result.outcome {
+            PotterSessionOutcome::UserCancelled => {
+                resume_note_project_paths
+                    .push(resume_project_path_for_note(&session_result.project_dir));
+                break 'session;
             }
-            if round_result.stop_due_to_finite_incantatem {
-                break;
+            PotterSessionOutcome::Fatal => {
+                // `std::process::exit` skips destructors, so explicitly drop the UI to restore
+                // terminal state before exiting.
+                drop(ui);
+                exit_with_code(PotterSessionOutcome::Fatal.exit_code());
             }
+            PotterSessionOutcome::Succeeded
+            | PotterSessionOutcome::BudgetExhausted
+            | PotterSessionOutcome::Stalled
+            | PotterSessionOutcome::TimedOut
+            | PotterSessionOutcome::TaskFailed => {}
         }
     }
 
     drop(ui);
-    if let Some(project_path) = resume_note_project_path {
+    for project_path in resume_note_project_paths {
         print_resume_note(&project_path);
     }
 
-    Ok(())
+    // With queued prompts, the exit code reflects the last session that ran.
+    exit_with_code(last_outcome.map_or(0, PotterSessionOutcome::exit_code));
+}
+
+/// Exit the process with `code`. Callers must drop the TUI first to restore the terminal.
+fn exit_with_code(code: i32) -> ! {
+    std::process::exit(code)
+}
+
+/// Whether `command` runs without the TUI (required by `--json`).
+fn is_headless_command(command: Option<&CliCommand>) -> bool {
+    matches!(
+        command,
+        Some(CliCommand::Exec { .. })
+            | Some(CliCommand::Resume {
+                project_path: Some(_)
+            })
+    )
 }
 
 fn run_update_action(action: codex_tui::UpdateAction) -> anyhow::Result<()> {
@@ -344,6 +714,12 @@ fn derive_resume_project_path_from_project_dir(project_dir: &Path) -> Option<Str
     Some(parts.join("/"))
 }
 
+/// Project path to suggest in the resume note, falling back to the raw project dir.
+fn resume_project_path_for_note(project_dir: &Path) -> String {
+    derive_resume_project_path_from_project_dir(project_dir)
+        .unwrap_or_else(|| project_dir.to_string_lossy().to_string())
+}
+
 fn print_resume_note(project_path: &str) {
     let command = format!("codex-potter resume {project_path}");
     println!("{} To continue this project, run:", ansi_bold("Note:"));
@@ -493,6 +869,115 @@ mod tests {
         assert_eq!(project_path, None);
     }
 
+    #[test]
+    fn exec_subcommand_parses_prompt_and_global_args() {
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "exec",
+            "Fix the build",
+            "--rounds",
+            "2",
+            "--verify",
+            "cargo test",
+            "--verify-timeout",
+            "10m",
+            "--max-duration",
+            "1h30m",
+            "--max-tokens",
+            "5M",
+        ])
+        .expect("parse args");
+
+        assert_eq!(cli.rounds.get(), 2);
+        assert_eq!(cli.verify_command, Some("cargo test".to_string()));
+        assert_eq!(cli.verify_timeout, std::time::Duration::from_secs(600));
+        assert_eq!(cli.max_duration, Some(std::time::Duration::from_secs(5400)));
+        assert_eq!(cli.max_tokens, Some(5_000_000));
+        let Some(CliCommand::Exec {
+            prompt,
+            prompt_file,
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        assert_eq!(prompt, Some("Fix the build".to_string()));
+        assert_eq!(prompt_file, None);
+    }
+
+    #[test]
+    fn exec_subcommand_requires_exactly_one_prompt_source() {
+        let cli = Cli::try_parse_from(["codex-potter", "exec", "--prompt-file", "goal.md"])
+            .expect("parse args");
+        let Some(CliCommand::Exec {
+            prompt,
+            prompt_file,
+        }) = cli.command
+        else {
+            panic!("expected exec command, got: {:?}", cli.command);
+        };
+        assert_eq!(prompt, None);
+        assert_eq!(prompt_file, Some(PathBuf::from("goal.md")));
+
+        assert!(Cli::try_parse_from(["codex-potter", "exec"]).is_err());
+        assert!(
+            Cli::try_parse_from(["codex-potter", "exec", "goal", "--prompt-file", "goal.md"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn parallel_must_be_at_least_one() {
+        assert!(Cli::try_parse_from(["codex-potter", "--parallel", "0"]).is_err());
+        let cli = Cli::try_parse_from(["codex-potter", "--parallel", "3"]).expect("parse args");
+        assert_eq!(cli.parallel.map(NonZeroUsize::get), Some(3));
+    }
+
+    #[test]
+    fn timeout_flags_parse_durations_and_policy() {
+        let cli = Cli::try_parse_from(["codex-potter"]).expect("parse args");
+        assert_eq!(cli.idle_timeout, None);
+        assert_eq!(cli.round_timeout, None);
+        assert_eq!(cli.on_timeout, CliOnTimeout::Retry);
+
+        let cli = Cli::try_parse_from([
+            "codex-potter",
+            "exec",
+            "Fix the build",
+            "--idle-timeout",
+            "30m",
+            "--round-timeout",
+            "2h",
+            "--on-timeout",
+            "stop",
+        ])
+        .expect("parse args");
+        assert_eq!(cli.idle_timeout, Some(std::time::Duration::from_secs(1800)));
+        assert_eq!(
+            cli.round_timeout,
+            Some(std::time::Duration::from_secs(7200))
+        );
+        assert_eq!(cli.on_timeout, CliOnTimeout::Stop);
+        assert!(Cli::try_parse_from(["codex-potter", "--idle-timeout", "0"]).is_err());
+    }
+
+    #[test]
+    fn json_is_only_allowed_for_headless_commands() {
+        let cli =
+            Cli::try_parse_from(["codex-potter", "exec", "goal", "--json"]).expect("parse args");
+        assert!(cli.json);
+        assert!(is_headless_command(cli.command.as_ref()));
+
+        let cli = Cli::try_parse_from(["codex-potter", "--json", "resume", "2026/02/01/1"])
+            .expect("parse args");
+        assert!(is_headless_command(cli.command.as_ref()));
+
+        let cli = Cli::try_parse_from(["codex-potter", "resume", "--json"]).expect("parse args");
+        assert!(!is_headless_command(cli.command.as_ref()));
+
+        let cli = Cli::try_parse_from(["codex-potter", "--json"]).expect("parse args");
+        assert!(!is_headless_command(cli.command.as_ref()));
+    }
+
     #[test]
     fn derive_resume_project_path_from_project_dir_strips_projects_root() {
         let project_dir = Path::new(".codexpotter/projects/2026/03/01/6");
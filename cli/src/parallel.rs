@@ -0,0 +1,497 @@
+//! Parallel sessions (`--parallel N`).
+//!
+//! Each prompt runs as its own CodexPotter session in a dedicated git worktree (see
+//! `crate::worktree`) with its own `codex app-server`, and up to `N` sessions run concurrently.
+//! Sessions run as background tasks that record their events into a [`SessionHub`]; the TUI
+//! renders one session's transcript at a time and the user switches between running sessions
+//! with <kbd>PgUp</kbd>/<kbd>PgDn</kbd>.
+//!
+//! Project records (`.codexpotter/projects/...`) are still created in the main checkout, so
+//! `codex-potter resume` finds them as usual.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::num::NonZeroUsize;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+use std::sync::PoisonError;
+
+use anyhow::Context;
+use codex_protocol::ThreadId;
+use codex_protocol::protocol::ErrorEvent;
+use codex_protocol::protocol::Event;
+use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::Op;
+use codex_protocol::protocol::TokenUsage;
+use codex_protocol::user_input::UserInput;
+use codex_tui::AppExitInfo;
+use codex_tui::ExitReason;
+use codex_tui::SessionViewExit;
+use codex_tui::SessionViewOptions;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::mpsc::unbounded_channel;
+use tokio::sync::watch;
+use tokio::task::JoinSet;
+
+use crate::round_runner::PotterSessionOutcome;
+use crate::round_runner::PotterSessionResult;
+use crate::round_runner::PotterSessionSettings;
+
+/// Maximum number of characters of the prompt shown in the session view header.
+const SESSION_LABEL_MAX_CHARS: usize = 48;
+
+#[derive(Debug, Default)]
+pub struct ParallelSessionsResult {
+    /// Outcome of the session that finished last, if any session ran.
+    pub last_outcome: Option<PotterSessionOutcome>,
+    /// Project directories of sessions stopped by the user, for resume notes.
+    pub cancelled_project_dirs: Vec<PathBuf>,
+    /// Whether the user interrupted the batch with <kbd>Ctrl</kbd>+<kbd>C</kbd>.
+    pub user_cancelled: bool,
+}
+
+/// Run `first_prompt` and every prompt queued while sessions are running, up to `parallel`
+/// sessions at a time, until all of them finish or the user interrupts.
+pub async fn run_parallel_sessions(
+    ui: &mut codex_tui::CodexPotterTui,
+    settings: &PotterSessionSettings,
+    first_prompt: String,
+    parallel: NonZeroUsize,
+) -> anyhow::Result<ParallelSessionsResult> {
+    // Background sessions have no one to ask, so `ask` approval rules use `approval.headless`
+    // and the agent's questions are answered by `user_input.headless`.
+    let settings = PotterSessionSettings {
+        isolate_in_worktree: true,
+        approval_policy: settings.approval_policy.clone().headless(),
+        user_input_policy: settings.user_input_policy.clone().headless(),
+        ..settings.clone()
+    };
+    let hub = SessionHub::default();
+    let (cancel_tx, cancel_rx) = watch::channel(false);
+    let mut tasks: JoinSet<(usize, anyhow::Result<PotterSessionResult>)> = JoinSet::new();
+    let mut pending_prompts = VecDeque::from([first_prompt]);
+    let mut running: Vec<RunningSession> = Vec::new();
+    let mut next_session_id = 0usize;
+    let mut focused_index = 0usize;
+    let mut on_screen: Option<usize> = None;
+    let mut result = ParallelSessionsResult::default();
+
+    loop {
+        loop {
+            // A session closes its transcript just before its task completes, so wait for such
+            // sessions instead of re-attaching the view to a transcript that no longer exists.
+            let joined = if running.iter().any(|session| !hub.is_open(session.id)) {
+                tasks.join_next().await
+            } else {
+                tasks.try_join_next()
+            };
+            let Some(joined) = joined else {
+                break;
+            };
+            let (session_id, session_result) = joined.context("parallel session panicked")?;
+            running.retain(|session| session.id != session_id);
+            match session_result {
+                Ok(session_result) => record_session_result(&mut result, session_result),
+                Err(err) => {
+                    cancel_tx.send_replace(true);
+                    while tasks.join_next().await.is_some() {}
+                    return Err(err);
+                }
+            }
+        }
+
+        pending_prompts.extend(std::iter::from_fn(|| ui.pop_queued_user_prompt()));
+        while running.len() < parallel.get()
+            && let Some(prompt) = pending_prompts.pop_front()
+        {
+            let session_id = next_session_id;
+            next_session_id += 1;
+            hub.open(session_id);
+            running.push(RunningSession {
+                id: session_id,
+                label: session_label(&prompt),
+            });
+
+            let mut session_ui = ParallelRoundUi::new(hub.clone(), session_id, cancel_rx.clone());
+            let settings = settings.clone();
+            let hub = hub.clone();
+            tasks.spawn(async move {
+                let session_result =
+                    crate::round_runner::run_potter_session(&mut session_ui, &settings, &prompt)
+                        .await;
+                hub.finish(session_id);
+                (session_id, session_result)
+            });
+        }
+
+        if running.is_empty() {
+            break;
+        }
+
+        focused_index = focused_index.min(running.len() - 1);
+        let session = &running[focused_index];
+        let replay = on_screen != Some(session.id);
+        if replay {
+            ui.clear()?;
+        }
+        on_screen = Some(session.id);
+
+        let (view_event_tx, view_event_rx) = unbounded_channel::<Event>();
+        // Interrupts from the view are handled through `SessionViewExit::UserRequested`.
+        let (view_op_tx, _view_op_rx) = unbounded_channel::<Op>();
+        let (_fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
+        hub.attach(session.id, view_event_tx, replay);
+        let view_exit = ui
+            .render_session_view(
+                SessionViewOptions {
+                    current: focused_index + 1,
+                    total: running.len(),
+                    label: session.label.clone(),
+                    render_header: replay,
+                },
+                view_op_tx,
+                view_event_rx,
+                fatal_exit_rx,
+            )
+            .await;
+        hub.detach();
+
+        match view_exit? {
+            SessionViewExit::Previous => {
+                focused_index = focused_index
+                    .checked_sub(1)
+                    .unwrap_or(running.len().saturating_sub(1));
+            }
+            SessionViewExit::Next => {
+                focused_index = (focused_index + 1) % running.len();
+            }
+            SessionViewExit::Detached => {}
+            SessionViewExit::UserRequested => {
+                result.user_cancelled = true;
+                cancel_tx.send_replace(true);
+                while let Some(joined) = tasks.join_next().await {
+                    let (_, session_result) = joined.context("parallel session panicked")?;
+                    record_session_result(&mut result, session_result?);
+                }
+                break;
+            }
+        }
+    }
+
+    Ok(result)
+}
+
+fn record_session_result(result: &mut ParallelSessionsResult, session: PotterSessionResult) {
+    result.last_outcome = Some(session.outcome);
+    if session.outcome == PotterSessionOutcome::UserCancelled {
+        result.cancelled_project_dirs.push(session.project_dir);
+    }
+}
+
+/// Short, single-line label derived from the session prompt.
+fn session_label(prompt: &str) -> String {
+    let first_line = prompt.lines().next().unwrap_or_default().trim();
+    let mut label = first_line
+        .chars()
+        .take(SESSION_LABEL_MAX_CHARS)
+        .collect::<String>();
+    if first_line.chars().count() > SESSION_LABEL_MAX_CHARS || prompt.trim() != first_line {
+        label.push('…');
+    }
+    label
+}
+
+#[derive(Debug)]
+struct RunningSession {
+    id: usize,
+    label: String,
+}
+
+/// Events recorded for every parallel session, plus the single view attached to one of them.
+#[derive(Clone, Default)]
+struct SessionHub {
+    state: Arc<Mutex<SessionHubState>>,
+}
+
+#[derive(Default)]
+struct SessionHubState {
+    transcripts: HashMap<usize, Transcript>,
+    viewer: Option<SessionViewer>,
+}
+
+#[derive(Default)]
+struct Transcript {
+    events: Vec<Event>,
+    /// Number of events already sent to a view, so re-attaching can resume where it stopped.
+    delivered: usize,
+}
+
+struct SessionViewer {
+    session_id: usize,
+    event_tx: UnboundedSender<Event>,
+}
+
+impl SessionHub {
+    fn lock(&self) -> MutexGuard<'_, SessionHubState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    fn open(&self, session_id: usize) {
+        self.lock()
+            .transcripts
+            .insert(session_id, Transcript::default());
+    }
+
+    fn is_open(&self, session_id: usize) -> bool {
+        self.lock().transcripts.contains_key(&session_id)
+    }
+
+    fn push(&self, session_id: usize, event: Event) {
+        let mut state = self.lock();
+        let SessionHubState {
+            transcripts,
+            viewer,
+        } = &mut *state;
+        let Some(transcript) = transcripts.get_mut(&session_id) else {
+            return;
+        };
+        let sent = viewer.as_ref().is_some_and(|viewer| {
+            viewer.session_id == session_id && viewer.event_tx.send(event.clone()).is_ok()
+        });
+        transcript.events.push(event);
+        if sent {
+            transcript.delivered = transcript.events.len();
+        }
+    }
+
+    /// Attach the view to `session_id`.
+    ///
+    /// With `replay`, the whole transcript is sent first (the screen was cleared); otherwise only
+    /// the events recorded since the session was last on screen.
+    fn attach(&self, session_id: usize, event_tx: UnboundedSender<Event>, replay: bool) {
+        let mut state = self.lock();
+        let Some(transcript) = state.transcripts.get_mut(&session_id) else {
+            return;
+        };
+        let start = if replay { 0 } else { transcript.delivered };
+        for event in &transcript.events[start..] {
+            let _ = event_tx.send(event.clone());
+        }
+        transcript.delivered = transcript.events.len();
+        state.viewer = Some(SessionViewer {
+            session_id,
+            event_tx,
+        });
+    }
+
+    fn detach(&self) {
+        self.lock().viewer = None;
+    }
+
+    /// Drop the transcript of a finished session.
+    ///
+    /// This also closes the attached view, whichever session it shows, so the orchestrator can
+    /// reap the session and start the next queued prompt.
+    fn finish(&self, session_id: usize) {
+        let mut state = self.lock();
+        state.transcripts.remove(&session_id);
+        state.viewer = None;
+    }
+}
+
+/// Round frontend for one session running in the background of `--parallel` mode.
+struct ParallelRoundUi {
+    hub: SessionHub,
+    session_id: usize,
+    cancel_rx: watch::Receiver<bool>,
+    token_usage: TokenUsage,
+    thread_id: Option<ThreadId>,
+}
+
+impl ParallelRoundUi {
+    fn new(hub: SessionHub, session_id: usize, cancel_rx: watch::Receiver<bool>) -> Self {
+        Self {
+            hub,
+            session_id,
+            cancel_rx,
+            token_usage: TokenUsage::default(),
+            thread_id: None,
+        }
+    }
+}
+
+impl crate::round_runner::PotterRoundUi for ParallelRoundUi {
+    async fn render_turn(
+        &mut self,
+        prompt: String,
+        _pad_before_first_cell: bool,
+        op_tx: UnboundedSender<Op>,
+        mut event_rx: UnboundedReceiver<Event>,
+        mut fatal_exit_rx: UnboundedReceiver<String>,
+    ) -> anyhow::Result<AppExitInfo> {
+        op_tx
+            .send(Op::UserInput {
+                items: vec![UserInput::Text {
+                    text: prompt,
+                    text_elements: Vec::new(),
+                }],
+                final_output_json_schema: None,
+            })
+            .map_err(|err| anyhow::Error::msg(err.to_string()))?;
+
+        let exit_reason = loop {
+            tokio::select! {
+                maybe_event = event_rx.recv() => {
+                    let Some(event) = maybe_event else {
+                        break ExitReason::Fatal("Backend disconnected".to_string());
+                    };
+                    match &event.msg {
+                        EventMsg::SessionConfigured(cfg) => self.thread_id = Some(cfg.session_id),
+                        EventMsg::TokenCount(ev) => {
+                            if let Some(info) = &ev.info {
+                                self.token_usage = info.total_token_usage.clone();
+                            }
+                        }
+                        _ => {}
+                    }
+                    let round_exit = match &event.msg {
+                        EventMsg::PotterRoundFinished { outcome } => {
+                            Some(crate::exec::exit_reason_from_outcome(outcome))
+                        }
+                        _ => None,
+                    };
+                    self.hub.push(self.session_id, event);
+                    if let Some(exit_reason) = round_exit {
+                        break exit_reason;
+                    }
+                }
+                maybe_fatal = fatal_exit_rx.recv() => {
+                    let Some(message) = maybe_fatal else {
+                        continue;
+                    };
+                    self.hub.push(
+                        self.session_id,
+                        Event {
+                            id: String::new(),
+                            msg: EventMsg::Error(ErrorEvent {
+                                message: message.clone(),
+                                codex_error_info: None,
+                            }),
+                        },
+                    );
+                    break ExitReason::Fatal(message);
+                }
+                changed = self.cancel_rx.changed() => {
+                    if changed.is_err() || *self.cancel_rx.borrow() {
+                        let _ = op_tx.send(Op::Interrupt);
+                        break ExitReason::UserRequested;
+                    }
+                }
+            }
+        };
+
+        Ok(AppExitInfo {
+            token_usage: self.token_usage.clone(),
+            thread_id: self.thread_id,
+            exit_reason,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn agent_message(message: &str) -> Event {
+        Event {
+            id: String::new(),
+            msg: EventMsg::AgentMessage(codex_protocol::protocol::AgentMessageEvent {
+                message: message.to_string(),
+            }),
+        }
+    }
+
+    fn drain_messages(rx: &mut UnboundedReceiver<Event>) -> Vec<String> {
+        let mut out = Vec::new();
+        while let Ok(event) = rx.try_recv() {
+            if let EventMsg::AgentMessage(ev) = event.msg {
+                out.push(ev.message);
+            }
+        }
+        out
+    }
+
+    #[test]
+    fn session_hub_replays_transcript_and_forwards_live_events_to_attached_view() {
+        let hub = SessionHub::default();
+        hub.open(0);
+        hub.open(1);
+        hub.push(0, agent_message("first"));
+        hub.push(1, agent_message("other session"));
+
+        let (tx, mut rx) = unbounded_channel::<Event>();
+        hub.attach(0, tx, true);
+        hub.push(0, agent_message("second"));
+        hub.push(1, agent_message("still other session"));
+        assert_eq!(drain_messages(&mut rx), vec!["first", "second"]);
+
+        hub.detach();
+        hub.push(0, agent_message("third"));
+        let (tx, mut rx) = unbounded_channel::<Event>();
+        hub.attach(0, tx, false);
+        assert_eq!(drain_messages(&mut rx), vec!["third"]);
+    }
+
+    #[test]
+    fn session_hub_finish_closes_attached_view() {
+        let hub = SessionHub::default();
+        hub.open(0);
+        hub.open(1);
+
+        let (tx, mut rx) = unbounded_channel::<Event>();
+        hub.attach(0, tx, true);
+        hub.finish(1);
+
+        assert!(matches!(
+            rx.try_recv(),
+            Err(tokio::sync::mpsc::error::TryRecvError::Disconnected)
+        ));
+    }
+
+    #[test]
+    fn session_label_truncates_to_first_line() {
+        assert_eq!(session_label("Fix the build"), "Fix the build");
+        assert_eq!(session_label("Fix the build\nand more"), "Fix the build…");
+        assert_eq!(
+            session_label(&"a".repeat(60)),
+            format!("{}…", "a".repeat(SESSION_LABEL_MAX_CHARS))
+        );
+    }
+
+    #[tokio::test]
+    async fn parallel_round_ui_interrupts_when_cancelled() {
+        use crate::round_runner::PotterRoundUi;
+
+        let (cancel_tx, cancel_rx) = watch::channel(false);
+        let hub = SessionHub::default();
+        hub.open(0);
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let (_event_tx, event_rx) = unbounded_channel::<Event>();
+        let (_fatal_tx, fatal_rx) = unbounded_channel::<String>();
+
+        cancel_tx.send_replace(true);
+        let exit_info = ParallelRoundUi::new(hub, 0, cancel_rx)
+            .render_turn("Continue".to_string(), false, op_tx, event_rx, fatal_rx)
+            .await
+            .expect("render turn");
+
+        assert!(matches!(exit_info.exit_reason, ExitReason::UserRequested));
+        assert!(matches!(op_rx.recv().await, Some(Op::UserInput { .. })));
+        assert!(matches!(op_rx.recv().await, Some(Op::Interrupt)));
+    }
+}
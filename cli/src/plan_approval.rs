@@ -0,0 +1,259 @@
+//! Human approval of the plan before any write-capable round (`--approve-plan`).
+//!
+//! With `--approve-plan`, the first round of a project is a planning round: it runs with a
+//! read-only sandbox and replies with the plan as a Markdown list instead of editing anything.
+//! The user then approves the plan, edits it in `$EDITOR`, or rejects it; a plan without tasks
+//! cannot be approved, and a planning round that proposes none fails. An approved plan becomes
+//! the progress file's `## Todo` section. Proposals (`plan_proposed`) and decisions
+//! (`plan_decided`) are recorded in `potter-rollout.jsonl`, so `resume` knows whether the project
+//! still needs a plan, is waiting for a decision, or may run write-capable rounds.
+
+use std::path::Path;
+
+use anyhow::Context;
+use serde::Deserialize;
+use serde::Serialize;
+
+use crate::potter_rollout::PotterRolloutLine;
+
+const NO_EDITOR_NOTICE: &str = "Cannot open external editor: set $VISUAL or $EDITOR";
+/// Why a planning round whose reply lists no tasks failed.
+pub const EMPTY_PLAN_MESSAGE: &str =
+    "the planning round proposed no tasks (the reply had no Markdown list); resume to plan again";
+
+/// What the user decided about a proposed plan.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum PlanDecision {
+    Approved,
+    /// Approved after editing the tasks in `$EDITOR`.
+    Edited,
+    Rejected,
+}
+
+impl PlanDecision {
+    pub fn is_approved(self) -> bool {
+        matches!(self, PlanDecision::Approved | PlanDecision::Edited)
+    }
+}
+
+/// A plan decision together with the final task list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlanReview {
+    pub decision: PlanDecision,
+    pub tasks: Vec<String>,
+}
+
+/// Where a project stands with respect to plan approval.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanState {
+    /// The project was not started with `--approve-plan`.
+    NotRequested,
+    /// The next round must be a planning round (none finished yet, or the last plan was
+    /// rejected).
+    NeedsPlan,
+    /// A plan was proposed but the user has not decided yet.
+    AwaitingDecision(Vec<String>),
+    /// The plan was approved; rounds run normally.
+    Approved,
+}
+
+impl PlanState {
+    /// Derive the plan state from a project's `potter-rollout.jsonl` lines.
+    pub fn from_lines(lines: &[PotterRolloutLine]) -> Self {
+        let mut state = PlanState::NotRequested;
+        for line in lines {
+            match line {
+                PotterRolloutLine::RoundStarted { planning: true, .. } => {
+                    state = PlanState::NeedsPlan;
+                }
+                PotterRolloutLine::PlanProposed { tasks } => {
+                    state = PlanState::AwaitingDecision(tasks.clone());
+                }
+                PotterRolloutLine::PlanDecided { decision, .. } => {
+                    state = if decision.is_approved() {
+                        PlanState::Approved
+                    } else {
+                        PlanState::NeedsPlan
+                    };
+                }
+                _ => {}
+            }
+        }
+        state
+    }
+}
+
+/// Developer prompt for a planning round: `developer_prompt` plus instructions to only plan.
+pub fn planning_developer_prompt(developer_prompt: &str) -> String {
+    format!(
+        "{developer_prompt}\n\n<PLANNING_ROUND>\n\nThis round only plans; it overrides the workflow above. The sandbox is read-only, so do not modify any file, including the progress file. Explore the working directory as needed, then reply with the plan as a Markdown list of concrete tasks (one `- ` item per task, in a sensible order) and nothing else. The user reviews the plan, and once approved it becomes the progress file's `Todo` section for the following rounds.\n\n</PLANNING_ROUND>\n"
+    )
+}
+
+/// Parse the tasks out of a plan written as a Markdown list.
+///
+/// Top-level `-`/`*`/`+` and numbered items become tasks (checkboxes are dropped); indented
+/// lines and other text are ignored.
+pub fn parse_plan_tasks(plan: &str) -> Vec<String> {
+    plan.lines()
+        .filter(|line| !line.starts_with(char::is_whitespace))
+        .filter_map(|line| {
+            let item = line
+                .strip_prefix("- ")
+                .or_else(|| line.strip_prefix("* "))
+                .or_else(|| line.strip_prefix("+ "))
+                .or_else(|| {
+                    let rest = line.trim_start_matches(|ch: char| ch.is_ascii_digit());
+                    if rest.len() == line.len() {
+                        return None;
+                    }
+                    rest.strip_prefix(". ").or_else(|| rest.strip_prefix(") "))
+                })?;
+            let item = ["[ ] ", "[x] ", "[X] "]
+                .iter()
+                .find_map(|checkbox| item.strip_prefix(checkbox))
+                .unwrap_or(item)
+                .trim();
+            (!item.is_empty()).then(|| item.to_string())
+        })
+        .collect()
+}
+
+/// Format `tasks` as the Markdown list written to `## Todo` (and seeded into `$EDITOR`).
+pub fn format_plan_tasks(tasks: &[String]) -> String {
+    tasks.iter().map(|task| format!("- {task}\n")).collect()
+}
+
+/// Let the user approve, edit, or reject `tasks` in the TUI until they approve or reject.
+pub async fn review_plan_with_tui(
+    tui: &mut codex_tui::CodexPotterTui,
+    mut tasks: Vec<String>,
+) -> anyhow::Result<PlanReview> {
+    let mut edited = false;
+    let mut notice = None;
+    loop {
+        match tui.prompt_plan_approval(&tasks, notice.take()).await? {
+            codex_tui::PlanApprovalChoice::Approve => {
+                let decision = if edited {
+                    PlanDecision::Edited
+                } else {
+                    PlanDecision::Approved
+                };
+                return Ok(PlanReview { decision, tasks });
+            }
+            codex_tui::PlanApprovalChoice::Edit => {
+                match tui
+                    .edit_in_external_editor(&format_plan_tasks(&tasks))
+                    .await?
+                {
+                    Some(text) => {
+                        tasks = parse_plan_tasks(&text);
+                        edited = true;
+                    }
+                    None => notice = Some(NO_EDITOR_NOTICE.to_string()),
+                }
+            }
+            codex_tui::PlanApprovalChoice::Reject => {
+                return Ok(PlanReview {
+                    decision: PlanDecision::Rejected,
+                    tasks,
+                });
+            }
+        }
+    }
+}
+
+/// Record `review` in `potter-rollout.jsonl` and, when approved, write the tasks to the progress
+/// file.
+pub fn record_plan_review(
+    potter_rollout_path: &Path,
+    workdir: &Path,
+    progress_file_rel: &Path,
+    review: &PlanReview,
+) -> anyhow::Result<()> {
+    if review.decision.is_approved() {
+        crate::project::set_progress_file_plan(workdir, progress_file_rel, &review.tasks)
+            .context("write approved plan to progress file")?;
+    }
+    crate::potter_rollout::append_line(
+        potter_rollout_path,
+        &PotterRolloutLine::PlanDecided {
+            decision: review.decision,
+            tasks: review.tasks.clone(),
+        },
+    )
+    .context("append potter-rollout plan_decided")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use codex_protocol::protocol::PotterRoundOutcome;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn parse_plan_tasks_reads_top_level_list_items() {
+        let plan = "Here is the plan:\n\n- [ ] Add the parser\n* Wire it into `main`\n  - detail that stays with the previous task\n2. Document the flag\n10) Add tests\n-not a list item\n";
+        assert_eq!(
+            parse_plan_tasks(plan),
+            vec![
+                "Add the parser".to_string(),
+                "Wire it into `main`".to_string(),
+                "Document the flag".to_string(),
+                "Add tests".to_string(),
+            ]
+        );
+        assert_eq!(
+            parse_plan_tasks(&format_plan_tasks(&parse_plan_tasks(plan))),
+            parse_plan_tasks(plan)
+        );
+    }
+
+    #[test]
+    fn plan_state_follows_proposals_and_decisions() {
+        let planning_round = PotterRolloutLine::RoundStarted {
+            current: 1,
+            total: 10,
+            planning: true,
+        };
+        let proposed = PotterRolloutLine::PlanProposed {
+            tasks: vec!["Add the parser".to_string()],
+        };
+        let finished = PotterRolloutLine::RoundFinished {
+            outcome: PotterRoundOutcome::Completed,
+        };
+        let decided = |decision| PotterRolloutLine::PlanDecided {
+            decision,
+            tasks: vec!["Add the parser".to_string()],
+        };
+
+        assert_eq!(PlanState::from_lines(&[]), PlanState::NotRequested);
+        assert_eq!(
+            PlanState::from_lines(&[planning_round.clone()]),
+            PlanState::NeedsPlan
+        );
+        assert_eq!(
+            PlanState::from_lines(&[planning_round.clone(), proposed.clone(), finished.clone()]),
+            PlanState::AwaitingDecision(vec!["Add the parser".to_string()])
+        );
+        assert_eq!(
+            PlanState::from_lines(&[
+                planning_round.clone(),
+                proposed.clone(),
+                finished.clone(),
+                decided(PlanDecision::Rejected),
+            ]),
+            PlanState::NeedsPlan
+        );
+        assert_eq!(
+            PlanState::from_lines(&[
+                planning_round,
+                proposed,
+                finished,
+                decided(PlanDecision::Edited),
+            ]),
+            PlanState::Approved
+        );
+    }
+}
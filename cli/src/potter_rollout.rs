@@ -4,7 +4,13 @@ use std::path::PathBuf;
 
 use anyhow::Context;
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PotterApprovalDecidedBy;
+use codex_protocol::protocol::PotterApprovalDecision;
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
+use codex_protocol::protocol::PotterPromptAnswer;
+use codex_protocol::protocol::PotterPromptAnsweredBy;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::TokenUsage;
 use serde::Deserialize;
 use serde::Serialize;
 
@@ -18,10 +24,16 @@ pub enum PotterRolloutLine {
     SessionStarted {
         user_message: Option<String>,
         user_prompt_file: PathBuf,
+        /// Git worktree the session runs in, when started with `--parallel`.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        worktree: Option<PathBuf>,
     },
     RoundStarted {
         current: u32,
         total: u32,
+        /// Planning round of `--approve-plan`: read-only, ends with a `PlanProposed` line.
+        #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+        planning: bool,
     },
     RoundConfigured {
         thread_id: ThreadId,
@@ -31,6 +43,24 @@ pub enum PotterRolloutLine {
         #[serde(default, skip_serializing_if = "Option::is_none")]
         rollout_base_dir: Option<PathBuf>,
     },
+    /// Result of the verification command (`--verify`) after a completed round.
+    RoundVerified {
+        command: String,
+        success: bool,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        exit_code: Option<i32>,
+        duration_secs: u64,
+        output_tail: String,
+    },
+    /// Plan a planning round replied with, parsed into `## Todo` tasks.
+    PlanProposed {
+        tasks: Vec<String>,
+    },
+    /// Wall time and final token usage of a round, written just before `RoundFinished`.
+    RoundUsage {
+        duration_secs: u64,
+        token_usage: TokenUsage,
+    },
     SessionSucceeded {
         rounds: u32,
         duration_secs: u64,
@@ -38,9 +68,38 @@ pub enum PotterRolloutLine {
         git_commit_start: String,
         git_commit_end: String,
     },
+    /// A session-wide budget stopped the session after this round.
+    BudgetExhausted {
+        reason: PotterBudgetExhaustedReason,
+    },
+    /// The last `rounds` completed rounds made no progress, which stopped the session
+    /// (`--max-stalled-rounds`).
+    SessionStalled {
+        rounds: u32,
+    },
     RoundFinished {
         outcome: PotterRoundOutcome,
     },
+    /// The user's decision on the last proposed plan, written after the planning round finished.
+    PlanDecided {
+        decision: crate::plan_approval::PlanDecision,
+        tasks: Vec<String>,
+    },
+    /// How an approval request of the round was answered (`[approval]` in `config.toml`).
+    ApprovalDecided {
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        command: Option<Vec<String>>,
+        #[serde(default, skip_serializing_if = "Vec::is_empty")]
+        paths: Vec<PathBuf>,
+        decision: PotterApprovalDecision,
+        decided_by: PotterApprovalDecidedBy,
+    },
+    /// How a question prompt or MCP elicitation of the round was answered (`[user_input]` in
+    /// `config.toml`).
+    PromptAnswered {
+        answers: Vec<PotterPromptAnswer>,
+        answered_by: PotterPromptAnsweredBy,
+    },
 }
 
 /// Resolve the full path to `potter-rollout.jsonl` within a project directory.
@@ -99,6 +158,26 @@ pub fn read_lines(path: &Path) -> anyhow::Result<Vec<PotterRolloutLine>> {
     Ok(out)
 }
 
+/// Final token usage of every recorded round, in round order.
+pub fn round_token_usage(lines: &[PotterRolloutLine]) -> Vec<TokenUsage> {
+    lines
+        .iter()
+        .filter_map(|line| match line {
+            PotterRolloutLine::RoundUsage { token_usage, .. } => Some(token_usage.clone()),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Sum per-round token usage into a session total.
+pub fn sum_token_usage(usages: &[TokenUsage]) -> TokenUsage {
+    let mut total = TokenUsage::default();
+    for usage in usages {
+        total.add_assign(usage);
+    }
+    total
+}
+
 /// Best-effort: resolve a rollout path to an absolute path suitable for recording.
 ///
 /// Returns:
@@ -135,6 +214,7 @@ mod tests {
             &PotterRolloutLine::SessionStarted {
                 user_message: Some("hello".to_string()),
                 user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+                worktree: None,
             },
         )
         .expect("append session_started");
@@ -143,6 +223,7 @@ mod tests {
             &PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
         )
         .expect("append round_started");
@@ -154,15 +235,75 @@ mod tests {
                 PotterRolloutLine::SessionStarted {
                     user_message: Some("hello".to_string()),
                     user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/28/1/MAIN.md"),
+                    worktree: None,
                 },
                 PotterRolloutLine::RoundStarted {
                     current: 1,
                     total: 10,
+                    planning: false,
                 },
             ]
         );
     }
 
+    #[test]
+    fn approval_decided_line_serializes_for_auditing() {
+        let line = PotterRolloutLine::ApprovalDecided {
+            command: Some(vec!["git".to_string(), "push".to_string()]),
+            paths: Vec::new(),
+            decision: PotterApprovalDecision::Deny,
+            decided_by: PotterApprovalDecidedBy::Rule {
+                rule: r#"command_prefix = ["git", "push"]"#.to_string(),
+            },
+        };
+        let json = serde_json::to_string(&line).expect("serialize");
+        assert_eq!(
+            json,
+            r#"{"type":"approval_decided","command":["git","push"],"decision":"deny","decided_by":{"type":"rule","rule":"command_prefix = [\"git\", \"push\"]"}}"#
+        );
+        assert_eq!(
+            serde_json::from_str::<PotterRolloutLine>(&json).expect("deserialize"),
+            line
+        );
+    }
+
+    #[test]
+    fn round_token_usage_collects_round_usage_lines_in_order() {
+        let usage = |input_tokens, output_tokens| TokenUsage {
+            input_tokens,
+            output_tokens,
+            total_tokens: input_tokens + output_tokens,
+            ..TokenUsage::default()
+        };
+        let lines = vec![
+            PotterRolloutLine::RoundStarted {
+                current: 1,
+                total: 2,
+                planning: false,
+            },
+            PotterRolloutLine::RoundUsage {
+                duration_secs: 10,
+                token_usage: usage(100, 20),
+            },
+            PotterRolloutLine::RoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+            },
+            PotterRolloutLine::RoundStarted {
+                current: 2,
+                total: 2,
+                planning: false,
+            },
+            PotterRolloutLine::RoundUsage {
+                duration_secs: 20,
+                token_usage: usage(300, 40),
+            },
+        ];
+
+        let rounds = round_token_usage(&lines);
+        assert_eq!(rounds, vec![usage(100, 20), usage(300, 40)]);
+        assert_eq!(sum_token_usage(&rounds), usage(400, 60));
+    }
+
     #[test]
     fn resolve_rollout_path_for_recording_canonicalizes_when_possible() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1,7 +1,9 @@
 use std::path::PathBuf;
 
 use codex_protocol::ThreadId;
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::TokenUsage;
 
 use crate::potter_rollout::PotterRolloutLine;
 
@@ -24,7 +26,12 @@ pub struct CompletedRoundIndex {
     pub round_total: u32,
     pub thread_id: ThreadId,
     pub rollout_path: PathBuf,
+    pub verification: Option<RoundVerifiedIndex>,
+    pub usage: Option<RoundUsageIndex>,
     pub session_succeeded: Option<SessionSucceededIndex>,
+    pub budget_exhausted: Option<PotterBudgetExhaustedReason>,
+    /// Consecutive rounds without progress, when that stopped the session after this round.
+    pub stalled_rounds: Option<u32>,
     pub outcome: PotterRoundOutcome,
 }
 
@@ -36,6 +43,21 @@ pub struct UnfinishedRoundIndex {
     pub rollout_path: PathBuf,
 }
 
+#[derive(Debug, Clone)]
+pub struct RoundVerifiedIndex {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub duration_secs: u64,
+    pub output_tail: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct RoundUsageIndex {
+    pub duration_secs: u64,
+    pub token_usage: TokenUsage,
+}
+
 #[derive(Debug, Clone)]
 pub struct SessionSucceededIndex {
     pub rounds: u32,
@@ -53,7 +75,11 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
         round_current: u32,
         round_total: u32,
         configured: Option<(ThreadId, PathBuf)>,
+        verification: Option<RoundVerifiedIndex>,
+        usage: Option<RoundUsageIndex>,
         session_succeeded: Option<SessionSucceededIndex>,
+        budget_exhausted: Option<PotterBudgetExhaustedReason>,
+        stalled_rounds: Option<u32>,
     }
 
     let mut current: Option<RoundBuilder> = None;
@@ -63,6 +89,7 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
             PotterRolloutLine::SessionStarted {
                 user_message,
                 user_prompt_file,
+                ..
             } => {
                 if session_started.is_some() || !completed_rounds.is_empty() || current.is_some() {
                     anyhow::bail!("potter-rollout: session_started must appear once at the top");
@@ -75,6 +102,7 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
             PotterRolloutLine::RoundStarted {
                 current: round_current,
                 total: round_total,
+                ..
             } => {
                 if session_started.is_none() {
                     anyhow::bail!("potter-rollout: missing session_started before first round");
@@ -86,7 +114,11 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                     round_current: *round_current,
                     round_total: *round_total,
                     configured: None,
+                    verification: None,
+                    usage: None,
                     session_succeeded: None,
+                    budget_exhausted: None,
+                    stalled_rounds: None,
                 });
             }
             PotterRolloutLine::RoundConfigured {
@@ -102,6 +134,27 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                 }
                 builder.configured = Some((*thread_id, rollout_path.clone()));
             }
+            PotterRolloutLine::RoundVerified {
+                command,
+                success,
+                exit_code,
+                duration_secs,
+                output_tail,
+            } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: round_verified outside a round");
+                };
+                if builder.verification.is_some() {
+                    anyhow::bail!("potter-rollout: duplicate round_verified in a single round");
+                }
+                builder.verification = Some(RoundVerifiedIndex {
+                    command: command.clone(),
+                    success: *success,
+                    exit_code: *exit_code,
+                    duration_secs: *duration_secs,
+                    output_tail: output_tail.clone(),
+                });
+            }
             PotterRolloutLine::SessionSucceeded {
                 rounds,
                 duration_secs,
@@ -123,6 +176,62 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                     git_commit_end: git_commit_end.clone(),
                 });
             }
+            PotterRolloutLine::RoundUsage {
+                duration_secs,
+                token_usage,
+            } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: round_usage outside a round");
+                };
+                if builder.usage.is_some() {
+                    anyhow::bail!("potter-rollout: duplicate round_usage in a single round");
+                }
+                builder.usage = Some(RoundUsageIndex {
+                    duration_secs: *duration_secs,
+                    token_usage: token_usage.clone(),
+                });
+            }
+            PotterRolloutLine::PlanProposed { .. } => {
+                if current.is_none() {
+                    anyhow::bail!("potter-rollout: plan_proposed outside a round");
+                }
+            }
+            PotterRolloutLine::PlanDecided { .. } => {
+                if current.is_some() {
+                    anyhow::bail!("potter-rollout: plan_decided before round_finished");
+                }
+                if completed_rounds.is_empty() {
+                    anyhow::bail!("potter-rollout: plan_decided before the first round");
+                }
+            }
+            PotterRolloutLine::ApprovalDecided { .. } => {
+                if current.is_none() {
+                    anyhow::bail!("potter-rollout: approval_decided outside a round");
+                }
+            }
+            PotterRolloutLine::PromptAnswered { .. } => {
+                if current.is_none() {
+                    anyhow::bail!("potter-rollout: prompt_answered outside a round");
+                }
+            }
+            PotterRolloutLine::BudgetExhausted { reason } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: budget_exhausted outside a round");
+                };
+                if builder.budget_exhausted.is_some() {
+                    anyhow::bail!("potter-rollout: duplicate budget_exhausted in a single round");
+                }
+                builder.budget_exhausted = Some(reason.clone());
+            }
+            PotterRolloutLine::SessionStalled { rounds } => {
+                let Some(builder) = current.as_mut() else {
+                    anyhow::bail!("potter-rollout: session_stalled outside a round");
+                };
+                if builder.stalled_rounds.is_some() {
+                    anyhow::bail!("potter-rollout: duplicate session_stalled in a single round");
+                }
+                builder.stalled_rounds = Some(*rounds);
+            }
             PotterRolloutLine::RoundFinished { outcome } => {
                 let Some(builder) = current.take() else {
                     anyhow::bail!("potter-rollout: round_finished without round_started");
@@ -135,7 +244,11 @@ pub fn build_resume_index(lines: &[PotterRolloutLine]) -> anyhow::Result<PotterR
                     round_total: builder.round_total,
                     thread_id,
                     rollout_path,
+                    verification: builder.verification,
+                    usage: builder.usage,
                     session_succeeded: builder.session_succeeded,
+                    budget_exhausted: builder.budget_exhausted,
+                    stalled_rounds: builder.stalled_rounds,
                     outcome: outcome.clone(),
                 });
             }
@@ -3,15 +3,23 @@
 //! CodexPotter runs multi-round workflows. When Codex emits certain transient network/streaming
 //! errors mid-turn (e.g. response stream disconnected), we want to keep the current round alive
 //! and let the agent recover by issuing a follow-up `continue` prompt.
+//!
+//! Hitting the usage limit is handled the same way, except that the `continue` is sent once the
+//! exhausted rate-limit window resets (taken from the latest `TokenCount` rate-limit snapshot).
 
 use std::time::Duration;
 
 use codex_protocol::potter_stream_recovery as protocol_recovery;
+use codex_protocol::protocol::CodexErrorInfo;
 use codex_protocol::protocol::ErrorEvent;
 use codex_protocol::protocol::EventMsg;
+use codex_protocol::protocol::RateLimitSnapshot;
 use codex_protocol::protocol::TurnCompleteEvent;
 
 const MAX_CONTINUE_RETRIES: u32 = 10;
+const MAX_USAGE_LIMIT_WAITS: u32 = 5;
+/// Waited past the reported reset, so the `continue` does not race the window rollover.
+const USAGE_LIMIT_RESET_SLACK_SECS: i64 = 30;
 
 /// A plan to retry a failed turn by sending a follow-up `continue` prompt.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -24,28 +32,70 @@ pub struct ContinueRetryPlan {
     pub backoff: Duration,
 }
 
+/// A plan to wait for the usage-limit window to reset, then send a follow-up `continue` prompt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UsageLimitWaitPlan {
+    /// 1-based wait number since the agent was last active.
+    pub attempt: u32,
+    /// Maximum number of waits allowed before giving up.
+    pub max_attempts: u32,
+    /// Unix timestamp (seconds) when `continue` is sent.
+    pub resumes_at: i64,
+    /// How long to wait before sending `continue`.
+    pub wait: Duration,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ContinueRetryDecision {
     Retry(ContinueRetryPlan),
+    WaitForUsageLimit(UsageLimitWaitPlan),
     GiveUp { attempts: u32, max_attempts: u32 },
 }
 
+/// Whether to respawn a dead app-server and continue, or give up. Unlike stream errors, a
+/// restart never waits for the usage limit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RestartDecision {
+    Retry(ContinueRetryPlan),
+    GiveUp { attempts: u32, max_attempts: u32 },
+}
+
+impl From<RestartDecision> for ContinueRetryDecision {
+    fn from(decision: RestartDecision) -> Self {
+        match decision {
+            RestartDecision::Retry(plan) => Self::Retry(plan),
+            RestartDecision::GiveUp {
+                attempts,
+                max_attempts,
+            } => Self::GiveUp {
+                attempts,
+                max_attempts,
+            },
+        }
+    }
+}
+
 /// Tracks retry/backoff state for "continue after stream disconnect" behavior.
 #[derive(Debug, Default)]
 pub struct PotterStreamRecovery {
     continue_sends_since_activity: u32,
+    usage_limit_waits_since_activity: u32,
+    /// Latest rate-limit snapshot reported by `TokenCount` events.
+    rate_limits: Option<RateLimitSnapshot>,
 }
 
 impl PotterStreamRecovery {
     pub fn new() -> Self {
         Self {
             continue_sends_since_activity: 0,
+            usage_limit_waits_since_activity: 0,
+            rate_limits: None,
         }
     }
 
     /// Returns `true` when CodexPotter is still in a continuous-error retry streak.
     pub fn is_in_retry_streak(&self) -> bool {
-        self.continue_sends_since_activity > 0
+        self.continue_sends_since_activity > 0 || self.usage_limit_waits_since_activity > 0
     }
 
     /// Returns `true` when `turn_complete` should be suppressed from the UI.
@@ -64,8 +114,14 @@ impl PotterStreamRecovery {
 
     /// Observe a non-error event and reset backoff state when we see activity.
     pub fn observe_event(&mut self, msg: &EventMsg) {
+        if let EventMsg::TokenCount(ev) = msg
+            && let Some(rate_limits) = &ev.rate_limits
+        {
+            self.rate_limits = Some(rate_limits.clone());
+        }
         if protocol_recovery::is_activity_event(msg) {
             self.continue_sends_since_activity = 0;
+            self.usage_limit_waits_since_activity = 0;
         }
     }
 
@@ -75,12 +131,74 @@ impl PotterStreamRecovery {
         if !protocol_recovery::is_retryable_stream_error(error) {
             return None;
         }
+        Some(self.plan_continue().into())
+    }
 
-        if self.continue_sends_since_activity >= MAX_CONTINUE_RETRIES {
+    /// Decide whether to respawn an app-server that died mid-turn and continue the thread. Shares
+    /// the retry budget with stream errors.
+    pub fn plan_restart(&mut self) -> RestartDecision {
+        self.plan_continue()
+    }
+
+    /// If `error` is the usage limit and the latest rate-limit snapshot says when the window
+    /// resets, returns a decision to wait until then (or to give up after too many waits).
+    pub fn plan_usage_limit_wait(
+        &mut self,
+        error: &ErrorEvent,
+        now_unix: i64,
+    ) -> Option<ContinueRetryDecision> {
+        if error.codex_error_info != Some(CodexErrorInfo::UsageLimitExceeded) {
+            return None;
+        }
+        let resets_at = self.usage_limit_resets_at()?;
+
+        if self.usage_limit_waits_since_activity >= MAX_USAGE_LIMIT_WAITS {
             return Some(ContinueRetryDecision::GiveUp {
+                attempts: self.usage_limit_waits_since_activity,
+                max_attempts: MAX_USAGE_LIMIT_WAITS,
+            });
+        }
+        self.usage_limit_waits_since_activity += 1;
+
+        let resumes_at = resets_at.max(now_unix) + USAGE_LIMIT_RESET_SLACK_SECS;
+        Some(ContinueRetryDecision::WaitForUsageLimit(
+            UsageLimitWaitPlan {
+                attempt: self.usage_limit_waits_since_activity,
+                max_attempts: MAX_USAGE_LIMIT_WAITS,
+                resumes_at,
+                wait: Duration::from_secs(u64::try_from(resumes_at - now_unix).unwrap_or(0)),
+            },
+        ))
+    }
+
+    /// When the limiting rate-limit window resets: the latest-resetting exhausted window, or else
+    /// the most used one (the snapshot may predate the request that hit the limit).
+    fn usage_limit_resets_at(&self) -> Option<i64> {
+        let rate_limits = self.rate_limits.as_ref()?;
+        let windows = [rate_limits.primary.as_ref(), rate_limits.secondary.as_ref()]
+            .into_iter()
+            .flatten()
+            .filter(|window| window.resets_at.is_some())
+            .collect::<Vec<_>>();
+        let exhausted = windows
+            .iter()
+            .filter(|window| window.used_percent >= 100.0)
+            .filter_map(|window| window.resets_at)
+            .max();
+        exhausted.or_else(|| {
+            windows
+                .iter()
+                .max_by(|a, b| a.used_percent.total_cmp(&b.used_percent))
+                .and_then(|window| window.resets_at)
+        })
+    }
+
+    fn plan_continue(&mut self) -> RestartDecision {
+        if self.continue_sends_since_activity >= MAX_CONTINUE_RETRIES {
+            return RestartDecision::GiveUp {
                 attempts: self.continue_sends_since_activity,
                 max_attempts: MAX_CONTINUE_RETRIES,
-            });
+            };
         }
 
         let attempt = self.continue_sends_since_activity + 1;
@@ -91,11 +209,11 @@ impl PotterStreamRecovery {
         });
         self.continue_sends_since_activity += 1;
 
-        Some(ContinueRetryDecision::Retry(ContinueRetryPlan {
+        RestartDecision::Retry(ContinueRetryPlan {
             attempt,
             max_attempts: MAX_CONTINUE_RETRIES,
             backoff,
-        }))
+        })
     }
 }
 
@@ -103,7 +221,8 @@ impl PotterStreamRecovery {
 mod tests {
     use super::*;
     use codex_protocol::protocol::AgentMessageDeltaEvent;
-    use codex_protocol::protocol::CodexErrorInfo;
+    use codex_protocol::protocol::RateLimitWindow;
+    use codex_protocol::protocol::TokenCountEvent;
     use pretty_assertions::assert_eq;
 
     fn retryable_error_event() -> ErrorEvent {
@@ -216,4 +335,105 @@ mod tests {
             last_agent_message: Some("done".to_string()),
         }));
     }
+
+    #[test]
+    fn plan_restart_shares_budget_with_stream_errors() {
+        let mut state = PotterStreamRecovery::new();
+        let err = retryable_error_event();
+
+        let Some(ContinueRetryDecision::Retry(_)) = state.plan_retry(&err) else {
+            panic!("expected retry plan");
+        };
+        let RestartDecision::Retry(plan) = state.plan_restart() else {
+            panic!("expected restart plan");
+        };
+        assert_eq!(
+            plan,
+            ContinueRetryPlan {
+                attempt: 2,
+                max_attempts: 10,
+                backoff: Duration::from_secs(1),
+            }
+        );
+    }
+
+    fn usage_limit_error_event() -> ErrorEvent {
+        ErrorEvent {
+            message: "You've hit your usage limit.".to_string(),
+            codex_error_info: Some(CodexErrorInfo::UsageLimitExceeded),
+        }
+    }
+
+    fn token_count(primary: (f64, i64), secondary: (f64, i64)) -> EventMsg {
+        let window = |(used_percent, resets_at): (f64, i64)| RateLimitWindow {
+            used_percent,
+            window_minutes: None,
+            resets_at: Some(resets_at),
+        };
+        EventMsg::TokenCount(TokenCountEvent {
+            info: None,
+            rate_limits: Some(RateLimitSnapshot {
+                primary: Some(window(primary)),
+                secondary: Some(window(secondary)),
+                credits: None,
+                plan_type: None,
+            }),
+        })
+    }
+
+    #[test]
+    fn plan_usage_limit_wait_waits_for_the_exhausted_window() {
+        let mut state = PotterStreamRecovery::new();
+        let err = usage_limit_error_event();
+        assert_eq!(state.plan_usage_limit_wait(&err, 1_000), None);
+
+        state.observe_event(&token_count((100.0, 4_000), (40.0, 90_000)));
+        assert_eq!(
+            state.plan_usage_limit_wait(&err, 1_000),
+            Some(ContinueRetryDecision::WaitForUsageLimit(
+                UsageLimitWaitPlan {
+                    attempt: 1,
+                    max_attempts: 5,
+                    resumes_at: 4_030,
+                    wait: Duration::from_secs(3_030),
+                }
+            ))
+        );
+        assert!(state.is_in_retry_streak());
+        assert_eq!(state.plan_retry(&err), None);
+
+        // Without an exhausted window, the most used one is the limiting one.
+        state.observe_event(&token_count((20.0, 4_000), (99.0, 90_000)));
+        let Some(ContinueRetryDecision::WaitForUsageLimit(plan)) =
+            state.plan_usage_limit_wait(&err, 100_000)
+        else {
+            panic!("expected usage limit wait");
+        };
+        assert_eq!(
+            (plan.attempt, plan.resumes_at, plan.wait),
+            (2, 100_030, Duration::from_secs(30))
+        );
+    }
+
+    #[test]
+    fn plan_usage_limit_wait_gives_up_after_five_waits() {
+        let mut state = PotterStreamRecovery::new();
+        let err = usage_limit_error_event();
+        state.observe_event(&token_count((100.0, 4_000), (40.0, 90_000)));
+
+        for _ in 0..5 {
+            let Some(ContinueRetryDecision::WaitForUsageLimit(_)) =
+                state.plan_usage_limit_wait(&err, 1_000)
+            else {
+                panic!("expected usage limit wait");
+            };
+        }
+        assert_eq!(
+            state.plan_usage_limit_wait(&err, 1_000),
+            Some(ContinueRetryDecision::GiveUp {
+                attempts: 5,
+                max_attempts: 5,
+            })
+        );
+    }
 }
@@ -0,0 +1,980 @@
+//! Typed model of a project's progress file (`MAIN.md`).
+//!
+//! The progress file is edited by the agent every round, so parsing never fails: whatever can be
+//! read is read, and everything that does not match the expected layout is reported as a
+//! [`ProgressFileIssue`]. After each round the runner calls [`check_progress_file`], which fixes
+//! the issues that have an obvious fix (see [`repair`]) and returns the rest so the next round can
+//! be told about them. [`ProgressFileWatcher`] feeds the TUI progress panel while a round runs.
+
+use std::fmt;
+use std::path::Path;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+use anyhow::Context;
+use codex_protocol::protocol::PotterProgress;
+use codex_protocol::protocol::PotterProgressItem;
+
+pub const OVERALL_GOAL: &str = "# Overall Goal";
+pub const IN_PROGRESS: &str = "## In Progress";
+pub const TODO: &str = "## Todo";
+pub const DONE: &str = "## Done";
+
+/// Sections every workflow template starts with.
+const REQUIRED_SECTIONS: [&str; 3] = [OVERALL_GOAL, TODO, DONE];
+
+/// The `status` front matter field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ProgressStatus {
+    /// The tasks are yet to be planned.
+    Initial,
+    /// Tasks are planned and being worked on.
+    Open,
+    /// The agent decided the request needs no work.
+    Skip,
+}
+
+impl ProgressStatus {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value {
+            "initial" => Some(Self::Initial),
+            "open" => Some(Self::Open),
+            "skip" => Some(Self::Skip),
+            _ => None,
+        }
+    }
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Initial => "initial",
+            Self::Open => "open",
+            Self::Skip => "skip",
+        }
+    }
+}
+
+/// A list item of the `In Progress`, `Todo` or `Done` section.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProgressTask {
+    /// Item text without the list marker and checkbox.
+    pub text: String,
+    /// Checked off with `[x]`.
+    pub checked: bool,
+}
+
+/// Tasks listed in a progress file.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct TaskCounts {
+    /// Open tasks in `## Todo` and `## In Progress`.
+    pub todo: usize,
+    /// Tasks in `## Done`.
+    pub done: usize,
+}
+
+/// Something in a progress file that does not match the expected layout.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ProgressFileIssue {
+    /// The file does not start with a `---` line.
+    MissingFrontMatter,
+    /// The front matter has no closing `---` line.
+    UnclosedFrontMatter,
+    MissingField(&'static str),
+    InvalidField {
+        key: &'static str,
+        value: String,
+    },
+    MissingSection(&'static str),
+}
+
+impl fmt::Display for ProgressFileIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::MissingFrontMatter => {
+                write!(f, "missing the YAML front matter (`---` on the first line)")
+            }
+            Self::UnclosedFrontMatter => write!(f, "the YAML front matter has no closing `---`"),
+            Self::MissingField(key) => write!(f, "the front matter has no `{key}` field"),
+            Self::InvalidField { key, value } => {
+                let expected = match *key {
+                    "status" => "initial, open or skip",
+                    _ => "true or false",
+                };
+                write!(f, "`{key}: {value}` is not valid, expected {expected}")
+            }
+            Self::MissingSection(heading) => write!(f, "missing the `{heading}` section"),
+        }
+    }
+}
+
+/// A parsed progress file.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgressFile {
+    pub status: Option<ProgressStatus>,
+    /// `finite_incantatem: true` asks the runner to stop after this round.
+    pub finite_incantatem: bool,
+    pub workflow: Option<String>,
+    pub short_title: Option<String>,
+    pub git_commit: Option<String>,
+    pub git_branch: Option<String>,
+    /// Body of the `# Overall Goal` section, up to the next heading.
+    pub overall_goal: Option<String>,
+    pub in_progress: Vec<ProgressTask>,
+    pub todo: Vec<ProgressTask>,
+    pub done: Vec<ProgressTask>,
+    /// Problems found while parsing, in file order.
+    pub issues: Vec<ProgressFileIssue>,
+}
+
+impl ProgressFile {
+    pub fn read(path: &Path) -> anyhow::Result<Self> {
+        let contents =
+            std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+        Ok(Self::parse(&contents))
+    }
+
+    pub fn parse(contents: &str) -> Self {
+        let mut issues = Vec::new();
+        let front_matter = FrontMatter::parse(contents);
+        match &front_matter {
+            None => issues.push(ProgressFileIssue::MissingFrontMatter),
+            Some(front_matter) if !front_matter.closed => {
+                issues.push(ProgressFileIssue::UnclosedFrontMatter);
+            }
+            Some(_) => {}
+        }
+        // Without a front matter there are no fields to check.
+        let has_front_matter = front_matter.is_some();
+        let front_matter = front_matter.unwrap_or_default();
+
+        let status = front_matter.string("status");
+        if has_front_matter {
+            match &status {
+                Some(value) if ProgressStatus::parse(value).is_some() => {}
+                Some(value) => issues.push(ProgressFileIssue::InvalidField {
+                    key: "status",
+                    value: value.clone(),
+                }),
+                None => issues.push(ProgressFileIssue::MissingField("status")),
+            }
+            match front_matter.bool_token("finite_incantatem") {
+                Some(token) if parse_yaml_bool(token).is_some() => {}
+                Some(_) => issues.push(ProgressFileIssue::InvalidField {
+                    key: "finite_incantatem",
+                    value: front_matter.string("finite_incantatem").unwrap_or_default(),
+                }),
+                None => issues.push(ProgressFileIssue::MissingField("finite_incantatem")),
+            }
+        }
+
+        for heading in REQUIRED_SECTIONS {
+            if !has_markdown_section(contents, heading) {
+                issues.push(ProgressFileIssue::MissingSection(heading));
+            }
+        }
+
+        let overall_goal = has_markdown_section(contents, OVERALL_GOAL).then(|| {
+            markdown_section_lines(contents, OVERALL_GOAL)
+                .into_iter()
+                .take_while(|line| !ends_markdown_section("######", line))
+                .collect::<Vec<_>>()
+                .join("\n")
+                .trim()
+                .to_string()
+        });
+
+        Self {
+            status: status.as_deref().and_then(ProgressStatus::parse),
+            finite_incantatem: front_matter.bool("finite_incantatem").unwrap_or(false),
+            workflow: front_matter.non_empty_string("workflow"),
+            short_title: front_matter.non_empty_string("short_title"),
+            git_commit: front_matter.non_empty_string("git_commit"),
+            git_branch: front_matter.non_empty_string("git_branch"),
+            overall_goal,
+            in_progress: section_tasks(contents, IN_PROGRESS),
+            todo: section_tasks(contents, TODO),
+            done: section_tasks(contents, DONE),
+            issues,
+        }
+    }
+
+    /// Planning while `status` is `initial`, execution while open tasks are listed, review
+    /// otherwise.
+    pub fn phase(&self) -> crate::project::ProgressPhase {
+        use crate::project::ProgressPhase;
+
+        if self.status == Some(ProgressStatus::Initial) {
+            return ProgressPhase::Planning;
+        }
+        if self.task_counts().todo > 0 {
+            ProgressPhase::Execution
+        } else {
+            ProgressPhase::Review
+        }
+    }
+
+    /// What the TUI progress panel shows of this file.
+    pub fn panel(&self) -> PotterProgress {
+        let item = |task: &ProgressTask| PotterProgressItem {
+            text: task.text.clone(),
+            checked: task.checked,
+        };
+        PotterProgress {
+            title: self.short_title.clone().or_else(|| {
+                self.overall_goal
+                    .as_deref()
+                    .and_then(|goal| goal.lines().map(str::trim).find(|line| !line.is_empty()))
+                    .map(str::to_string)
+            }),
+            status: self.status.map(|status| status.as_str().to_string()),
+            in_progress: self.in_progress.iter().map(item).collect(),
+            todo: self.todo.iter().map(item).collect(),
+            done: self.done.iter().map(item).collect(),
+        }
+    }
+
+    pub fn task_counts(&self) -> TaskCounts {
+        let todo = self
+            .in_progress
+            .iter()
+            .chain(&self.todo)
+            .filter(|task| !task.checked)
+            .count();
+        TaskCounts {
+            todo,
+            done: self.done.len(),
+        }
+    }
+}
+
+/// The result of [`repair`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ProgressFileRepair {
+    pub contents: String,
+    /// Issues fixed in `contents`.
+    pub repaired: Vec<ProgressFileIssue>,
+    /// Issues left for the agent to fix.
+    pub unresolved: Vec<ProgressFileIssue>,
+}
+
+/// Fix the issues of a progress file that have an obvious fix:
+///
+/// - an unclosed front matter is closed before the first heading;
+/// - a missing `finite_incantatem` becomes `false`, and an invalid one is rewritten to the value it
+///   is read as (`false` unless its first word is `true`), so a repair never changes whether the
+///   round finished;
+/// - a missing `## Todo` or `## Done` section is appended empty.
+///
+/// A missing front matter, a missing or invalid `status` and a missing `# Overall Goal` are left
+/// alone: only the agent knows what they should say.
+pub fn repair(contents: &str) -> ProgressFileRepair {
+    let mut contents = contents.to_string();
+    let mut repaired = Vec::new();
+    // Every fix removes the issue it was made for, so this ends after at most one pass per issue.
+    while let Some((issue, fixed)) = ProgressFile::parse(&contents)
+        .issues
+        .into_iter()
+        .find_map(|issue| repair_issue(&contents, &issue).map(|fixed| (issue, fixed)))
+    {
+        contents = fixed;
+        repaired.push(issue);
+    }
+    let unresolved = ProgressFile::parse(&contents).issues;
+    ProgressFileRepair {
+        contents,
+        repaired,
+        unresolved,
+    }
+}
+
+fn repair_issue(contents: &str, issue: &ProgressFileIssue) -> Option<String> {
+    let fixed = match issue {
+        ProgressFileIssue::UnclosedFrontMatter => close_front_matter(contents),
+        ProgressFileIssue::MissingField("finite_incantatem")
+        | ProgressFileIssue::InvalidField {
+            key: "finite_incantatem",
+            ..
+        } => {
+            let value = ProgressFile::parse(contents).finite_incantatem;
+            set_front_matter_value(contents, "finite_incantatem", &value.to_string()).ok()?
+        }
+        ProgressFileIssue::MissingSection(heading) if *heading != OVERALL_GOAL => {
+            format!("{}\n\n{heading}\n", contents.trim_end())
+        }
+        _ => return None,
+    };
+    (fixed != contents).then_some(fixed)
+}
+
+/// Insert the closing `---` before the first Markdown heading, or at the end of the file.
+fn close_front_matter(contents: &str) -> String {
+    let mut out = String::new();
+    let mut closed = false;
+    for (idx, line) in contents.lines().enumerate() {
+        if !closed && idx > 0 && ends_markdown_section("######", line) {
+            out.truncate(out.trim_end().len());
+            out.push_str("\n---\n\n");
+            closed = true;
+        }
+        out.push_str(line);
+        out.push('\n');
+    }
+    if !closed {
+        out.truncate(out.trim_end().len());
+        out.push_str("\n---\n");
+    }
+    out
+}
+
+/// Issues of a progress file after [`check_progress_file`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ProgressFileCheck {
+    pub repaired: Vec<ProgressFileIssue>,
+    pub unresolved: Vec<ProgressFileIssue>,
+}
+
+impl ProgressFileCheck {
+    pub fn is_clean(&self) -> bool {
+        self.repaired.is_empty() && self.unresolved.is_empty()
+    }
+}
+
+/// Validate the progress file at `path`, writing back the [`repair`]ed contents when anything
+/// was fixed.
+pub fn check_progress_file(path: &Path) -> anyhow::Result<ProgressFileCheck> {
+    let contents =
+        std::fs::read_to_string(path).with_context(|| format!("read {}", path.display()))?;
+    let repair = repair(&contents);
+    if repair.contents != contents {
+        std::fs::write(path, &repair.contents)
+            .with_context(|| format!("write {}", path.display()))?;
+    }
+    Ok(ProgressFileCheck {
+        repaired: repair.repaired,
+        unresolved: repair.unresolved,
+    })
+}
+
+/// Notices changes to the progress file by polling its modification time and size, so the TUI
+/// progress panel can refresh between events.
+#[derive(Debug)]
+pub struct ProgressFileWatcher {
+    path: PathBuf,
+    last_seen: Option<(SystemTime, u64)>,
+}
+
+impl ProgressFileWatcher {
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            last_seen: None,
+        }
+    }
+
+    /// The panel contents of the progress file, when it changed since the last call.
+    pub fn poll(&mut self) -> Option<PotterProgress> {
+        let metadata = std::fs::metadata(&self.path).ok()?;
+        let seen = (metadata.modified().ok()?, metadata.len());
+        if self.last_seen == Some(seen) {
+            return None;
+        }
+        self.last_seen = Some(seen);
+        Some(ProgressFile::read(&self.path).ok()?.panel())
+    }
+}
+
+/// Prepend a note asking the agent to fix the progress file issues CodexPotter could not repair
+/// after the previous round.
+pub fn developer_prompt_with_issues(developer_prompt: &str, issues: &[String]) -> String {
+    if issues.is_empty() {
+        return developer_prompt.to_string();
+    }
+
+    let issues = issues
+        .iter()
+        .map(|issue| format!("- {issue}"))
+        .collect::<Vec<_>>()
+        .join("\n");
+    format!(
+        "<PROGRESS_FILE_INVALID>\n\nAfter the previous round the progress file has problems CodexPotter could not fix on its own:\n\n{issues}\n\nFix the progress file first: keep the YAML front matter between `---` lines with the `status` and `finite_incantatem` fields, and keep the `# Overall Goal`, `## Todo` and `## Done` sections.\n\n</PROGRESS_FILE_INVALID>\n\n{developer_prompt}"
+    )
+}
+
+/// Key/value lines of the YAML front matter, in file order.
+#[derive(Debug, Default)]
+struct FrontMatter<'a> {
+    fields: Vec<(&'a str, &'a str)>,
+    /// Whether a closing `---` was found; without one the whole file is scanned.
+    closed: bool,
+}
+
+impl<'a> FrontMatter<'a> {
+    fn parse(contents: &'a str) -> Option<Self> {
+        let mut lines = contents.lines();
+        if lines.next()?.trim_end() != "---" {
+            return None;
+        }
+
+        let mut fields = Vec::new();
+        for line in lines {
+            let trimmed = line.trim();
+            if trimmed == "---" {
+                return Some(Self {
+                    fields,
+                    closed: true,
+                });
+            }
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+            if let Some((key, value)) = trimmed.split_once(':') {
+                fields.push((key.trim(), value.trim()));
+            }
+        }
+        Some(Self {
+            fields,
+            closed: false,
+        })
+    }
+
+    /// The raw value of the first `key` line.
+    fn raw(&self, key: &str) -> Option<&'a str> {
+        self.fields
+            .iter()
+            .find_map(|(k, value)| (*k == key).then_some(*value))
+    }
+
+    /// The first word of the `key` value, unquoted.
+    fn bool_token(&self, key: &str) -> Option<&'a str> {
+        let first_token = self.raw(key)?.split_whitespace().next().unwrap_or_default();
+        Some(first_token.trim_matches(&['"', '\''][..]))
+    }
+
+    /// `true` only when the first word of the value is `true` (in any case, quoted or not).
+    fn bool(&self, key: &str) -> Option<bool> {
+        Some(parse_yaml_bool(self.bool_token(key)?).unwrap_or(false))
+    }
+
+    fn string(&self, key: &str) -> Option<String> {
+        let value = strip_yaml_inline_comment(self.raw(key)?).trim();
+        Some(unquote_yaml_scalar(value))
+    }
+
+    fn non_empty_string(&self, key: &str) -> Option<String> {
+        let value = self.string(key)?.trim().to_string();
+        (!value.is_empty()).then_some(value)
+    }
+}
+
+fn parse_yaml_bool(token: &str) -> Option<bool> {
+    if token.eq_ignore_ascii_case("true") {
+        Some(true)
+    } else if token.eq_ignore_ascii_case("false") {
+        Some(false)
+    } else {
+        None
+    }
+}
+
+fn strip_yaml_inline_comment(raw: &str) -> &str {
+    let mut in_single = false;
+    let mut in_double = false;
+    let mut prev_was_whitespace = true;
+
+    for (idx, ch) in raw.char_indices() {
+        match ch {
+            '\'' if !in_double => in_single = !in_single,
+            '"' if !in_single => in_double = !in_double,
+            '#' if !in_single && !in_double && prev_was_whitespace => return raw[..idx].trim_end(),
+            _ => {}
+        }
+
+        prev_was_whitespace = ch.is_whitespace();
+    }
+
+    raw.trim_end()
+}
+
+fn unquote_yaml_scalar(raw: &str) -> String {
+    let raw = raw.trim();
+    let bytes = raw.as_bytes();
+    if bytes.len() >= 2 && bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"' {
+        return raw[1..raw.len() - 1]
+            .replace("\\\\", "\\")
+            .replace("\\\"", "\"");
+    }
+    if bytes.len() >= 2 && bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\'' {
+        return raw[1..raw.len() - 1].replace("''", "'");
+    }
+    raw.to_string()
+}
+
+/// Set `key` in the YAML front matter, adding it before the closing `---` when missing.
+pub fn set_front_matter_value(contents: &str, key: &str, value: &str) -> anyhow::Result<String> {
+    let mut lines = contents.lines();
+    let first = lines
+        .next()
+        .map(str::trim_end)
+        .context("progress file is empty")?;
+    if first != "---" {
+        anyhow::bail!("progress file missing YAML front matter delimiter `---` at top");
+    }
+
+    let mut out = String::new();
+    out.push_str(first);
+    out.push('\n');
+
+    let mut in_front_matter = true;
+    let mut saw_footer = false;
+    let mut saw_key = false;
+    for line in lines {
+        if in_front_matter {
+            let trimmed = line.trim_end();
+            if trimmed == "---" {
+                in_front_matter = false;
+                saw_footer = true;
+                if !saw_key {
+                    out.push_str(&format!("{key}: {value}\n"));
+                }
+                out.push_str(trimmed);
+                out.push('\n');
+                continue;
+            }
+
+            let mut replaced = false;
+            if let Some((k, _)) = trimmed.split_once(':')
+                && k.trim() == key
+            {
+                let comment = trimmed.find('#').map(|idx| &trimmed[idx..]);
+                let key_part = &trimmed[..trimmed.find(':').expect("split_once") + 1];
+                out.push_str(key_part);
+                out.push(' ');
+                out.push_str(value);
+                if let Some(comment) = comment {
+                    out.push(' ');
+                    out.push_str(comment);
+                }
+                out.push('\n');
+                replaced = true;
+                saw_key = true;
+            }
+
+            if !replaced {
+                out.push_str(trimmed);
+                out.push('\n');
+            }
+            continue;
+        }
+
+        out.push_str(line);
+        out.push('\n');
+    }
+
+    if !saw_footer {
+        anyhow::bail!("progress file YAML front matter missing closing `---`");
+    }
+
+    Ok(out)
+}
+
+/// Whether `line` is a heading of `heading`'s level or higher, which ends `heading`'s section.
+fn ends_markdown_section(heading: &str, line: &str) -> bool {
+    let level = heading.chars().take_while(|ch| *ch == '#').count();
+    let hashes = line.chars().take_while(|ch| *ch == '#').count();
+    hashes > 0 && hashes <= level && line[hashes..].starts_with(' ')
+}
+
+fn has_markdown_section(contents: &str, heading: &str) -> bool {
+    contents.lines().any(|line| line.trim_end() == heading)
+}
+
+/// The body lines of the Markdown section starting at the `heading` line (empty when missing).
+fn markdown_section_lines<'a>(contents: &'a str, heading: &str) -> Vec<&'a str> {
+    contents
+        .lines()
+        .skip_while(|line| line.trim_end() != heading)
+        .skip(1)
+        .take_while(|line| !ends_markdown_section(heading, line))
+        .collect()
+}
+
+/// Replace the body of the Markdown section starting at the `heading` line (up to the next
+/// heading of the same or a higher level) with `body`.
+pub fn replace_markdown_section(
+    contents: &str,
+    heading: &str,
+    body: &str,
+) -> anyhow::Result<String> {
+    let is_boundary = |line: &str| ends_markdown_section(heading, line);
+
+    let mut out = String::new();
+    let mut lines = contents.lines();
+    let mut found = false;
+    for line in lines.by_ref() {
+        out.push_str(line);
+        out.push('\n');
+        if line.trim_end() == heading {
+            found = true;
+            break;
+        }
+    }
+    if !found {
+        anyhow::bail!("progress file has no `{heading}` section");
+    }
+
+    out.push('\n');
+    if !body.is_empty() {
+        out.push_str(body.trim_end());
+        out.push_str("\n\n");
+    }
+    let mut rest = lines.skip_while(|line| !is_boundary(line)).peekable();
+    if rest.peek().is_none() {
+        return Ok(out.trim_end().to_string() + "\n");
+    }
+    for line in rest {
+        out.push_str(line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+fn section_tasks(contents: &str, heading: &str) -> Vec<ProgressTask> {
+    markdown_section_lines(contents, heading)
+        .into_iter()
+        .filter_map(list_item)
+        .map(|item| {
+            let unchecked = item.strip_prefix("[ ]");
+            let checked = item
+                .strip_prefix("[x]")
+                .or_else(|| item.strip_prefix("[X]"));
+            ProgressTask {
+                text: checked.or(unchecked).unwrap_or(item).trim().to_string(),
+                checked: checked.is_some(),
+            }
+        })
+        .collect()
+}
+
+/// The text of a non-empty `-`/`*`/`+` or numbered Markdown list item.
+fn list_item(line: &str) -> Option<&str> {
+    let line = line.trim_start();
+    let item = ["- ", "* ", "+ "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+        .or_else(|| {
+            let digits = line.chars().take_while(char::is_ascii_digit).count();
+            line[digits..].strip_prefix(". ").filter(|_| digits > 0)
+        })?
+        .trim();
+    (!item.is_empty()).then_some(item)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::project::ProgressPhase;
+    use pretty_assertions::assert_eq;
+
+    const VALID: &str = "---\nstatus: open\nworkflow: implement\nfinite_incantatem: false # set to true to stop\nshort_title: Fix resume picker search\ngit_commit: \"abc123\"\ngit_branch: \"\"\n---\n\n# Overall Goal\n\nFix the search.\n\n## In Progress\n\n- Add tests\n\n## Todo\n\n- Add the parser\n- [x] Drop the old lexer\n\n## Done\n\n- Shipped the CLI\n1. Wrote docs\n\nNotes\n";
+
+    fn task(text: &str, checked: bool) -> ProgressTask {
+        ProgressTask {
+            text: text.to_string(),
+            checked,
+        }
+    }
+
+    #[test]
+    fn parse_reads_front_matter_and_sections() {
+        assert_eq!(
+            ProgressFile::parse(VALID),
+            ProgressFile {
+                status: Some(ProgressStatus::Open),
+                finite_incantatem: false,
+                workflow: Some("implement".to_string()),
+                short_title: Some("Fix resume picker search".to_string()),
+                git_commit: Some("abc123".to_string()),
+                git_branch: None,
+                overall_goal: Some("Fix the search.".to_string()),
+                in_progress: vec![task("Add tests", false)],
+                todo: vec![
+                    task("Add the parser", false),
+                    task("Drop the old lexer", true)
+                ],
+                done: vec![task("Shipped the CLI", false), task("Wrote docs", false)],
+                issues: Vec::new(),
+            }
+        );
+    }
+
+    #[test]
+    fn read_reads_git_commit_front_matter_key() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let workdir = temp.path();
+        let rel = PathBuf::from(".codexpotter/projects/2026/01/27/1/MAIN.md");
+        let abs = workdir.join(&rel);
+        std::fs::create_dir_all(abs.parent().expect("parent")).expect("mkdir");
+
+        std::fs::write(
+            &abs,
+            "---\nstatus: open\ngit_commit: \"abc123\"\n---\n\n# Goal\n\nHi\n",
+        )
+        .expect("write");
+
+        let progress = ProgressFile::read(&abs).expect("read progress file");
+        assert_eq!(progress.git_commit, Some("abc123".to_string()));
+    }
+
+    #[test]
+    fn read_reads_multi_word_short_title() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("MAIN.md");
+        std::fs::write(
+            &path,
+            r#"---
+status: open
+short_title: Fix resume picker search
+git_branch: main
+---
+
+# Overall Goal
+"#,
+        )
+        .expect("write progress file");
+
+        let progress = ProgressFile::read(&path).expect("read progress file");
+        assert_eq!(
+            progress.short_title,
+            Some("Fix resume picker search".to_string())
+        );
+        assert_eq!(progress.git_branch, Some("main".to_string()));
+    }
+
+    #[test]
+    fn panel_falls_back_to_the_overall_goal_for_the_title() {
+        let panel = ProgressFile::parse(VALID).panel();
+        assert_eq!(panel.title.as_deref(), Some("Fix resume picker search"));
+        assert_eq!(panel.status.as_deref(), Some("open"));
+        assert_eq!(
+            panel.todo,
+            vec![
+                PotterProgressItem {
+                    text: "Add the parser".to_string(),
+                    checked: false,
+                },
+                PotterProgressItem {
+                    text: "Drop the old lexer".to_string(),
+                    checked: true,
+                },
+            ]
+        );
+
+        let panel = ProgressFile::parse(
+            "---\nstatus: initial\nshort_title:\n---\n\n# Overall Goal\n\nAdd a parser\nfor the config.\n",
+        )
+        .panel();
+        assert_eq!(panel.title.as_deref(), Some("Add a parser"));
+        assert_eq!(panel.status.as_deref(), Some("initial"));
+    }
+
+    #[test]
+    fn watcher_reports_only_changes() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("MAIN.md");
+        let mut watcher = ProgressFileWatcher::new(path.clone());
+        assert_eq!(watcher.poll(), None);
+
+        std::fs::write(&path, VALID).expect("write progress file");
+        assert_eq!(watcher.poll(), Some(ProgressFile::parse(VALID).panel()));
+        assert_eq!(watcher.poll(), None);
+
+        let updated = VALID.replace("- Add tests\n", "");
+        std::fs::write(&path, &updated).expect("write progress file");
+        assert_eq!(
+            watcher.poll().map(|panel| panel.in_progress),
+            Some(Vec::new())
+        );
+    }
+
+    #[test]
+    fn phase_follows_status_and_open_tasks() {
+        let progress = |status: &str, in_progress: &str, todo: &str| {
+            format!(
+                "---\nstatus: {status}\nfinite_incantatem: false\n---\n\n# Overall Goal\n\nHi\n\n## In Progress\n\n{in_progress}\n## Todo\n\n{todo}\n## Done\n\n- Shipped\n"
+            )
+        };
+        let phase = |contents: String| ProgressFile::parse(&contents).phase();
+
+        assert_eq!(
+            phase(progress("initial", "", "- Add the parser\n")),
+            ProgressPhase::Planning
+        );
+        assert_eq!(
+            phase(progress("open", "", "- Add the parser\n")),
+            ProgressPhase::Execution
+        );
+        assert_eq!(
+            phase(progress("open", "1. Add tests\n", "")),
+            ProgressPhase::Execution
+        );
+        assert_eq!(
+            phase(progress("open", "", "- [x] Add the parser\n")),
+            ProgressPhase::Review
+        );
+        assert_eq!(phase(progress("open", "", "")), ProgressPhase::Review);
+    }
+
+    #[test]
+    fn task_counts_count_open_and_done_tasks() {
+        assert_eq!(
+            ProgressFile::parse(VALID).task_counts(),
+            TaskCounts { todo: 2, done: 2 }
+        );
+        assert_eq!(ProgressFile::parse("").task_counts(), TaskCounts::default());
+    }
+
+    #[test]
+    fn parse_reports_issues_in_file_order() {
+        let contents =
+            "---\nstatus: done\nfinite_incantatem: maybe\n---\n\n## Todo\n\n- Add the parser\n";
+        assert_eq!(
+            ProgressFile::parse(contents).issues,
+            vec![
+                ProgressFileIssue::InvalidField {
+                    key: "status",
+                    value: "done".to_string(),
+                },
+                ProgressFileIssue::InvalidField {
+                    key: "finite_incantatem",
+                    value: "maybe".to_string(),
+                },
+                ProgressFileIssue::MissingSection(OVERALL_GOAL),
+                ProgressFileIssue::MissingSection(DONE),
+            ]
+        );
+        assert_eq!(
+            ProgressFile::parse("# Overall Goal\n\nHi\n\n## Todo\n\n## Done\n").issues,
+            vec![ProgressFileIssue::MissingFrontMatter]
+        );
+    }
+
+    #[test]
+    fn finite_incantatem_accepts_any_case_and_quotes() {
+        let with_flag = |value: &str| {
+            VALID.replace(
+                "finite_incantatem: false # set to true to stop",
+                &format!("finite_incantatem: {value}"),
+            )
+        };
+        for (value, expected) in [
+            ("True", true),
+            ("TRUE", true),
+            ("\"true\"", true),
+            ("'true'", true),
+            ("False", false),
+        ] {
+            let contents = with_flag(value);
+            let progress = ProgressFile::parse(&contents);
+            assert_eq!(
+                (progress.finite_incantatem, progress.issues),
+                (expected, Vec::new()),
+                "finite_incantatem: {value}"
+            );
+            assert_eq!(
+                repair(&contents).contents,
+                contents,
+                "finite_incantatem: {value}"
+            );
+        }
+
+        // An invalid value is repaired to what it was read as, not to `false`.
+        let repaired = repair(&with_flag("True, all done"));
+        assert_eq!(
+            repaired.repaired,
+            vec![ProgressFileIssue::InvalidField {
+                key: "finite_incantatem",
+                value: "True, all done".to_string(),
+            }]
+        );
+        assert!(ProgressFile::parse(&repaired.contents).finite_incantatem);
+        assert!(repaired.contents.contains("\nfinite_incantatem: true\n"));
+    }
+
+    #[test]
+    fn repair_fixes_front_matter_and_sections_and_keeps_the_rest() {
+        let contents = "---\nstatus: open\n\n# Overall Goal\n\nHi\n\n## Todo\n\n- Add the parser\n";
+        assert_eq!(
+            repair(contents),
+            ProgressFileRepair {
+                contents: "---\nstatus: open\nfinite_incantatem: false\n---\n\n# Overall Goal\n\nHi\n\n## Todo\n\n- Add the parser\n\n## Done\n".to_string(),
+                repaired: vec![
+                    ProgressFileIssue::UnclosedFrontMatter,
+                    ProgressFileIssue::MissingField("finite_incantatem"),
+                    ProgressFileIssue::MissingSection(DONE),
+                ],
+                unresolved: Vec::new(),
+            }
+        );
+
+        let contents = "---\nstatus: done\nfinite_incantatem: false\n---\n\n## Todo\n\n## Done\n";
+        assert_eq!(
+            repair(contents),
+            ProgressFileRepair {
+                contents: contents.to_string(),
+                repaired: Vec::new(),
+                unresolved: vec![
+                    ProgressFileIssue::InvalidField {
+                        key: "status",
+                        value: "done".to_string(),
+                    },
+                    ProgressFileIssue::MissingSection(OVERALL_GOAL),
+                ],
+            }
+        );
+    }
+
+    #[test]
+    fn check_progress_file_writes_repairs_back() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("MAIN.md");
+        std::fs::write(
+            &path,
+            "---\nstatus: open\nfinite_incantatem: yes\n---\n\n# Overall Goal\n\nHi\n\n## Todo\n\n## Done\n",
+        )
+        .expect("write progress file");
+
+        let check = check_progress_file(&path).expect("check progress file");
+        assert_eq!(
+            check,
+            ProgressFileCheck {
+                repaired: vec![ProgressFileIssue::InvalidField {
+                    key: "finite_incantatem",
+                    value: "yes".to_string(),
+                }],
+                unresolved: Vec::new(),
+            }
+        );
+        assert!(
+            std::fs::read_to_string(&path)
+                .expect("read progress file")
+                .contains("finite_incantatem: false\n")
+        );
+        assert!(
+            check_progress_file(&path)
+                .expect("check progress file")
+                .is_clean()
+        );
+    }
+
+    #[test]
+    fn developer_prompt_with_issues_lists_them_before_the_prompt() {
+        assert_eq!(developer_prompt_with_issues("Work.", &[]), "Work.");
+
+        let prompt = developer_prompt_with_issues(
+            "Work.",
+            &[ProgressFileIssue::MissingSection(OVERALL_GOAL).to_string()],
+        );
+        assert!(prompt.starts_with("<PROGRESS_FILE_INVALID>"));
+        assert!(prompt.contains("- missing the `# Overall Goal` section\n"));
+        assert!(prompt.ends_with("</PROGRESS_FILE_INVALID>\n\nWork."));
+    }
+}
@@ -6,27 +6,23 @@ use anyhow::Context;
 use chrono::DateTime;
 use chrono::Local;
 
-const PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/prompts/project_main.md"
-));
-const DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
-    env!("CARGO_MANIFEST_DIR"),
-    "/prompts/developer_prompt.md"
-));
-const PROMPT_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/prompts/prompt.md"));
+use crate::progress_file::ProgressFile;
+use crate::progress_file::replace_markdown_section;
+use crate::progress_file::set_front_matter_value;
 
 #[derive(Debug, Clone)]
 pub struct ProjectInit {
     pub progress_file_rel: PathBuf,
     pub git_commit_start: String,
+    pub git_branch: String,
 }
 
+/// Create the next project directory and write its progress file from `project_main_template`.
 pub fn init_project(
     workdir: &Path,
     user_prompt: &str,
     now: DateTime<Local>,
+    project_main_template: &str,
 ) -> anyhow::Result<ProjectInit> {
     let (git_commit, git_branch) = resolve_git_metadata(workdir);
 
@@ -45,13 +41,15 @@ pub fn init_project(
         create_next_project_dir(&projects_root, &year, &month, &day)?;
 
     let main_md = project_dir.join("MAIN.md");
-    let main_md_contents = render_project_main(user_prompt, &git_commit, &git_branch);
+    let main_md_contents =
+        render_project_main(project_main_template, user_prompt, &git_commit, &git_branch);
     std::fs::write(&main_md, main_md_contents)
         .with_context(|| format!("write {}", main_md.display()))?;
 
     Ok(ProjectInit {
         progress_file_rel,
         git_commit_start: git_commit,
+        git_branch,
     })
 }
 
@@ -59,33 +57,26 @@ pub fn resolve_git_commit(workdir: &Path) -> String {
     git_stdout_trimmed(workdir, &["rev-parse", "HEAD"]).unwrap_or_default()
 }
 
-pub fn render_project_main(user_prompt: &str, git_commit: &str, git_branch: &str) -> String {
+pub fn render_project_main(
+    template: &str,
+    user_prompt: &str,
+    git_commit: &str,
+    git_branch: &str,
+) -> String {
     let git_commit = yaml_escape_double_quoted(git_commit);
     let git_branch = yaml_escape_double_quoted(git_branch);
 
-    PROJECT_MAIN_TEMPLATE
-        .replace("{{USER_PROMPT}}", user_prompt)
-        .replace("{{GIT_COMMIT}}", &git_commit)
-        .replace("{{GIT_BRANCH}}", &git_branch)
-}
-
-pub fn render_developer_prompt(progress_file_rel: &Path) -> String {
-    let progress_file_rel = progress_file_rel.to_string_lossy();
-    DEVELOPER_PROMPT_TEMPLATE.replace("{{PROGRESS_FILE}}", &progress_file_rel)
-}
-
-pub fn fixed_prompt() -> &'static str {
-    PROMPT_TEMPLATE
+    template
+        .replace(crate::prompt_templates::USER_PROMPT, user_prompt)
+        .replace(crate::prompt_templates::GIT_COMMIT, &git_commit)
+        .replace(crate::prompt_templates::GIT_BRANCH, &git_branch)
 }
 
 pub fn progress_file_has_finite_incantatem_true(
     workdir: &Path,
     progress_file_rel: &Path,
 ) -> anyhow::Result<bool> {
-    let progress_file = workdir.join(progress_file_rel);
-    let contents = std::fs::read_to_string(&progress_file)
-        .with_context(|| format!("read {}", progress_file.display()))?;
-    Ok(front_matter_bool(&contents, "finite_incantatem").unwrap_or(false))
+    Ok(ProgressFile::read(&workdir.join(progress_file_rel))?.finite_incantatem)
 }
 
 /// Set `finite_incantatem` in the progress file YAML front matter.
@@ -97,7 +88,11 @@ pub fn set_progress_file_finite_incantatem(
     let progress_file = workdir.join(progress_file_rel);
     let contents = std::fs::read_to_string(&progress_file)
         .with_context(|| format!("read {}", progress_file.display()))?;
-    let updated = set_front_matter_bool(&contents, "finite_incantatem", value)?;
+    let updated = set_front_matter_value(
+        &contents,
+        "finite_incantatem",
+        if value { "true" } else { "false" },
+    )?;
     if updated != contents {
         std::fs::write(&progress_file, updated)
             .with_context(|| format!("write {}", progress_file.display()))?;
@@ -105,35 +100,51 @@ pub fn set_progress_file_finite_incantatem(
     Ok(())
 }
 
-/// Return the `git_commit` value recorded in the progress file front matter.
-pub fn progress_file_git_commit_start(
+/// Write an approved plan (`--approve-plan`) to the progress file: `tasks` replace the `## Todo`
+/// section and `status` becomes `open`, so the next round starts executing instead of planning.
+pub fn set_progress_file_plan(
     workdir: &Path,
     progress_file_rel: &Path,
-) -> anyhow::Result<String> {
+    tasks: &[String],
+) -> anyhow::Result<()> {
     let progress_file = workdir.join(progress_file_rel);
     let contents = std::fs::read_to_string(&progress_file)
         .with_context(|| format!("read {}", progress_file.display()))?;
-    Ok(front_matter_string(&contents, "git_commit").unwrap_or_default())
+    let updated = set_front_matter_value(&contents, "status", "open")?;
+    let updated = replace_markdown_section(
+        &updated,
+        crate::progress_file::TODO,
+        &crate::plan_approval::format_plan_tasks(tasks),
+    )?;
+    if updated != contents {
+        std::fs::write(&progress_file, updated)
+            .with_context(|| format!("write {}", progress_file.display()))?;
+    }
+    Ok(())
 }
 
-/// Return the `short_title` value recorded in the progress file front matter.
-pub fn progress_file_short_title(progress_file: &Path) -> anyhow::Result<Option<String>> {
-    read_progress_file_front_matter_string(progress_file, "short_title")
+/// Where a project stands in its workflow, as read from the progress file before a round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ProgressPhase {
+    /// `status: initial`: the tasks are yet to be planned.
+    Planning,
+    /// Tasks are left in `## Todo` or `## In Progress`.
+    Execution,
+    /// No tasks are left; the round reviews the finished work.
+    Review,
 }
 
-/// Return the `git_branch` value recorded in the progress file front matter.
-pub fn progress_file_git_branch(progress_file: &Path) -> anyhow::Result<Option<String>> {
-    read_progress_file_front_matter_string(progress_file, "git_branch")
-}
+impl ProgressPhase {
+    pub const ALL: [ProgressPhase; 3] = [Self::Planning, Self::Execution, Self::Review];
 
-fn read_progress_file_front_matter_string(
-    progress_file: &Path,
-    key: &str,
-) -> anyhow::Result<Option<String>> {
-    let contents = std::fs::read_to_string(progress_file)
-        .with_context(|| format!("read {}", progress_file.display()))?;
-    let value = front_matter_string(&contents, key).map(|value| value.trim().to_string());
-    Ok(value.and_then(|value| if value.is_empty() { None } else { Some(value) }))
+    /// Name used in `config.toml` (`[codex.phases.<name>]`).
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Planning => "planning",
+            Self::Execution => "execution",
+            Self::Review => "review",
+        }
+    }
 }
 
 fn create_next_project_dir(
@@ -142,16 +153,22 @@ fn create_next_project_dir(
     month: &str,
     day: &str,
 ) -> anyhow::Result<(PathBuf, PathBuf)> {
+    let day_dir = projects_root.join(year).join(month).join(day);
+    std::fs::create_dir_all(&day_dir).with_context(|| format!("create {}", day_dir.display()))?;
+
     for idx in 1.. {
         let idx = idx.to_string();
-        let project_dir = projects_root.join(year).join(month).join(day).join(&idx);
-        if project_dir.exists() {
-            continue;
+        let project_dir = day_dir.join(&idx);
+        // `create_dir` fails on existing dirs, so concurrent sessions (`--parallel`) never claim
+        // the same index.
+        match std::fs::create_dir(&project_dir) {
+            Ok(()) => {}
+            Err(err) if err.kind() == std::io::ErrorKind::AlreadyExists => continue,
+            Err(err) => {
+                return Err(err).with_context(|| format!("create {}", project_dir.display()));
+            }
         }
 
-        std::fs::create_dir_all(&project_dir)
-            .with_context(|| format!("create {}", project_dir.display()))?;
-
         let progress_file_rel = PathBuf::from(".codexpotter")
             .join("projects")
             .join(year)
@@ -165,162 +182,6 @@ fn create_next_project_dir(
     unreachable!("project index overflow");
 }
 
-fn front_matter_bool(contents: &str, key: &str) -> Option<bool> {
-    let mut lines = contents.lines();
-    let first = lines.next()?.trim_end();
-    if first != "---" {
-        return None;
-    }
-
-    for line in lines {
-        let trimmed = line.trim();
-        if trimmed == "---" {
-            break;
-        }
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let Some((k, v)) = trimmed.split_once(':') else {
-            continue;
-        };
-        if k.trim() != key {
-            continue;
-        }
-
-        let raw = v.trim();
-        let first_token = raw.split_whitespace().next().unwrap_or_default();
-        let unquoted = first_token.trim_matches(&['"', '\''][..]);
-        return Some(unquoted.eq_ignore_ascii_case("true"));
-    }
-
-    None
-}
-
-fn front_matter_string(contents: &str, key: &str) -> Option<String> {
-    let mut lines = contents.lines();
-    let first = lines.next()?.trim_end();
-    if first != "---" {
-        return None;
-    }
-
-    for line in lines {
-        let trimmed = line.trim();
-        if trimmed == "---" {
-            break;
-        }
-        if trimmed.is_empty() || trimmed.starts_with('#') {
-            continue;
-        }
-        let Some((k, v)) = trimmed.split_once(':') else {
-            continue;
-        };
-        if k.trim() != key {
-            continue;
-        }
-
-        let raw = v.trim_start();
-        let value = strip_yaml_inline_comment(raw).trim();
-        return Some(unquote_yaml_scalar(value));
-    }
-
-    None
-}
-
-fn strip_yaml_inline_comment(raw: &str) -> &str {
-    let mut in_single = false;
-    let mut in_double = false;
-    let mut prev_was_whitespace = true;
-
-    for (idx, ch) in raw.char_indices() {
-        match ch {
-            '\'' if !in_double => in_single = !in_single,
-            '"' if !in_single => in_double = !in_double,
-            '#' if !in_single && !in_double && prev_was_whitespace => return raw[..idx].trim_end(),
-            _ => {}
-        }
-
-        prev_was_whitespace = ch.is_whitespace();
-    }
-
-    raw.trim_end()
-}
-
-fn unquote_yaml_scalar(raw: &str) -> String {
-    let raw = raw.trim();
-    let bytes = raw.as_bytes();
-    if bytes.len() >= 2 && bytes[0] == b'"' && bytes[bytes.len() - 1] == b'"' {
-        return raw[1..raw.len() - 1]
-            .replace("\\\\", "\\")
-            .replace("\\\"", "\"");
-    }
-    if bytes.len() >= 2 && bytes[0] == b'\'' && bytes[bytes.len() - 1] == b'\'' {
-        return raw[1..raw.len() - 1].replace("''", "'");
-    }
-    raw.to_string()
-}
-
-fn set_front_matter_bool(contents: &str, key: &str, value: bool) -> anyhow::Result<String> {
-    let mut lines = contents.lines();
-    let first = lines
-        .next()
-        .map(str::trim_end)
-        .context("progress file is empty")?;
-    if first != "---" {
-        anyhow::bail!("progress file missing YAML front matter delimiter `---` at top");
-    }
-
-    let mut out = String::new();
-    out.push_str(first);
-    out.push('\n');
-
-    let mut in_front_matter = true;
-    let mut saw_footer = false;
-    for line in lines {
-        if in_front_matter {
-            let trimmed = line.trim_end();
-            if trimmed == "---" {
-                in_front_matter = false;
-                saw_footer = true;
-                out.push_str(trimmed);
-                out.push('\n');
-                continue;
-            }
-
-            let mut replaced = false;
-            if let Some((k, _)) = trimmed.split_once(':')
-                && k.trim() == key
-            {
-                let comment = trimmed.find('#').map(|idx| &trimmed[idx..]);
-                let key_part = &trimmed[..trimmed.find(':').expect("split_once") + 1];
-                out.push_str(key_part);
-                out.push(' ');
-                out.push_str(if value { "true" } else { "false" });
-                if let Some(comment) = comment {
-                    out.push(' ');
-                    out.push_str(comment);
-                }
-                out.push('\n');
-                replaced = true;
-            }
-
-            if !replaced {
-                out.push_str(trimmed);
-                out.push('\n');
-            }
-            continue;
-        }
-
-        out.push_str(line);
-        out.push('\n');
-    }
-
-    if !saw_footer {
-        anyhow::bail!("progress file YAML front matter missing closing `---`");
-    }
-
-    Ok(out)
-}
-
 fn resolve_git_metadata(workdir: &Path) -> (String, String) {
     let git_commit = git_stdout_trimmed(workdir, &["rev-parse", "HEAD"]).unwrap_or_default();
     let git_branch =
@@ -355,6 +216,7 @@ fn yaml_escape_double_quoted(value: &str) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::prompt_templates::PromptTemplates;
     use chrono::TimeZone;
     use pretty_assertions::assert_eq;
     use std::process::Command;
@@ -367,7 +229,13 @@ mod tests {
             .single()
             .expect("timestamp");
 
-        let first = init_project(temp.path(), "do something", now).expect("init project");
+        let first = init_project(
+            temp.path(),
+            "do something",
+            now,
+            &PromptTemplates::default().project_main,
+        )
+        .expect("init project");
         assert_eq!(
             first.progress_file_rel,
             PathBuf::from(".codexpotter/projects/2026/01/27/1/MAIN.md")
@@ -385,7 +253,13 @@ mod tests {
         assert!(main.contains("git_commit: \"\""));
         assert!(main.contains("git_branch: \"\""));
 
-        let second = init_project(temp.path(), "do something else", now).expect("init project");
+        let second = init_project(
+            temp.path(),
+            "do something else",
+            now,
+            &PromptTemplates::default().project_main,
+        )
+        .expect("init project");
         assert_eq!(
             second.progress_file_rel,
             PathBuf::from(".codexpotter/projects/2026/01/27/2/MAIN.md")
@@ -393,9 +267,6 @@ mod tests {
 
         let second_main = temp.path().join(&second.progress_file_rel);
         assert!(second_main.exists());
-
-        let developer = render_developer_prompt(&second.progress_file_rel);
-        assert!(developer.contains(".codexpotter/projects/2026/01/27/2/MAIN.md"));
     }
 
     #[test]
@@ -420,7 +291,7 @@ mod tests {
     }
 
     #[test]
-    fn progress_file_git_commit_start_reads_front_matter_key() {
+    fn set_progress_file_plan_fills_todo_and_opens_status() {
         let temp = tempfile::tempdir().expect("tempdir");
         let workdir = temp.path();
         let rel = PathBuf::from(".codexpotter/projects/2026/01/27/1/MAIN.md");
@@ -429,36 +300,20 @@ mod tests {
 
         std::fs::write(
             &abs,
-            "---\nstatus: open\ngit_commit: \"abc123\"\n---\n\n# Goal\n\nHi\n",
+            "---\nstatus: initial\nfinite_incantatem: false\n---\n\n# Overall Goal\n\nHi\n\n## In Progress\n\n## Todo\n\n- stale\n\n## Done\n",
         )
         .expect("write");
 
-        let got = progress_file_git_commit_start(workdir, &rel).expect("read git_commit");
-        assert_eq!(got, "abc123");
-    }
-
-    #[test]
-    fn progress_file_short_title_reads_multi_word_front_matter_value() {
-        let temp = tempfile::tempdir().expect("tempdir");
-        let progress = temp.path().join("MAIN.md");
-        std::fs::write(
-            &progress,
-            r#"---
-status: open
-short_title: Fix resume picker search
-git_branch: main
----
-
-# Overall Goal
-"#,
+        set_progress_file_plan(
+            workdir,
+            &rel,
+            &["Add the parser".to_string(), "Add tests".to_string()],
         )
-        .expect("write progress file");
-
-        let short_title = progress_file_short_title(&progress).expect("read short_title");
-        assert_eq!(short_title, Some("Fix resume picker search".to_string()));
-
-        let git_branch = progress_file_git_branch(&progress).expect("read git_branch");
-        assert_eq!(git_branch, Some("main".to_string()));
+        .expect("set plan");
+        assert_eq!(
+            std::fs::read_to_string(&abs).expect("read updated"),
+            "---\nstatus: open\nfinite_incantatem: false\n---\n\n# Overall Goal\n\nHi\n\n## In Progress\n\n## Todo\n\n- Add the parser\n- Add tests\n\n## Done\n"
+        );
     }
 
     #[test]
@@ -536,7 +391,13 @@ git_branch: main
             .with_ymd_and_hms(2026, 1, 27, 12, 0, 0)
             .single()
             .expect("timestamp");
-        let init = init_project(workdir, "do something", now).expect("init project");
+        let init = init_project(
+            workdir,
+            "do something",
+            now,
+            &PromptTemplates::default().project_main,
+        )
+        .expect("init project");
 
         let main = std::fs::read_to_string(workdir.join(&init.progress_file_rel)).expect("read");
         assert!(main.contains(&format!("git_commit: \"{git_commit}\"")));
@@ -552,7 +413,13 @@ git_branch: main
                 .success()
         );
 
-        let detached = init_project(workdir, "do something else", now).expect("init detached");
+        let detached = init_project(
+            workdir,
+            "do something else",
+            now,
+            &PromptTemplates::default().project_main,
+        )
+        .expect("init detached");
         let main =
             std::fs::read_to_string(workdir.join(&detached.progress_file_rel)).expect("read");
         assert!(main.contains(&format!("git_commit: \"{git_commit}\"")));
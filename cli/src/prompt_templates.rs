@@ -0,0 +1,382 @@
+//! Prompt templates (`developer_prompt.md`, `project_main.md`, `prompt.md`).
+//!
+//! Each template is looked up in `<workdir>/.codexpotter/prompts/`, then
+//! `~/.codexpotter/prompts/`, and falls back to the copy built into the binary. Workflows other
+//! than `implement` look in a `<workflow>/` subdirectory of each location instead. Templates are
+//! validated at startup, so a broken override fails fast instead of misleading the agent
+//! mid-session.
+
+use std::io::ErrorKind;
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+
+use crate::progress_file::ProgressFile;
+use crate::workflow::Workflow;
+
+const PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/project_main.md"
+));
+const DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/developer_prompt.md"
+));
+const PROMPT_TEMPLATE: &str =
+    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/prompts/prompt.md"));
+const PLAN_PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/plan/project_main.md"
+));
+const PLAN_DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/plan/developer_prompt.md"
+));
+const REVIEW_PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/review/project_main.md"
+));
+const REVIEW_DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/review/developer_prompt.md"
+));
+const RESEARCH_PROJECT_MAIN_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/research/project_main.md"
+));
+const RESEARCH_DEVELOPER_PROMPT_TEMPLATE: &str = include_str!(concat!(
+    env!("CARGO_MANIFEST_DIR"),
+    "/prompts/workflows/research/developer_prompt.md"
+));
+
+/// The user's request (`project_main.md` only).
+pub const USER_PROMPT: &str = "{{USER_PROMPT}}";
+/// Path of the progress file the agent should keep updated.
+pub const PROGRESS_FILE: &str = "{{PROGRESS_FILE}}";
+/// 1-based number of the current round.
+pub const ROUND: &str = "{{ROUND}}";
+/// Round budget of the current session segment.
+pub const TOTAL_ROUNDS: &str = "{{TOTAL_ROUNDS}}";
+/// Rounds left after the current one.
+pub const REMAINING_ROUNDS: &str = "{{REMAINING_ROUNDS}}";
+/// Git commit the project started from.
+pub const GIT_COMMIT: &str = "{{GIT_COMMIT}}";
+/// Git branch the project started on.
+pub const GIT_BRANCH: &str = "{{GIT_BRANCH}}";
+
+const SESSION_PLACEHOLDERS: &[&str] = &[
+    PROGRESS_FILE,
+    ROUND,
+    TOTAL_ROUNDS,
+    REMAINING_ROUNDS,
+    GIT_COMMIT,
+    GIT_BRANCH,
+];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PromptTemplateKind {
+    DeveloperPrompt,
+    ProjectMain,
+    Prompt,
+}
+
+impl PromptTemplateKind {
+    fn file_name(self) -> &'static str {
+        match self {
+            PromptTemplateKind::DeveloperPrompt => "developer_prompt.md",
+            PromptTemplateKind::ProjectMain => "project_main.md",
+            PromptTemplateKind::Prompt => "prompt.md",
+        }
+    }
+
+    fn builtin(self, workflow: Workflow) -> &'static str {
+        match (self, workflow) {
+            (PromptTemplateKind::DeveloperPrompt, Workflow::Implement) => DEVELOPER_PROMPT_TEMPLATE,
+            (PromptTemplateKind::DeveloperPrompt, Workflow::Plan) => PLAN_DEVELOPER_PROMPT_TEMPLATE,
+            (PromptTemplateKind::DeveloperPrompt, Workflow::Review) => {
+                REVIEW_DEVELOPER_PROMPT_TEMPLATE
+            }
+            (PromptTemplateKind::DeveloperPrompt, Workflow::Research) => {
+                RESEARCH_DEVELOPER_PROMPT_TEMPLATE
+            }
+            (PromptTemplateKind::ProjectMain, Workflow::Implement) => PROJECT_MAIN_TEMPLATE,
+            (PromptTemplateKind::ProjectMain, Workflow::Plan) => PLAN_PROJECT_MAIN_TEMPLATE,
+            (PromptTemplateKind::ProjectMain, Workflow::Review) => REVIEW_PROJECT_MAIN_TEMPLATE,
+            (PromptTemplateKind::ProjectMain, Workflow::Research) => RESEARCH_PROJECT_MAIN_TEMPLATE,
+            (PromptTemplateKind::Prompt, _) => PROMPT_TEMPLATE,
+        }
+    }
+
+    fn allowed_placeholders(self) -> &'static [&'static str] {
+        match self {
+            PromptTemplateKind::DeveloperPrompt | PromptTemplateKind::Prompt => {
+                SESSION_PLACEHOLDERS
+            }
+            PromptTemplateKind::ProjectMain => &[USER_PROMPT, GIT_COMMIT, GIT_BRANCH],
+        }
+    }
+
+    fn required_placeholders(self) -> &'static [&'static str] {
+        match self {
+            PromptTemplateKind::DeveloperPrompt => &[PROGRESS_FILE],
+            PromptTemplateKind::ProjectMain => &[USER_PROMPT],
+            PromptTemplateKind::Prompt => &[],
+        }
+    }
+}
+
+/// The prompt templates used by every session of one `codex-potter` invocation.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PromptTemplates {
+    /// Developer instructions describing the workflow.
+    pub developer_prompt: String,
+    /// Initial contents of the progress file (`MAIN.md`).
+    pub project_main: String,
+    /// User message sent at the start of every round.
+    pub prompt: String,
+}
+
+impl Default for PromptTemplates {
+    fn default() -> Self {
+        Self::builtin(Workflow::Implement)
+    }
+}
+
+impl PromptTemplates {
+    /// The templates built into the binary for `workflow`.
+    pub fn builtin(workflow: Workflow) -> Self {
+        Self {
+            developer_prompt: PromptTemplateKind::DeveloperPrompt
+                .builtin(workflow)
+                .to_string(),
+            project_main: PromptTemplateKind::ProjectMain
+                .builtin(workflow)
+                .to_string(),
+            prompt: PromptTemplateKind::Prompt.builtin(workflow).to_string(),
+        }
+    }
+
+    /// Resolve every template of `workflow` through the lookup chain for `workdir` and validate
+    /// it.
+    pub fn load(workdir: &Path, workflow: Workflow) -> anyhow::Result<Self> {
+        let mut dirs = vec![workdir.join(".codexpotter").join("prompts")];
+        if let Some(home) = dirs::home_dir() {
+            dirs.push(home.join(".codexpotter").join("prompts"));
+        }
+        if workflow != Workflow::Implement {
+            for dir in &mut dirs {
+                dir.push(workflow.name());
+            }
+        }
+        Self::load_from(&dirs, workflow)
+    }
+
+    fn load_from(dirs: &[PathBuf], workflow: Workflow) -> anyhow::Result<Self> {
+        Ok(Self {
+            developer_prompt: load_template(dirs, PromptTemplateKind::DeveloperPrompt, workflow)?,
+            project_main: load_template(dirs, PromptTemplateKind::ProjectMain, workflow)?,
+            prompt: load_template(dirs, PromptTemplateKind::Prompt, workflow)?,
+        })
+    }
+}
+
+/// Values for the placeholders that stay fixed for a whole session.
+#[derive(Debug, Clone, Copy)]
+pub struct SessionPromptValues<'a> {
+    pub progress_file: &'a Path,
+    pub git_commit: &'a str,
+    pub git_branch: &'a str,
+}
+
+/// Substitute the session-level placeholders, leaving round placeholders for
+/// [`render_round_placeholders`].
+pub fn render_session_placeholders(template: &str, values: SessionPromptValues<'_>) -> String {
+    template
+        .replace(PROGRESS_FILE, &values.progress_file.to_string_lossy())
+        .replace(GIT_COMMIT, values.git_commit)
+        .replace(GIT_BRANCH, values.git_branch)
+}
+
+/// Substitute the round placeholders for round `round_current` of `round_total`.
+pub fn render_round_placeholders(prompt: &str, round_current: u32, round_total: u32) -> String {
+    prompt
+        .replace(ROUND, &round_current.to_string())
+        .replace(TOTAL_ROUNDS, &round_total.to_string())
+        .replace(
+            REMAINING_ROUNDS,
+            &round_total.saturating_sub(round_current).to_string(),
+        )
+}
+
+fn load_template(
+    dirs: &[PathBuf],
+    kind: PromptTemplateKind,
+    workflow: Workflow,
+) -> anyhow::Result<String> {
+    for dir in dirs {
+        let path = dir.join(kind.file_name());
+        match std::fs::read_to_string(&path) {
+            Ok(template) => {
+                validate_template(kind, &template)
+                    .with_context(|| format!("invalid prompt template {}", path.display()))?;
+                return Ok(template);
+            }
+            Err(err) if err.kind() == ErrorKind::NotFound => {}
+            Err(err) => return Err(err).with_context(|| format!("read {}", path.display())),
+        }
+    }
+    Ok(kind.builtin(workflow).to_string())
+}
+
+fn validate_template(kind: PromptTemplateKind, template: &str) -> anyhow::Result<()> {
+    if let Some(missing) = kind
+        .required_placeholders()
+        .iter()
+        .find(|placeholder| !template.contains(**placeholder))
+    {
+        anyhow::bail!("missing required placeholder {missing}");
+    }
+
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let Some(len) = rest[start..].find("}}") else {
+            break;
+        };
+        let placeholder = &rest[start..start + len + 2];
+        if !kind.allowed_placeholders().contains(&placeholder) {
+            anyhow::bail!(
+                "unknown placeholder {placeholder} (supported: {})",
+                kind.allowed_placeholders().join(", ")
+            );
+        }
+        rest = &rest[start + len + 2..];
+    }
+
+    // The runner reads the new progress file like any other, so it must start out valid.
+    if kind == PromptTemplateKind::ProjectMain {
+        let issues = ProgressFile::parse(template).issues;
+        if !issues.is_empty() {
+            anyhow::bail!(
+                "not a valid progress file: {}",
+                issues
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join("; ")
+            );
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn builtin_templates_are_valid() {
+        for workflow in [
+            Workflow::Plan,
+            Workflow::Implement,
+            Workflow::Review,
+            Workflow::Research,
+        ] {
+            for kind in [
+                PromptTemplateKind::DeveloperPrompt,
+                PromptTemplateKind::ProjectMain,
+                PromptTemplateKind::Prompt,
+            ] {
+                validate_template(kind, kind.builtin(workflow)).expect("builtin template");
+            }
+            let project_main = PromptTemplates::builtin(workflow).project_main;
+            assert!(project_main.contains(&format!("\nworkflow: {}\n", workflow.name())));
+        }
+    }
+
+    #[test]
+    fn load_from_prefers_earlier_directories_and_falls_back_to_builtin() {
+        let repo = tempfile::tempdir().expect("tempdir");
+        let home = tempfile::tempdir().expect("tempdir");
+        std::fs::write(repo.path().join("prompt.md"), "Repo round {{ROUND}}").expect("write");
+        std::fs::write(home.path().join("prompt.md"), "Home prompt").expect("write");
+        std::fs::write(
+            home.path().join("developer_prompt.md"),
+            "Home workflow for {{PROGRESS_FILE}}",
+        )
+        .expect("write");
+
+        let templates = PromptTemplates::load_from(
+            &[repo.path().to_path_buf(), home.path().to_path_buf()],
+            Workflow::Implement,
+        )
+        .expect("load templates");
+        assert_eq!(
+            templates,
+            PromptTemplates {
+                developer_prompt: "Home workflow for {{PROGRESS_FILE}}".to_string(),
+                project_main: PROJECT_MAIN_TEMPLATE.to_string(),
+                prompt: "Repo round {{ROUND}}".to_string(),
+            }
+        );
+    }
+
+    #[test]
+    fn load_from_rejects_missing_and_unknown_placeholders() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("developer_prompt.md");
+
+        std::fs::write(&path, "No progress file here").expect("write");
+        let err = PromptTemplates::load_from(&[dir.path().to_path_buf()], Workflow::Review)
+            .expect_err("missing");
+        assert!(format!("{err:#}").contains("missing required placeholder {{PROGRESS_FILE}}"));
+
+        std::fs::write(&path, "{{PROGRESS_FILE}} {{USER_PROMPT}}").expect("write");
+        let err = PromptTemplates::load_from(&[dir.path().to_path_buf()], Workflow::Review)
+            .expect_err("unknown");
+        assert!(format!("{err:#}").contains("unknown placeholder {{USER_PROMPT}}"));
+    }
+
+    #[test]
+    fn load_from_rejects_project_main_that_is_not_a_progress_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("project_main.md");
+
+        std::fs::write(
+            &path,
+            "---\nstatus: initial\n---\n\n# Overall Goal\n\n{{USER_PROMPT}}\n\n## Todo\n",
+        )
+        .expect("write");
+        let err = PromptTemplates::load_from(&[dir.path().to_path_buf()], Workflow::Implement)
+            .expect_err("invalid progress file");
+        assert!(format!("{err:#}").contains(
+            "not a valid progress file: the front matter has no `finite_incantatem` field; missing the `## Done` section"
+        ));
+
+        std::fs::write(
+            &path,
+            "---\nstatus: initial\nfinite_incantatem: false\n---\n\n# Overall Goal\n\n{{USER_PROMPT}}\n\n## Todo\n\n## Done\n\n## Notes\n",
+        )
+        .expect("write");
+        PromptTemplates::load_from(&[dir.path().to_path_buf()], Workflow::Implement)
+            .expect("valid progress file");
+    }
+
+    #[test]
+    fn render_placeholders_substitutes_session_then_round_values() {
+        let template = "{{PROGRESS_FILE}} @ {{GIT_BRANCH}} ({{GIT_COMMIT}}): round {{ROUND}}/{{TOTAL_ROUNDS}}, {{REMAINING_ROUNDS}} left";
+        let session = render_session_placeholders(
+            template,
+            SessionPromptValues {
+                progress_file: Path::new(".codexpotter/projects/2026/01/27/2/MAIN.md"),
+                git_commit: "abc123",
+                git_branch: "main",
+            },
+        );
+        assert_eq!(
+            render_round_placeholders(&session, 3, 10),
+            ".codexpotter/projects/2026/01/27/2/MAIN.md @ main (abc123): round 3/10, 7 left"
+        );
+    }
+}
@@ -2,7 +2,6 @@ use std::collections::HashMap;
 use std::collections::HashSet;
 use std::ffi::OsStr;
 use std::io::BufRead as _;
-use std::num::NonZeroUsize;
 use std::path::Path;
 use std::path::PathBuf;
 use std::time::Instant;
@@ -13,6 +12,8 @@ use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::PotterRoundOutcome;
 use codex_protocol::protocol::SessionConfiguredEvent;
 use codex_tui::ExitReason;
+
+use crate::round_runner::PotterSessionOutcome;
 use tokio::sync::mpsc::unbounded_channel;
 
 const PROJECT_MAIN_FILE: &str = "MAIN.md";
@@ -88,6 +89,28 @@ pub fn resolve_project_paths(
     })
 }
 
+/// UI operations `resume` needs on top of rendering rounds.
+pub trait PotterResumeUi: crate::round_runner::PotterRoundUi {
+    fn clear(&mut self) -> anyhow::Result<()>;
+
+    /// Let the user pick one of `actions`; `None` means the user cancelled.
+    async fn prompt_action_picker(&mut self, actions: Vec<String>)
+    -> anyhow::Result<Option<usize>>;
+}
+
+impl PotterResumeUi for codex_tui::CodexPotterTui {
+    fn clear(&mut self) -> anyhow::Result<()> {
+        codex_tui::CodexPotterTui::clear(self)
+    }
+
+    async fn prompt_action_picker(
+        &mut self,
+        actions: Vec<String>,
+    ) -> anyhow::Result<Option<usize>> {
+        codex_tui::CodexPotterTui::prompt_action_picker(self, actions).await
+    }
+}
+
 /// Replay a CodexPotter project directory and optionally continue iterating rounds.
 ///
 /// Replay is history-only: it never re-runs tools or executes commands. After rendering replay,
@@ -96,15 +119,16 @@ pub fn resolve_project_paths(
 /// When the last recorded round is unfinished (EOF without `PotterRoundFinished`), `resume` first
 /// renders the session + round boundary markers before showing the action picker, so the user
 /// always sees the initial prompt and round context first.
+///
+/// `settings.rounds` is the number of rounds to iterate when the last recorded round is complete;
+/// the workdir and turn prompt are derived from the resolved project instead.
 pub async fn run_resume(
-    ui: &mut codex_tui::CodexPotterTui,
+    ui: &mut impl PotterResumeUi,
     cwd: &Path,
     project_path: &Path,
-    codex_bin: String,
-    backend_launch: crate::app_server_backend::AppServerLaunchConfig,
-    codex_compat_home: Option<PathBuf>,
-    iterate_rounds: NonZeroUsize,
+    settings: &crate::round_runner::PotterSessionSettings,
 ) -> anyhow::Result<ResumeExit> {
+    let iterate_rounds = settings.rounds;
     let resolved = resolve_project_paths(cwd, project_path)?;
     std::env::set_current_dir(&resolved.workdir)
         .with_context(|| format!("set current directory to {}", resolved.workdir.display()))?;
@@ -159,13 +183,13 @@ pub async fn run_resume(
                 break;
             }
             ReplayRoundExitDecision::FatalExitRequested => {
-                return Ok(ResumeExit::FatalExitRequested);
+                return Ok(ResumeExit::Session(PotterSessionOutcome::Fatal));
             }
         }
     }
 
     if user_cancelled_replay {
-        return Ok(ResumeExit::Completed);
+        return Ok(ResumeExit::Cancelled);
     }
 
     if let Some(unfinished) = unfinished_round.as_mut() {
@@ -194,11 +218,59 @@ pub async fn run_resume(
 
         match exit_info.exit_reason {
             ExitReason::Completed | ExitReason::TaskFailed(_) => {}
-            ExitReason::UserRequested => return Ok(ResumeExit::Completed),
-            ExitReason::Fatal(_) => return Ok(ResumeExit::FatalExitRequested),
+            ExitReason::UserRequested => return Ok(ResumeExit::Cancelled),
+            ExitReason::Fatal(_) => return Ok(ResumeExit::Session(PotterSessionOutcome::Fatal)),
         }
     }
 
+    // Budgets span every run of the project, so an exhausted budget stops before offering more
+    // rounds.
+    let recorded_usage = crate::budget::usage_from_lines(&potter_rollout_lines);
+    if let Some(reason) = settings.budget.exhausted(recorded_usage) {
+        let (event_tx, event_rx) = unbounded_channel::<Event>();
+        for msg in [
+            EventMsg::PotterBudgetExhausted { reason },
+            EventMsg::PotterRoundFinished {
+                outcome: PotterRoundOutcome::Completed,
+            },
+        ] {
+            let _ = event_tx.send(Event {
+                id: "".to_string(),
+                msg,
+            });
+        }
+        drop(event_tx);
+
+        let (_fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
+        ui.render_turn(String::new(), true, op_tx.clone(), event_rx, fatal_exit_rx)
+            .await?;
+        return Ok(ResumeExit::Session(PotterSessionOutcome::BudgetExhausted));
+    }
+
+    // `--approve-plan` projects must not run write-capable rounds before a plan is approved. A
+    // continued unfinished round proposes a fresh plan, so only ask for a pending decision here.
+    let mut plan_state = crate::plan_approval::PlanState::from_lines(&potter_rollout_lines);
+    if unfinished_round.is_none()
+        && let crate::plan_approval::PlanState::AwaitingDecision(tasks) = &plan_state
+    {
+        let review = ui.review_plan(tasks.clone()).await?;
+        crate::plan_approval::record_plan_review(
+            &potter_rollout_path,
+            &resolved.workdir,
+            &progress_file_rel,
+            &review,
+        )?;
+        if !review.decision.is_approved() {
+            return Ok(ResumeExit::Session(PotterSessionOutcome::UserCancelled));
+        }
+        plan_state = crate::plan_approval::PlanState::Approved;
+    }
+    let needs_plan = matches!(
+        plan_state,
+        crate::plan_approval::PlanState::NeedsPlan
+            | crate::plan_approval::PlanState::AwaitingDecision(_)
+    );
+
     let iterate_rounds_usize = iterate_rounds.get();
     let action = match &unfinished_round {
         Some(unfinished) => {
@@ -225,12 +297,15 @@ pub async fn run_resume(
 
     let selection = ui.prompt_action_picker(vec![action]).await?;
     let Some(index) = selection else {
-        return Ok(ResumeExit::Completed);
+        return Ok(ResumeExit::Cancelled);
     };
     if index != 0 {
-        return Ok(ResumeExit::Completed);
+        return Ok(ResumeExit::Cancelled);
     }
 
+    // Repair the progress file before touching it; what is left goes to the next round.
+    let progress_file_check = crate::progress_file::check_progress_file(&resolved.progress_file)
+        .context("check progress file")?;
     crate::project::set_progress_file_finite_incantatem(
         &resolved.workdir,
         &progress_file_rel,
@@ -241,18 +316,78 @@ pub async fn run_resume(
     let baseline_rounds = count_completed_rounds(&potter_rollout_lines);
     let baseline_rounds_u32 = u32::try_from(baseline_rounds).unwrap_or(u32::MAX);
 
-    let developer_prompt = crate::project::render_developer_prompt(&progress_file_rel);
-    let turn_prompt = crate::project::fixed_prompt().trim_end().to_string();
-    let git_commit_start =
-        crate::project::progress_file_git_commit_start(&resolved.workdir, &progress_file_rel)
-            .context("read git_commit from progress file")?;
+    // Sessions started with `--parallel` keep running in their own git worktree.
+    let session_worktree = potter_rollout_lines
+        .iter()
+        .find_map(|line| match line {
+            crate::potter_rollout::PotterRolloutLine::SessionStarted { worktree, .. } => {
+                worktree.clone()
+            }
+            _ => None,
+        })
+        .filter(|worktree| worktree.is_dir());
+    let progress = crate::progress_file::ProgressFile::read(&resolved.progress_file)
+        .context("read progress file")?;
+    let git_commit_start = progress.git_commit.unwrap_or_default();
+    let git_branch = progress.git_branch.unwrap_or_default();
+    let workflow = crate::workflow::Workflow::from_front_matter(progress.workflow.as_deref())?;
+    let prompt_templates = if workflow == settings.workflow {
+        settings.prompt_templates.clone()
+    } else {
+        crate::prompt_templates::PromptTemplates::load(&resolved.workdir, workflow)?
+    };
+    let progress_file = match &session_worktree {
+        Some(_) => resolved.progress_file.clone(),
+        None => progress_file_rel.clone(),
+    };
+    let prompt_values = crate::prompt_templates::SessionPromptValues {
+        progress_file: &progress_file,
+        git_commit: &git_commit_start,
+        git_branch: &git_branch,
+    };
+    let developer_prompt = crate::prompt_templates::render_session_placeholders(
+        &prompt_templates.developer_prompt,
+        prompt_values,
+    );
+    let turn_prompt = crate::prompt_templates::render_session_placeholders(
+        &prompt_templates.prompt,
+        prompt_values,
+    )
+    .trim_end()
+    .to_string();
+
+    // The worktree is the sandbox's workspace; the progress file and git metadata are not in it.
+    let workspace_write = match &session_worktree {
+        Some(worktree) => settings.workspace_write.clone().with_writable_roots(
+            crate::worktree::session_writable_roots(
+                &resolved.workdir,
+                &resolved.project_dir,
+                worktree,
+            )
+            .context("resolve worktree writable roots")?,
+        ),
+        None => settings.workspace_write.clone(),
+    };
+    let thread_cwd = session_worktree.unwrap_or_else(|| resolved.workdir.clone());
+    // A resumed session has not stalled yet: count from the project as it is now.
+    let stall = match settings.max_stalled_rounds {
+        Some(_) => crate::stall::StallState::new(crate::stall::ProgressFingerprint::capture(
+            &thread_cwd,
+            &resolved.workdir.join(&progress_file_rel),
+        )),
+        None => crate::stall::StallState::default(),
+    };
 
-    let round_context = crate::round_runner::PotterRoundContext {
-        codex_bin,
+    let mut round_context = crate::round_runner::PotterRoundContext {
+        codex_bin: settings.codex_bin.clone(),
         developer_prompt,
-        backend_launch,
-        codex_compat_home,
-        thread_cwd: Some(resolved.workdir.clone()),
+        backend_launch: settings
+            .backend_launch
+            .with_default_sandbox(workflow.default_sandbox()),
+        codex_compat_home: settings.codex_compat_home.clone(),
+        repo_baseline: workflow.repo_baseline(&thread_cwd),
+        workflow,
+        thread_cwd: Some(thread_cwd),
         turn_prompt,
         workdir: resolved.workdir.clone(),
         progress_file_rel: progress_file_rel.clone(),
@@ -260,6 +395,39 @@ pub async fn run_resume(
         git_commit_start,
         potter_rollout_path,
         project_started_at: Instant::now(),
+        event_stream: settings.event_stream.as_ref().map(|stream| {
+            let project_dir_rel = resolved
+                .project_dir
+                .strip_prefix(&resolved.workdir)
+                .unwrap_or(&resolved.project_dir);
+            stream.for_session(
+                &crate::derive_resume_project_path_from_project_dir(project_dir_rel)
+                    .unwrap_or_else(|| project_dir_rel.to_string_lossy().to_string()),
+            )
+        }),
+        verify_command: settings.verify_command.clone(),
+        verify_timeout: settings.verify_timeout,
+        failed_verification: last_failed_verification(&potter_rollout_lines),
+        budget: settings.budget,
+        approval_policy: settings.approval_policy.clone(),
+        user_input_policy: settings.user_input_policy.clone(),
+        command_guard: settings.command_guard.clone(),
+        blocked_command: last_blocked_command(&potter_rollout_lines),
+        progress_file_issues: progress_file_check
+            .unresolved
+            .iter()
+            .map(ToString::to_string)
+            .collect(),
+        codex_overrides: settings.codex_overrides.clone(),
+        workspace_write,
+        app_server: settings
+            .reuse_app_server
+            .then(crate::app_server_backend::SharedAppServer::default),
+        context_checkpoint: settings.context_checkpoint,
+        max_stalled_rounds: settings.max_stalled_rounds,
+        stall,
+        timeouts: settings.timeouts,
+        on_timeout: settings.on_timeout,
     };
 
     match unfinished_round {
@@ -296,18 +464,19 @@ pub async fn run_resume(
                     session_succeeded_rounds: baseline_rounds_u32.saturating_add(1),
                     resume_thread_id: unfinished.thread_id,
                     replay_event_msgs,
+                    planning_round: needs_plan,
                 },
             )
             .await?;
 
-            match &round_result.exit_reason {
-                ExitReason::UserRequested => return Ok(ResumeExit::Completed),
-                ExitReason::TaskFailed(_) => return Ok(ResumeExit::Completed),
-                ExitReason::Fatal(_) => return Ok(ResumeExit::FatalExitRequested),
-                ExitReason::Completed => {}
+            if let Some(outcome) = round_result.session_outcome() {
+                return Ok(ResumeExit::Session(outcome));
             }
-            if round_result.stop_due_to_finite_incantatem {
-                return Ok(ResumeExit::Completed);
+            round_context.failed_verification = round_result.failed_verification();
+            round_context.blocked_command = round_result.blocked_command;
+            round_context.progress_file_issues = round_result.progress_file_issues;
+            if let Some(stall) = round_result.stall {
+                round_context.stall = stall;
             }
 
             for offset in 0..remaining_after_continue {
@@ -325,18 +494,19 @@ pub async fn run_resume(
                         round_current: current_round,
                         round_total: unfinished.round_total,
                         session_succeeded_rounds,
+                        planning_round: false,
                     },
                 )
                 .await?;
 
-                match &round_result.exit_reason {
-                    ExitReason::UserRequested => break,
-                    ExitReason::TaskFailed(_) => break,
-                    ExitReason::Fatal(_) => return Ok(ResumeExit::FatalExitRequested),
-                    ExitReason::Completed => {}
+                if let Some(outcome) = round_result.session_outcome() {
+                    return Ok(ResumeExit::Session(outcome));
                 }
-                if round_result.stop_due_to_finite_incantatem {
-                    break;
+                round_context.failed_verification = round_result.failed_verification();
+                round_context.blocked_command = round_result.blocked_command;
+                round_context.progress_file_issues = round_result.progress_file_issues;
+                if let Some(stall) = round_result.stall {
+                    round_context.stall = stall;
                 }
             }
         }
@@ -354,31 +524,45 @@ pub async fn run_resume(
                         round_current: current_round,
                         round_total: iterate_rounds_u32,
                         session_succeeded_rounds,
+                        planning_round: needs_plan && offset == 0,
                     },
                 )
                 .await?;
 
-                match &round_result.exit_reason {
-                    ExitReason::UserRequested => break,
-                    ExitReason::TaskFailed(_) => break,
-                    ExitReason::Fatal(_) => return Ok(ResumeExit::FatalExitRequested),
-                    ExitReason::Completed => {}
+                if let Some(outcome) = round_result.session_outcome() {
+                    return Ok(ResumeExit::Session(outcome));
                 }
-                if round_result.stop_due_to_finite_incantatem {
-                    break;
+                round_context.failed_verification = round_result.failed_verification();
+                round_context.blocked_command = round_result.blocked_command;
+                round_context.progress_file_issues = round_result.progress_file_issues;
+                if let Some(stall) = round_result.stall {
+                    round_context.stall = stall;
                 }
             }
         }
     }
 
-    Ok(ResumeExit::Completed)
+    Ok(ResumeExit::Session(PotterSessionOutcome::BudgetExhausted))
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 /// Outcome of running `codex-potter resume`.
 pub enum ResumeExit {
-    Completed,
-    FatalExitRequested,
+    /// No new round ran: the user cancelled replay or declined the follow-up action.
+    Cancelled,
+    /// Replay or the resumed rounds ended the session with this outcome.
+    Session(crate::round_runner::PotterSessionOutcome),
+}
+
+impl ResumeExit {
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ResumeExit::Cancelled => {
+                crate::round_runner::PotterSessionOutcome::UserCancelled.exit_code()
+            }
+            ResumeExit::Session(outcome) => outcome.exit_code(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -424,7 +608,9 @@ fn replay_round_exit_decision(
             _ => ReplayRoundExitDecision::FatalExitRequested,
         },
         ExitReason::UserRequested => match outcome {
-            PotterRoundOutcome::UserRequested => ReplayRoundExitDecision::Continue,
+            PotterRoundOutcome::UserRequested | PotterRoundOutcome::InterruptedEndSession => {
+                ReplayRoundExitDecision::Continue
+            }
             _ => ReplayRoundExitDecision::UserCancelled,
         },
     }
@@ -522,16 +708,79 @@ impl UnfinishedRoundPlan {
     }
 }
 
+/// Rounds that count against `--rounds`.
+///
+/// A round that ran out of context window is started again in a fresh thread under the same round
+/// number, so its `ContextExhausted` attempts only count once the runner gave up on fresh threads,
+/// as they did while the session ran (`MAX_FRESH_ROUNDS_AFTER_CONTEXT_EXHAUSTED`). A timed-out
+/// attempt never counts: it was either retried under the same round number or ended the session
+/// without finishing the round.
 fn count_completed_rounds(lines: &[crate::potter_rollout::PotterRolloutLine]) -> usize {
+    let mut rounds = 0;
+    let mut fresh_rounds = 0;
+    for line in lines {
+        let crate::potter_rollout::PotterRolloutLine::RoundFinished { outcome } = line else {
+            continue;
+        };
+        match outcome {
+            PotterRoundOutcome::ContextExhausted
+                if fresh_rounds < crate::round_runner::MAX_FRESH_ROUNDS_AFTER_CONTEXT_EXHAUSTED =>
+            {
+                fresh_rounds += 1;
+            }
+            PotterRoundOutcome::TimedOut { .. } => {}
+            _ => {
+                fresh_rounds = 0;
+                rounds += 1;
+            }
+        }
+    }
+    rounds
+}
+
+/// The most recent verification, if it failed, so the next round can address its output.
+fn last_failed_verification(
+    lines: &[crate::potter_rollout::PotterRolloutLine],
+) -> Option<crate::verify::VerificationResult> {
     lines
         .iter()
-        .filter(|line| {
-            matches!(
-                line,
-                crate::potter_rollout::PotterRolloutLine::RoundFinished { .. }
-            )
+        .rev()
+        .find_map(|line| match line {
+            crate::potter_rollout::PotterRolloutLine::RoundVerified {
+                command,
+                success,
+                exit_code,
+                duration_secs,
+                output_tail,
+            } => Some(crate::verify::VerificationResult {
+                command: command.clone(),
+                success: *success,
+                exit_code: *exit_code,
+                duration: std::time::Duration::from_secs(*duration_secs),
+                output_tail: output_tail.clone(),
+            }),
+            _ => None,
         })
-        .count()
+        .filter(|verification| !verification.success)
+}
+
+/// The command the guard stopped in the last finished round, so the next round is told about it.
+fn last_blocked_command(
+    lines: &[crate::potter_rollout::PotterRolloutLine],
+) -> Option<crate::command_guard::BlockedCommand> {
+    let outcome = lines.iter().rev().find_map(|line| match line {
+        crate::potter_rollout::PotterRolloutLine::RoundFinished { outcome } => Some(outcome),
+        _ => None,
+    })?;
+    match outcome {
+        PotterRoundOutcome::CommandBlocked { command, rule } => {
+            Some(crate::command_guard::BlockedCommand {
+                command: command.clone(),
+                rule: rule.clone(),
+            })
+        }
+        _ => None,
+    }
 }
 
 fn build_round_replay_plans(
@@ -542,6 +791,7 @@ fn build_round_replay_plans(
 
     let mut session_started = Some(index.session_started);
     let mut rounds = Vec::new();
+    let mut round_token_usage = Vec::new();
 
     for round in index.completed_rounds {
         let mut events = Vec::new();
@@ -573,6 +823,25 @@ fn build_round_replay_plans(
             .with_context(|| format!("replay rollout {}", rollout_path.display()))?;
         events.append(&mut rollout_events);
 
+        if let Some(verification) = round.verification {
+            events.push(EventMsg::PotterRoundVerified {
+                command: verification.command,
+                success: verification.success,
+                exit_code: verification.exit_code,
+                duration: std::time::Duration::from_secs(verification.duration_secs),
+                output_tail: verification.output_tail,
+            });
+        }
+
+        if let Some(usage) = round.usage {
+            round_token_usage.push(usage.token_usage.clone());
+            events.push(EventMsg::PotterRoundUsage {
+                duration: std::time::Duration::from_secs(usage.duration_secs),
+                token_usage: usage.token_usage,
+                session_token_usage: crate::potter_rollout::sum_token_usage(&round_token_usage),
+            });
+        }
+
         if let Some(session_succeeded) = round.session_succeeded {
             events.push(EventMsg::PotterSessionSucceeded {
                 rounds: session_succeeded.rounds,
@@ -580,9 +849,18 @@ fn build_round_replay_plans(
                 user_prompt_file: session_succeeded.user_prompt_file,
                 git_commit_start: session_succeeded.git_commit_start,
                 git_commit_end: session_succeeded.git_commit_end,
+                round_token_usage: round_token_usage.clone(),
             });
         }
 
+        if let Some(reason) = round.budget_exhausted {
+            events.push(EventMsg::PotterBudgetExhausted { reason });
+        }
+
+        if let Some(rounds) = round.stalled_rounds {
+            events.push(EventMsg::PotterSessionStalled { rounds });
+        }
+
         events.push(EventMsg::PotterRoundFinished {
             outcome: round.outcome.clone(),
         });
@@ -947,6 +1225,7 @@ fn filter_pending_interactive_prompts_for_replay(events: Vec<EventMsg>) -> Vec<E
 #[cfg(test)]
 mod tests {
     use super::*;
+    use codex_protocol::protocol::PotterRoundTimeout;
     use pretty_assertions::assert_eq;
 
     fn write_main(root: &Path, rel: &str) -> PathBuf {
@@ -1124,6 +1403,84 @@ mod tests {
         assert_eq!(decision, ReplayRoundExitDecision::FatalExitRequested);
     }
 
+    #[test]
+    fn last_blocked_command_only_reports_the_last_round() {
+        let blocked = crate::potter_rollout::PotterRolloutLine::RoundFinished {
+            outcome: PotterRoundOutcome::CommandBlocked {
+                command: vec!["git".to_string(), "push".to_string()],
+                rule: "builtin: git push".to_string(),
+            },
+        };
+        let completed = crate::potter_rollout::PotterRolloutLine::RoundFinished {
+            outcome: PotterRoundOutcome::Completed,
+        };
+
+        assert_eq!(
+            last_blocked_command(&[completed.clone(), blocked.clone()]),
+            Some(crate::command_guard::BlockedCommand {
+                command: vec!["git".to_string(), "push".to_string()],
+                rule: "builtin: git push".to_string(),
+            })
+        );
+        assert_eq!(last_blocked_command(&[blocked, completed]), None);
+    }
+
+    #[test]
+    fn count_completed_rounds_skips_context_exhausted_attempts() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("potter-rollout.jsonl");
+        // Round 1, round 2 after two fresh threads, then round 3 ran out of context window four
+        // times in a row: the fourth time counts, as it did while the session ran.
+        for outcome in [
+            PotterRoundOutcome::Completed,
+            PotterRoundOutcome::ContextExhausted,
+            PotterRoundOutcome::ContextExhausted,
+            PotterRoundOutcome::Completed,
+            PotterRoundOutcome::ContextExhausted,
+            PotterRoundOutcome::ContextExhausted,
+            PotterRoundOutcome::ContextExhausted,
+            PotterRoundOutcome::ContextExhausted,
+        ] {
+            crate::potter_rollout::append_line(
+                &path,
+                &crate::potter_rollout::PotterRolloutLine::RoundFinished { outcome },
+            )
+            .expect("append round");
+        }
+
+        let lines = load_potter_rollout_lines(&path).expect("load rollout");
+        assert_eq!(count_completed_rounds(&lines), 3);
+    }
+
+    #[test]
+    fn count_completed_rounds_skips_timed_out_attempts() {
+        let temp = tempfile::tempdir().expect("tempdir");
+        let path = temp.path().join("potter-rollout.jsonl");
+        // Round 1 was retried twice after `--idle-timeout`, then round 2 timed out and ended the
+        // session.
+        for outcome in [
+            PotterRoundOutcome::TimedOut {
+                timeout: PotterRoundTimeout::Idle { limit_secs: 600 },
+            },
+            PotterRoundOutcome::TimedOut {
+                timeout: PotterRoundTimeout::Idle { limit_secs: 600 },
+            },
+            PotterRoundOutcome::Completed,
+            PotterRoundOutcome::TimedOut {
+                timeout: PotterRoundTimeout::Round { limit_secs: 3600 },
+            },
+        ] {
+            crate::potter_rollout::append_line(
+                &path,
+                &crate::potter_rollout::PotterRolloutLine::RoundFinished { outcome },
+            )
+            .expect("append round");
+        }
+
+        let lines = load_potter_rollout_lines(&path).expect("load rollout");
+        assert_eq!(count_completed_rounds(&lines), 1);
+    }
+
     #[test]
     fn load_potter_rollout_lines_errors_when_missing() {
         let temp = tempfile::tempdir().expect("tempdir");
@@ -1152,10 +1509,12 @@ mod tests {
             crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: Some("hello".to_string()),
                 user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/1/MAIN.md"),
+                worktree: None,
             },
             crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
             crate::potter_rollout::PotterRolloutLine::RoundConfigured {
                 thread_id,
@@ -1207,10 +1566,12 @@ mod tests {
             crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: Some("hello".to_string()),
                 user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/1/MAIN.md"),
+                worktree: None,
             },
             crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
             crate::potter_rollout::PotterRolloutLine::RoundConfigured {
                 thread_id,
@@ -1224,6 +1585,7 @@ mod tests {
             crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 2,
                 total: 10,
+                planning: false,
             },
             crate::potter_rollout::PotterRolloutLine::RoundConfigured {
                 thread_id: next_thread_id,
@@ -1252,10 +1614,12 @@ mod tests {
             crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: Some("hello".to_string()),
                 user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/1/MAIN.md"),
+                worktree: None,
             },
             crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
         ];
 
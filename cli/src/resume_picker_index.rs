@@ -84,10 +84,14 @@ fn row_for_progress_file(
         return Ok(None);
     }
 
-    let short_title = crate::project::progress_file_short_title(&resolved.progress_file)?;
-    let git_branch = crate::project::progress_file_git_branch(&resolved.progress_file)?;
-
-    let user_request = match short_title {
+    let progress = crate::progress_file::ProgressFile::read(&resolved.progress_file)?;
+    let git_branch = progress.git_branch;
+    // Only non-default workflows are worth calling out in the picker.
+    let workflow = progress
+        .workflow
+        .filter(|workflow| workflow != crate::workflow::Workflow::Implement.name());
+
+    let user_request = match progress.short_title {
         Some(title) => title,
         None => index
             .session_started
@@ -102,6 +106,7 @@ fn row_for_progress_file(
         created_at,
         updated_at,
         git_branch,
+        workflow,
     }))
 }
 
@@ -246,6 +251,7 @@ git_branch: "{git_branch}"
             &crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: user_message.map(ToOwned::to_owned),
                 user_prompt_file: main_rel,
+                worktree: None,
             },
         )
         .expect("append session_started");
@@ -254,6 +260,7 @@ git_branch: "{git_branch}"
             &crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
         )
         .expect("append round_started");
@@ -384,6 +391,7 @@ git_branch: "{git_branch}"
             &crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: Some("hello".to_string()),
                 user_prompt_file: main_rel,
+                worktree: None,
             },
         )
         .expect("append session_started");
@@ -392,6 +400,7 @@ git_branch: "{git_branch}"
             &crate::potter_rollout::PotterRolloutLine::RoundStarted {
                 current: 1,
                 total: 10,
+                planning: false,
             },
         )
         .expect("append round_started");
@@ -427,6 +436,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(10),
             git_branch: None,
+            workflow: None,
         };
         let b = ResumePickerRow {
             project_path: PathBuf::from("/b"),
@@ -434,6 +444,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(20),
             git_branch: None,
+            workflow: None,
         };
         let c = ResumePickerRow {
             project_path: PathBuf::from("/c"),
@@ -441,6 +452,7 @@ git_branch: "{git_branch}"
             created_at: SystemTime::UNIX_EPOCH,
             updated_at: SystemTime::UNIX_EPOCH + Duration::from_secs(20),
             git_branch: None,
+            workflow: None,
         };
 
         let mut rows = vec![a.clone(), b.clone(), c.clone()];
@@ -1,21 +1,85 @@
+use std::num::NonZeroUsize;
+use std::path::Path;
 use std::path::PathBuf;
 use std::time::Instant;
 
 use anyhow::Context;
+use chrono::Local;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
 use codex_protocol::protocol::PotterRoundOutcome;
+use codex_protocol::protocol::TokenUsage;
+use codex_tui::AppExitInfo;
 use codex_tui::ExitReason;
+use tokio::sync::mpsc::UnboundedReceiver;
+use tokio::sync::mpsc::UnboundedSender;
 use tokio::sync::mpsc::unbounded_channel;
 
+/// Frontend that drives a single round.
+///
+/// Implementations submit `prompt` as `Op::UserInput`, consume backend events until the control
+/// plane emits `EventMsg::PotterRoundFinished` (or a fatal error arrives), and report why the
+/// round ended. The interactive TUI and the headless `exec` sink both implement this.
+pub trait PotterRoundUi {
+    async fn render_turn(
+        &mut self,
+        prompt: String,
+        pad_before_first_cell: bool,
+        op_tx: UnboundedSender<Op>,
+        event_rx: UnboundedReceiver<Event>,
+        fatal_exit_rx: UnboundedReceiver<String>,
+    ) -> anyhow::Result<AppExitInfo>;
+
+    /// Ask the user to approve, edit, or reject the plan of a planning round (`--approve-plan`).
+    async fn review_plan(
+        &mut self,
+        _tasks: Vec<String>,
+    ) -> anyhow::Result<crate::plan_approval::PlanReview> {
+        anyhow::bail!("plan approval (--approve-plan) requires the interactive TUI")
+    }
+}
+
+impl PotterRoundUi for codex_tui::CodexPotterTui {
+    async fn render_turn(
+        &mut self,
+        prompt: String,
+        pad_before_first_cell: bool,
+        op_tx: UnboundedSender<Op>,
+        event_rx: UnboundedReceiver<Event>,
+        fatal_exit_rx: UnboundedReceiver<String>,
+    ) -> anyhow::Result<AppExitInfo> {
+        codex_tui::CodexPotterTui::render_turn(
+            self,
+            prompt,
+            pad_before_first_cell,
+            op_tx,
+            event_rx,
+            fatal_exit_rx,
+        )
+        .await
+    }
+
+    async fn review_plan(
+        &mut self,
+        tasks: Vec<String>,
+    ) -> anyhow::Result<crate::plan_approval::PlanReview> {
+        crate::plan_approval::review_plan_with_tui(self, tasks).await
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct PotterRoundContext {
     pub codex_bin: String,
+    /// Developer prompt with session placeholders filled in; round placeholders are filled in
+    /// per round.
     pub developer_prompt: String,
     pub backend_launch: crate::app_server_backend::AppServerLaunchConfig,
     pub codex_compat_home: Option<PathBuf>,
     pub thread_cwd: Option<PathBuf>,
+    /// Round prompt with session placeholders filled in; round placeholders are filled in per
+    /// round.
     pub turn_prompt: String,
     pub workdir: PathBuf,
     pub progress_file_rel: PathBuf,
@@ -23,6 +87,60 @@ pub struct PotterRoundContext {
     pub git_commit_start: String,
     pub potter_rollout_path: PathBuf,
     pub project_started_at: Instant,
+    /// JSONL event stream (`--json`), already bound to this session.
+    pub event_stream: Option<crate::event_stream::JsonEventStream>,
+    /// Verification command run after every completed round (`--verify`).
+    pub verify_command: Option<String>,
+    /// Longest run of the verification command (`--verify-timeout`).
+    pub verify_timeout: std::time::Duration,
+    /// Failed verification from the previous round, reported to the agent in the developer prompt.
+    pub failed_verification: Option<crate::verify::VerificationResult>,
+    /// Session-wide limits checked against the usage recorded in `potter_rollout_path`.
+    pub budget: crate::budget::SessionBudget,
+    pub workflow: crate::workflow::Workflow,
+    /// Repository state captured when the session started, for workflows that restrict repository
+    /// changes.
+    pub repo_baseline: Option<crate::workflow::RepoSnapshot>,
+    /// Decides the agent's approval requests.
+    pub approval_policy: crate::approval_policy::ApprovalPolicy,
+    /// Answers the agent's questions and MCP elicitations when no one is there to ask.
+    pub user_input_policy: crate::user_input_policy::UserInputPolicy,
+    /// Commands that stop the round when the agent runs them.
+    pub command_guard: crate::command_guard::CommandGuard,
+    /// Command the guard stopped in the previous round, reported to the agent in the developer
+    /// prompt.
+    pub blocked_command: Option<crate::command_guard::BlockedCommand>,
+    /// Progress file issues the previous round left unresolved, reported to the agent in the
+    /// developer prompt.
+    pub progress_file_issues: Vec<String>,
+    /// Model, profile, effort and `-c` overrides forwarded to Codex.
+    pub codex_overrides: crate::codex_overrides::CodexOverrides,
+    /// Writable roots, network access, and tmp exclusions of `workspace-write` turns.
+    pub workspace_write: crate::sandbox_policy::WorkspaceWriteSettings,
+    /// App-server process shared by the session's rounds (`--reuse-app-server`).
+    pub app_server: Option<crate::app_server_backend::SharedAppServer>,
+    /// Percentage of the context window left at which the agent checkpoints its progress
+    /// (`--context-checkpoint`).
+    pub context_checkpoint: Option<u8>,
+    /// Completed rounds in a row without progress after which the session stops
+    /// (`--max-stalled-rounds`).
+    pub max_stalled_rounds: Option<u32>,
+    /// Progress fingerprint and no-progress streak carried between rounds.
+    pub stall: crate::stall::StallState,
+    /// Watchdog limits of each round (`--idle-timeout`, `--round-timeout`).
+    pub timeouts: crate::app_server_backend::RoundTimeouts,
+    /// What to do after a round timed out (`--on-timeout`).
+    pub on_timeout: OnTimeout,
+}
+
+/// What the session does after a watchdog limit killed a round (`--on-timeout`).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OnTimeout {
+    /// Run the round again in a fresh app-server process and thread.
+    #[default]
+    RetryRound,
+    /// End the session.
+    EndSession,
 }
 
 #[derive(Debug, Clone)]
@@ -31,6 +149,8 @@ pub struct PotterSessionStartedInfo {
     pub working_dir: PathBuf,
     pub project_dir: PathBuf,
     pub user_prompt_file: PathBuf,
+    /// Git worktree the session runs in (`--parallel`), recorded so `resume` can return to it.
+    pub worktree: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone)]
@@ -40,6 +160,8 @@ pub struct PotterRoundOptions {
     pub round_current: u32,
     pub round_total: u32,
     pub session_succeeded_rounds: u32,
+    /// Run this round as the read-only planning round of `--approve-plan`.
+    pub planning_round: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -53,45 +175,391 @@ pub struct PotterContinueRoundOptions {
     pub resume_thread_id: codex_protocol::ThreadId,
     /// Persisted EventMsg items from the upstream rollout to replay before continuing.
     pub replay_event_msgs: Vec<EventMsg>,
+    /// The unfinished round is the planning round of `--approve-plan`.
+    pub planning_round: bool,
 }
 
 #[derive(Debug)]
 pub struct PotterRoundResult {
     pub exit_reason: ExitReason,
+    /// The progress file is marked `finite_incantatem: true` and the verifier (if any) passed.
     pub stop_due_to_finite_incantatem: bool,
+    /// Verification run after this round, when `--verify` is set and the round completed.
+    pub verification: Option<crate::verify::VerificationResult>,
+    /// Set when a session-wide budget ran out during this round.
+    pub budget_exhausted: Option<PotterBudgetExhaustedReason>,
+    /// Set when the command guard stopped this round.
+    pub blocked_command: Option<crate::command_guard::BlockedCommand>,
+    /// Progress file issues left after this round's repair.
+    pub progress_file_issues: Vec<String>,
+    /// The round ran out of context window (`PotterRoundOutcome::ContextExhausted`).
+    pub context_exhausted: bool,
+    /// Stall state after this round, when stall detection is enabled and the round completed.
+    pub stall: Option<crate::stall::StallState>,
+    /// This round reached `--max-stalled-rounds` rounds in a row without progress.
+    pub stalled: bool,
+    /// A watchdog limit killed the round (`PotterRoundOutcome::TimedOut`).
+    pub timed_out: bool,
 }
 
+impl PotterRoundResult {
+    /// The verification to report to the next round, if this round's verifier failed.
+    pub fn failed_verification(&self) -> Option<crate::verify::VerificationResult> {
+        self.verification
+            .clone()
+            .filter(|verification| !verification.success)
+    }
+
+    /// The session outcome if the session ends after this round, or `None` to keep iterating.
+    pub fn session_outcome(&self) -> Option<PotterSessionOutcome> {
+        match &self.exit_reason {
+            ExitReason::Completed if self.stop_due_to_finite_incantatem => {
+                Some(PotterSessionOutcome::Succeeded)
+            }
+            ExitReason::Completed if self.budget_exhausted.is_some() => {
+                Some(PotterSessionOutcome::BudgetExhausted)
+            }
+            ExitReason::Completed if self.timed_out => Some(PotterSessionOutcome::TimedOut),
+            ExitReason::Completed if self.stalled => Some(PotterSessionOutcome::Stalled),
+            ExitReason::Completed => None,
+            ExitReason::UserRequested => Some(PotterSessionOutcome::UserCancelled),
+            ExitReason::TaskFailed(_) => Some(PotterSessionOutcome::TaskFailed),
+            ExitReason::Fatal(_) => Some(PotterSessionOutcome::Fatal),
+        }
+    }
+}
+
+/// How a CodexPotter session ended. Each outcome maps to a distinct process exit code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PotterSessionOutcome {
+    /// The progress file was marked `finite_incantatem: true` (and the verifier passed).
+    Succeeded,
+    /// The round budget (or `--max-duration` / `--max-tokens`) ran out before the task was marked
+    /// done.
+    BudgetExhausted,
+    /// `--max-stalled-rounds` rounds in a row completed without progress.
+    Stalled,
+    /// A round hit `--idle-timeout` or `--round-timeout` and was not retried.
+    TimedOut,
+    /// A round ended with `ExitReason::TaskFailed`.
+    TaskFailed,
+    /// The user interrupted the session or declined to continue.
+    UserCancelled,
+    /// The backend hit an unrecoverable error.
+    Fatal,
+}
+
+impl PotterSessionOutcome {
+    /// Process exit code reported for this outcome (see `docs/wiki/cli.md`).
+    ///
+    /// `2` is left to usage errors, matching clap.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            PotterSessionOutcome::Succeeded => 0,
+            PotterSessionOutcome::Fatal => 1,
+            PotterSessionOutcome::BudgetExhausted => 3,
+            PotterSessionOutcome::TaskFailed => 4,
+            PotterSessionOutcome::Stalled => 5,
+            PotterSessionOutcome::TimedOut => 6,
+            PotterSessionOutcome::UserCancelled => 130,
+        }
+    }
+}
+
+/// Settings shared by every session started from one `codex-potter` invocation.
+#[derive(Debug, Clone)]
+pub struct PotterSessionSettings {
+    pub codex_bin: String,
+    pub backend_launch: crate::app_server_backend::AppServerLaunchConfig,
+    pub codex_compat_home: Option<PathBuf>,
+    pub workdir: PathBuf,
+    /// Workflow for new sessions (`--workflow`).
+    pub workflow: crate::workflow::Workflow,
+    /// Prompt templates of `workflow`, resolved (and validated) at startup.
+    pub prompt_templates: crate::prompt_templates::PromptTemplates,
+    pub rounds: NonZeroUsize,
+    /// JSONL event stream (`--json`); bound to each session's id when the session starts.
+    pub event_stream: Option<crate::event_stream::JsonEventStream>,
+    /// Run each session in its own git worktree instead of `workdir` (`--parallel`).
+    ///
+    /// Project records are still written under `workdir`.
+    pub isolate_in_worktree: bool,
+    /// Verification command run after every completed round (`--verify`).
+    pub verify_command: Option<String>,
+    /// Longest run of the verification command (`--verify-timeout`).
+    pub verify_timeout: std::time::Duration,
+    /// Session-wide limits (`--max-duration`, `--max-tokens`).
+    pub budget: crate::budget::SessionBudget,
+    /// Plan in a read-only first round and wait for the user to approve it (`--approve-plan`).
+    pub approve_plan: bool,
+    /// Decides the agent's approval requests (`[approval]` in `config.toml`).
+    pub approval_policy: crate::approval_policy::ApprovalPolicy,
+    /// Answers the agent's questions without the TUI (`[user_input]` in `config.toml`).
+    pub user_input_policy: crate::user_input_policy::UserInputPolicy,
+    /// Deny-list checked against every command the agent runs (`[command_guard]` in
+    /// `config.toml`).
+    pub command_guard: crate::command_guard::CommandGuard,
+    /// Model, profile, effort and `-c` overrides forwarded to Codex (`--model`, `--profile`,
+    /// `--effort`, `-c`, `[codex]` in `config.toml`).
+    pub codex_overrides: crate::codex_overrides::CodexOverrides,
+    /// Writable roots, network access, and tmp exclusions of `workspace-write` turns
+    /// (`--writable-root`, `--network`, `[sandbox]` in `config.toml`).
+    pub workspace_write: crate::sandbox_policy::WorkspaceWriteSettings,
+    /// Keep one app-server process for all rounds of a session (`--reuse-app-server`).
+    pub reuse_app_server: bool,
+    /// Checkpoint progress once at most this percentage of the context window is left
+    /// (`--context-checkpoint`); `None` waits for the window to overflow.
+    pub context_checkpoint: Option<u8>,
+    /// Stop after this many completed rounds in a row without progress (`--max-stalled-rounds`);
+    /// `None` disables stall detection.
+    pub max_stalled_rounds: Option<u32>,
+    /// Watchdog limits of each round (`--idle-timeout`, `--round-timeout`).
+    pub timeouts: crate::app_server_backend::RoundTimeouts,
+    /// Retry a timed-out round or end the session (`--on-timeout`).
+    pub on_timeout: OnTimeout,
+}
+
+#[derive(Debug)]
+pub struct PotterSessionResult {
+    /// Project directory relative to the workdir (e.g. `.codexpotter/projects/2026/02/01/1`).
+    pub project_dir: PathBuf,
+    pub outcome: PotterSessionOutcome,
+}
+
+/// Initialize a new project for `user_prompt` and run up to `settings.rounds` rounds.
+///
+/// The loop stops early when a round does not complete normally or when the progress file is
+/// marked `finite_incantatem: true` (and the verifier passes). The caller decides how to react to
+/// the final outcome.
+pub async fn run_potter_session(
+    ui: &mut impl PotterRoundUi,
+    settings: &PotterSessionSettings,
+    user_prompt: &str,
+) -> anyhow::Result<PotterSessionResult> {
+    let workdir = &settings.workdir;
+    let init = crate::project::init_project(
+        workdir,
+        user_prompt,
+        Local::now(),
+        &settings.prompt_templates.project_main,
+    )
+    .context("initialize .codexpotter project")?;
+    let project_started_at = Instant::now();
+    let project_dir = init
+        .progress_file_rel
+        .parent()
+        .context("derive CodexPotter project dir from progress file path")?
+        .to_path_buf();
+    let project_dir_abs = workdir.join(&project_dir);
+    let potter_rollout_path = crate::potter_rollout::potter_rollout_path(&project_dir_abs);
+    let user_prompt_file = init.progress_file_rel.clone();
+    let project_path = crate::derive_resume_project_path_from_project_dir(&project_dir)
+        .unwrap_or_else(|| project_dir.to_string_lossy().to_string());
+    let worktree = if settings.isolate_in_worktree {
+        Some(
+            crate::worktree::create_session_worktree(workdir, &project_path)
+                .context("create session worktree")?,
+        )
+    } else {
+        None
+    };
+    // Inside a worktree the agent's cwd is not `workdir`, so point it at the progress file in the
+    // main checkout by absolute path.
+    let progress_file = match &worktree {
+        Some(_) => workdir.join(&init.progress_file_rel),
+        None => init.progress_file_rel.clone(),
+    };
+    let prompt_values = crate::prompt_templates::SessionPromptValues {
+        progress_file: &progress_file,
+        git_commit: &init.git_commit_start,
+        git_branch: &init.git_branch,
+    };
+    let developer_prompt = crate::prompt_templates::render_session_placeholders(
+        &settings.prompt_templates.developer_prompt,
+        prompt_values,
+    );
+    let turn_prompt = crate::prompt_templates::render_session_placeholders(
+        &settings.prompt_templates.prompt,
+        prompt_values,
+    )
+    .trim_end()
+    .to_string();
+    let session_cwd = worktree.clone().unwrap_or_else(|| workdir.clone());
+    // The worktree is the sandbox's workspace; the progress file and git metadata are not in it.
+    let workspace_write = match &worktree {
+        Some(worktree) => settings.workspace_write.clone().with_writable_roots(
+            crate::worktree::session_writable_roots(workdir, &project_dir_abs, worktree)
+                .context("resolve worktree writable roots")?,
+        ),
+        None => settings.workspace_write.clone(),
+    };
+
+    let mut round_context = PotterRoundContext {
+        codex_bin: settings.codex_bin.clone(),
+        developer_prompt,
+        backend_launch: settings
+            .backend_launch
+            .with_default_sandbox(settings.workflow.default_sandbox()),
+        codex_compat_home: settings.codex_compat_home.clone(),
+        thread_cwd: Some(session_cwd.clone()),
+        turn_prompt,
+        workdir: workdir.clone(),
+        progress_file_rel: init.progress_file_rel.clone(),
+        user_prompt_file: user_prompt_file.clone(),
+        git_commit_start: init.git_commit_start.clone(),
+        potter_rollout_path,
+        project_started_at,
+        event_stream: settings
+            .event_stream
+            .as_ref()
+            .map(|stream| stream.for_session(&project_path)),
+        verify_command: settings.verify_command.clone(),
+        verify_timeout: settings.verify_timeout,
+        failed_verification: None,
+        budget: settings.budget,
+        workflow: settings.workflow,
+        repo_baseline: settings.workflow.repo_baseline(&session_cwd),
+        approval_policy: settings.approval_policy.clone(),
+        user_input_policy: settings.user_input_policy.clone(),
+        command_guard: settings.command_guard.clone(),
+        blocked_command: None,
+        progress_file_issues: Vec::new(),
+        codex_overrides: settings.codex_overrides.clone(),
+        workspace_write,
+        app_server: settings
+            .reuse_app_server
+            .then(crate::app_server_backend::SharedAppServer::default),
+        context_checkpoint: settings.context_checkpoint,
+        max_stalled_rounds: settings.max_stalled_rounds,
+        stall: match settings.max_stalled_rounds {
+            Some(_) => crate::stall::StallState::new(crate::stall::ProgressFingerprint::capture(
+                &session_cwd,
+                &workdir.join(&init.progress_file_rel),
+            )),
+            None => crate::stall::StallState::default(),
+        },
+        timeouts: settings.timeouts,
+        on_timeout: settings.on_timeout,
+    };
+
+    let total_rounds = u32::try_from(settings.rounds.get()).unwrap_or(u32::MAX);
+    let mut outcome = PotterSessionOutcome::BudgetExhausted;
+    for round_index in 0..settings.rounds.get() {
+        let current_round = u32::try_from(round_index.saturating_add(1)).unwrap_or(u32::MAX);
+        let session_started = if round_index == 0 {
+            Some(PotterSessionStartedInfo {
+                user_message: Some(user_prompt.to_string()),
+                working_dir: session_cwd.clone(),
+                project_dir: project_dir.clone(),
+                user_prompt_file: user_prompt_file.clone(),
+                worktree: worktree.clone(),
+            })
+        } else {
+            None
+        };
+
+        let round_result = run_potter_round(
+            ui,
+            &round_context,
+            PotterRoundOptions {
+                pad_before_first_cell: round_index != 0,
+                session_started,
+                round_current: current_round,
+                round_total: total_rounds,
+                session_succeeded_rounds: current_round,
+                planning_round: settings.approve_plan && round_index == 0,
+            },
+        )
+        .await?;
+
+        if let Some(session_outcome) = round_result.session_outcome() {
+            outcome = session_outcome;
+            break;
+        }
+        round_context.failed_verification = round_result.failed_verification();
+        round_context.blocked_command = round_result.blocked_command;
+        round_context.progress_file_issues = round_result.progress_file_issues;
+        if let Some(stall) = round_result.stall {
+            round_context.stall = stall;
+        }
+    }
+
+    Ok(PotterSessionResult {
+        project_dir,
+        outcome,
+    })
+}
+
+/// How many times in a row a round that ran out of context window is started again in a fresh
+/// thread before it counts against `--rounds`.
+pub(crate) const MAX_FRESH_ROUNDS_AFTER_CONTEXT_EXHAUSTED: u32 = 3;
+
+/// How many times a timed-out round is started again (`--on-timeout retry`) before the session
+/// ends as timed out.
+const MAX_RETRIES_AFTER_TIMEOUT: u32 = 2;
+
+/// Run one round.
+///
+/// A round that ran out of context window is started again in a fresh thread under the same round
+/// number, up to `MAX_FRESH_ROUNDS_AFTER_CONTEXT_EXHAUSTED` times; so is a timed-out round with
+/// `--on-timeout retry`, up to `MAX_RETRIES_AFTER_TIMEOUT` times.
 pub async fn run_potter_round(
-    ui: &mut codex_tui::CodexPotterTui,
+    ui: &mut impl PotterRoundUi,
     context: &PotterRoundContext,
     options: PotterRoundOptions,
 ) -> anyhow::Result<PotterRoundResult> {
     let PotterRoundOptions {
-        pad_before_first_cell,
-        session_started,
+        mut pad_before_first_cell,
+        mut session_started,
         round_current,
         round_total,
         session_succeeded_rounds,
+        planning_round,
     } = options;
 
-    run_potter_round_inner(
-        ui,
-        context,
-        PotterRoundInnerOptions {
-            pad_before_first_cell,
-            session_started,
-            round_current,
-            round_total,
-            session_succeeded_rounds,
-            prompt: context.turn_prompt.clone(),
-            resume_thread_id: None,
-            emit_round_started_event: true,
-            record_round_started: true,
-            record_round_configured: true,
-            replay_event_msgs: Vec::new(),
-        },
-    )
-    .await
+    let mut fresh_rounds = 0;
+    let mut timeout_retries = 0;
+    loop {
+        let round_result = run_potter_round_inner(
+            ui,
+            context,
+            PotterRoundInnerOptions {
+                pad_before_first_cell,
+                session_started: session_started.take(),
+                round_current,
+                round_total,
+                session_succeeded_rounds,
+                planning_round,
+                prompt: crate::prompt_templates::render_round_placeholders(
+                    &context.turn_prompt,
+                    round_current,
+                    round_total,
+                ),
+                resume_thread_id: None,
+                emit_round_started_event: true,
+                record_round_started: true,
+                record_round_configured: true,
+                replay_event_msgs: Vec::new(),
+            },
+        )
+        .await?;
+
+        match round_result.session_outcome() {
+            None if round_result.context_exhausted
+                && fresh_rounds < MAX_FRESH_ROUNDS_AFTER_CONTEXT_EXHAUSTED =>
+            {
+                fresh_rounds += 1;
+            }
+            Some(PotterSessionOutcome::TimedOut)
+                if context.on_timeout == OnTimeout::RetryRound
+                    && timeout_retries < MAX_RETRIES_AFTER_TIMEOUT =>
+            {
+                timeout_retries += 1;
+            }
+            _ => return Ok(round_result),
+        }
+        pad_before_first_cell = true;
+    }
 }
 
 /// Continue an unfinished round by resuming its thread and sending a `Continue` prompt.
@@ -99,7 +567,7 @@ pub async fn run_potter_round(
 /// This is primarily used by `codex-potter resume` when the last recorded round has no
 /// `PotterRoundFinished` marker yet.
 pub async fn continue_potter_round(
-    ui: &mut codex_tui::CodexPotterTui,
+    ui: &mut impl PotterRoundUi,
     context: &PotterRoundContext,
     options: PotterContinueRoundOptions,
 ) -> anyhow::Result<PotterRoundResult> {
@@ -110,9 +578,10 @@ pub async fn continue_potter_round(
         session_succeeded_rounds,
         resume_thread_id,
         replay_event_msgs,
+        planning_round,
     } = options;
 
-    run_potter_round_inner(
+    let round_result = run_potter_round_inner(
         ui,
         context,
         PotterRoundInnerOptions {
@@ -121,6 +590,7 @@ pub async fn continue_potter_round(
             round_current,
             round_total,
             session_succeeded_rounds,
+            planning_round,
             prompt: String::from("Continue"),
             resume_thread_id: Some(resume_thread_id),
             emit_round_started_event: false,
@@ -129,7 +599,31 @@ pub async fn continue_potter_round(
             replay_event_msgs,
         },
     )
-    .await
+    .await?;
+
+    // The resumed thread ran out of context window or timed out: start the round again in a fresh
+    // one.
+    let start_again = match round_result.session_outcome() {
+        None => round_result.context_exhausted,
+        Some(PotterSessionOutcome::TimedOut) => context.on_timeout == OnTimeout::RetryRound,
+        Some(_) => false,
+    };
+    if start_again {
+        return run_potter_round(
+            ui,
+            context,
+            PotterRoundOptions {
+                pad_before_first_cell: true,
+                session_started: None,
+                round_current,
+                round_total,
+                session_succeeded_rounds,
+                planning_round,
+            },
+        )
+        .await;
+    }
+    Ok(round_result)
 }
 
 struct PotterRoundInnerOptions {
@@ -138,6 +632,7 @@ struct PotterRoundInnerOptions {
     round_current: u32,
     round_total: u32,
     session_succeeded_rounds: u32,
+    planning_round: bool,
     prompt: String,
     resume_thread_id: Option<codex_protocol::ThreadId>,
     emit_round_started_event: bool,
@@ -147,7 +642,7 @@ struct PotterRoundInnerOptions {
 }
 
 async fn run_potter_round_inner(
-    ui: &mut codex_tui::CodexPotterTui,
+    ui: &mut impl PotterRoundUi,
     context: &PotterRoundContext,
     options: PotterRoundInnerOptions,
 ) -> anyhow::Result<PotterRoundResult> {
@@ -157,6 +652,7 @@ async fn run_potter_round_inner(
         round_current,
         round_total,
         session_succeeded_rounds,
+        planning_round,
         prompt,
         resume_thread_id,
         emit_round_started_event,
@@ -170,33 +666,41 @@ async fn run_potter_round_inner(
     let (ui_event_tx, ui_event_rx) = unbounded_channel::<Event>();
     let (fatal_exit_tx, fatal_exit_rx) = unbounded_channel::<String>();
 
+    let event_stream = context.event_stream.as_ref();
+    let round_started_at = Instant::now();
+
     if let Some(session_started) = session_started {
+        let msg = EventMsg::PotterSessionStarted {
+            user_message: session_started.user_message.clone(),
+            working_dir: session_started.working_dir,
+            project_dir: session_started.project_dir,
+            user_prompt_file: session_started.user_prompt_file.clone(),
+        };
+        emit_json_event(event_stream, round_current, &msg)?;
         let _ = ui_event_tx.send(Event {
             id: "".to_string(),
-            msg: EventMsg::PotterSessionStarted {
-                user_message: session_started.user_message.clone(),
-                working_dir: session_started.working_dir,
-                project_dir: session_started.project_dir,
-                user_prompt_file: session_started.user_prompt_file.clone(),
-            },
+            msg,
         });
         crate::potter_rollout::append_line(
             &context.potter_rollout_path,
             &crate::potter_rollout::PotterRolloutLine::SessionStarted {
                 user_message: session_started.user_message,
                 user_prompt_file: session_started.user_prompt_file,
+                worktree: session_started.worktree,
             },
         )
         .context("append potter-rollout session_started")?;
     }
 
     if emit_round_started_event {
+        let msg = EventMsg::PotterRoundStarted {
+            current: round_current,
+            total: round_total,
+        };
+        emit_json_event(event_stream, round_current, &msg)?;
         let _ = ui_event_tx.send(Event {
             id: "".to_string(),
-            msg: EventMsg::PotterRoundStarted {
-                current: round_current,
-                total: round_total,
-            },
+            msg,
         });
         if record_round_started {
             crate::potter_rollout::append_line(
@@ -204,6 +708,7 @@ async fn run_potter_round_inner(
                 &crate::potter_rollout::PotterRolloutLine::RoundStarted {
                     current: round_current,
                     total: round_total,
+                    planning: planning_round,
                 },
             )
             .context("append potter-rollout round_started")?;
@@ -212,6 +717,7 @@ async fn run_potter_round_inner(
         anyhow::bail!("internal error: record_round_started without emitting PotterRoundStarted");
     }
 
+    // Replayed history was already streamed when it originally happened, so it only goes to the UI.
     for msg in replay_event_msgs {
         let _ = ui_event_tx.send(Event {
             id: "".to_string(),
@@ -222,16 +728,41 @@ async fn run_potter_round_inner(
     let forwarder = {
         let ui_event_tx = ui_event_tx.clone();
         let workdir = context.workdir.clone();
+        let git_workdir = context
+            .thread_cwd
+            .clone()
+            .unwrap_or_else(|| context.workdir.clone());
         let progress_file_rel = context.progress_file_rel.clone();
         let user_prompt_file = context.user_prompt_file.clone();
         let git_commit_start = context.git_commit_start.clone();
         let potter_rollout_path = context.potter_rollout_path.clone();
         let fatal_exit_tx = fatal_exit_tx.clone();
         let project_started_at = context.project_started_at;
+        let event_stream = context.event_stream.clone();
+        let verify_command = context.verify_command.clone();
+        let verify_timeout = context.verify_timeout;
+        let budget = context.budget;
+        let workflow = context.workflow;
+        let repo_baseline = context.repo_baseline.clone();
+        let max_stalled_rounds = context.max_stalled_rounds;
+        let stall_state = context.stall.clone();
+        let mut progress_watcher = crate::progress_file::ProgressFileWatcher::new(
+            context.workdir.join(&context.progress_file_rel),
+        );
 
         tokio::spawn(async move {
             let mut has_recorded_round_configured = !record_round_configured;
-            while let Some(event) = backend_event_rx.recv().await {
+            let mut forwarded = ForwardedRound::default();
+            let mut token_usage = TokenUsage::default();
+            let mut last_agent_message: Option<String> = None;
+            // The progress panel only goes to the UI; the file itself is the durable record.
+            if let Some(progress) = progress_watcher.poll() {
+                let _ = ui_event_tx.send(Event {
+                    id: "".to_string(),
+                    msg: EventMsg::PotterProgressUpdated { progress },
+                });
+            }
+            while let Some(mut event) = backend_event_rx.recv().await {
                 if !has_recorded_round_configured
                     && let EventMsg::SessionConfigured(cfg) = &event.msg
                 {
@@ -258,17 +789,253 @@ async fn run_potter_round_inner(
                     }
                 }
 
+                if let EventMsg::PotterApprovalDecided {
+                    command,
+                    paths,
+                    decision,
+                    decided_by,
+                } = &event.msg
+                    && let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::ApprovalDecided {
+                            command: command.clone(),
+                            paths: paths.clone(),
+                            decision: *decision,
+                            decided_by: decided_by.clone(),
+                        },
+                    )
+                {
+                    let _ = fatal_exit_tx.send(format!(
+                        "failed to write {}: {err:#}",
+                        potter_rollout_path.display()
+                    ));
+                    break;
+                }
+
+                if let EventMsg::PotterPromptAnswered {
+                    answers,
+                    answered_by,
+                } = &event.msg
+                    && let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::PromptAnswered {
+                            answers: answers.clone(),
+                            answered_by: *answered_by,
+                        },
+                    )
+                {
+                    let _ = fatal_exit_tx.send(format!(
+                        "failed to write {}: {err:#}",
+                        potter_rollout_path.display()
+                    ));
+                    break;
+                }
+
+                if let EventMsg::TokenCount(ev) = &event.msg
+                    && let Some(info) = &ev.info
+                {
+                    token_usage = info.total_token_usage.clone();
+                }
+                if let EventMsg::TurnComplete(ev) = &event.msg {
+                    last_agent_message = ev.last_agent_message.clone();
+                }
+                if let EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::CommandBlocked { command, rule },
+                } = &event.msg
+                {
+                    forwarded.blocked_command = Some(crate::command_guard::BlockedCommand {
+                        command: command.clone(),
+                        rule: rule.clone(),
+                    });
+                }
+
                 if matches!(
                     &event.msg,
                     EventMsg::PotterRoundFinished {
-                        outcome: PotterRoundOutcome::Completed
+                        outcome: PotterRoundOutcome::ContextExhausted
                     }
-                ) && crate::project::progress_file_has_finite_incantatem_true(
-                    &workdir,
-                    &progress_file_rel,
-                )
-                .unwrap_or(false)
+                ) {
+                    forwarded.context_exhausted = true;
+                }
+                if matches!(
+                    &event.msg,
+                    EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::TimedOut { .. }
+                    }
+                ) {
+                    forwarded.timed_out = true;
+                }
+
+                let mut round_completed = matches!(
+                    &event.msg,
+                    EventMsg::PotterRoundFinished { outcome } if outcome.is_completed()
+                );
+
+                // A round that broke the workflow's repository rules fails the session.
+                if round_completed
+                    && let Some(baseline) = repo_baseline.as_ref()
+                    && let Some(message) = workflow.repo_policy_violation(&git_workdir, baseline)
                 {
+                    round_completed = false;
+                    event.msg = EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::TaskFailed { message },
+                    };
+                }
+
+                let planned_tasks = (round_completed && planning_round).then(|| {
+                    crate::plan_approval::parse_plan_tasks(
+                        last_agent_message.as_deref().unwrap_or_default(),
+                    )
+                });
+                // A plan without tasks leaves nothing to approve: the planning round failed, and
+                // `resume` plans again.
+                if planned_tasks.as_ref().is_some_and(Vec::is_empty) {
+                    round_completed = false;
+                    event.msg = EventMsg::PotterRoundFinished {
+                        outcome: PotterRoundOutcome::TaskFailed {
+                            message: crate::plan_approval::EMPTY_PLAN_MESSAGE.to_string(),
+                        },
+                    };
+                }
+
+                // The planning round only proposes a plan; there is nothing to verify yet.
+                if round_completed && let Some(tasks) = planned_tasks {
+                    if let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::PlanProposed {
+                            tasks: tasks.clone(),
+                        },
+                    ) {
+                        let _ = fatal_exit_tx.send(format!(
+                            "failed to write {}: {err:#}",
+                            potter_rollout_path.display()
+                        ));
+                        break;
+                    }
+                    forwarded.proposed_plan = Some(tasks);
+                } else if round_completed && let Some(command) = verify_command.as_deref() {
+                    let result =
+                        crate::verify::run_verification(command, &git_workdir, verify_timeout)
+                            .await;
+                    if let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::RoundVerified {
+                            command: result.command.clone(),
+                            success: result.success,
+                            exit_code: result.exit_code,
+                            duration_secs: result.duration.as_secs(),
+                            output_tail: result.output_tail.clone(),
+                        },
+                    ) {
+                        let _ = fatal_exit_tx.send(format!(
+                            "failed to write {}: {err:#}",
+                            potter_rollout_path.display()
+                        ));
+                        break;
+                    }
+                    let msg = EventMsg::PotterRoundVerified {
+                        command: result.command.clone(),
+                        success: result.success,
+                        exit_code: result.exit_code,
+                        duration: result.duration,
+                        output_tail: result.output_tail.clone(),
+                    };
+                    if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &msg) {
+                        let _ = fatal_exit_tx.send(format!("{err:#}"));
+                        break;
+                    }
+                    let _ = ui_event_tx.send(Event {
+                        id: "".to_string(),
+                        msg,
+                    });
+                    forwarded.verification = Some(result);
+                }
+
+                // Read the stop flag as the agent left it, before the repair below rewrites the
+                // file.
+                if matches!(&event.msg, EventMsg::PotterRoundFinished { .. }) {
+                    forwarded.finite_incantatem =
+                        crate::project::progress_file_has_finite_incantatem_true(
+                            &workdir,
+                            &progress_file_rel,
+                        )
+                        .unwrap_or(false);
+                }
+
+                // Repair what the agent broke in the progress file; the rest goes to the next round.
+                if matches!(&event.msg, EventMsg::PotterRoundFinished { .. })
+                    && let Ok(check) =
+                        crate::progress_file::check_progress_file(&workdir.join(&progress_file_rel))
+                    && !check.is_clean()
+                {
+                    let unresolved: Vec<String> =
+                        check.unresolved.iter().map(ToString::to_string).collect();
+                    let msg = EventMsg::PotterProgressFileIssues {
+                        repaired: check.repaired.iter().map(ToString::to_string).collect(),
+                        unresolved: unresolved.clone(),
+                    };
+                    if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &msg) {
+                        let _ = fatal_exit_tx.send(format!("{err:#}"));
+                        break;
+                    }
+                    let _ = ui_event_tx.send(Event {
+                        id: "".to_string(),
+                        msg,
+                    });
+                    forwarded.progress_file_issues = unresolved;
+                }
+
+                let mut recorded_lines = Vec::new();
+                if matches!(&event.msg, EventMsg::PotterRoundFinished { .. }) {
+                    let round_duration = round_started_at.elapsed();
+                    if let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::RoundUsage {
+                            duration_secs: round_duration.as_secs(),
+                            token_usage: token_usage.clone(),
+                        },
+                    ) {
+                        let _ = fatal_exit_tx.send(format!(
+                            "failed to write {}: {err:#}",
+                            potter_rollout_path.display()
+                        ));
+                        break;
+                    }
+                    recorded_lines = match crate::potter_rollout::read_lines(&potter_rollout_path) {
+                        Ok(lines) => lines,
+                        Err(err) => {
+                            let _ = fatal_exit_tx.send(format!(
+                                "failed to read {}: {err:#}",
+                                potter_rollout_path.display()
+                            ));
+                            break;
+                        }
+                    };
+                    let msg = EventMsg::PotterRoundUsage {
+                        duration: round_duration,
+                        token_usage: token_usage.clone(),
+                        session_token_usage: crate::potter_rollout::sum_token_usage(
+                            &crate::potter_rollout::round_token_usage(&recorded_lines),
+                        ),
+                    };
+                    if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &msg) {
+                        let _ = fatal_exit_tx.send(format!("{err:#}"));
+                        break;
+                    }
+                    let _ = ui_event_tx.send(Event {
+                        id: "".to_string(),
+                        msg,
+                    });
+                }
+
+                let session_succeeded = round_completed
+                    && forwarded
+                        .verification
+                        .as_ref()
+                        .is_none_or(|verification| verification.success)
+                    && forwarded.finite_incantatem;
+
+                if session_succeeded {
                     if let Err(err) = crate::potter_rollout::append_line(
                         &potter_rollout_path,
                         &crate::potter_rollout::PotterRolloutLine::SessionSucceeded {
@@ -276,7 +1043,7 @@ async fn run_potter_round_inner(
                             duration_secs: project_started_at.elapsed().as_secs(),
                             user_prompt_file: user_prompt_file.clone(),
                             git_commit_start: git_commit_start.clone(),
-                            git_commit_end: crate::project::resolve_git_commit(&workdir),
+                            git_commit_end: crate::project::resolve_git_commit(&git_workdir),
                         },
                     ) {
                         let _ = fatal_exit_tx.send(format!(
@@ -285,16 +1052,97 @@ async fn run_potter_round_inner(
                         ));
                         break;
                     }
+                    let msg = EventMsg::PotterSessionSucceeded {
+                        rounds: session_succeeded_rounds,
+                        duration: project_started_at.elapsed(),
+                        user_prompt_file: user_prompt_file.clone(),
+                        git_commit_start: git_commit_start.clone(),
+                        git_commit_end: crate::project::resolve_git_commit(&git_workdir),
+                        round_token_usage: crate::potter_rollout::round_token_usage(
+                            &recorded_lines,
+                        ),
+                    };
+                    if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &msg) {
+                        let _ = fatal_exit_tx.send(format!("{err:#}"));
+                        break;
+                    }
                     let _ = ui_event_tx.send(Event {
                         id: "".to_string(),
-                        msg: EventMsg::PotterSessionSucceeded {
-                            rounds: session_succeeded_rounds,
-                            duration: project_started_at.elapsed(),
-                            user_prompt_file: user_prompt_file.clone(),
-                            git_commit_start: git_commit_start.clone(),
-                            git_commit_end: crate::project::resolve_git_commit(&workdir),
+                        msg,
+                    });
+                }
+
+                // A round that ran out of context window or timed out may be started again, unless
+                // the budget ran out too.
+                if (round_completed || forwarded.context_exhausted || forwarded.timed_out)
+                    && !session_succeeded
+                    && !budget.is_unlimited()
+                    && let Some(reason) =
+                        budget.exhausted(crate::budget::usage_from_lines(&recorded_lines))
+                {
+                    if let Err(err) = crate::potter_rollout::append_line(
+                        &potter_rollout_path,
+                        &crate::potter_rollout::PotterRolloutLine::BudgetExhausted {
+                            reason: reason.clone(),
                         },
+                    ) {
+                        let _ = fatal_exit_tx.send(format!(
+                            "failed to write {}: {err:#}",
+                            potter_rollout_path.display()
+                        ));
+                        break;
+                    }
+                    let msg = EventMsg::PotterBudgetExhausted {
+                        reason: reason.clone(),
+                    };
+                    if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &msg) {
+                        let _ = fatal_exit_tx.send(format!("{err:#}"));
+                        break;
+                    }
+                    let _ = ui_event_tx.send(Event {
+                        id: "".to_string(),
+                        msg,
                     });
+                    forwarded.budget_exhausted = Some(reason);
+                }
+
+                if round_completed
+                    && !planning_round
+                    && !session_succeeded
+                    && forwarded.budget_exhausted.is_none()
+                    && let Some(max_stalled_rounds) = max_stalled_rounds
+                {
+                    let stall =
+                        stall_state.after_round(crate::stall::ProgressFingerprint::capture(
+                            &git_workdir,
+                            &workdir.join(&progress_file_rel),
+                        ));
+                    if stall.stalled_rounds >= max_stalled_rounds {
+                        let rounds = stall.stalled_rounds;
+                        if let Err(err) = crate::potter_rollout::append_line(
+                            &potter_rollout_path,
+                            &crate::potter_rollout::PotterRolloutLine::SessionStalled { rounds },
+                        ) {
+                            let _ = fatal_exit_tx.send(format!(
+                                "failed to write {}: {err:#}",
+                                potter_rollout_path.display()
+                            ));
+                            break;
+                        }
+                        let msg = EventMsg::PotterSessionStalled { rounds };
+                        if let Err(err) =
+                            emit_json_event(event_stream.as_ref(), round_current, &msg)
+                        {
+                            let _ = fatal_exit_tx.send(format!("{err:#}"));
+                            break;
+                        }
+                        let _ = ui_event_tx.send(Event {
+                            id: "".to_string(),
+                            msg,
+                        });
+                        forwarded.stalled = true;
+                    }
+                    forwarded.stall = Some(stall);
                 }
 
                 if let EventMsg::PotterRoundFinished { outcome } = &event.msg
@@ -312,21 +1160,103 @@ async fn run_potter_round_inner(
                     break;
                 }
 
+                if let Err(err) = emit_json_event(event_stream.as_ref(), round_current, &event.msg)
+                {
+                    let _ = fatal_exit_tx.send(format!("{err:#}"));
+                    break;
+                }
+
+                // Agent edits to the progress file always come with events, so checking between
+                // events keeps the panel current.
+                if let Some(progress) = progress_watcher.poll() {
+                    let _ = ui_event_tx.send(Event {
+                        id: "".to_string(),
+                        msg: EventMsg::PotterProgressUpdated { progress },
+                    });
+                }
+
                 if ui_event_tx.send(event).is_err() {
                     break;
                 }
             }
+            forwarded
         })
     };
 
+    let developer_instructions = crate::verify::developer_prompt_with_feedback(
+        &crate::prompt_templates::render_round_placeholders(
+            &context.developer_prompt,
+            round_current,
+            round_total,
+        ),
+        context.failed_verification.as_ref(),
+    );
+    let developer_instructions = crate::command_guard::developer_prompt_with_blocked_command(
+        &developer_instructions,
+        context.blocked_command.as_ref(),
+    );
+    let developer_instructions = crate::progress_file::developer_prompt_with_issues(
+        &developer_instructions,
+        &context.progress_file_issues,
+    );
+    let developer_instructions = if planning_round {
+        crate::plan_approval::planning_developer_prompt(&developer_instructions)
+    } else {
+        developer_instructions
+    };
+    // The `--approve-plan` planning round plans whatever the progress file says.
+    let codex_overrides = if planning_round {
+        context
+            .codex_overrides
+            .for_phase(crate::project::ProgressPhase::Planning)
+    } else {
+        context
+            .codex_overrides
+            .for_progress_file(&context.workdir.join(&context.progress_file_rel))
+    };
+    let launch = if planning_round {
+        crate::app_server_backend::AppServerLaunchConfig::read_only()
+    } else {
+        context.backend_launch
+    };
+    // What is left of the session budget stops the round when it runs out.
+    let remaining_budget = if context.budget.is_unlimited() {
+        context.budget
+    } else {
+        let lines = crate::potter_rollout::read_lines(&context.potter_rollout_path)
+            .context("read potter-rollout for the session budget")?;
+        context
+            .budget
+            .remaining(crate::budget::usage_from_lines(&lines))
+    };
+    let timeouts = crate::app_server_backend::RoundTimeouts {
+        budget_duration: remaining_budget.max_duration,
+        budget_tokens: remaining_budget.max_tokens,
+        ..context.timeouts
+    };
     let backend = tokio::spawn(crate::app_server_backend::run_app_server_backend(
         crate::app_server_backend::AppServerBackendConfig {
             codex_bin: context.codex_bin.clone(),
-            developer_instructions: Some(context.developer_prompt.clone()),
-            launch: context.backend_launch,
+            developer_instructions: Some(developer_instructions),
+            launch,
             codex_home: context.codex_compat_home.clone(),
             thread_cwd: context.thread_cwd.clone(),
             resume_thread_id,
+            approval_policy: context.approval_policy.clone(),
+            user_input_policy: context.user_input_policy.clone(),
+            command_guard: context.command_guard.clone(),
+            codex_overrides,
+            workspace_write: context.workspace_write.clone(),
+            shared_app_server: context.app_server.clone(),
+            context_checkpoint: context.context_checkpoint.map(|percent_left| {
+                crate::app_server_backend::ContextCheckpoint {
+                    percent_left: i64::from(percent_left),
+                    prompt: context_checkpoint_prompt(
+                        &context.workdir.join(&context.progress_file_rel),
+                    ),
+                }
+            }),
+            timeouts,
         },
         op_rx,
         backend_event_tx,
@@ -354,6 +1284,14 @@ async fn run_potter_round_inner(
             return Ok(PotterRoundResult {
                 exit_reason,
                 stop_due_to_finite_incantatem: false,
+                verification: None,
+                budget_exhausted: None,
+                blocked_command: None,
+                progress_file_issues: Vec::new(),
+                context_exhausted: false,
+                stall: None,
+                stalled: false,
+                timed_out: false,
             });
         }
     }
@@ -361,16 +1299,217 @@ async fn run_potter_round_inner(
     backend
         .await
         .context("app-server render backend panicked")??;
-    let _ = forwarder.await;
+    let ForwardedRound {
+        verification,
+        budget_exhausted,
+        proposed_plan,
+        blocked_command,
+        progress_file_issues,
+        finite_incantatem,
+        context_exhausted,
+        stall,
+        stalled,
+        timed_out,
+    } = forwarder.await.unwrap_or_default();
 
-    let stop_due_to_finite_incantatem = crate::project::progress_file_has_finite_incantatem_true(
-        &context.workdir,
-        &context.progress_file_rel,
-    )
-    .context("check progress file finite_incantatem")?;
+    // A session that ran out of budget stops here; `resume` asks for the decision later.
+    if let Some(tasks) = proposed_plan
+        && budget_exhausted.is_none()
+    {
+        let review = ui.review_plan(tasks).await?;
+        crate::plan_approval::record_plan_review(
+            &context.potter_rollout_path,
+            &context.workdir,
+            &context.progress_file_rel,
+            &review,
+        )?;
+        if !review.decision.is_approved() {
+            return Ok(PotterRoundResult {
+                exit_reason: ExitReason::UserRequested,
+                stop_due_to_finite_incantatem: false,
+                verification: None,
+                budget_exhausted: None,
+                blocked_command: None,
+                progress_file_issues: Vec::new(),
+                context_exhausted: false,
+                stall: None,
+                stalled: false,
+                timed_out: false,
+            });
+        }
+    }
+
+    let verification_passed = verification
+        .as_ref()
+        .is_none_or(|verification| verification.success);
+    if finite_incantatem && !verification_passed {
+        // The agent considers the task done but the verifier disagrees: keep iterating.
+        crate::project::set_progress_file_finite_incantatem(
+            &context.workdir,
+            &context.progress_file_rel,
+            false,
+        )
+        .context("reset progress file finite_incantatem")?;
+    }
 
     Ok(PotterRoundResult {
         exit_reason,
-        stop_due_to_finite_incantatem,
+        // Neither a checkpoint nor a killed round is a finished task; the fresh round confirms it.
+        stop_due_to_finite_incantatem: finite_incantatem
+            && verification_passed
+            && !context_exhausted
+            && !timed_out,
+        verification,
+        budget_exhausted,
+        blocked_command,
+        progress_file_issues,
+        context_exhausted,
+        stall,
+        stalled,
+        timed_out,
     })
 }
+
+/// What the event forwarder learned about a round while passing its events to the UI.
+#[derive(Debug, Default)]
+struct ForwardedRound {
+    verification: Option<crate::verify::VerificationResult>,
+    budget_exhausted: Option<PotterBudgetExhaustedReason>,
+    /// Tasks proposed by the `--approve-plan` planning round.
+    proposed_plan: Option<Vec<String>>,
+    blocked_command: Option<crate::command_guard::BlockedCommand>,
+    /// Progress file issues the post-round repair could not fix.
+    progress_file_issues: Vec<String>,
+    /// `finite_incantatem` as the agent left it, read before the post-round repair.
+    finite_incantatem: bool,
+    context_exhausted: bool,
+    stall: Option<crate::stall::StallState>,
+    stalled: bool,
+    timed_out: bool,
+}
+
+/// Follow-up prompt of a context checkpoint, asking the agent to leave the progress file ready for
+/// a fresh thread.
+fn context_checkpoint_prompt(progress_file: &Path) -> String {
+    format!(
+        "Your context window is almost full. This thread ends after this turn, and the next round continues in a fresh thread that only knows what the progress file says. Do not start new work. Update `{}` so the next round can pick up where you left off: what is done, what is in flight (including the exact state of any half-finished change), and the next steps. Then end your turn.",
+        progress_file.display()
+    )
+}
+
+fn emit_json_event(
+    event_stream: Option<&crate::event_stream::JsonEventStream>,
+    round: u32,
+    msg: &EventMsg,
+) -> anyhow::Result<()> {
+    match event_stream {
+        Some(event_stream) => event_stream.emit(round, msg),
+        None => Ok(()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use pretty_assertions::assert_eq;
+
+    fn round_result(exit_reason: ExitReason, finite_incantatem: bool) -> PotterRoundResult {
+        PotterRoundResult {
+            exit_reason,
+            stop_due_to_finite_incantatem: finite_incantatem,
+            verification: None,
+            budget_exhausted: None,
+            blocked_command: None,
+            progress_file_issues: Vec::new(),
+            context_exhausted: false,
+            stall: None,
+            stalled: false,
+            timed_out: false,
+        }
+    }
+
+    #[test]
+    fn session_outcome_maps_round_results() {
+        assert_eq!(
+            round_result(ExitReason::Completed, true).session_outcome(),
+            Some(PotterSessionOutcome::Succeeded)
+        );
+        assert_eq!(
+            round_result(ExitReason::Completed, false).session_outcome(),
+            None
+        );
+        let mut budget_exhausted = round_result(ExitReason::Completed, false);
+        budget_exhausted.budget_exhausted = Some(PotterBudgetExhaustedReason::MaxTokens {
+            limit: 10,
+            used: 12,
+        });
+        assert_eq!(
+            budget_exhausted.session_outcome(),
+            Some(PotterSessionOutcome::BudgetExhausted)
+        );
+        let mut stalled = round_result(ExitReason::Completed, false);
+        stalled.stalled = true;
+        assert_eq!(
+            stalled.session_outcome(),
+            Some(PotterSessionOutcome::Stalled)
+        );
+        let mut timed_out = round_result(ExitReason::Completed, false);
+        timed_out.timed_out = true;
+        assert_eq!(
+            timed_out.session_outcome(),
+            Some(PotterSessionOutcome::TimedOut)
+        );
+        assert_eq!(
+            round_result(ExitReason::UserRequested, false).session_outcome(),
+            Some(PotterSessionOutcome::UserCancelled)
+        );
+        assert_eq!(
+            round_result(ExitReason::TaskFailed("boom".to_string()), false).session_outcome(),
+            Some(PotterSessionOutcome::TaskFailed)
+        );
+        assert_eq!(
+            round_result(ExitReason::Fatal("boom".to_string()), false).session_outcome(),
+            Some(PotterSessionOutcome::Fatal)
+        );
+    }
+
+    #[test]
+    fn failed_verification_only_reports_failed_runs() {
+        let verification = crate::verify::VerificationResult {
+            command: "cargo test".to_string(),
+            success: false,
+            exit_code: Some(101),
+            duration: std::time::Duration::from_secs(1),
+            output_tail: String::new(),
+        };
+        let mut result = round_result(ExitReason::Completed, false);
+        assert_eq!(result.failed_verification(), None);
+
+        result.verification = Some(verification.clone());
+        assert_eq!(result.failed_verification(), Some(verification.clone()));
+
+        result.verification = Some(crate::verify::VerificationResult {
+            success: true,
+            exit_code: Some(0),
+            ..verification
+        });
+        assert_eq!(result.failed_verification(), None);
+    }
+
+    #[test]
+    fn session_outcome_exit_codes_are_distinct() {
+        let codes = [
+            PotterSessionOutcome::Succeeded,
+            PotterSessionOutcome::BudgetExhausted,
+            PotterSessionOutcome::TaskFailed,
+            PotterSessionOutcome::Stalled,
+            PotterSessionOutcome::TimedOut,
+            PotterSessionOutcome::UserCancelled,
+            PotterSessionOutcome::Fatal,
+        ]
+        .map(PotterSessionOutcome::exit_code);
+
+        assert_eq!(codes, [0, 3, 4, 5, 6, 130, 1]);
+    }
+}
@@ -0,0 +1,378 @@
+//! Fine-grained `workspace-write` sandbox settings: extra writable roots, network access, and the
+//! tmp exclusions, merged into the thread's sandbox and sent as the `sandboxPolicy` of every
+//! `turn/start`.
+//!
+//! Set with `--writable-root <PATH>` (repeatable), `--network on|off`, `--exclude-tmpdir-env-var`,
+//! and `--exclude-slash-tmp`, or in the `[sandbox]` table of `~/.codexpotter/config.toml` (and,
+//! for the tmp exclusions only, `<workdir>/.codexpotter/config.toml`):
+//!
+//! ```toml
+//! [sandbox]
+//! writable_roots = ["../build-cache"]  # relative to the working directory
+//! network_access = true
+//! exclude_tmpdir_env_var = false
+//! exclude_slash_tmp = false
+//! ```
+//!
+//! Writable roots from the command line and the home file are combined; for the other keys the
+//! command line wins over the home file, which wins over the repository file. The agent can write
+//! the repository file, so it must not widen its own sandbox there: `writable_roots` and
+//! `network_access` in the repository file are rejected at startup.
+
+use std::path::Path;
+use std::path::PathBuf;
+
+use anyhow::Context;
+use codex_protocol::AbsolutePathBuf;
+use toml_edit::TableLike;
+
+use crate::app_server_backend::AppServerLaunchConfig;
+use crate::app_server_protocol::SandboxMode;
+use crate::app_server_protocol::SandboxPolicy;
+use crate::config::LayeredConfig;
+use crate::config::reject_in_repository;
+use crate::config::string_array;
+
+/// `workspace-write` refinements of one `codex-potter` invocation.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct WorkspaceWriteSettings {
+    /// Directories writable besides the workspace; absolute once loaded.
+    pub writable_roots: Vec<PathBuf>,
+    pub network_access: Option<bool>,
+    pub exclude_tmpdir_env_var: Option<bool>,
+    pub exclude_slash_tmp: Option<bool>,
+}
+
+impl WorkspaceWriteSettings {
+    /// Layer `cli` on top of the `[sandbox]` tables of `config`. Relative writable roots are
+    /// resolved against `workdir`.
+    ///
+    /// The repository file may only set the tmp exclusions; `writable_roots` and
+    /// `network_access` there are rejected.
+    pub fn load(
+        config: &LayeredConfig,
+        workdir: &Path,
+        cli: WorkspaceWriteSettings,
+    ) -> anyhow::Result<Self> {
+        let mut merged = cli;
+        for layer in config.tables("sandbox", |source, table| {
+            reject_in_repository(
+                source,
+                "sandbox",
+                table,
+                &["writable_roots", "network_access"],
+            )?;
+            parse_sandbox_config(table)
+        })? {
+            merged.writable_roots.extend(layer.writable_roots);
+            merged.network_access = merged.network_access.or(layer.network_access);
+            merged.exclude_tmpdir_env_var = merged
+                .exclude_tmpdir_env_var
+                .or(layer.exclude_tmpdir_env_var);
+            merged.exclude_slash_tmp = merged.exclude_slash_tmp.or(layer.exclude_slash_tmp);
+        }
+
+        let mut writable_roots = Vec::new();
+        for root in merged.writable_roots {
+            let root = AbsolutePathBuf::resolve_path_against_base(&root, workdir)
+                .with_context(|| format!("resolve writable root {}", root.display()))?
+                .into_path_buf();
+            if !writable_roots.contains(&root) {
+                writable_roots.push(root);
+            }
+        }
+        merged.writable_roots = writable_roots;
+        Ok(merged)
+    }
+
+    /// Also make `roots` (absolute) writable, e.g. the main checkout's directories a session in a
+    /// git worktree writes to (`crate::worktree::session_writable_roots`).
+    pub fn with_writable_roots(mut self, roots: impl IntoIterator<Item = PathBuf>) -> Self {
+        for root in roots {
+            if !self.writable_roots.contains(&root) {
+                self.writable_roots.push(root);
+            }
+        }
+        self
+    }
+
+    /// Whether nothing is configured, leaving the sandbox to `--sandbox` alone.
+    pub fn is_empty(&self) -> bool {
+        self == &Self::default()
+    }
+
+    /// The `sandboxPolicy` of each `turn/start` under `launch`, for a thread whose effective
+    /// sandbox is `thread_sandbox`.
+    ///
+    /// The settings refine `workspace-write`, which `--sandbox default` turns into; read-only and
+    /// full-access sandboxes (including `--yolo` and planning rounds) are left alone. A turn policy
+    /// replaces the thread's, so whatever is not set here (network access from
+    /// `~/.codex/config.toml` or `[codex.config]`, its writable roots) is kept from
+    /// `thread_sandbox`.
+    pub fn turn_policy(
+        &self,
+        launch: AppServerLaunchConfig,
+        thread_sandbox: &SandboxPolicy,
+    ) -> Option<SandboxPolicy> {
+        if self.is_empty() || launch.bypass_approvals_and_sandbox {
+            return None;
+        }
+        if matches!(
+            launch.thread_sandbox,
+            Some(SandboxMode::ReadOnly | SandboxMode::DangerFullAccess)
+        ) {
+            return None;
+        }
+        let (mut writable_roots, network_access, exclude_tmpdir_env_var, exclude_slash_tmp) =
+            match thread_sandbox {
+                SandboxPolicy::WorkspaceWrite {
+                    writable_roots,
+                    network_access,
+                    exclude_tmpdir_env_var,
+                    exclude_slash_tmp,
+                } => (
+                    writable_roots.clone(),
+                    *network_access,
+                    *exclude_tmpdir_env_var,
+                    *exclude_slash_tmp,
+                ),
+                _ => (Vec::new(), false, false, false),
+            };
+        for root in &self.writable_roots {
+            if let Ok(root) = AbsolutePathBuf::from_absolute_path(root)
+                && !writable_roots.contains(&root)
+            {
+                writable_roots.push(root);
+            }
+        }
+        Some(SandboxPolicy::WorkspaceWrite {
+            writable_roots,
+            network_access: self.network_access.unwrap_or(network_access),
+            exclude_tmpdir_env_var: self
+                .exclude_tmpdir_env_var
+                .unwrap_or(exclude_tmpdir_env_var),
+            exclude_slash_tmp: self.exclude_slash_tmp.unwrap_or(exclude_slash_tmp),
+        })
+    }
+}
+
+fn parse_sandbox_config(sandbox: &dyn TableLike) -> anyhow::Result<WorkspaceWriteSettings> {
+    let writable_roots = sandbox
+        .get("writable_roots")
+        .map(|item| {
+            string_array(item).context("`sandbox.writable_roots` must be an array of strings")
+        })
+        .transpose()?
+        .unwrap_or_default()
+        .into_iter()
+        .map(PathBuf::from)
+        .collect();
+    Ok(WorkspaceWriteSettings {
+        writable_roots,
+        network_access: read_bool(sandbox, "network_access")?,
+        exclude_tmpdir_env_var: read_bool(sandbox, "exclude_tmpdir_env_var")?,
+        exclude_slash_tmp: read_bool(sandbox, "exclude_slash_tmp")?,
+    })
+}
+
+fn read_bool(table: &dyn TableLike, key: &str) -> anyhow::Result<Option<bool>> {
+    table
+        .get(key)
+        .map(|item| {
+            item.as_bool()
+                .with_context(|| format!("`sandbox.{key}` must be a boolean"))
+        })
+        .transpose()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigSource;
+    use pretty_assertions::assert_eq;
+
+    fn launch(thread_sandbox: Option<SandboxMode>) -> AppServerLaunchConfig {
+        AppServerLaunchConfig {
+            spawn_sandbox: thread_sandbox,
+            thread_sandbox,
+            bypass_approvals_and_sandbox: false,
+        }
+    }
+
+    fn load(
+        layers: &[(ConfigSource, &str)],
+        cli: WorkspaceWriteSettings,
+    ) -> anyhow::Result<WorkspaceWriteSettings> {
+        WorkspaceWriteSettings::load(
+            &LayeredConfig::from_toml(layers)?,
+            Path::new("/repo/app"),
+            cli,
+        )
+    }
+
+    #[test]
+    fn settings_layer_cli_over_home_and_repository_config() {
+        let cli = WorkspaceWriteSettings {
+            writable_roots: vec![PathBuf::from("/tmp/out"), PathBuf::from("/repo/cache")],
+            network_access: Some(true),
+            ..Default::default()
+        };
+        let settings = load(
+            &[
+                (
+                    ConfigSource::Home,
+                    "[sandbox]\nwritable_roots = [\"/opt/shared\", \"../cache\"]\nnetwork_access = false\nexclude_slash_tmp = true\n",
+                ),
+                (
+                    ConfigSource::Repository,
+                    "[sandbox]\nexclude_slash_tmp = false\nexclude_tmpdir_env_var = true\n",
+                ),
+            ],
+            cli,
+        )
+        .expect("load settings");
+        assert_eq!(
+            settings,
+            WorkspaceWriteSettings {
+                writable_roots: vec![
+                    PathBuf::from("/tmp/out"),
+                    PathBuf::from("/repo/cache"),
+                    PathBuf::from("/opt/shared"),
+                ],
+                network_access: Some(true),
+                exclude_tmpdir_env_var: Some(true),
+                exclude_slash_tmp: Some(true),
+            }
+        );
+    }
+
+    #[test]
+    fn repository_config_cannot_widen_the_sandbox() {
+        for (content, key) in [
+            (
+                "[sandbox]\nwritable_roots = [\"/\"]\n",
+                "`sandbox.writable_roots`",
+            ),
+            (
+                "[sandbox]\nnetwork_access = true\n",
+                "`sandbox.network_access`",
+            ),
+        ] {
+            let err = load(
+                &[(ConfigSource::Repository, content)],
+                WorkspaceWriteSettings::default(),
+            )
+            .expect_err("repository sandbox widening is rejected");
+            assert!(
+                format!("{err:#}").contains(key),
+                "unexpected error for {content:?}: {err:#}"
+            );
+        }
+    }
+
+    #[test]
+    fn turn_policy_refines_workspace_write_only() {
+        let settings = WorkspaceWriteSettings {
+            writable_roots: vec![PathBuf::from("/repo/cache")],
+            network_access: Some(true),
+            ..Default::default()
+        };
+        let expected = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![
+                AbsolutePathBuf::from_absolute_path("/repo/cache").expect("absolute path"),
+            ],
+            network_access: true,
+            exclude_tmpdir_env_var: false,
+            exclude_slash_tmp: false,
+        };
+        let thread_sandbox = SandboxPolicy::ReadOnly;
+
+        assert_eq!(
+            settings.turn_policy(launch(None), &thread_sandbox),
+            Some(expected.clone())
+        );
+        assert_eq!(
+            settings.turn_policy(launch(Some(SandboxMode::WorkspaceWrite)), &thread_sandbox),
+            Some(expected)
+        );
+        assert_eq!(
+            settings.turn_policy(launch(Some(SandboxMode::ReadOnly)), &thread_sandbox),
+            None
+        );
+        assert_eq!(
+            settings.turn_policy(
+                AppServerLaunchConfig::from_cli(crate::CliSandbox::Default, true),
+                &thread_sandbox
+            ),
+            None
+        );
+        assert_eq!(
+            WorkspaceWriteSettings::default().turn_policy(launch(None), &thread_sandbox),
+            None
+        );
+    }
+
+    #[test]
+    fn turn_policy_keeps_what_it_does_not_set_from_the_thread_sandbox() {
+        // `[codex.config] sandbox_workspace_write.network_access = true` with `--writable-root`.
+        let settings = WorkspaceWriteSettings {
+            writable_roots: vec![PathBuf::from("/repo/cache")],
+            ..Default::default()
+        };
+        let thread_sandbox = SandboxPolicy::WorkspaceWrite {
+            writable_roots: vec![
+                AbsolutePathBuf::from_absolute_path("/repo/shared").expect("absolute path"),
+            ],
+            network_access: true,
+            exclude_tmpdir_env_var: true,
+            exclude_slash_tmp: false,
+        };
+        assert_eq!(
+            settings.turn_policy(launch(None), &thread_sandbox),
+            Some(SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path("/repo/shared").expect("absolute path"),
+                    AbsolutePathBuf::from_absolute_path("/repo/cache").expect("absolute path"),
+                ],
+                network_access: true,
+                exclude_tmpdir_env_var: true,
+                exclude_slash_tmp: false,
+            })
+        );
+
+        let settings = WorkspaceWriteSettings {
+            network_access: Some(false),
+            ..settings
+        };
+        assert_eq!(
+            settings.turn_policy(launch(None), &thread_sandbox),
+            Some(SandboxPolicy::WorkspaceWrite {
+                writable_roots: vec![
+                    AbsolutePathBuf::from_absolute_path("/repo/shared").expect("absolute path"),
+                    AbsolutePathBuf::from_absolute_path("/repo/cache").expect("absolute path"),
+                ],
+                network_access: false,
+                exclude_tmpdir_env_var: true,
+                exclude_slash_tmp: false,
+            })
+        );
+    }
+
+    #[test]
+    fn invalid_sandbox_config_is_rejected() {
+        for content in [
+            "sandbox = 1\n",
+            "[sandbox]\nwritable_roots = \"../cache\"\n",
+            "[sandbox]\nnetwork_access = \"on\"\n",
+        ] {
+            assert!(
+                load(
+                    &[(ConfigSource::Home, content)],
+                    WorkspaceWriteSettings::default()
+                )
+                .is_err(),
+                "expected an error for:\n{content}"
+            );
+        }
+    }
+}
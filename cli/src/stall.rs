@@ -0,0 +1,230 @@
+//! Stall detection (`--max-stalled-rounds`): stop a session whose rounds no longer make progress.
+//!
+//! After every completed round the runner takes a [`ProgressFingerprint`] of the project: the git
+//! tree of the working directory (uncommitted and untracked changes included) and the task lists
+//! of the progress file with their open/done counts. A round that ends on a fingerprint already
+//! seen since the last finished task (or the session start) made no progress, even when it undid
+//! what the round before did; after `--max-stalled-rounds` such rounds in a row the session stops
+//! as stalled.
+
+use std::collections::HashSet;
+use std::path::Path;
+use std::process::Command;
+
+use crate::progress_file::ProgressFile;
+use crate::progress_file::ProgressTask;
+use crate::progress_file::TaskCounts;
+
+/// What a round can change about a project.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct ProgressFingerprint {
+    /// Tree hash of the working directory, ignoring `.codexpotter/`; `None` outside a git
+    /// repository.
+    git_tree: Option<String>,
+    /// `## In Progress`, `## Todo` and `## Done` of the progress file; notes elsewhere in the file
+    /// are not progress.
+    task_lists: [Vec<ProgressTask>; 3],
+    tasks: TaskCounts,
+}
+
+impl ProgressFingerprint {
+    /// Fingerprint the repository at `git_workdir` and the progress file at `progress_file`.
+    pub fn capture(git_workdir: &Path, progress_file: &Path) -> Self {
+        let contents = std::fs::read_to_string(progress_file).unwrap_or_default();
+        let progress_file = ProgressFile::parse(&contents);
+        let tasks = progress_file.task_counts();
+        Self {
+            git_tree: working_tree_hash(git_workdir),
+            task_lists: [
+                progress_file.in_progress,
+                progress_file.todo,
+                progress_file.done,
+            ],
+            tasks,
+        }
+    }
+}
+
+/// Consecutive rounds without progress, carried from one round to the next.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct StallState {
+    /// Fingerprints reached since the last finished task, or since session start.
+    seen: HashSet<ProgressFingerprint>,
+    /// Most `## Done` tasks any fingerprint so far had.
+    most_done: usize,
+    /// Completed rounds in a row that ended on a fingerprint in `seen`.
+    pub stalled_rounds: u32,
+}
+
+impl StallState {
+    /// Start counting from the project's state at session start.
+    pub fn new(baseline: ProgressFingerprint) -> Self {
+        Self {
+            most_done: baseline.tasks.done,
+            seen: HashSet::from([baseline]),
+            stalled_rounds: 0,
+        }
+    }
+
+    /// The state after a completed round that left the project at `fingerprint`.
+    pub fn after_round(&self, fingerprint: ProgressFingerprint) -> Self {
+        // A finished task is real progress; the states before it are forgotten.
+        if fingerprint.tasks.done > self.most_done {
+            return Self::new(fingerprint);
+        }
+        let mut seen = self.seen.clone();
+        let stalled_rounds = if seen.insert(fingerprint) {
+            0
+        } else {
+            self.stalled_rounds.saturating_add(1)
+        };
+        Self {
+            seen,
+            most_done: self.most_done,
+            stalled_rounds,
+        }
+    }
+}
+
+/// Hash the working tree like `git add -A && git write-tree` would, using a copy of the index so
+/// the repository's own index is left alone.
+fn working_tree_hash(git_workdir: &Path) -> Option<String> {
+    let toplevel = git_stdout(git_workdir, None, &["rev-parse", "--show-toplevel"])?;
+    let toplevel = Path::new(&toplevel);
+    let index = git_stdout(toplevel, None, &["rev-parse", "--git-path", "index"])?;
+    let index = toplevel.join(index);
+
+    let temp = tempfile::tempdir().ok()?;
+    let temp_index = temp.path().join("index");
+    // A repository without commits may not have an index yet; git then starts from an empty one.
+    if index.exists() {
+        std::fs::copy(&index, &temp_index).ok()?;
+    }
+    git_stdout(
+        toplevel,
+        Some(&temp_index),
+        &["add", "-A", "--", ".", ":(exclude,glob)**/.codexpotter/**"],
+    );
+    git_stdout(toplevel, Some(&temp_index), &["write-tree"])
+}
+
+fn git_stdout(workdir: &Path, index: Option<&Path>, args: &[&str]) -> Option<String> {
+    let mut command = Command::new("git");
+    command.arg("-C").arg(workdir).args(args);
+    if let Some(index) = index {
+        command.env("GIT_INDEX_FILE", index);
+    }
+    let output = command.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_git::git;
+    use crate::test_git::git_available;
+    use crate::test_git::init_repo;
+    use pretty_assertions::assert_eq;
+
+    fn fingerprint(tree: &str, todo: &[&str], done: &[&str]) -> ProgressFingerprint {
+        let tasks = |texts: &[&str]| {
+            texts
+                .iter()
+                .map(|text| ProgressTask {
+                    text: text.to_string(),
+                    checked: false,
+                })
+                .collect::<Vec<_>>()
+        };
+        ProgressFingerprint {
+            git_tree: Some(tree.to_string()),
+            task_lists: [Vec::new(), tasks(todo), tasks(done)],
+            tasks: TaskCounts {
+                todo: todo.len(),
+                done: done.len(),
+            },
+        }
+    }
+
+    #[test]
+    fn stall_state_counts_rounds_that_change_nothing() {
+        let state = StallState::new(fingerprint("a", &["parser", "tests"], &[]));
+        let state = state.after_round(fingerprint("a", &["parser", "tests"], &[]));
+        assert_eq!(state.stalled_rounds, 1);
+        let state = state.after_round(fingerprint("a", &["parser", "tests"], &[]));
+        assert_eq!(state.stalled_rounds, 2);
+        let state = state.after_round(fingerprint("b", &["parser", "tests"], &[]));
+        assert_eq!(state.stalled_rounds, 0);
+        let state = state.after_round(fingerprint("b", &["parser"], &["tests"]));
+        assert_eq!(state.stalled_rounds, 0);
+        assert_eq!(
+            StallState::default()
+                .after_round(fingerprint("b", &["parser"], &[]))
+                .stalled_rounds,
+            0
+        );
+    }
+
+    #[test]
+    fn stall_state_counts_rounds_that_go_back_and_forth() {
+        let a = fingerprint("a", &["parser"], &[]);
+        let b = fingerprint("b", &["parser"], &[]);
+
+        let state = StallState::new(a.clone()).after_round(b.clone());
+        assert_eq!(state.stalled_rounds, 0);
+        let state = state.after_round(a.clone());
+        assert_eq!(state.stalled_rounds, 1);
+        let state = state.after_round(b.clone());
+        assert_eq!(state.stalled_rounds, 2);
+
+        // Finishing a task forgets the states before it.
+        let state = state.after_round(fingerprint("c", &[], &["parser"]));
+        assert_eq!(state.stalled_rounds, 0);
+        assert_eq!(state.after_round(a).stalled_rounds, 0);
+    }
+
+    #[test]
+    fn fingerprint_tracks_uncommitted_changes_but_not_codexpotter_files() {
+        if !git_available() {
+            return;
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path();
+        init_repo(repo);
+        std::fs::write(repo.join("README.md"), "hello\n").expect("write file");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+        let progress_file = repo.join(".codexpotter/projects/2026/02/01/1/MAIN.md");
+        std::fs::create_dir_all(progress_file.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&progress_file, "## Todo\n\n- Add the parser\n").expect("write progress");
+
+        let start = ProgressFingerprint::capture(repo, &progress_file);
+        assert!(start.git_tree.is_some());
+        assert_eq!(start.tasks, TaskCounts { todo: 1, done: 0 });
+
+        std::fs::write(repo.join(".codexpotter/kb.md"), "notes\n").expect("write kb");
+        assert_eq!(ProgressFingerprint::capture(repo, &progress_file), start);
+        // Only the task lists of the progress file count.
+        std::fs::write(
+            &progress_file,
+            "# Overall Goal\n\nParse things\n\n## Todo\n\n- Add the parser\n",
+        )
+        .expect("write progress");
+        assert_eq!(ProgressFingerprint::capture(repo, &progress_file), start);
+
+        std::fs::write(repo.join("parser.rs"), "fn parse() {}\n").expect("write file");
+        let changed = ProgressFingerprint::capture(repo, &progress_file);
+        assert_ne!(changed.git_tree, start.git_tree);
+        // Nothing was staged in the repository's own index.
+        git(repo, &["diff", "--cached", "--quiet"]);
+
+        std::fs::write(&progress_file, "## Done\n\n- Add the parser\n").expect("write progress");
+        let done = ProgressFingerprint::capture(repo, &progress_file);
+        assert_eq!(done.git_tree, changed.git_tree);
+        assert_eq!(done.tasks, TaskCounts { todo: 0, done: 1 });
+    }
+}
@@ -0,0 +1,30 @@
+//! Git helpers for tests that run against a real repository.
+
+use std::path::Path;
+use std::process::Command;
+
+/// Whether a `git` binary can be run; tests that need one return early without it.
+pub fn git_available() -> bool {
+    Command::new("git").arg("--version").output().is_ok()
+}
+
+/// Run `git -C <workdir> <args>` and fail the test unless it succeeds.
+pub fn git(workdir: &Path, args: &[&str]) {
+    assert!(
+        Command::new("git")
+            .arg("-C")
+            .arg(workdir)
+            .args(args)
+            .status()
+            .expect("run git")
+            .success(),
+        "git {args:?} failed"
+    );
+}
+
+/// Create an empty repository at `repo` with a committer identity.
+pub fn init_repo(repo: &Path) {
+    git(repo, &["init", "-q"]);
+    git(repo, &["config", "user.name", "test"]);
+    git(repo, &["config", "user.email", "test@example.com"]);
+}
@@ -0,0 +1,319 @@
+//! Answers for the questions the agent asks mid-turn (`request_user_input` tool calls) and for MCP
+//! server elicitations.
+//!
+//! In the TUI the user answers them in an overlay. Without the TUI (`exec`, `--json`,
+//! `--parallel`) `user_input.headless` answers instead, read from the `[user_input]` table of
+//! `~/.codexpotter/config.toml`. `first_option` accepts every MCP elicitation, so the repository
+//! file, which the agent can write, cannot set it:
+//!
+//! ```toml
+//! [user_input]
+//! # "decide_yourself" (default): tell the agent to make the call itself; decline elicitations.
+//! # "first_option": pick the first offered option; accept elicitations.
+//! headless = "first_option"
+//! ```
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use codex_protocol::protocol::PotterElicitationDecision;
+use codex_protocol::protocol::PotterPromptAnswer;
+use codex_protocol::request_user_input::DECIDE_YOURSELF_ANSWER;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use toml_edit::TableLike;
+
+use crate::config::LayeredConfig;
+use crate::config::read_str;
+use crate::config::reject_in_repository;
+
+/// How questions are answered when no one is there to ask.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HeadlessUserInput {
+    /// Pick the first offered option (questions without options get the decide-yourself answer).
+    FirstOption,
+    /// Tell the agent to decide itself.
+    DecideYourself,
+}
+
+impl HeadlessUserInput {
+    fn parse(value: &str) -> anyhow::Result<Self> {
+        match value {
+            "first_option" => Ok(Self::FirstOption),
+            "decide_yourself" => Ok(Self::DecideYourself),
+            other => {
+                anyhow::bail!("unknown answer {other:?} (expected first_option or decide_yourself)")
+            }
+        }
+    }
+}
+
+/// Question answering of one `codex-potter` invocation.
+#[derive(Debug, Clone)]
+pub struct UserInputPolicy {
+    headless: HeadlessUserInput,
+    /// Whether the user can answer (the TUI is running).
+    interactive: bool,
+}
+
+impl Default for UserInputPolicy {
+    /// Let the agent decide (no configuration, no TUI).
+    fn default() -> Self {
+        Self {
+            headless: HeadlessUserInput::DecideYourself,
+            interactive: false,
+        }
+    }
+}
+
+impl UserInputPolicy {
+    /// Load `user_input.headless` from the `[user_input]` tables of `config`.
+    pub fn load(config: &LayeredConfig, interactive: bool) -> anyhow::Result<Self> {
+        let headless = config
+            .tables("user_input", |source, table| {
+                reject_in_repository(source, "user_input", table, &["headless"])?;
+                parse_headless(table)
+            })?
+            .into_iter()
+            .find_map(|headless| headless);
+        Ok(Self {
+            headless: headless.unwrap_or(HeadlessUserInput::DecideYourself),
+            interactive,
+        })
+    }
+
+    /// The same policy, answering with `user_input.headless` (`exec`, `--json`, `--parallel`).
+    pub fn headless(self) -> Self {
+        Self {
+            interactive: false,
+            ..self
+        }
+    }
+
+    /// Whether prompts go to the user instead of being answered right away.
+    pub fn asks_user(&self) -> bool {
+        self.interactive
+    }
+
+    /// Answer `questions` with `user_input.headless`.
+    pub fn answer(&self, questions: &[RequestUserInputQuestion]) -> RequestUserInputResponse {
+        let answers = questions
+            .iter()
+            .map(|question| {
+                let first_option = question
+                    .options
+                    .as_ref()
+                    .and_then(|options| options.first())
+                    .map(|option| option.label.clone());
+                let answer = match (self.headless, first_option) {
+                    (HeadlessUserInput::FirstOption, Some(label)) => label,
+                    _ => DECIDE_YOURSELF_ANSWER.to_string(),
+                };
+                (
+                    question.id.clone(),
+                    RequestUserInputAnswer {
+                        answers: vec![answer],
+                    },
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        RequestUserInputResponse { answers }
+    }
+
+    /// Answer an elicitation with `user_input.headless`.
+    pub fn decide_elicitation(&self) -> PotterElicitationDecision {
+        match self.headless {
+            HeadlessUserInput::FirstOption => PotterElicitationDecision::Accept,
+            HeadlessUserInput::DecideYourself => PotterElicitationDecision::Decline,
+        }
+    }
+}
+
+/// The answered questions as shown in the transcript; secret answers are masked.
+pub fn transcript_answers(
+    questions: &[RequestUserInputQuestion],
+    response: &RequestUserInputResponse,
+) -> Vec<PotterPromptAnswer> {
+    questions
+        .iter()
+        .map(|question| {
+            let answers = response
+                .answers
+                .get(&question.id)
+                .map(|answer| answer.answers.as_slice())
+                .unwrap_or_default();
+            let answer = if answers.is_empty() {
+                "(no answer)".to_string()
+            } else if question.is_secret {
+                "(secret)".to_string()
+            } else {
+                answers.join(", ")
+            };
+            PotterPromptAnswer {
+                question: question.question.clone(),
+                answer,
+            }
+        })
+        .collect()
+}
+
+fn parse_headless(user_input: &dyn TableLike) -> anyhow::Result<Option<HeadlessUserInput>> {
+    read_str(user_input, "headless")?
+        .map(HeadlessUserInput::parse)
+        .transpose()
+        .context("user_input.headless")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ConfigSource;
+    use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+    use pretty_assertions::assert_eq;
+
+    fn question(id: &str, options: &[&str]) -> RequestUserInputQuestion {
+        RequestUserInputQuestion {
+            id: id.to_string(),
+            header: id.to_string(),
+            question: format!("{id}?"),
+            is_other: false,
+            is_secret: false,
+            options: (!options.is_empty()).then(|| {
+                options
+                    .iter()
+                    .map(|label| RequestUserInputQuestionOption {
+                        label: label.to_string(),
+                        description: String::new(),
+                    })
+                    .collect()
+            }),
+        }
+    }
+
+    fn answers(response: &RequestUserInputResponse, id: &str) -> Vec<String> {
+        response.answers[id].answers.clone()
+    }
+
+    fn load(layers: &[(ConfigSource, &str)], interactive: bool) -> anyhow::Result<UserInputPolicy> {
+        UserInputPolicy::load(&LayeredConfig::from_toml(layers)?, interactive)
+    }
+
+    #[test]
+    fn headless_answer_follows_config() {
+        let questions = [question("scope", &["cli", "tui"]), question("name", &[])];
+
+        let policy = load(
+            &[(
+                ConfigSource::Home,
+                "[user_input]\nheadless = \"first_option\"\n",
+            )],
+            true,
+        )
+        .expect("load policy");
+        assert!(policy.asks_user());
+        let response = policy.headless().answer(&questions);
+        assert_eq!(answers(&response, "scope"), vec!["cli".to_string()]);
+        assert_eq!(
+            answers(&response, "name"),
+            vec![DECIDE_YOURSELF_ANSWER.to_string()]
+        );
+
+        let policy = load(
+            &[(
+                ConfigSource::Home,
+                "[user_input]\nheadless = \"decide_yourself\"\n",
+            )],
+            false,
+        )
+        .expect("load policy");
+        assert!(!policy.asks_user());
+        assert_eq!(
+            answers(&policy.answer(&questions), "scope"),
+            vec![DECIDE_YOURSELF_ANSWER.to_string()]
+        );
+        assert_eq!(
+            policy.decide_elicitation(),
+            PotterElicitationDecision::Decline
+        );
+    }
+
+    #[test]
+    fn repository_config_cannot_set_headless_answer() {
+        let err = load(
+            &[(
+                ConfigSource::Repository,
+                "[user_input]\nheadless = \"first_option\"\n",
+            )],
+            false,
+        )
+        .expect_err("repository headless is rejected");
+        assert!(
+            format!("{err:#}").contains("`user_input.headless`"),
+            "unexpected error: {err:#}"
+        );
+    }
+
+    #[test]
+    fn invalid_headless_answer_is_rejected() {
+        assert!(
+            load(
+                &[(ConfigSource::Home, "[user_input]\nheadless = \"ask\"\n")],
+                false
+            )
+            .is_err()
+        );
+        assert!(load(&[(ConfigSource::Home, "user_input = 1\n")], false).is_err());
+        assert_eq!(
+            load(&[(ConfigSource::Home, "model = \"x\"\n")], false)
+                .expect("load policy")
+                .decide_elicitation(),
+            PotterElicitationDecision::Decline
+        );
+    }
+
+    #[test]
+    fn transcript_masks_secret_answers() {
+        let mut secret = question("token", &[]);
+        secret.is_secret = true;
+        let questions = [
+            question("scope", &["cli"]),
+            secret,
+            question("skipped", &[]),
+        ];
+        let response = RequestUserInputResponse {
+            answers: HashMap::from([
+                (
+                    "scope".to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec!["cli".to_string()],
+                    },
+                ),
+                (
+                    "token".to_string(),
+                    RequestUserInputAnswer {
+                        answers: vec!["hunter2".to_string()],
+                    },
+                ),
+            ]),
+        };
+
+        assert_eq!(
+            transcript_answers(&questions, &response),
+            vec![
+                PotterPromptAnswer {
+                    question: "scope?".to_string(),
+                    answer: "cli".to_string(),
+                },
+                PotterPromptAnswer {
+                    question: "token?".to_string(),
+                    answer: "(secret)".to_string(),
+                },
+                PotterPromptAnswer {
+                    question: "skipped?".to_string(),
+                    answer: "(no answer)".to_string(),
+                },
+            ]
+        );
+    }
+}
@@ -0,0 +1,205 @@
+//! Verification command gate (`--verify`).
+//!
+//! After every completed round, codex-potter runs the configured shell command in the session
+//! working directory. A session only succeeds when the progress file is marked
+//! `finite_incantatem: true` *and* the verifier passes; a failing verifier's output tail is fed
+//! back to the next round through the developer prompt.
+
+use std::path::Path;
+use std::process::Stdio;
+use std::time::Duration;
+use std::time::Instant;
+
+/// Keep at most this many trailing output lines from the verifier.
+const OUTPUT_TAIL_MAX_LINES: usize = 40;
+/// Keep at most this many trailing output bytes from the verifier.
+const OUTPUT_TAIL_MAX_BYTES: usize = 4096;
+
+/// Result of one verification run.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VerificationResult {
+    pub command: String,
+    pub success: bool,
+    /// `None` when the command could not be started or was killed by a signal.
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    /// Truncated tail of stdout followed by stderr.
+    pub output_tail: String,
+}
+
+/// Run `command` through the platform shell in `cwd` and capture its result.
+///
+/// Spawn failures and runs that outlast `timeout` (`--verify-timeout`) are reported as a failed
+/// verification rather than an error, so a broken or hung command surfaces to the agent like any
+/// other failure. A timed-out command is killed.
+pub async fn run_verification(command: &str, cwd: &Path, timeout: Duration) -> VerificationResult {
+    let started_at = Instant::now();
+    let output = tokio::time::timeout(
+        timeout,
+        shell_command(command)
+            .current_dir(cwd)
+            .stdin(Stdio::null())
+            .kill_on_drop(true)
+            .output(),
+    )
+    .await;
+
+    match output {
+        Ok(Ok(output)) => {
+            let mut combined = String::from_utf8_lossy(&output.stdout).to_string();
+            combined.push_str(&String::from_utf8_lossy(&output.stderr));
+            VerificationResult {
+                command: command.to_string(),
+                success: output.status.success(),
+                exit_code: output.status.code(),
+                duration: started_at.elapsed(),
+                output_tail: output_tail(&combined),
+            }
+        }
+        Ok(Err(err)) => VerificationResult {
+            command: command.to_string(),
+            success: false,
+            exit_code: None,
+            duration: started_at.elapsed(),
+            output_tail: format!("failed to run verification command: {err}"),
+        },
+        Err(_) => VerificationResult {
+            command: command.to_string(),
+            success: false,
+            exit_code: None,
+            duration: started_at.elapsed(),
+            output_tail: format!(
+                "verification command timed out after {}s and was killed",
+                timeout.as_secs()
+            ),
+        },
+    }
+}
+
+/// Developer prompt for the round after `verification` failed, or `developer_prompt` unchanged
+/// when there is nothing to report.
+pub fn developer_prompt_with_feedback(
+    developer_prompt: &str,
+    verification: Option<&VerificationResult>,
+) -> String {
+    let Some(verification) = verification.filter(|verification| !verification.success) else {
+        return developer_prompt.to_string();
+    };
+
+    let status = match verification.exit_code {
+        Some(code) => format!("exited with code {code}"),
+        None => "did not exit normally".to_string(),
+    };
+    format!(
+        "{developer_prompt}\n\n<VERIFICATION_FAILED>\n\nAfter the previous round, the verification command `{}` {status}. The task is not done until it passes: fix the failures, and do not mark `finite_incantatem` as true while it fails.\n\nOutput tail:\n\n```\n{}\n```\n\n</VERIFICATION_FAILED>\n",
+        verification.command,
+        verification.output_tail.trim_end(),
+    )
+}
+
+#[cfg(not(windows))]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(windows)]
+fn shell_command(command: &str) -> tokio::process::Command {
+    let mut cmd = tokio::process::Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+fn output_tail(output: &str) -> String {
+    let lines = output.lines().collect::<Vec<_>>();
+    let mut tail = lines[lines.len().saturating_sub(OUTPUT_TAIL_MAX_LINES)..].join("\n");
+    if tail.len() > OUTPUT_TAIL_MAX_BYTES {
+        let mut start = tail.len() - OUTPUT_TAIL_MAX_BYTES;
+        while !tail.is_char_boundary(start) {
+            start += 1;
+        }
+        tail = tail.split_off(start);
+    }
+    tail
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn output_tail_keeps_last_lines_and_bytes() {
+        let output = (1..=100)
+            .map(|n| format!("line {n}"))
+            .collect::<Vec<_>>()
+            .join("\n");
+        let tail = output_tail(&output);
+        assert_eq!(tail.lines().count(), OUTPUT_TAIL_MAX_LINES);
+        assert_eq!(tail.lines().last(), Some("line 100"));
+
+        let long = "é".repeat(OUTPUT_TAIL_MAX_BYTES);
+        assert!(output_tail(&long).len() <= OUTPUT_TAIL_MAX_BYTES);
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn run_verification_reports_exit_code_and_output() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let passed = run_verification("echo ok", dir.path(), Duration::from_secs(30)).await;
+        assert!(passed.success);
+        assert_eq!(passed.exit_code, Some(0));
+        assert_eq!(passed.output_tail, "ok");
+
+        let failed = run_verification(
+            "echo out; echo err >&2; exit 3",
+            dir.path(),
+            Duration::from_secs(30),
+        )
+        .await;
+        assert!(!failed.success);
+        assert_eq!(failed.exit_code, Some(3));
+        assert_eq!(failed.output_tail, "out\nerr");
+    }
+
+    #[cfg(not(windows))]
+    #[tokio::test]
+    async fn run_verification_fails_a_command_that_outlasts_the_timeout() {
+        let dir = tempfile::tempdir().expect("tempdir");
+
+        let timed_out = run_verification("sleep 30", dir.path(), Duration::from_secs(1)).await;
+        assert!(!timed_out.success);
+        assert_eq!(timed_out.exit_code, None);
+        assert!(timed_out.duration < Duration::from_secs(30));
+        assert_eq!(
+            timed_out.output_tail,
+            "verification command timed out after 1s and was killed"
+        );
+    }
+
+    #[test]
+    fn developer_prompt_with_feedback_only_appends_failures() {
+        let mut verification = VerificationResult {
+            command: "cargo test".to_string(),
+            success: true,
+            exit_code: Some(0),
+            duration: Duration::from_secs(1),
+            output_tail: "test result: ok".to_string(),
+        };
+        assert_eq!(
+            developer_prompt_with_feedback("base", Some(&verification)),
+            "base"
+        );
+        assert_eq!(developer_prompt_with_feedback("base", None), "base");
+
+        verification.success = false;
+        verification.exit_code = Some(101);
+        verification.output_tail = "test foo ... FAILED\n".to_string();
+        let prompt = developer_prompt_with_feedback("base", Some(&verification));
+        assert!(prompt.starts_with("base\n\n<VERIFICATION_FAILED>"));
+        assert!(prompt.contains("`cargo test` exited with code 101"));
+        assert!(prompt.contains("```\ntest foo ... FAILED\n```"));
+    }
+}
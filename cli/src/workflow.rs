@@ -0,0 +1,250 @@
+//! Named workflow bundles (`--workflow`).
+//!
+//! A workflow picks the developer prompt and progress file template, the sandbox used when
+//! `--sandbox` is left at `default`, and which repository changes the runner tolerates. The
+//! workflow of a project is recorded in the progress file front matter (`workflow:`), so `resume`
+//! keeps using it.
+
+use std::collections::BTreeMap;
+use std::collections::BTreeSet;
+use std::collections::hash_map::DefaultHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::path::Path;
+use std::process::Command;
+
+use clap::ValueEnum;
+
+use crate::app_server_protocol::SandboxMode;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+#[clap(rename_all = "kebab-case")]
+pub enum Workflow {
+    /// Investigate and write an implementation plan; no repository changes.
+    Plan,
+    /// Make the requested changes and commit them (the original loop).
+    #[default]
+    Implement,
+    /// Review existing changes and write a report; no repository changes.
+    Review,
+    /// Answer a question with a written report; never commits.
+    Research,
+}
+
+/// Repository changes a workflow tolerates, checked after every completed round.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum RepoPolicy {
+    AnyChanges,
+    NoCommits,
+    ReadOnly,
+}
+
+impl Workflow {
+    /// Name used on the command line and in the progress file front matter.
+    pub fn name(self) -> &'static str {
+        match self {
+            Workflow::Plan => "plan",
+            Workflow::Implement => "implement",
+            Workflow::Review => "review",
+            Workflow::Research => "research",
+        }
+    }
+
+    /// Parse the front matter `workflow:` value; projects without one use `implement`.
+    pub fn from_front_matter(value: Option<&str>) -> anyhow::Result<Self> {
+        match value {
+            None => Ok(Workflow::Implement),
+            Some(value) => Workflow::from_str(value, true)
+                .map_err(|_| anyhow::anyhow!("unknown workflow `{value}` in progress file")),
+        }
+    }
+
+    /// Sandbox requested when the user leaves `--sandbox` at `default`.
+    pub fn default_sandbox(self) -> Option<SandboxMode> {
+        match self {
+            Workflow::Implement => None,
+            Workflow::Plan | Workflow::Review | Workflow::Research => {
+                Some(SandboxMode::WorkspaceWrite)
+            }
+        }
+    }
+
+    fn repo_policy(self) -> RepoPolicy {
+        match self {
+            Workflow::Implement => RepoPolicy::AnyChanges,
+            Workflow::Research => RepoPolicy::NoCommits,
+            Workflow::Plan | Workflow::Review => RepoPolicy::ReadOnly,
+        }
+    }
+
+    /// Snapshot the repository state the workflow's rules are checked against, or `None` when
+    /// the workflow tolerates any change (or `git_workdir` is not a git repository).
+    pub fn repo_baseline(self, git_workdir: &Path) -> Option<RepoSnapshot> {
+        if self.repo_policy() == RepoPolicy::AnyChanges {
+            return None;
+        }
+        RepoSnapshot::capture(git_workdir)
+    }
+
+    /// Describe how the repository at `git_workdir` broke this workflow's rules since `baseline`
+    /// was captured, or `None` when it did not.
+    pub fn repo_policy_violation(
+        self,
+        git_workdir: &Path,
+        baseline: &RepoSnapshot,
+    ) -> Option<String> {
+        let policy = self.repo_policy();
+        if policy == RepoPolicy::AnyChanges {
+            return None;
+        }
+        let current = RepoSnapshot::capture(git_workdir)?;
+        if current.head != baseline.head {
+            return Some(format!(
+                "the {} workflow must not create commits (HEAD moved from {} to {})",
+                self.name(),
+                short_commit(&baseline.head),
+                short_commit(&current.head)
+            ));
+        }
+
+        if policy == RepoPolicy::ReadOnly {
+            let changed = current
+                .files
+                .keys()
+                .chain(baseline.files.keys())
+                .filter(|path| current.files.get(*path) != baseline.files.get(*path))
+                .map(String::as_str)
+                .collect::<BTreeSet<_>>();
+            if !changed.is_empty() {
+                return Some(format!(
+                    "the {} workflow must not modify the repository (changed: {})",
+                    self.name(),
+                    changed.into_iter().collect::<Vec<_>>().join(", ")
+                ));
+            }
+        }
+        None
+    }
+}
+
+/// `HEAD` plus a content fingerprint of every uncommitted file, so pre-existing local changes
+/// (e.g. the changes under review) do not count as violations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RepoSnapshot {
+    head: String,
+    /// Uncommitted paths relative to the repository root, mapped to a hash of their contents
+    /// (`None` when deleted). `.codexpotter/` is ignored.
+    files: BTreeMap<String, Option<u64>>,
+}
+
+impl RepoSnapshot {
+    fn capture(git_workdir: &Path) -> Option<Self> {
+        let head = git_stdout(git_workdir, &["rev-parse", "HEAD"])?
+            .trim()
+            .to_string();
+        let toplevel = git_stdout(git_workdir, &["rev-parse", "--show-toplevel"])?;
+        let toplevel = Path::new(toplevel.trim());
+        let status = git_stdout(
+            git_workdir,
+            &["status", "--porcelain", "--untracked-files=all"],
+        )?;
+        let files = status
+            .lines()
+            .filter_map(|line| line.get(3..))
+            .map(|path| path.rsplit_once(" -> ").map_or(path, |(_, to)| to))
+            .filter(|path| !path.split('/').any(|component| component == ".codexpotter"))
+            .map(|path| {
+                let hash = std::fs::read(toplevel.join(path)).ok().map(|contents| {
+                    let mut hasher = DefaultHasher::new();
+                    contents.hash(&mut hasher);
+                    hasher.finish()
+                });
+                (path.to_string(), hash)
+            })
+            .collect();
+        Some(Self { head, files })
+    }
+}
+
+fn short_commit(commit: &str) -> &str {
+    commit.get(..7).unwrap_or(commit)
+}
+
+fn git_stdout(workdir: &Path, args: &[&str]) -> Option<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workdir)
+        .args(args)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_git::git;
+    use crate::test_git::git_available;
+    use crate::test_git::init_repo;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn from_front_matter_defaults_to_implement() {
+        assert_eq!(
+            Workflow::from_front_matter(None).ok(),
+            Some(Workflow::Implement)
+        );
+        assert_eq!(
+            Workflow::from_front_matter(Some("review")).ok(),
+            Some(Workflow::Review)
+        );
+        assert!(Workflow::from_front_matter(Some("bugfix")).is_err());
+    }
+
+    #[test]
+    fn repo_policy_violation_reports_changes_and_commits() {
+        if !git_available() {
+            return;
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path();
+        init_repo(repo);
+        std::fs::write(repo.join("README.md"), "hello\n").expect("write file");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+        std::fs::write(repo.join("notes.txt"), "pre-existing change\n").expect("write file");
+        let baseline = Workflow::Review.repo_baseline(repo).expect("baseline");
+        assert_eq!(Workflow::Implement.repo_baseline(repo), None);
+
+        std::fs::create_dir_all(repo.join(".codexpotter/kb")).expect("mkdir");
+        std::fs::write(repo.join(".codexpotter/kb/notes.md"), "notes\n").expect("write kb");
+        assert_eq!(
+            Workflow::Review.repo_policy_violation(repo, &baseline),
+            None
+        );
+
+        std::fs::write(repo.join("README.md"), "changed\n").expect("write file");
+        std::fs::write(repo.join("notes.txt"), "edited\n").expect("write file");
+        assert_eq!(
+            Workflow::Review.repo_policy_violation(repo, &baseline),
+            Some(
+                "the review workflow must not modify the repository (changed: README.md, notes.txt)"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            Workflow::Research.repo_policy_violation(repo, &baseline),
+            None
+        );
+
+        git(repo, &["commit", "-q", "-am", "change"]);
+        let violation = Workflow::Research
+            .repo_policy_violation(repo, &baseline)
+            .expect("commit violation");
+        assert!(violation.starts_with("the research workflow must not create commits"));
+    }
+}
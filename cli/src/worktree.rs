@@ -0,0 +1,210 @@
+//! Git worktrees that isolate parallel sessions (`--parallel`).
+//!
+//! Each parallel session gets its own checkout under `.codexpotter/worktrees/<project path>` on a
+//! new `codexpotter/<project path>` branch, so concurrent agents never edit the same files. The
+//! worktrees are kept after the session ends so the results can be reviewed and merged.
+
+use std::path::Path;
+use std::path::PathBuf;
+use std::process::Command;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use anyhow::Context;
+
+/// `git worktree add` takes repository-wide locks, so concurrent sessions create worktrees one at
+/// a time.
+static WORKTREE_ADD_LOCK: Mutex<()> = Mutex::new(());
+
+/// Fail unless `workdir` is inside a git repository with at least one commit.
+pub fn ensure_worktree_support(workdir: &Path) -> anyhow::Result<()> {
+    run_git(workdir, &["rev-parse", "--verify", "--quiet", "HEAD"])
+        .context("no commit to branch from")?;
+    Ok(())
+}
+
+/// Create the worktree for the project at `project_path` (e.g. `2026/02/01/1`).
+///
+/// The worktree starts from the current `HEAD`. Returns the directory the session should run in:
+/// when `workdir` is a subdirectory of the repository, this is the same subdirectory inside the
+/// new worktree.
+pub fn create_session_worktree(workdir: &Path, project_path: &str) -> anyhow::Result<PathBuf> {
+    let worktree = workdir
+        .join(".codexpotter")
+        .join("worktrees")
+        .join(project_path);
+    let branch = format!("codexpotter/{project_path}");
+    if let Some(parent) = worktree.parent() {
+        std::fs::create_dir_all(parent).with_context(|| format!("create {}", parent.display()))?;
+    }
+
+    let prefix = run_git(workdir, &["rev-parse", "--show-prefix"])?;
+    {
+        let _guard = WORKTREE_ADD_LOCK
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner);
+        let worktree_arg = worktree.to_string_lossy();
+        run_git(
+            workdir,
+            &[
+                "worktree",
+                "add",
+                "-q",
+                "-b",
+                &branch,
+                &worktree_arg,
+                "HEAD",
+            ],
+        )
+        .with_context(|| format!("create git worktree {}", worktree.display()))?;
+    }
+
+    let prefix = prefix.trim();
+    if prefix.is_empty() {
+        return Ok(worktree);
+    }
+    Ok(worktree.join(prefix))
+}
+
+/// Paths outside its worktree that a session in `session_cwd` must be able to write.
+///
+/// From the main checkout, only the session's own project directory (`project_dir`, holding the
+/// progress file) and the shared knowledge base: `.codexpotter/worktrees/` holds the other
+/// sessions' checkouts. From the git common dir, only what a commit writes: `objects`, `refs`,
+/// `logs`, and the worktree's own `worktrees/<name>` (its `HEAD` and index). `hooks` and `config`
+/// stay read-only, since git runs them outside the sandbox.
+pub fn session_writable_roots(
+    workdir: &Path,
+    project_dir: &Path,
+    session_cwd: &Path,
+) -> anyhow::Result<Vec<PathBuf>> {
+    let git_common_dir = run_git(
+        session_cwd,
+        &["rev-parse", "--path-format=absolute", "--git-common-dir"],
+    )?;
+    let git_common_dir = PathBuf::from(git_common_dir.trim());
+    let git_dir = run_git(
+        session_cwd,
+        &["rev-parse", "--path-format=absolute", "--git-dir"],
+    )?;
+    Ok(vec![
+        project_dir.to_path_buf(),
+        workdir.join(".codexpotter").join("kb"),
+        git_common_dir.join("objects"),
+        git_common_dir.join("refs"),
+        git_common_dir.join("logs"),
+        PathBuf::from(git_dir.trim()),
+    ])
+}
+
+fn run_git(workdir: &Path, args: &[&str]) -> anyhow::Result<String> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(workdir)
+        .args(args)
+        .output()
+        .with_context(|| format!("run `git {}`", args.join(" ")))?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "`git {}` failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_git::git;
+    use crate::test_git::git_available;
+    use crate::test_git::init_repo;
+    use pretty_assertions::assert_eq;
+
+    #[test]
+    fn create_session_worktree_checks_out_branch_and_keeps_subdirectory() {
+        if !git_available() {
+            return;
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path();
+        init_repo(repo);
+
+        assert!(ensure_worktree_support(repo).is_err());
+
+        let subdir = repo.join("app");
+        std::fs::create_dir_all(&subdir).expect("mkdir");
+        std::fs::write(subdir.join("README.md"), "hello\n").expect("write file");
+        git(repo, &["add", "."]);
+        git(repo, &["commit", "-q", "-m", "init"]);
+        ensure_worktree_support(&subdir).expect("repo with a commit");
+
+        let cwd = create_session_worktree(&subdir, "2026/02/01/1").expect("create worktree");
+        assert_eq!(
+            cwd,
+            subdir
+                .join(".codexpotter/worktrees/2026/02/01/1")
+                .join("app")
+        );
+        assert!(cwd.join("README.md").is_file());
+
+        let branch = run_git(&cwd, &["symbolic-ref", "--short", "HEAD"]).expect("branch");
+        assert_eq!(branch.trim(), "codexpotter/2026/02/01/1");
+    }
+
+    #[test]
+    fn worktree_sessions_can_write_only_their_project_and_commit_metadata() {
+        use crate::app_server_backend::AppServerLaunchConfig;
+        use crate::app_server_protocol::SandboxPolicy;
+        use crate::sandbox_policy::WorkspaceWriteSettings;
+        use codex_protocol::AbsolutePathBuf;
+
+        if !git_available() {
+            return;
+        }
+
+        let temp = tempfile::tempdir().expect("tempdir");
+        let repo = temp.path().canonicalize().expect("canonicalize tempdir");
+        init_repo(&repo);
+        let subdir = repo.join("app");
+        std::fs::create_dir_all(&subdir).expect("mkdir");
+        std::fs::write(subdir.join("README.md"), "hello\n").expect("write file");
+        git(&repo, &["add", "."]);
+        git(&repo, &["commit", "-q", "-m", "init"]);
+        let cwd = create_session_worktree(&subdir, "2026/02/01/1").expect("create worktree");
+        let project_dir = subdir.join(".codexpotter/projects/2026/02/01/1");
+
+        let roots = session_writable_roots(&subdir, &project_dir, &cwd).expect("writable roots");
+        let settings = WorkspaceWriteSettings::default().with_writable_roots(roots);
+        let policy = settings.turn_policy(
+            AppServerLaunchConfig {
+                spawn_sandbox: None,
+                thread_sandbox: None,
+                bypass_approvals_and_sandbox: false,
+            },
+            &SandboxPolicy::ReadOnly,
+        );
+        let git_dir = repo.join(".git");
+        assert_eq!(
+            policy,
+            Some(SandboxPolicy::WorkspaceWrite {
+                writable_roots: [
+                    project_dir,
+                    subdir.join(".codexpotter/kb"),
+                    git_dir.join("objects"),
+                    git_dir.join("refs"),
+                    git_dir.join("logs"),
+                    git_dir.join("worktrees/1"),
+                ]
+                .into_iter()
+                .map(|root| AbsolutePathBuf::from_absolute_path(root).expect("absolute path"))
+                .collect(),
+                network_access: false,
+                exclude_tmpdir_env_var: false,
+                exclude_slash_tmp: false,
+            })
+        );
+    }
+}
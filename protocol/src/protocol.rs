@@ -17,6 +17,8 @@ use crate::openai_models::ReasoningEffort as ReasoningEffortConfig;
 use crate::parse_command::ParsedCommand;
 use crate::plan_tool::UpdatePlanArgs;
 use crate::request_user_input::RequestUserInputEvent;
+use crate::request_user_input::RequestUserInputQuestion;
+use crate::request_user_input::RequestUserInputResponse;
 use crate::user_input::UserInput;
 use serde::Deserialize;
 use serde::Serialize;
@@ -42,6 +44,34 @@ pub enum Op {
 
     /// Request a single history entry identified by `log_id` + `offset`.
     GetHistoryEntryRequest { offset: usize, log_id: u64 },
+
+    /// Finish the current `codex-potter` round with `outcome` (outside of the app-server
+    /// protocol).
+    ///
+    /// Sent after an interrupted turn when the user decides not to continue the round; the
+    /// control plane answers with [`EventMsg::PotterRoundFinished`].
+    PotterFinishRound { outcome: PotterRoundOutcome },
+
+    /// Answer a [`EventMsg::PotterApprovalRequest`] with the user's decision (outside of the
+    /// app-server protocol).
+    PotterApprovalResponse {
+        id: String,
+        decision: PotterApprovalDecision,
+    },
+
+    /// Answer a [`EventMsg::PotterUserInputRequest`] with the user's answers (outside of the
+    /// app-server protocol).
+    PotterUserInputResponse {
+        id: String,
+        response: RequestUserInputResponse,
+    },
+
+    /// Answer a [`EventMsg::PotterElicitationRequest`] with the user's decision (outside of the
+    /// app-server protocol).
+    PotterElicitationResponse {
+        id: String,
+        decision: PotterElicitationDecision,
+    },
 }
 
 /// Event Queue Entry - events from agent
@@ -158,6 +188,26 @@ pub enum EventMsg {
         error_message: String,
     },
 
+    /// `codex-potter` hit the usage limit and waits for the rate-limit window to reset, then
+    /// continues the round with a follow-up `continue` prompt.
+    ///
+    /// Like a retry, the wait is cleared by `PotterStreamRecoveryRecovered` once the agent is
+    /// active again.
+    PotterUsageLimitWait {
+        /// Unix timestamp (seconds) when the round continues.
+        resumes_at: i64,
+        /// The usage limit error message.
+        error_message: String,
+    },
+
+    /// `codex-potter` saw the thread's context window fill up past the `--context-checkpoint`
+    /// threshold and interrupts the turn to ask the agent to save its progress in the progress
+    /// file. The round then finishes as `ContextExhausted`.
+    PotterContextCheckpoint {
+        /// Percentage of the context window left when the threshold was crossed.
+        percent_left: i64,
+    },
+
     /// `codex-potter` stream recovery finished successfully (activity observed).
     ///
     /// This event exists to let the UI clear any transient retry indicators. Successful recoveries
@@ -174,6 +224,121 @@ pub enum EventMsg {
         max_attempts: u32,
     },
 
+    /// `codex-potter` ran the verification command (`--verify`) after a completed round.
+    ///
+    /// Emitted before `PotterRoundFinished`; the session only succeeds when the verifier passes.
+    PotterRoundVerified {
+        /// Shell command that was run.
+        command: String,
+        /// Whether the command exited successfully.
+        success: bool,
+        /// Exit code, when the command exited normally.
+        exit_code: Option<i32>,
+        /// Wall time spent running the command.
+        duration: Duration,
+        /// Truncated tail of the command output.
+        output_tail: String,
+    },
+
+    /// `codex-potter` validated the progress file after a round and found it broken.
+    ///
+    /// Emitted before `PotterRoundUsage`. The `unresolved` issues are reported to the next round
+    /// in its developer prompt.
+    PotterProgressFileIssues {
+        /// Issues fixed in place by `codex-potter`.
+        repaired: Vec<String>,
+        /// Issues left for the agent to fix.
+        unresolved: Vec<String>,
+    },
+
+    /// `codex-potter` read the progress file after it changed on disk, for the TUI progress panel.
+    ///
+    /// Sent at the start of every round and whenever the file changed since the last event. Only
+    /// rendered; not written to the `--json` event stream.
+    PotterProgressUpdated {
+        progress: PotterProgress,
+    },
+
+    /// `codex-potter` round usage summary (outside of the app-server protocol).
+    ///
+    /// Emitted right before `PotterRoundFinished`.
+    PotterRoundUsage {
+        /// Wall time spent in the round.
+        duration: Duration,
+        /// Final token usage of the round's thread.
+        token_usage: TokenUsage,
+        /// Token usage summed over every recorded round of the project, including this one.
+        session_token_usage: TokenUsage,
+    },
+
+    /// `codex-potter` stopped the session because a session-wide budget (`--max-duration`,
+    /// `--max-tokens`) was exhausted.
+    ///
+    /// Emitted before `PotterRoundFinished` of the last round that ran.
+    PotterBudgetExhausted {
+        reason: PotterBudgetExhaustedReason,
+    },
+
+    /// `codex-potter` stopped the session because its last rounds made no progress
+    /// (`--max-stalled-rounds`): the repository tree, the progress file, and its task counts stayed
+    /// the same.
+    ///
+    /// Emitted before `PotterRoundFinished` of the last round that ran.
+    PotterSessionStalled {
+        /// Consecutive completed rounds without progress.
+        rounds: u32,
+    },
+
+    /// `codex-potter` needs the user to decide an approval request (outside of the app-server
+    /// protocol).
+    ///
+    /// Emitted when an `[approval]` rule says `ask`; the UI answers with
+    /// [`Op::PotterApprovalResponse`] carrying the same `id`.
+    PotterApprovalRequest {
+        id: String,
+        /// Command argv, for command execution requests.
+        command: Option<Vec<String>>,
+        /// Changed paths, for file change requests.
+        paths: Vec<PathBuf>,
+        /// Why the agent asks, when it says so.
+        reason: Option<String>,
+    },
+
+    /// `codex-potter` needs the user to answer the agent's questions (outside of the app-server
+    /// protocol).
+    ///
+    /// Emitted for `request_user_input` tool calls in interactive sessions; the UI answers with
+    /// [`Op::PotterUserInputResponse`] carrying the same `id`.
+    PotterUserInputRequest {
+        id: String,
+        questions: Vec<RequestUserInputQuestion>,
+    },
+
+    /// `codex-potter` needs the user to answer an MCP server elicitation (outside of the
+    /// app-server protocol).
+    ///
+    /// The UI answers with [`Op::PotterElicitationResponse`] carrying the same `id`.
+    PotterElicitationRequest {
+        id: String,
+        server_name: String,
+        message: String,
+    },
+
+    /// `codex-potter` answered a question prompt or an elicitation (outside of the app-server
+    /// protocol).
+    PotterPromptAnswered {
+        answers: Vec<PotterPromptAnswer>,
+        answered_by: PotterPromptAnsweredBy,
+    },
+
+    /// `codex-potter` answered an approval request (outside of the app-server protocol).
+    PotterApprovalDecided {
+        command: Option<Vec<String>>,
+        paths: Vec<PathBuf>,
+        decision: PotterApprovalDecision,
+        decided_by: PotterApprovalDecidedBy,
+    },
+
     /// `codex-potter` session finished successfully (outside of the app-server protocol).
     PotterSessionSucceeded {
         /// Total number of rounds rendered for this CodexPotter project.
@@ -186,6 +351,9 @@ pub enum EventMsg {
         git_commit_start: String,
         /// Git commit after CodexPotter finished (empty when unavailable).
         git_commit_end: String,
+        /// Final token usage of every round of this project, oldest first.
+        #[serde(default)]
+        round_token_usage: Vec<TokenUsage>,
     },
 
     WebSearchEnd(WebSearchEndEvent),
@@ -251,8 +419,140 @@ pub enum EventMsg {
 pub enum PotterRoundOutcome {
     Completed,
     UserRequested,
-    TaskFailed { message: String },
-    Fatal { message: String },
+    /// The user interrupted a turn, continued the round with a new instruction, and the round then
+    /// ran to completion.
+    InterruptedThenContinued,
+    /// The user interrupted a turn and moved on to the next round.
+    InterruptedNextRound,
+    /// The user interrupted a turn and ended the session.
+    InterruptedEndSession,
+    /// The agent ran a command the `codex-potter` command guard forbids, so the turn was
+    /// interrupted; the session moves on to the next round.
+    CommandBlocked {
+        /// The offending command.
+        command: Vec<String>,
+        /// The guard rule that matched.
+        rule: String,
+    },
+    /// The thread's context window overflowed, or the agent checkpointed its progress before it
+    /// would; the session continues in a fresh round that does not count against `--rounds`.
+    ContextExhausted,
+    /// The round hit `--idle-timeout`, `--round-timeout` or the session budget and its app-server
+    /// was killed; the session retries the round in a fresh process or ends, depending on
+    /// `--on-timeout`. A round stopped by the budget always ends the session.
+    TimedOut {
+        timeout: PotterRoundTimeout,
+    },
+    TaskFailed {
+        message: String,
+    },
+    Fatal {
+        message: String,
+    },
+}
+
+impl PotterRoundOutcome {
+    /// Whether the round ran to completion, so its work can be verified and the session may
+    /// succeed after it.
+    pub fn is_completed(&self) -> bool {
+        matches!(
+            self,
+            PotterRoundOutcome::Completed | PotterRoundOutcome::InterruptedThenContinued
+        )
+    }
+}
+
+/// How `codex-potter` answered an approval request.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterApprovalDecision {
+    Accept,
+    Deny,
+}
+
+/// What decided a `codex-potter` approval request.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PotterApprovalDecidedBy {
+    /// The first matching `[[approval.rules]]` entry, described by `rule`.
+    Rule { rule: String },
+    /// `approval.default`, because no rule matched.
+    Default,
+    /// The user, in the approval overlay.
+    User,
+    /// `approval.headless`, because a rule said `ask` and there was no one to ask.
+    Headless,
+    /// The `codex-potter` command guard, whose rule `rule` forbids the command; this overrides the
+    /// approval rules.
+    CommandGuard { rule: String },
+}
+
+/// How `codex-potter` answered an MCP server elicitation.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterElicitationDecision {
+    Accept,
+    Decline,
+    Cancel,
+}
+
+/// One answered question, as shown in the transcript.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PotterPromptAnswer {
+    pub question: String,
+    /// The answer text; secret answers are replaced with a placeholder.
+    pub answer: String,
+}
+
+/// What answered a `codex-potter` question prompt or elicitation.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PotterPromptAnsweredBy {
+    /// The user, in the question overlay.
+    User,
+    /// `user_input.headless`, because there was no one to ask.
+    Headless,
+}
+
+/// What the TUI progress panel shows of a `codex-potter` progress file.
+#[derive(Debug, Clone, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PotterProgress {
+    /// `short_title`, or the first line of the `Overall Goal` when it is not set yet.
+    pub title: Option<String>,
+    /// The `status` front matter field, when it is valid.
+    pub status: Option<String>,
+    pub in_progress: Vec<PotterProgressItem>,
+    pub todo: Vec<PotterProgressItem>,
+    pub done: Vec<PotterProgressItem>,
+}
+
+/// One task of a `codex-potter` progress file section.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct PotterProgressItem {
+    pub text: String,
+    /// Checked off with `[x]`.
+    pub checked: bool,
+}
+
+/// Which `codex-potter` watchdog limit stopped a round.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PotterRoundTimeout {
+    /// A running turn produced no app-server events for `limit_secs` (`--idle-timeout`).
+    Idle { limit_secs: u64 },
+    /// The round ran for `limit_secs` (`--round-timeout`).
+    Round { limit_secs: u64 },
+    /// `--max-duration` or `--max-tokens` ran out during the round; `PotterBudgetExhausted`
+    /// follows with which.
+    Budget,
+}
+
+/// Which session-wide budget stopped a `codex-potter` session.
+#[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum PotterBudgetExhaustedReason {
+    MaxDuration { limit_secs: u64, elapsed_secs: u64 },
+    MaxTokens { limit: u64, used: u64 },
 }
 
 /// Codex errors that we expose to clients.
@@ -3,6 +3,10 @@ use std::collections::HashMap;
 use serde::Deserialize;
 use serde::Serialize;
 
+/// Answer `codex-potter` gives when the agent should make the call itself.
+pub const DECIDE_YOURSELF_ANSWER: &str =
+    "No one is available to answer. Decide yourself, note the assumption, and carry on.";
+
 #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
 pub struct RequestUserInputQuestionOption {
     pub label: String,
@@ -25,13 +25,22 @@ pub async fn prompt_action_picker(
         })
         .collect();
 
-    let mut view = ListSelectionView::new(SelectionViewParams {
+    let view = ListSelectionView::new(SelectionViewParams {
         title: Some("Select Action".to_string()),
         footer_hint: Some(Line::from("Press enter to run, or esc to exit.")),
         items,
         ..Default::default()
     });
 
+    run_selection_view(tui, view).await
+}
+
+/// Show `view` below a blank padding line until the user selects an item or cancels
+/// (Esc/Ctrl+C), and return the selected item index.
+pub(crate) async fn run_selection_view(
+    tui: &mut Tui,
+    mut view: ListSelectionView,
+) -> anyhow::Result<Option<usize>> {
     let width = tui.terminal.last_known_screen_size.width.max(1);
     tui.draw(view.desired_height(width).saturating_add(1), |frame| {
         let area = frame.area();
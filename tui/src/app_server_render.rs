@@ -10,6 +10,7 @@ use std::time::Instant;
 use codex_protocol::protocol::Event;
 use codex_protocol::protocol::EventMsg;
 use codex_protocol::protocol::Op;
+use codex_protocol::protocol::PotterRoundOutcome;
 use codex_protocol::protocol::TokenUsage;
 use codex_protocol::protocol::TurnStartedEvent;
 use codex_protocol::user_input::UserInput;
@@ -40,6 +41,8 @@ use crate::history_cell;
 use crate::history_cell::HistoryCell;
 use crate::history_cell_potter::PotterStreamRecoveryRetryCell;
 use crate::history_cell_potter::PotterStreamRecoveryUnrecoverableCell;
+use crate::history_cell_potter::PotterUsageLimitWaitCell;
+use crate::interrupt_prompt::InterruptChoice;
 use crate::render::renderable::Renderable;
 use crate::streaming::chunking::AdaptiveChunkingPolicy;
 use crate::streaming::commit_tick::CommitTickScope;
@@ -111,6 +114,7 @@ pub async fn prompt_user_with_tui(
     tui: &mut Tui,
     show_startup_banner: bool,
     check_for_update_on_startup: bool,
+    codex_model_overrides: &crate::codex_config::CodexModelOverrides,
     composer_draft: Option<ChatComposerDraft>,
 ) -> anyhow::Result<Option<String>> {
     let (app_event_tx_raw, mut app_event_rx) = unbounded_channel::<AppEvent>();
@@ -132,11 +136,17 @@ pub async fn prompt_user_with_tui(
     let mut should_pad_prompt_viewport = !show_startup_banner;
     if show_startup_banner {
         let width = tui.terminal.last_known_screen_size.width.max(1);
-        let codex_model = crate::codex_config::resolve_codex_model_config(&file_search_dir)?;
-        let model_label = match codex_model.reasoning_effort {
+        let codex_model = crate::codex_config::resolve_codex_model_config(
+            &file_search_dir,
+            codex_model_overrides,
+        )?;
+        let mut model_label = match codex_model.reasoning_effort {
             Some(effort) => format!("{} {effort}", codex_model.model),
             None => codex_model.model,
         };
+        if let Some(profile) = codex_model.profile {
+            model_label.push_str(&format!(" ({profile})"));
+        }
         let banner_lines = crate::startup_banner::build_startup_banner_lines(
             width,
             crate::CODEX_POTTER_VERSION,
@@ -469,6 +479,33 @@ pub struct RenderOnlyBackendChannels {
     pub fatal_exit_rx: UnboundedReceiver<String>,
 }
 
+/// Identifies the session rendered by [`run_session_view_with_tui`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionViewOptions {
+    /// 1-based position of the viewed session among the running sessions.
+    pub current: usize,
+    /// Number of running sessions.
+    pub total: usize,
+    /// Label shown in the header, typically the project path (e.g. `2026/02/01/1`).
+    pub label: String,
+    /// When false, the header is skipped because the view re-attaches to the transcript that is
+    /// already on screen.
+    pub render_header: bool,
+}
+
+/// Why a parallel-session transcript view stopped rendering.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SessionViewExit {
+    /// The user asked to view the previous session (<kbd>PgUp</kbd>).
+    Previous,
+    /// The user asked to view the next session (<kbd>PgDn</kbd>).
+    Next,
+    /// The control plane closed the event stream, for example because a session finished.
+    Detached,
+    /// The user interrupted (<kbd>Ctrl</kbd>+<kbd>C</kbd>).
+    UserRequested,
+}
+
 fn text_user_input_op(text: String) -> Op {
     Op::UserInput {
         items: vec![UserInput::Text {
@@ -484,7 +521,8 @@ fn text_user_input_op(text: String) -> Op {
 /// This function consumes backend events, updates the transcript, and drives the bottom composer.
 /// Any prompts queued while the turn is running are appended to `queued_user_messages`. The
 /// current composer draft is written back to `composer_draft` so it can be restored by subsequent
-/// prompt screens.
+/// prompt screens, and whether the progress panel is collapsed to `progress_panel_collapsed` so it
+/// stays that way in the next round.
 pub async fn run_render_only_with_tui_options_and_queue(
     tui: &mut Tui,
     prompt: String,
@@ -492,6 +530,7 @@ pub async fn run_render_only_with_tui_options_and_queue(
     backend: RenderOnlyBackendChannels,
     queued_user_messages: &mut VecDeque<String>,
     composer_draft: &mut Option<crate::bottom_pane::ChatComposerDraft>,
+    progress_panel_collapsed: &mut bool,
 ) -> anyhow::Result<AppExitInfo> {
     let RenderOnlyBackendChannels {
         codex_op_tx,
@@ -519,6 +558,7 @@ pub async fn run_render_only_with_tui_options_and_queue(
     if let Some(draft) = composer_draft.take() {
         bottom_pane.composer_mut().restore_draft(draft);
     }
+    bottom_pane.set_progress_panel_collapsed(*progress_panel_collapsed);
     let (history_log_id, history_entry_count) = prompt_history.metadata();
     bottom_pane
         .composer_mut()
@@ -544,11 +584,97 @@ pub async fn run_render_only_with_tui_options_and_queue(
             &mut fatal_exit_rx,
         )
         .await;
-    *queued_user_messages = app.queued_user_messages;
     *composer_draft = app.bottom_pane.composer_mut().take_draft();
+    *progress_panel_collapsed = app.bottom_pane.progress_panel_collapsed();
+    *queued_user_messages = app.queued_user_messages;
     result
 }
 
+/// Render the live transcript of one of several concurrently running sessions (`--parallel`).
+///
+/// Unlike [`run_render_only_with_tui_options_and_queue`], this does not submit a prompt and keeps
+/// rendering across `PotterRoundFinished` markers until the control plane closes the event stream
+/// or the user switches sessions. Prompts queued via the composer are appended to
+/// `queued_user_messages`, the composer draft is written back to `composer_draft`, and the
+/// progress panel state to `progress_panel_collapsed`.
+pub async fn run_session_view_with_tui(
+    tui: &mut Tui,
+    options: SessionViewOptions,
+    backend: RenderOnlyBackendChannels,
+    queued_user_messages: &mut VecDeque<String>,
+    composer_draft: &mut Option<crate::bottom_pane::ChatComposerDraft>,
+    progress_panel_collapsed: &mut bool,
+) -> anyhow::Result<SessionViewExit> {
+    let RenderOnlyBackendChannels {
+        codex_op_tx,
+        mut codex_event_rx,
+        mut fatal_exit_rx,
+    } = backend;
+
+    let (app_event_tx_raw, mut app_event_rx) = unbounded_channel::<AppEvent>();
+    let app_event_tx = AppEventSender::new(app_event_tx_raw);
+
+    let file_search_dir = std::env::current_dir().unwrap_or_else(|_| std::env::temp_dir());
+    let file_search = FileSearchManager::new(file_search_dir, app_event_tx.clone());
+    let prompt_history = crate::prompt_history_store::PromptHistoryStore::new();
+
+    let driver = RenderOnlyProcessor::new(app_event_tx.clone());
+    if options.render_header {
+        app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+            crate::history_cell_potter::new_potter_session_view_header(
+                options.current,
+                options.total,
+                options.label,
+            ),
+        )));
+    }
+
+    let mut bottom_pane = new_default_bottom_pane(tui, app_event_tx.clone(), true);
+    if let Some(draft) = composer_draft.take() {
+        bottom_pane.composer_mut().restore_draft(draft);
+    }
+    bottom_pane.set_progress_panel_collapsed(*progress_panel_collapsed);
+    let (history_log_id, history_entry_count) = prompt_history.metadata();
+    bottom_pane
+        .composer_mut()
+        .set_history_metadata(history_log_id, history_entry_count);
+    let queued_user_messages_state = std::mem::take(queued_user_messages);
+    let mut app = RenderAppState::new(
+        driver,
+        app_event_tx.clone(),
+        codex_op_tx,
+        bottom_pane,
+        prompt_history,
+        file_search,
+        queued_user_messages_state,
+    );
+    app.session_view = true;
+    app.has_emitted_history_lines = !options.render_header;
+    app.refresh_queued_user_messages();
+
+    let result = app
+        .run(
+            tui,
+            &mut app_event_rx,
+            &mut codex_event_rx,
+            &mut fatal_exit_rx,
+        )
+        .await;
+    *queued_user_messages = std::mem::take(&mut app.queued_user_messages);
+    *composer_draft = app.bottom_pane.composer_mut().take_draft();
+    *progress_panel_collapsed = app.bottom_pane.progress_panel_collapsed();
+    let exit_info = result?;
+
+    Ok(app
+        .session_view_exit
+        .unwrap_or(match exit_info.exit_reason {
+            ExitReason::UserRequested => SessionViewExit::UserRequested,
+            ExitReason::Completed | ExitReason::TaskFailed(_) | ExitReason::Fatal(_) => {
+                SessionViewExit::Detached
+            }
+        }))
+}
+
 struct RenderOnlyProcessor {
     app_event_tx: AppEventSender,
     stream: StreamController,
@@ -577,6 +703,7 @@ struct PendingPotterSessionSucceeded {
     user_prompt_file: PathBuf,
     git_commit_start: String,
     git_commit_end: String,
+    round_token_usage: Vec<TokenUsage>,
 }
 
 impl RenderOnlyProcessor {
@@ -695,12 +822,104 @@ impl RenderOnlyProcessor {
                     crate::history_cell_potter::new_potter_round_started(current, total),
                 )));
             }
+            EventMsg::PotterRoundVerified {
+                command,
+                success,
+                exit_code,
+                duration,
+                output_tail,
+            } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::PotterRoundVerifiedCell {
+                        command,
+                        success,
+                        exit_code,
+                        duration,
+                        output_tail,
+                    },
+                )));
+            }
+            EventMsg::PotterProgressFileIssues {
+                repaired,
+                unresolved,
+            } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_progress_file_issues(
+                        repaired, unresolved,
+                    ),
+                )));
+            }
+            EventMsg::PotterRoundUsage {
+                duration,
+                token_usage,
+                session_token_usage,
+            } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_round_usage(
+                        duration,
+                        token_usage,
+                        session_token_usage,
+                    ),
+                )));
+            }
+            EventMsg::PotterContextCheckpoint { percent_left } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_context_checkpoint(percent_left),
+                )));
+            }
+            EventMsg::PotterBudgetExhausted { reason } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_budget_exhausted(reason),
+                )));
+            }
+            EventMsg::PotterSessionStalled { rounds } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_session_stalled(rounds),
+                )));
+            }
+            EventMsg::PotterApprovalDecided {
+                command,
+                paths,
+                decision,
+                decided_by,
+            } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_approval_decided(
+                        command, paths, decision, decided_by,
+                    ),
+                )));
+            }
+            EventMsg::PotterPromptAnswered {
+                answers,
+                answered_by,
+            } => {
+                self.flush_pending_exploring_cell();
+                self.flush_pending_success_ran_cell();
+                self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                    crate::history_cell_potter::new_potter_prompt_answered(answers, answered_by),
+                )));
+            }
             EventMsg::PotterSessionSucceeded {
                 rounds,
                 duration,
                 user_prompt_file,
                 git_commit_start,
                 git_commit_end,
+                round_token_usage,
             } => {
                 self.flush_pending_exploring_cell();
                 self.flush_pending_success_ran_cell();
@@ -710,11 +929,30 @@ impl RenderOnlyProcessor {
                     user_prompt_file,
                     git_commit_start,
                     git_commit_end,
+                    round_token_usage,
                 });
             }
-            EventMsg::PotterRoundFinished { .. } => {
+            EventMsg::PotterRoundFinished { outcome } => {
                 self.flush_pending_exploring_cell();
                 self.flush_pending_success_ran_cell();
+                match outcome {
+                    PotterRoundOutcome::CommandBlocked { command, rule } => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            crate::history_cell_potter::new_potter_command_blocked(command, rule),
+                        )));
+                    }
+                    PotterRoundOutcome::ContextExhausted => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            crate::history_cell_potter::new_potter_context_exhausted(),
+                        )));
+                    }
+                    PotterRoundOutcome::TimedOut { timeout } => {
+                        self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
+                            crate::history_cell_potter::new_potter_round_timed_out(timeout),
+                        )));
+                    }
+                    _ => {}
+                }
                 if let Some(done) = self.pending_potter_session_succeeded.take() {
                     self.app_event_tx.send(AppEvent::InsertHistoryCell(Box::new(
                         crate::history_cell_potter::new_potter_session_succeeded(
@@ -723,6 +961,7 @@ impl RenderOnlyProcessor {
                             done.user_prompt_file,
                             done.git_commit_start,
                             done.git_commit_end,
+                            done.round_token_usage,
                         ),
                     )));
                 }
@@ -778,6 +1017,7 @@ impl RenderOnlyProcessor {
                             done.user_prompt_file,
                             done.git_commit_start,
                             done.git_commit_end,
+                            done.round_token_usage,
                         ),
                     )));
                 }
@@ -1044,10 +1284,27 @@ struct RenderAppState {
     reasoning_status: ReasoningStatusTracker,
     stream_error_status_header: Option<String>,
     potter_stream_recovery_retry_cell: Option<PotterStreamRecoveryRetryCell>,
+    /// Countdown shown while waiting for the usage limit to reset.
+    potter_usage_limit_wait_cell: Option<PotterUsageLimitWaitCell>,
     commit_anim_running: Arc<AtomicBool>,
     has_emitted_history_lines: bool,
     exit_after_next_draw: bool,
     exit_reason: ExitReason,
+    /// When true, keep rendering across rounds and allow switching sessions (`--parallel`).
+    session_view: bool,
+    session_view_exit: Option<SessionViewExit>,
+    /// Ctrl+C sent `Op::Interrupt`; waiting for the running turn to abort.
+    interrupt_requested: bool,
+    /// The turn was interrupted; ask the user how to go on after the next draw.
+    pending_interrupt_choice: bool,
+    /// The user chose to continue the interrupted round; the next submitted prompt becomes the
+    /// new instruction.
+    awaiting_interrupt_instruction: bool,
+    /// Approval requests waiting for the user, asked one at a time after the next draw.
+    pending_approvals: VecDeque<crate::approval_prompt::ApprovalRequest>,
+    /// Agent questions and MCP elicitations waiting for the user, asked one at a time after the
+    /// next draw.
+    pending_user_inputs: VecDeque<crate::user_input_prompt::UserInputPrompt>,
 }
 
 impl RenderAppState {
@@ -1071,10 +1328,18 @@ impl RenderAppState {
             reasoning_status: ReasoningStatusTracker::new(),
             stream_error_status_header: None,
             potter_stream_recovery_retry_cell: None,
+            potter_usage_limit_wait_cell: None,
             commit_anim_running: Arc::new(AtomicBool::new(false)),
             has_emitted_history_lines: false,
             exit_after_next_draw: false,
             exit_reason: ExitReason::UserRequested,
+            session_view: false,
+            session_view_exit: None,
+            interrupt_requested: false,
+            pending_interrupt_choice: false,
+            awaiting_interrupt_instruction: false,
+            pending_approvals: VecDeque::new(),
+            pending_user_inputs: VecDeque::new(),
         }
     }
 
@@ -1098,6 +1363,11 @@ impl RenderAppState {
             transient_lines.extend(cell.display_lines(width));
         }
 
+        if let Some(cell) = self.potter_usage_limit_wait_cell.as_ref() {
+            transient_lines.push(Line::from(""));
+            transient_lines.extend(cell.display_lines(width));
+        }
+
         // When the bottom pane shrinks (e.g., after a turn completes and the status indicator is
         // removed), the prompt background can end up directly adjacent to the last transcript
         // line. Keep a blank line between the transcript and the bottom pane for readability.
@@ -1168,6 +1438,15 @@ impl RenderAppState {
                             if self.exit_after_next_draw {
                                 break;
                             }
+                            if self.pending_interrupt_choice {
+                                self.pending_interrupt_choice = false;
+                                self.handle_interrupt_choice(tui).await?;
+                            }
+                            if let Some(request) = self.pending_approvals.pop_front() {
+                                self.handle_approval_request(tui, request).await?;
+                            } else if let Some(prompt) = self.pending_user_inputs.pop_front() {
+                                self.handle_user_input_prompt(tui, prompt).await?;
+                            }
                         }
                             TuiEvent::Key(key_event) => {
                                 if external_editor_integration::is_ctrl_g(&key_event) {
@@ -1201,7 +1480,15 @@ impl RenderAppState {
                         }
                         None => {
                             if !self.exit_after_next_draw {
-                                self.exit_reason = ExitReason::Fatal("Backend disconnected".to_string());
+                                if self.session_view {
+                                    // Keep live output (for example pending "Explored" cells) in
+                                    // the transcript; the view may be re-attached later.
+                                    self.processor.flush_pending_exploring_cell();
+                                    self.processor.flush_pending_success_ran_cell();
+                                    self.session_view_exit = Some(SessionViewExit::Detached);
+                                } else {
+                                    self.exit_reason = ExitReason::Fatal("Backend disconnected".to_string());
+                                }
                                 self.exit_after_next_draw = true;
                                 tui.frame_requester().schedule_frame();
                             }
@@ -1261,6 +1548,76 @@ impl RenderAppState {
         Ok(())
     }
 
+    async fn handle_interrupt_choice(&mut self, tui: &mut Tui) -> anyhow::Result<()> {
+        let outcome = match crate::interrupt_prompt::prompt_interrupt_choice(tui).await? {
+            InterruptChoice::ContinueRound => {
+                self.awaiting_interrupt_instruction = true;
+                self.handle_app_event(
+                    tui,
+                    AppEvent::InsertHistoryCell(Box::new(
+                        crate::history_cell_potter::new_potter_interrupt_continue_hint(),
+                    )),
+                )?;
+                tui.frame_requester().schedule_frame();
+                return Ok(());
+            }
+            InterruptChoice::NextRound => PotterRoundOutcome::InterruptedNextRound,
+            InterruptChoice::EndSession => PotterRoundOutcome::InterruptedEndSession,
+        };
+        self.finish_interrupted_round(outcome);
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    async fn handle_approval_request(
+        &mut self,
+        tui: &mut Tui,
+        request: crate::approval_prompt::ApprovalRequest,
+    ) -> anyhow::Result<()> {
+        let decision = crate::approval_prompt::prompt_approval(tui, &request).await?;
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PotterApprovalResponse {
+                id: request.id,
+                decision,
+            }));
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    async fn handle_user_input_prompt(
+        &mut self,
+        tui: &mut Tui,
+        prompt: crate::user_input_prompt::UserInputPrompt,
+    ) -> anyhow::Result<()> {
+        let op = match prompt {
+            crate::user_input_prompt::UserInputPrompt::Questions { id, questions } => {
+                let response = crate::user_input_prompt::prompt_questions(tui, &questions).await?;
+                Op::PotterUserInputResponse { id, response }
+            }
+            crate::user_input_prompt::UserInputPrompt::Elicitation {
+                id,
+                server_name,
+                message,
+            } => {
+                let decision =
+                    crate::user_input_prompt::prompt_elicitation(tui, &server_name, &message)
+                        .await?;
+                Op::PotterElicitationResponse { id, decision }
+            }
+        };
+        self.app_event_tx.send(AppEvent::CodexOp(op));
+        tui.frame_requester().schedule_frame();
+        Ok(())
+    }
+
+    /// Ask the control plane to finish the interrupted round; the runner exits once it answers
+    /// with `PotterRoundFinished`.
+    fn finish_interrupted_round(&mut self, outcome: PotterRoundOutcome) {
+        self.awaiting_interrupt_instruction = false;
+        self.app_event_tx
+            .send(AppEvent::CodexOp(Op::PotterFinishRound { outcome }));
+    }
+
     fn handle_key_event(
         &mut self,
         key_event: crossterm::event::KeyEvent,
@@ -1288,6 +1645,37 @@ impl RenderAppState {
             return;
         }
 
+        if crate::bottom_pane::TOGGLE_PROGRESS_PANEL_KEY.is_press(key_event)
+            && self.bottom_pane.toggle_progress_panel()
+        {
+            frame_requester.schedule_frame();
+            return;
+        }
+
+        if self.session_view
+            && key_event.modifiers == crossterm::event::KeyModifiers::NONE
+            && matches!(
+                key_event.code,
+                crossterm::event::KeyCode::PageUp | crossterm::event::KeyCode::PageDown
+            )
+        {
+            if !is_press {
+                return;
+            }
+            self.processor.flush_pending_exploring_cell();
+            self.processor.flush_pending_success_ran_cell();
+            self.session_view_exit = Some(
+                if matches!(key_event.code, crossterm::event::KeyCode::PageUp) {
+                    SessionViewExit::Previous
+                } else {
+                    SessionViewExit::Next
+                },
+            );
+            self.exit_after_next_draw = true;
+            frame_requester.schedule_frame();
+            return;
+        }
+
         if key_event
             .modifiers
             .contains(crossterm::event::KeyModifiers::CONTROL)
@@ -1297,6 +1685,12 @@ impl RenderAppState {
                 return;
             }
             if self.bottom_pane.composer().is_empty() {
+                if self.awaiting_interrupt_instruction {
+                    self.finish_interrupted_round(PotterRoundOutcome::InterruptedEndSession);
+                    frame_requester.schedule_frame();
+                    return;
+                }
+
                 // Preserve any live output (for example pending "Explored" / "Ran" cells) in the
                 // transcript before clearing the inline viewport on exit.
                 self.processor.flush_pending_exploring_cell();
@@ -1304,6 +1698,14 @@ impl RenderAppState {
 
                 self.app_event_tx.send(AppEvent::CodexOp(Op::Interrupt));
 
+                // The first Ctrl+C stops the running turn and, once it has aborted, asks how to go
+                // on. Pressing it again (or in a session view) tears the round down right away.
+                if !self.session_view && !self.interrupt_requested {
+                    self.interrupt_requested = true;
+                    frame_requester.schedule_frame();
+                    return;
+                }
+
                 // Treat Ctrl+C as an explicit user cancellation, even if the turn just finished,
                 // so callers can stop multi-round loops reliably.
                 if !matches!(self.exit_reason, ExitReason::Fatal(_)) {
@@ -1334,8 +1736,17 @@ impl RenderAppState {
                     .composer()
                     .encode_prompt_history_text(&text);
                 self.prompt_history.record_submission(&history_text);
-                self.queued_user_messages.push_back(text);
-                self.refresh_queued_user_messages();
+                if self.awaiting_interrupt_instruction {
+                    // Continue the interrupted round in the same thread.
+                    self.awaiting_interrupt_instruction = false;
+                    self.processor.emit_user_prompt(text.clone());
+                    self.bottom_pane.set_task_running(true);
+                    self.app_event_tx
+                        .send(AppEvent::CodexOp(text_user_input_op(text)));
+                } else {
+                    self.queued_user_messages.push_back(text);
+                    self.refresh_queued_user_messages();
+                }
                 frame_requester.schedule_frame();
             }
             InputResult::None => {}
@@ -1381,6 +1792,11 @@ impl RenderAppState {
                 .unwrap_or((area.x, area.bottom().saturating_sub(1)));
             frame.set_cursor_position(cursor);
         })?;
+        if self.potter_usage_limit_wait_cell.is_some() {
+            // Keep the countdown ticking.
+            tui.frame_requester()
+                .schedule_frame_in(Duration::from_secs(1));
+        }
         Ok(())
     }
 
@@ -1479,6 +1895,25 @@ impl RenderAppState {
                     max_attempts: *max_attempts,
                     error_message: error_message.clone(),
                 });
+                self.potter_usage_limit_wait_cell = None;
+
+                self.processor.current_elapsed_secs = self
+                    .bottom_pane
+                    .status_widget()
+                    .map(super::status_indicator_widget::StatusIndicatorWidget::elapsed_seconds);
+                self.processor.handle_retryable_stream_error();
+                frame_requester.schedule_frame();
+                return Ok(());
+            }
+            EventMsg::PotterUsageLimitWait {
+                resumes_at,
+                error_message,
+            } => {
+                self.potter_usage_limit_wait_cell = Some(PotterUsageLimitWaitCell {
+                    resumes_at: *resumes_at,
+                    error_message: error_message.clone(),
+                });
+                self.potter_stream_recovery_retry_cell = None;
 
                 self.processor.current_elapsed_secs = self
                     .bottom_pane
@@ -1488,8 +1923,14 @@ impl RenderAppState {
                 frame_requester.schedule_frame();
                 return Ok(());
             }
+            EventMsg::PotterProgressUpdated { progress } => {
+                self.bottom_pane.set_progress(progress.clone());
+                frame_requester.schedule_frame();
+                return Ok(());
+            }
             EventMsg::PotterStreamRecoveryRecovered => {
                 self.potter_stream_recovery_retry_cell = None;
+                self.potter_usage_limit_wait_cell = None;
                 frame_requester.schedule_frame();
                 return Ok(());
             }
@@ -1499,6 +1940,7 @@ impl RenderAppState {
                 ..
             } => {
                 self.potter_stream_recovery_retry_cell = None;
+                self.potter_usage_limit_wait_cell = None;
                 self.processor.current_elapsed_secs = self
                     .bottom_pane
                     .status_widget()
@@ -1534,6 +1976,9 @@ impl RenderAppState {
 
         match &event.msg {
             EventMsg::PotterRoundStarted { current, total } => {
+                if self.session_view {
+                    self.bottom_pane.set_task_running(true);
+                }
                 self.bottom_pane
                     .set_status_header_prefix(Some(format!("Round {current}/{total}")));
             }
@@ -1576,11 +2021,67 @@ impl RenderAppState {
             return Ok(());
         }
 
-        let should_exit_on_round_end = matches!(&event.msg, EventMsg::PotterRoundFinished { .. });
-        let should_stop_footer = match &event.msg {
-            EventMsg::PotterRoundFinished { .. } => should_exit_on_round_end,
-            _ => false,
-        };
+        if let EventMsg::PotterApprovalRequest {
+            id,
+            command,
+            paths,
+            reason,
+        } = &event.msg
+        {
+            self.pending_approvals
+                .push_back(crate::approval_prompt::ApprovalRequest {
+                    id: id.clone(),
+                    command: command.clone(),
+                    paths: paths.clone(),
+                    reason: reason.clone(),
+                });
+            frame_requester.schedule_frame();
+        }
+
+        match &event.msg {
+            EventMsg::PotterUserInputRequest { id, questions } => {
+                self.pending_user_inputs.push_back(
+                    crate::user_input_prompt::UserInputPrompt::Questions {
+                        id: id.clone(),
+                        questions: questions.clone(),
+                    },
+                );
+                frame_requester.schedule_frame();
+            }
+            EventMsg::PotterElicitationRequest {
+                id,
+                server_name,
+                message,
+            } => {
+                self.pending_user_inputs.push_back(
+                    crate::user_input_prompt::UserInputPrompt::Elicitation {
+                        id: id.clone(),
+                        server_name: server_name.clone(),
+                        message: message.clone(),
+                    },
+                );
+                frame_requester.schedule_frame();
+            }
+            _ => {}
+        }
+
+        if self.interrupt_requested
+            && matches!(
+                &event.msg,
+                EventMsg::TurnAborted(ev)
+                    if ev.reason == codex_protocol::protocol::TurnAbortReason::Interrupted
+            )
+        {
+            self.interrupt_requested = false;
+            self.pending_interrupt_choice = true;
+            self.bottom_pane.set_task_running(false);
+            frame_requester.schedule_frame();
+        }
+
+        let is_round_end = matches!(&event.msg, EventMsg::PotterRoundFinished { .. });
+        // Session views span every round of a session; the control plane detaches them instead.
+        let should_exit_on_round_end = is_round_end && !self.session_view;
+        let should_stop_footer = is_round_end;
         let should_update_context = matches!(
             &event.msg,
             EventMsg::TokenCount(_) | EventMsg::TurnStarted(_)
@@ -1590,18 +2091,18 @@ impl RenderAppState {
         match &event.msg {
             EventMsg::PotterRoundFinished { outcome } if should_exit_on_round_end => {
                 self.exit_reason = match outcome {
-                    codex_protocol::protocol::PotterRoundOutcome::Completed => {
-                        ExitReason::Completed
-                    }
-                    codex_protocol::protocol::PotterRoundOutcome::UserRequested => {
-                        ExitReason::UserRequested
-                    }
-                    codex_protocol::protocol::PotterRoundOutcome::TaskFailed { message } => {
+                    PotterRoundOutcome::Completed
+                    | PotterRoundOutcome::InterruptedThenContinued
+                    | PotterRoundOutcome::InterruptedNextRound
+                    | PotterRoundOutcome::CommandBlocked { .. }
+                    | PotterRoundOutcome::ContextExhausted
+                    | PotterRoundOutcome::TimedOut { .. } => ExitReason::Completed,
+                    PotterRoundOutcome::UserRequested
+                    | PotterRoundOutcome::InterruptedEndSession => ExitReason::UserRequested,
+                    PotterRoundOutcome::TaskFailed { message } => {
                         ExitReason::TaskFailed(message.clone())
                     }
-                    codex_protocol::protocol::PotterRoundOutcome::Fatal { message } => {
-                        ExitReason::Fatal(message.clone())
-                    }
+                    PotterRoundOutcome::Fatal { message } => ExitReason::Fatal(message.clone()),
                 };
                 self.exit_after_next_draw = true;
                 frame_requester.schedule_frame();
@@ -1984,14 +2485,14 @@ mod tests {
     }
 
     #[test]
-    fn potter_stream_recovery_update_replaces_existing_retry_block_in_place() {
+    fn potter_usage_limit_wait_shows_countdown_until_recovered() {
         let width: u16 = 80;
 
-        let (tx_raw, mut rx_app) = unbounded_channel::<AppEvent>();
+        let (tx_raw, _rx_app) = unbounded_channel::<AppEvent>();
         let app_event_tx = AppEventSender::new(tx_raw);
 
         let processor = RenderOnlyProcessor::new(app_event_tx.clone());
-        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let (op_tx, _op_rx) = unbounded_channel::<Op>();
         let mut bottom_pane = BottomPane::new(BottomPaneParams {
             frame_requester: crate::tui::FrameRequester::test_dummy(),
             enhanced_keys_supported: false,
@@ -2001,7 +2502,6 @@ mod tests {
             disable_paste_burst: false,
         });
         bottom_pane.set_task_running(true);
-
         let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
         let mut app = RenderAppState::new(
             processor,
@@ -2013,28 +2513,109 @@ mod tests {
             VecDeque::new(),
         );
 
+        let resumes_at = chrono::Utc::now().timestamp() + 2 * 3600;
         app.handle_codex_event(
             crate::tui::FrameRequester::test_dummy(),
             Event {
-                id: "err-1".into(),
-                msg: EventMsg::PotterStreamRecoveryUpdate {
-                    attempt: 1,
-                    max_attempts: 10,
-                    error_message:
-                        "stream disconnected before completion: error sending request for url (...)"
-                            .to_string(),
+                id: "err".into(),
+                msg: EventMsg::PotterUsageLimitWait {
+                    resumes_at,
+                    error_message: "You've hit your usage limit.".to_string(),
                 },
             },
         )
-        .expect("handle first update");
+        .expect("handle codex event");
 
         let transient_blob = lines_to_plain_strings(&app.build_transient_lines(width)).join("\n");
         assert!(
-            transient_blob.contains("• CodexPotter: retry 1/10"),
-            "missing retry 1/10: {transient_blob:?}"
+            transient_blob.contains("• CodexPotter: usage limit reached, continuing in 1h 59m")
+                || transient_blob
+                    .contains("• CodexPotter: usage limit reached, continuing in 2h 00m 00s"),
+            "missing countdown: {transient_blob:?}"
+        );
+        assert!(
+            transient_blob.contains("└ You've hit your usage limit."),
+            "missing usage limit error: {transient_blob:?}"
         );
 
-        app.handle_codex_event(
+        let cell = app
+            .potter_usage_limit_wait_cell
+            .as_ref()
+            .expect("usage limit wait cell");
+        let countdown = lines_to_plain_strings(&cell.display_lines_at(width, resumes_at - 65));
+        assert!(
+            countdown[0].contains("continuing in 1m 05s"),
+            "unexpected countdown: {countdown:?}"
+        );
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "recovered".into(),
+                msg: EventMsg::PotterStreamRecoveryRecovered,
+            },
+        )
+        .expect("handle recovered event");
+
+        let transient_blob = lines_to_plain_strings(&app.build_transient_lines(width)).join("\n");
+        assert!(
+            !transient_blob.contains("usage limit reached"),
+            "expected countdown to be cleared: {transient_blob:?}"
+        );
+    }
+
+    #[test]
+    fn potter_stream_recovery_update_replaces_existing_retry_block_in_place() {
+        let width: u16 = 80;
+
+        let (tx_raw, mut rx_app) = unbounded_channel::<AppEvent>();
+        let app_event_tx = AppEventSender::new(tx_raw);
+
+        let processor = RenderOnlyProcessor::new(app_event_tx.clone());
+        let (op_tx, mut op_rx) = unbounded_channel::<Op>();
+        let mut bottom_pane = BottomPane::new(BottomPaneParams {
+            frame_requester: crate::tui::FrameRequester::test_dummy(),
+            enhanced_keys_supported: false,
+            app_event_tx: app_event_tx.clone(),
+            animations_enabled: false,
+            placeholder_text: "Assign new task to CodexPotter".to_string(),
+            disable_paste_burst: false,
+        });
+        bottom_pane.set_task_running(true);
+
+        let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
+        let mut app = RenderAppState::new(
+            processor,
+            app_event_tx,
+            op_tx,
+            bottom_pane,
+            crate::prompt_history_store::PromptHistoryStore::new(),
+            file_search,
+            VecDeque::new(),
+        );
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "err-1".into(),
+                msg: EventMsg::PotterStreamRecoveryUpdate {
+                    attempt: 1,
+                    max_attempts: 10,
+                    error_message:
+                        "stream disconnected before completion: error sending request for url (...)"
+                            .to_string(),
+                },
+            },
+        )
+        .expect("handle first update");
+
+        let transient_blob = lines_to_plain_strings(&app.build_transient_lines(width)).join("\n");
+        assert!(
+            transient_blob.contains("• CodexPotter: retry 1/10"),
+            "missing retry 1/10: {transient_blob:?}"
+        );
+
+        app.handle_codex_event(
             crate::tui::FrameRequester::test_dummy(),
             Event {
                 id: "err-3".into(),
@@ -2320,6 +2901,122 @@ mod tests {
         assert!(app.exit_after_next_draw, "expected app to request exit");
     }
 
+    #[test]
+    fn session_view_keeps_rendering_across_rounds_and_switches_on_page_keys() {
+        let (tx_raw, _rx_app) = unbounded_channel::<AppEvent>();
+        let app_event_tx = AppEventSender::new(tx_raw);
+
+        let processor = RenderOnlyProcessor::new(app_event_tx.clone());
+        let (op_tx, _op_rx) = unbounded_channel::<Op>();
+        let bottom_pane = BottomPane::new(BottomPaneParams {
+            frame_requester: crate::tui::FrameRequester::test_dummy(),
+            enhanced_keys_supported: false,
+            app_event_tx: app_event_tx.clone(),
+            animations_enabled: false,
+            placeholder_text: "Assign new task to CodexPotter".to_string(),
+            disable_paste_burst: false,
+        });
+        let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
+        let mut app = RenderAppState::new(
+            processor,
+            app_event_tx,
+            op_tx,
+            bottom_pane,
+            crate::prompt_history_store::PromptHistoryStore::new(),
+            file_search,
+            VecDeque::new(),
+        );
+        app.session_view = true;
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "round-finished".into(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: codex_protocol::protocol::PotterRoundOutcome::Completed,
+                },
+            },
+        )
+        .expect("handle round finished event");
+        assert!(
+            !app.exit_after_next_draw,
+            "expected session view to keep rendering after a round"
+        );
+
+        app.handle_key_event(
+            crossterm::event::KeyEvent::new(
+                crossterm::event::KeyCode::PageDown,
+                crossterm::event::KeyModifiers::NONE,
+            ),
+            crate::tui::FrameRequester::test_dummy(),
+        );
+        pretty_assertions::assert_eq!(app.session_view_exit, Some(SessionViewExit::Next));
+        assert!(app.exit_after_next_draw, "expected app to request exit");
+    }
+
+    #[test]
+    fn render_only_progress_panel_toggles_once_progress_arrives() {
+        let (tx_raw, _rx_app) = unbounded_channel::<AppEvent>();
+        let app_event_tx = AppEventSender::new(tx_raw);
+
+        let processor = RenderOnlyProcessor::new(app_event_tx.clone());
+        let (op_tx, _op_rx) = unbounded_channel::<Op>();
+        let bottom_pane = BottomPane::new(BottomPaneParams {
+            frame_requester: crate::tui::FrameRequester::test_dummy(),
+            enhanced_keys_supported: false,
+            app_event_tx: app_event_tx.clone(),
+            animations_enabled: false,
+            placeholder_text: "Assign new task to CodexPotter".to_string(),
+            disable_paste_burst: false,
+        });
+        let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
+        let mut app = RenderAppState::new(
+            processor,
+            app_event_tx,
+            op_tx,
+            bottom_pane,
+            crate::prompt_history_store::PromptHistoryStore::new(),
+            file_search,
+            VecDeque::new(),
+        );
+        let ctrl_t = || {
+            crossterm::event::KeyEvent::new(
+                crossterm::event::KeyCode::Char('t'),
+                crossterm::event::KeyModifiers::CONTROL,
+            )
+        };
+
+        let height_without_progress = app.bottom_pane.desired_height(80);
+        app.handle_key_event(ctrl_t(), crate::tui::FrameRequester::test_dummy());
+        assert!(
+            !app.bottom_pane.progress_panel_collapsed(),
+            "expected ctrl+t to do nothing before progress arrives"
+        );
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "progress".into(),
+                msg: EventMsg::PotterProgressUpdated {
+                    progress: codex_protocol::protocol::PotterProgress {
+                        title: Some("Fix resume picker search".to_string()),
+                        ..Default::default()
+                    },
+                },
+            },
+        )
+        .expect("handle progress updated event");
+        assert!(
+            app.bottom_pane.desired_height(80) > height_without_progress,
+            "expected the progress panel to take space"
+        );
+
+        app.handle_key_event(ctrl_t(), crate::tui::FrameRequester::test_dummy());
+        assert!(app.bottom_pane.progress_panel_collapsed());
+        app.handle_key_event(ctrl_t(), crate::tui::FrameRequester::test_dummy());
+        assert!(!app.bottom_pane.progress_panel_collapsed());
+    }
+
     #[test]
     fn render_only_context_window_percent_uses_baseline_and_last_token_usage() {
         let (tx_raw, _rx_app) = unbounded_channel::<AppEvent>();
@@ -2411,6 +3108,94 @@ mod tests {
         assert_eq!(app.bottom_pane.context_window_used_tokens(), Some(123_456));
     }
 
+    #[test]
+    fn ctrl_c_interrupts_turn_and_finishes_round_with_chosen_outcome() {
+        use crossterm::event::KeyCode;
+        use crossterm::event::KeyEvent;
+        use crossterm::event::KeyModifiers;
+
+        let (tx_raw, mut rx_app) = unbounded_channel::<AppEvent>();
+        let app_event_tx = AppEventSender::new(tx_raw);
+
+        let processor = RenderOnlyProcessor::new(app_event_tx.clone());
+        let (op_tx, _op_rx) = unbounded_channel::<Op>();
+        let mut bottom_pane = BottomPane::new(BottomPaneParams {
+            frame_requester: crate::tui::FrameRequester::test_dummy(),
+            enhanced_keys_supported: false,
+            app_event_tx: app_event_tx.clone(),
+            animations_enabled: false,
+            placeholder_text: "Assign new task to CodexPotter".to_string(),
+            disable_paste_burst: false,
+        });
+        bottom_pane.set_task_running(true);
+        let file_search = FileSearchManager::new(std::env::temp_dir(), app_event_tx.clone());
+        let mut app = RenderAppState::new(
+            processor,
+            app_event_tx,
+            op_tx,
+            bottom_pane,
+            crate::prompt_history_store::PromptHistoryStore::new(),
+            file_search,
+            VecDeque::new(),
+        );
+
+        app.handle_key_event(
+            KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL),
+            crate::tui::FrameRequester::test_dummy(),
+        );
+        assert!(!app.exit_after_next_draw, "first Ctrl+C must not exit");
+        let mut ops = Vec::new();
+        while let Ok(event) = rx_app.try_recv() {
+            if let AppEvent::CodexOp(op) = event {
+                ops.push(op);
+            }
+        }
+        pretty_assertions::assert_eq!(ops, vec![Op::Interrupt]);
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "aborted".into(),
+                msg: EventMsg::TurnAborted(codex_protocol::protocol::TurnAbortedEvent {
+                    turn_id: Some("turn-1".to_string()),
+                    reason: codex_protocol::protocol::TurnAbortReason::Interrupted,
+                }),
+            },
+        )
+        .expect("handle codex event");
+        assert!(
+            app.pending_interrupt_choice,
+            "expected the interrupt prompt"
+        );
+        assert!(!app.exit_after_next_draw);
+
+        app.finish_interrupted_round(PotterRoundOutcome::InterruptedNextRound);
+        let finish_op =
+            std::iter::from_fn(|| rx_app.try_recv().ok()).find_map(|event| match event {
+                AppEvent::CodexOp(op) => Some(op),
+                _ => None,
+            });
+        pretty_assertions::assert_eq!(
+            finish_op,
+            Some(Op::PotterFinishRound {
+                outcome: PotterRoundOutcome::InterruptedNextRound,
+            })
+        );
+
+        app.handle_codex_event(
+            crate::tui::FrameRequester::test_dummy(),
+            Event {
+                id: "round-finished".into(),
+                msg: EventMsg::PotterRoundFinished {
+                    outcome: PotterRoundOutcome::InterruptedNextRound,
+                },
+            },
+        )
+        .expect("handle codex event");
+        assert!(app.exit_after_next_draw, "expected app to request exit");
+        assert!(matches!(app.exit_reason, ExitReason::Completed));
+    }
+
     #[test]
     fn render_only_composer_processes_repeat_cursor_movement() {
         use crossterm::event::KeyCode;
@@ -3285,6 +4070,7 @@ mod tests {
                 user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/11/MAIN.md"),
                 git_commit_start: String::from("fb827a203635875b58d7e6792da84f22d723d41b"),
                 git_commit_end: String::from("662d232cafebabedeadbeefdeadbeefdeadbeef"),
+                round_token_usage: Vec::new(),
             },
         });
         proc.handle_codex_event(Event {
@@ -3978,6 +4764,231 @@ mod tests {
         assert_snapshot!("render_only_potter_round_started", rendered);
     }
 
+    #[test]
+    fn render_only_potter_round_verified_emits_output_tail_on_failure() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-round-verified".into(),
+            msg: EventMsg::PotterRoundVerified {
+                command: "cargo test".to_string(),
+                success: false,
+                exit_code: Some(101),
+                duration: Duration::from_secs(12),
+                output_tail: "test foo ... FAILED\ntest result: FAILED".to_string(),
+            },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [verified] = events.as_slice() else {
+            panic!("expected exactly one verification cell");
+        };
+        let rendered = verified.join("\n") + "\n";
+        assert_snapshot!("render_only_potter_round_verified_failed", rendered);
+    }
+
+    #[test]
+    fn render_only_potter_budget_exhausted_emits_stop_notice() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-budget-exhausted".into(),
+            msg: EventMsg::PotterBudgetExhausted {
+                reason: codex_protocol::protocol::PotterBudgetExhaustedReason::MaxTokens {
+                    limit: 5_000_000,
+                    used: 5_230_000,
+                },
+            },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [notice] = events.as_slice() else {
+            panic!("expected exactly one budget notice cell");
+        };
+        assert_eq!(
+            notice,
+            &vec![
+                "■ CodexPotter: stopped, token budget exhausted (5.23M of 5M tokens)".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn render_only_potter_session_stalled_emits_stop_notice() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-session-stalled".into(),
+            msg: EventMsg::PotterSessionStalled { rounds: 3 },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [notice] = events.as_slice() else {
+            panic!("expected exactly one stall notice cell");
+        };
+        assert_eq!(
+            notice,
+            &vec!["■ CodexPotter: stopped, no progress in the last 3 rounds".to_string()]
+        );
+    }
+
+    #[test]
+    fn render_only_potter_progress_file_issues_emit_warning() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-progress-file-issues".into(),
+            msg: EventMsg::PotterProgressFileIssues {
+                repaired: vec!["missing the `## Done` section".to_string()],
+                unresolved: vec!["the front matter has no `status` field".to_string()],
+            },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [warning] = events.as_slice() else {
+            panic!("expected exactly one progress file warning cell");
+        };
+        assert_eq!(
+            warning,
+            &vec![
+                "⚠ CodexPotter: progress file was broken".to_string(),
+                "  fixed: missing the `## Done` section".to_string(),
+                "  left for the next round: the front matter has no `status` field".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn render_only_potter_context_checkpoint_and_exhausted_emit_notices() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-context-checkpoint".into(),
+            msg: EventMsg::PotterContextCheckpoint { percent_left: 8 },
+        });
+        proc.handle_codex_event(Event {
+            id: "potter-round-finished".into(),
+            msg: EventMsg::PotterRoundFinished {
+                outcome: codex_protocol::protocol::PotterRoundOutcome::ContextExhausted,
+            },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [checkpoint, exhausted] = events.as_slice() else {
+            panic!("expected checkpoint and exhausted notice cells");
+        };
+        assert_eq!(
+            checkpoint,
+            &vec![
+                "• CodexPotter: 8% context left  (saving progress before a fresh round)"
+                    .to_string()
+            ]
+        );
+        assert_eq!(
+            exhausted,
+            &vec![
+                "• CodexPotter: context window exhausted  (continuing in a fresh round that does not count against --rounds)"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn render_only_potter_round_timed_out_emits_notice() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+
+        proc.handle_codex_event(Event {
+            id: "potter-round-finished".into(),
+            msg: EventMsg::PotterRoundFinished {
+                outcome: codex_protocol::protocol::PotterRoundOutcome::TimedOut {
+                    timeout: codex_protocol::protocol::PotterRoundTimeout::Idle {
+                        limit_secs: 1800,
+                    },
+                },
+            },
+        });
+
+        let events = drain_history_cell_strings(&mut rx, u16::MAX);
+        let [notice] = events.as_slice() else {
+            panic!("expected exactly one timeout notice cell");
+        };
+        assert_eq!(
+            notice,
+            &vec![
+                "✗ CodexPotter: round timed out, no events for 30m 00s  (app-server killed)"
+                    .to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn render_only_potter_round_usage_renders_round_and_session_totals() {
+        let (mut proc, mut rx) = make_render_only_processor_without_prompt();
+        let usage = |input_tokens, output_tokens| TokenUsage {
+            input_tokens,
+            cached_input_tokens: input_tokens / 2,
+            output_tokens,
+            reasoning_output_tokens: output_tokens / 4,
+            total_tokens: input_tokens + output_tokens,
+        };
+
+        proc.handle_codex_event(Event {
+            id: "potter-round-usage".into(),
+            msg: EventMsg::PotterRoundUsage {
+                duration: Duration::from_secs(5 * 60 + 3),
+                token_usage: usage(100_000, 20_000),
+                session_token_usage: usage(300_000, 60_000),
+            },
+        });
+        proc.handle_codex_event(Event {
+            id: "potter-succeeded".into(),
+            msg: EventMsg::PotterSessionSucceeded {
+                rounds: 3,
+                duration: Duration::from_secs(15 * 60),
+                user_prompt_file: PathBuf::from(".codexpotter/projects/2026/02/01/11/MAIN.md"),
+                git_commit_start: String::new(),
+                git_commit_end: String::new(),
+                round_token_usage: vec![
+                    usage(80_000, 20_000),
+                    usage(120_000, 20_000),
+                    usage(100_000, 20_000),
+                ],
+            },
+        });
+        proc.handle_codex_event(Event {
+            id: "turn-complete".into(),
+            msg: EventMsg::TurnComplete(TurnCompleteEvent {
+                turn_id: "turn-1".to_string(),
+                last_agent_message: None,
+            }),
+        });
+
+        let events = drain_history_cell_strings(&mut rx, 200);
+        let [round_usage, .., succeeded] = events.as_slice() else {
+            panic!("expected round usage and session succeeded cells");
+        };
+        assert_eq!(
+            round_usage,
+            &vec![
+                "  ↳ Round: 120K tokens in 5m 03s (input 100K, cached 50K, output 20K, reasoning 5K) · Session: 360K tokens"
+                    .to_string()
+            ]
+        );
+        let token_lines = succeeded
+            .iter()
+            .filter(|line| line.contains("Tokens:") || line.contains("per round:"))
+            .map(|line| line.trim_end().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(
+            token_lines,
+            vec![
+                "    Tokens:       360K total (input 300K, cached 150K, output 60K, reasoning 15K)"
+                    .to_string(),
+                "                  per round: 100K, 140K, 120K".to_string(),
+            ]
+        );
+    }
+
     fn render_prompt_footer_line(override_mode: Option<PromptFooterOverride>) -> String {
         let area = Rect::new(0, 0, 80, 1);
         let mut buf = ratatui::buffer::Buffer::empty(area);
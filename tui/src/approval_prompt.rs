@@ -0,0 +1,126 @@
+use std::path::PathBuf;
+
+use codex_protocol::protocol::PotterApprovalDecision;
+use ratatui::text::Line;
+
+use crate::bottom_pane::ListSelectionView;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::tui::Tui;
+
+/// An approval request an `ask` rule of the `[approval]` config left to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApprovalRequest {
+    pub(crate) id: String,
+    pub(crate) command: Option<Vec<String>>,
+    pub(crate) paths: Vec<PathBuf>,
+    pub(crate) reason: Option<String>,
+}
+
+/// Ask the user to accept or deny `request`; Esc/Ctrl+C deny.
+pub(crate) async fn prompt_approval(
+    tui: &mut Tui,
+    request: &ApprovalRequest,
+) -> anyhow::Result<PotterApprovalDecision> {
+    let selected =
+        crate::action_picker_prompt::run_selection_view(tui, approval_view(request)).await?;
+    Ok(match selected {
+        Some(0) => PotterApprovalDecision::Accept,
+        _ => PotterApprovalDecision::Deny,
+    })
+}
+
+fn approval_view(request: &ApprovalRequest) -> ListSelectionView {
+    let (title, subject) = match &request.command {
+        Some(command) => (
+            "Allow the agent to run this command?",
+            shlex::try_join(command.iter().map(String::as_str))
+                .unwrap_or_else(|_| command.join(" ")),
+        ),
+        None => (
+            "Allow the agent to change these files?",
+            request
+                .paths
+                .iter()
+                .map(|path| path.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", "),
+        ),
+    };
+    let items = vec![
+        SelectionItem {
+            name: "Accept".to_string(),
+            description: Some("Let the agent go ahead".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Deny".to_string(),
+            description: Some("The agent is told the request was declined".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+    ];
+
+    ListSelectionView::new(SelectionViewParams {
+        title: Some(title.to_string()),
+        subtitle: Some(subject),
+        footer_note: request
+            .reason
+            .as_ref()
+            .map(|reason| Line::from(format!("Reason: {reason}"))),
+        footer_hint: Some(Line::from("Press enter to confirm, or esc to deny.")),
+        items,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::renderable::Renderable;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn approval_view_shows_command_and_choices() {
+        let view = approval_view(&ApprovalRequest {
+            id: "approval-1".to_string(),
+            command: Some(vec![
+                "git".to_string(),
+                "push".to_string(),
+                "origin".to_string(),
+            ]),
+            paths: Vec::new(),
+            reason: Some("needs network access".to_string()),
+        });
+        let width = 80;
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, view.desired_height(width))).expect("terminal");
+        terminal
+            .draw(|frame| view.render(frame.area(), frame.buffer_mut()))
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        let rendered = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for expected in [
+            "Allow the agent to run this command?",
+            "git push origin",
+            "Accept",
+            "Deny",
+            "Reason: needs network access",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected:?} in:\n{rendered}"
+            );
+        }
+    }
+}
@@ -6,7 +6,7 @@
 //! running.
 //!
 //! This crate also includes a small subset of upstream selection-style views for CodexPotter's
-//! resume action picker.
+//! resume action picker, and CodexPotter's progress panel (`progress_panel.rs`).
 
 mod chat_composer;
 mod chat_composer_history;
@@ -15,6 +15,7 @@ mod footer;
 mod list_selection_view;
 mod paste_burst;
 pub mod popup_consts;
+mod progress_panel;
 mod queued_user_messages;
 mod scroll_state;
 mod selection_popup_common;
@@ -28,6 +29,8 @@ pub use chat_composer::InputResult;
 pub use list_selection_view::ListSelectionView;
 pub use list_selection_view::SelectionItem;
 pub use list_selection_view::SelectionViewParams;
+pub use progress_panel::ProgressPanel;
+pub use progress_panel::TOGGLE_PROGRESS_PANEL_KEY;
 pub use queued_user_messages::QueuedUserMessages;
 
 use ratatui::buffer::Buffer;
@@ -64,7 +67,7 @@ pub struct BottomPaneParams {
 ///
 /// This is a minimal subset of upstream Codex's `BottomPane`: it owns the prompt input
 /// (`ChatComposer`), renders queued user messages while a task is running, and optionally renders
-/// a status indicator above the composer.
+/// a status indicator and the progress panel above the composer.
 pub struct BottomPane {
     frame_requester: FrameRequester,
     animations_enabled: bool,
@@ -76,6 +79,7 @@ pub struct BottomPane {
     context_window_percent: Option<i64>,
     context_window_used_tokens: Option<i64>,
 
+    progress_panel: ProgressPanel,
     queued_user_messages: QueuedUserMessages,
     composer: ChatComposer,
     prompt_footer_override: Option<PromptFooterOverride>,
@@ -110,6 +114,7 @@ impl BottomPane {
             status_details: None,
             context_window_percent: None,
             context_window_used_tokens: None,
+            progress_panel: ProgressPanel::new(),
             queued_user_messages: QueuedUserMessages::new(),
             composer,
             prompt_footer_override: None,
@@ -197,6 +202,31 @@ impl BottomPane {
         self.queued_user_messages.messages = queued;
     }
 
+    pub fn set_progress(&mut self, progress: codex_protocol::protocol::PotterProgress) {
+        self.progress_panel.progress = Some(progress);
+        self.request_redraw();
+    }
+
+    pub fn progress_panel_collapsed(&self) -> bool {
+        self.progress_panel.collapsed
+    }
+
+    pub fn set_progress_panel_collapsed(&mut self, collapsed: bool) {
+        self.progress_panel.collapsed = collapsed;
+    }
+
+    /// Collapse the progress panel to its header line, or expand it again.
+    ///
+    /// Returns `false` when there is no panel to toggle.
+    pub fn toggle_progress_panel(&mut self) -> bool {
+        if self.progress_panel.progress.is_none() {
+            return false;
+        }
+        self.progress_panel.collapsed = !self.progress_panel.collapsed;
+        self.request_redraw();
+        true
+    }
+
     pub fn set_prompt_footer_override(&mut self, override_mode: Option<PromptFooterOverride>) {
         self.prompt_footer_override = override_mode;
     }
@@ -302,13 +332,31 @@ impl Renderable for BottomPane {
             }
         }
 
-        let queue_height = top_area.height.saturating_sub(status_height);
+        let progress_height = self
+            .progress_panel
+            .desired_height(width)
+            .min(top_area.height.saturating_sub(status_height));
+        if progress_height > 0 {
+            self.progress_panel.render(
+                Rect::new(
+                    top_area.x,
+                    top_area.y + status_height,
+                    top_area.width,
+                    progress_height,
+                ),
+                buf,
+            );
+        }
+
+        let queue_height = top_area
+            .height
+            .saturating_sub(status_height.saturating_add(progress_height));
         if queue_height == 0 {
             return;
         }
         let queue_area = Rect::new(
             top_area.x,
-            top_area.y + status_height,
+            top_area.y + status_height + progress_height,
             top_area.width,
             queue_height,
         );
@@ -320,6 +368,7 @@ impl Renderable for BottomPane {
             .as_ref()
             .map(|status| status.desired_height(width).saturating_add(2))
             .unwrap_or(0)
+            + self.progress_panel.desired_height(width)
             + self.queued_user_messages.desired_height(width)
             + self.composer.desired_height(width)
             + 1
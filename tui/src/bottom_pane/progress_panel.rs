@@ -0,0 +1,223 @@
+//! Progress panel pinned above the composer while rounds run.
+//!
+//! Shows the current progress file (`MAIN.md`) as sent by the runner in
+//! `EventMsg::PotterProgressUpdated`: a header with the title, status and task counts, and the
+//! `In Progress` / `Todo` / `Done` items styled like the `Updated Plan` checklist. `Ctrl+T`
+//! collapses it to the header line.
+
+use codex_protocol::protocol::PotterProgress;
+use codex_protocol::protocol::PotterProgressItem;
+use crossterm::event::KeyCode;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::Style;
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::text::Span;
+use ratatui::widgets::Paragraph;
+
+use crate::key_hint;
+use crate::key_hint::KeyBinding;
+use crate::render::renderable::Renderable;
+use crate::wrapping::RtOptions;
+use crate::wrapping::word_wrap_lines;
+
+/// Key that collapses and expands the panel.
+pub const TOGGLE_PROGRESS_PANEL_KEY: KeyBinding = key_hint::ctrl(KeyCode::Char('t'));
+
+/// Items shown per section before the rest is summarized as `… N more`.
+const MAX_ITEMS_PER_SECTION: usize = 5;
+
+/// Widget that displays the progress file of the running session.
+pub struct ProgressPanel {
+    pub progress: Option<PotterProgress>,
+    pub collapsed: bool,
+}
+
+impl ProgressPanel {
+    pub fn new() -> Self {
+        Self {
+            progress: None,
+            collapsed: false,
+        }
+    }
+
+    fn lines(&self, width: u16) -> Vec<Line<'static>> {
+        let Some(progress) = self.progress.as_ref() else {
+            return Vec::new();
+        };
+        if width < 4 {
+            return Vec::new();
+        }
+
+        let open = |items: &[PotterProgressItem]| items.iter().filter(|item| !item.checked).count();
+        let counts = format!(
+            "{} in progress · {} todo · {} done",
+            open(&progress.in_progress),
+            open(&progress.todo),
+            progress.done.len()
+        );
+        let (marker, toggle) = if self.collapsed {
+            ("▸ ", " expand")
+        } else {
+            ("▾ ", " collapse")
+        };
+        let mut header = vec![
+            marker.dim(),
+            progress
+                .title
+                .clone()
+                .unwrap_or_else(|| "Progress".to_string())
+                .bold(),
+        ];
+        if let Some(status) = progress.status.as_ref() {
+            header.push(" · ".dim());
+            header.push(status.clone().cyan());
+        }
+        header.push(" · ".dim());
+        header.push(counts.dim());
+        header.push("  ".into());
+        header.push(Span::from(TOGGLE_PROGRESS_PANEL_KEY).dim());
+        header.push(toggle.dim());
+
+        let mut lines = word_wrap_lines(
+            [Line::from(header)],
+            RtOptions::new(width as usize)
+                .initial_indent(Line::from("  "))
+                .subsequent_indent(Line::from("    ")),
+        );
+        if self.collapsed {
+            return lines;
+        }
+
+        for (title, items, active) in [
+            ("In Progress", &progress.in_progress, true),
+            ("Todo", &progress.todo, false),
+            ("Done", &progress.done, false),
+        ] {
+            if items.is_empty() {
+                continue;
+            }
+            lines.push(Line::from(vec!["    ".into(), title.dim().bold()]));
+            for item in items.iter().take(MAX_ITEMS_PER_SECTION) {
+                let done = item.checked || title == "Done";
+                let (checkbox, style) = if done {
+                    ("✔ ", Style::default().crossed_out().dim())
+                } else if active {
+                    ("□ ", Style::default().cyan().bold())
+                } else {
+                    ("□ ", Style::default().dim())
+                };
+                lines.extend(word_wrap_lines(
+                    [Line::from(Span::styled(item.text.clone(), style))],
+                    RtOptions::new(width as usize)
+                        .initial_indent(Line::from(format!("    {checkbox}")))
+                        .subsequent_indent(Line::from("      ")),
+                ));
+            }
+            if items.len() > MAX_ITEMS_PER_SECTION {
+                lines.push(Line::from(
+                    format!("      … {} more", items.len() - MAX_ITEMS_PER_SECTION)
+                        .dim()
+                        .italic(),
+                ));
+            }
+        }
+        lines
+    }
+}
+
+impl Renderable for ProgressPanel {
+    fn render(&self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+
+        Paragraph::new(self.lines(area.width)).render(area, buf);
+    }
+
+    fn desired_height(&self, width: u16) -> u16 {
+        u16::try_from(self.lines(width).len()).unwrap_or(u16::MAX)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pretty_assertions::assert_eq;
+
+    fn item(text: &str, checked: bool) -> PotterProgressItem {
+        PotterProgressItem {
+            text: text.to_string(),
+            checked,
+        }
+    }
+
+    fn panel() -> ProgressPanel {
+        let mut panel = ProgressPanel::new();
+        panel.progress = Some(PotterProgress {
+            title: Some("Fix resume picker search".to_string()),
+            status: Some("open".to_string()),
+            in_progress: vec![item("Add tests", false)],
+            todo: vec![
+                item("Add the parser", false),
+                item("Drop the old lexer", true),
+            ],
+            done: (1..=7).map(|n| item(&format!("Step {n}"), false)).collect(),
+        });
+        panel
+    }
+
+    fn rendered(panel: &ProgressPanel, width: u16) -> Vec<String> {
+        panel
+            .lines(width)
+            .iter()
+            .map(|line| {
+                line.spans
+                    .iter()
+                    .map(|span| span.content.as_ref())
+                    .collect::<String>()
+            })
+            .collect()
+    }
+
+    #[test]
+    fn empty_panel_takes_no_space() {
+        assert_eq!(ProgressPanel::new().desired_height(80), 0);
+    }
+
+    #[test]
+    fn expanded_panel_lists_sections() {
+        assert_eq!(
+            rendered(&panel(), 100),
+            vec![
+                "  ▾ Fix resume picker search · open · 1 in progress · 1 todo · 7 done  ctrl + t collapse",
+                "    In Progress",
+                "    □ Add tests",
+                "    Todo",
+                "    □ Add the parser",
+                "    ✔ Drop the old lexer",
+                "    Done",
+                "    ✔ Step 1",
+                "    ✔ Step 2",
+                "    ✔ Step 3",
+                "    ✔ Step 4",
+                "    ✔ Step 5",
+                "      … 2 more",
+            ]
+        );
+    }
+
+    #[test]
+    fn collapsed_panel_keeps_the_header() {
+        let mut panel = panel();
+        panel.collapsed = true;
+        assert_eq!(
+            rendered(&panel, 100),
+            vec![
+                "  ▸ Fix resume picker search · open · 1 in progress · 1 todo · 7 done  ctrl + t expand"
+            ]
+        );
+        assert_eq!(panel.desired_height(100), 1);
+    }
+}
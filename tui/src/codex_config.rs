@@ -22,14 +22,30 @@ pub struct CodexProfileModelConfig {
     pub reasoning_effort: Option<ReasoningEffort>,
 }
 
+/// Values passed on the `codex-potter` command line (or its `[codex]` config), which take
+/// precedence over every Codex config layer.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct CodexModelOverrides {
+    pub model: Option<String>,
+    pub profile: Option<String>,
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ResolvedCodexModelConfig {
     pub model: String,
     pub reasoning_effort: Option<ReasoningEffort>,
+    pub profile: Option<String>,
 }
 
-pub fn resolve_codex_model_config(cwd: &Path) -> io::Result<ResolvedCodexModelConfig> {
-    let raw = load_codex_model_config(cwd)?;
+pub fn resolve_codex_model_config(
+    cwd: &Path,
+    overrides: &CodexModelOverrides,
+) -> io::Result<ResolvedCodexModelConfig> {
+    let mut raw = load_codex_model_config(cwd)?;
+    if let Some(profile) = &overrides.profile {
+        raw.profile = Some(profile.clone());
+    }
 
     let profile_config = match &raw.profile {
         Some(name) => raw.profiles.get(name).cloned().ok_or_else(|| {
@@ -41,15 +57,21 @@ pub fn resolve_codex_model_config(cwd: &Path) -> io::Result<ResolvedCodexModelCo
         None => CodexProfileModelConfig::default(),
     };
 
-    let model = profile_config
+    let model = overrides
         .model
+        .clone()
+        .or(profile_config.model)
         .or(raw.model)
         .unwrap_or_else(|| DEFAULT_FALLBACK_MODEL.to_string());
-    let reasoning_effort = profile_config.reasoning_effort.or(raw.reasoning_effort);
+    let reasoning_effort = overrides
+        .reasoning_effort
+        .or(profile_config.reasoning_effort)
+        .or(raw.reasoning_effort);
 
     Ok(ResolvedCodexModelConfig {
         model,
         reasoning_effort,
+        profile: raw.profile,
     })
 }
 
@@ -335,7 +357,8 @@ model_reasoning_effort = "high"
         );
 
         let cwd = tempfile::tempdir().expect("cwd");
-        let resolved = resolve_codex_model_config(cwd.path()).expect("resolve");
+        let resolved = resolve_codex_model_config(cwd.path(), &CodexModelOverrides::default())
+            .expect("resolve");
         assert_eq!(resolved.model, "gpt-5.2-codex");
         assert_eq!(resolved.reasoning_effort, Some(ReasoningEffort::High));
     }
@@ -363,10 +386,70 @@ model = "gpt-5.2-codex"
 "#,
         );
 
-        let resolved = resolve_codex_model_config(repo.path()).expect("resolve");
+        let resolved = resolve_codex_model_config(repo.path(), &CodexModelOverrides::default())
+            .expect("resolve");
         assert_eq!(resolved.model, "gpt-5.2-codex");
     }
 
+    #[test]
+    #[serial]
+    fn overrides_take_precedence_over_config_layers() {
+        let codex_home = tempfile::tempdir().expect("tempdir");
+        let _env = EnvVarGuard::set("CODEX_HOME", codex_home.path());
+
+        write_config(
+            &codex_home.path().join("config.toml"),
+            r#"
+model = "gpt-5.2"
+model_reasoning_effort = "low"
+
+[profiles.work]
+model = "gpt-5.2-codex"
+model_reasoning_effort = "high"
+"#,
+        );
+
+        let cwd = tempfile::tempdir().expect("cwd");
+        let resolved = resolve_codex_model_config(
+            cwd.path(),
+            &CodexModelOverrides {
+                profile: Some("work".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect("resolve");
+        assert_eq!(
+            resolved,
+            ResolvedCodexModelConfig {
+                model: "gpt-5.2-codex".to_string(),
+                reasoning_effort: Some(ReasoningEffort::High),
+                profile: Some("work".to_string()),
+            }
+        );
+
+        let resolved = resolve_codex_model_config(
+            cwd.path(),
+            &CodexModelOverrides {
+                model: Some("o3".to_string()),
+                profile: Some("work".to_string()),
+                reasoning_effort: Some(ReasoningEffort::XHigh),
+            },
+        )
+        .expect("resolve");
+        assert_eq!(resolved.model, "o3");
+        assert_eq!(resolved.reasoning_effort, Some(ReasoningEffort::XHigh));
+
+        let err = resolve_codex_model_config(
+            cwd.path(),
+            &CodexModelOverrides {
+                profile: Some("missing".to_string()),
+                ..Default::default()
+            },
+        )
+        .expect_err("expected error");
+        assert_eq!(err.kind(), io::ErrorKind::NotFound);
+    }
+
     #[test]
     #[serial]
     fn resolving_selected_profile_errors_when_profile_is_missing() {
@@ -382,7 +465,8 @@ profile = "missing"
         );
 
         let cwd = tempfile::tempdir().expect("cwd");
-        let err = resolve_codex_model_config(cwd.path()).expect_err("expected error");
+        let err = resolve_codex_model_config(cwd.path(), &CodexModelOverrides::default())
+            .expect_err("expected error");
         assert_eq!(err.kind(), io::ErrorKind::NotFound);
         assert!(
             err.to_string()
@@ -418,7 +502,8 @@ model = "gpt-5.2-codex"
         let cwd = repo.path().join("subdir");
         std::fs::create_dir_all(&cwd).expect("mkdir subdir");
 
-        let resolved = resolve_codex_model_config(&cwd).expect("resolve");
+        let resolved =
+            resolve_codex_model_config(&cwd, &CodexModelOverrides::default()).expect("resolve");
         assert_eq!(resolved.model, "gpt-5.2-codex");
     }
 }
@@ -18,6 +18,15 @@ pub fn is_ctrl_g(key_event: &KeyEvent) -> bool {
 pub async fn run_external_editor(
     tui: &mut Tui,
     composer: &ChatComposer,
+) -> anyhow::Result<Option<String>> {
+    let seed = composer.current_text_with_pending();
+    run_external_editor_with_seed(tui, &seed).await
+}
+
+/// Edit `seed` in `$VISUAL`/`$EDITOR`; `Ok(None)` means neither is set.
+pub async fn run_external_editor_with_seed(
+    tui: &mut Tui,
+    seed: &str,
 ) -> anyhow::Result<Option<String>> {
     let editor_cmd = match external_editor::resolve_editor_command() {
         Ok(cmd) => cmd,
@@ -25,10 +34,9 @@ pub async fn run_external_editor(
         Err(err) => return Err(anyhow::Error::new(err)),
     };
 
-    let seed = composer.current_text_with_pending();
     let editor_result = tui
         .with_restored(tui::RestoreMode::KeepRaw, || async {
-            external_editor::run_editor(&seed, &editor_cmd).await
+            external_editor::run_editor(seed, &editor_cmd).await
         })
         .await;
 
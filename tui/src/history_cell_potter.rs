@@ -4,7 +4,9 @@
 //!
 //! Upstream Codex does not render these cells. They are used to surface CodexPotter-specific
 //! runner behavior, such as multi-round iteration markers, project hints, stream recovery retries,
-//! and the final "session succeeded" summary.
+//! verification command results, per-round token usage, budget stops, interrupted turns, approval
+//! decisions, answered agent questions, commands stopped by the command guard, rounds stopped by
+//! the watchdog, broken progress files, and the final "session succeeded" summary.
 //!
 //! See `tui/AGENTS.md` ("Additional CodexPotter items" and "auto retry on stream/network errors").
 
@@ -12,6 +14,13 @@ use std::path::PathBuf;
 use std::time::Duration;
 use std::{ffi::OsStr, path::Path};
 
+use codex_protocol::protocol::PotterApprovalDecidedBy;
+use codex_protocol::protocol::PotterApprovalDecision;
+use codex_protocol::protocol::PotterBudgetExhaustedReason;
+use codex_protocol::protocol::PotterPromptAnswer;
+use codex_protocol::protocol::PotterPromptAnsweredBy;
+use codex_protocol::protocol::PotterRoundTimeout;
+use codex_protocol::protocol::TokenUsage;
 use ratatui::style::Modifier;
 use ratatui::style::Style;
 use ratatui::style::Stylize;
@@ -22,7 +31,9 @@ use unicode_width::UnicodeWidthStr;
 
 use crate::history_cell::HistoryCell;
 use crate::history_cell::PrefixedWrappedHistoryCell;
+use crate::status_indicator_widget::fmt_elapsed_compact;
 use crate::text_formatting::capitalize_first;
+use crate::token_format::format_tokens_compact;
 use crate::ui_colors::secondary_color;
 use crate::wrapping::RtOptions;
 use crate::wrapping::word_wrap_lines;
@@ -42,6 +53,271 @@ pub fn new_potter_round_started(current: u32, total: u32) -> PrefixedWrappedHist
     PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
 }
 
+/// Render the header shown when switching between parallel sessions (`--parallel`).
+pub fn new_potter_session_view_header(
+    current: usize,
+    total: usize,
+    label: String,
+) -> PrefixedWrappedHistoryCell {
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        format!("session {current}/{total} · {label}").into(),
+        "  (PgUp/PgDn to switch)".dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
+}
+
+/// Render the notice shown when a session-wide budget stops the session.
+pub fn new_potter_budget_exhausted(
+    reason: PotterBudgetExhaustedReason,
+) -> PrefixedWrappedHistoryCell {
+    let details = match reason {
+        PotterBudgetExhaustedReason::MaxDuration {
+            limit_secs,
+            elapsed_secs,
+        } => format!(
+            "time budget exhausted ({} of {})",
+            crate::status_indicator_widget::fmt_elapsed_compact(elapsed_secs),
+            crate::status_indicator_widget::fmt_elapsed_compact(limit_secs),
+        ),
+        PotterBudgetExhaustedReason::MaxTokens { limit, used } => format!(
+            "token budget exhausted ({} of {} tokens)",
+            format_tokens_compact(i64::try_from(used).unwrap_or(i64::MAX)),
+            format_tokens_compact(i64::try_from(limit).unwrap_or(i64::MAX)),
+        ),
+    };
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        format!("stopped, {details}").red(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "■ ".red(), "  ")
+}
+
+/// Notice shown when `--max-stalled-rounds` rounds in a row completed without progress.
+pub fn new_potter_session_stalled(rounds: u32) -> PrefixedWrappedHistoryCell {
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        format!("stopped, no progress in the last {rounds} rounds").red(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "■ ".red(), "  ")
+}
+
+/// Warning shown when the progress file was broken after a round: what CodexPotter repaired and
+/// what is left for the next round.
+pub fn new_potter_progress_file_issues(
+    repaired: Vec<String>,
+    unresolved: Vec<String>,
+) -> PrefixedWrappedHistoryCell {
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        "progress file was broken".red(),
+    ])];
+    lines.extend(
+        repaired
+            .into_iter()
+            .map(|issue| Line::from(format!("fixed: {issue}").dim())),
+    );
+    lines.extend(
+        unresolved
+            .into_iter()
+            .map(|issue| Line::from(format!("left for the next round: {issue}").dim())),
+    );
+    PrefixedWrappedHistoryCell::new(Text::from(lines), "⚠ ".red(), "  ")
+}
+
+/// Render the hint shown after the user chose to continue an interrupted round.
+pub fn new_potter_interrupt_continue_hint() -> PrefixedWrappedHistoryCell {
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        "turn interrupted, type a new instruction to continue this round".into(),
+        "  (Ctrl+C to end the session)".dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
+}
+
+/// Render how an approval request of the agent was answered.
+pub fn new_potter_approval_decided(
+    command: Option<Vec<String>>,
+    paths: Vec<PathBuf>,
+    decision: PotterApprovalDecision,
+    decided_by: PotterApprovalDecidedBy,
+) -> PrefixedWrappedHistoryCell {
+    let subject = match command {
+        Some(command) => shlex::try_join(command.iter().map(String::as_str))
+            .unwrap_or_else(|_| command.join(" ")),
+        None => paths
+            .iter()
+            .map(|path| path.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", "),
+    };
+    let decided_by = match decided_by {
+        PotterApprovalDecidedBy::Rule { rule } => format!("rule {rule}"),
+        PotterApprovalDecidedBy::Default => "approval.default".to_string(),
+        PotterApprovalDecidedBy::User => "you".to_string(),
+        PotterApprovalDecidedBy::Headless => "approval.headless".to_string(),
+        PotterApprovalDecidedBy::CommandGuard { rule } => format!("command guard: {rule}"),
+    };
+    let (bullet, verdict) = match decision {
+        PotterApprovalDecision::Accept => ("✓ ".green(), "approved ".green()),
+        PotterApprovalDecision::Deny => ("✗ ".red(), "denied ".red()),
+    };
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        verdict,
+        subject.into(),
+        format!("  (by {decided_by})").dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, bullet, "  ")
+}
+
+/// Render how the agent's questions (or an MCP elicitation) were answered.
+pub fn new_potter_prompt_answered(
+    answers: Vec<PotterPromptAnswer>,
+    answered_by: PotterPromptAnsweredBy,
+) -> PrefixedWrappedHistoryCell {
+    let answered_by = match answered_by {
+        PotterPromptAnsweredBy::User => "you",
+        PotterPromptAnsweredBy::Headless => "user_input.headless",
+    };
+    let mut lines = vec![Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        "answered the agent".into(),
+        format!("  (by {answered_by})").dim(),
+    ])];
+    lines.extend(answers.into_iter().map(|answer| {
+        Line::from(vec![
+            answer.question.dim(),
+            " → ".dim(),
+            answer.answer.into(),
+        ])
+    }));
+    PrefixedWrappedHistoryCell::new(Text::from(lines), "• ".dim(), "  ")
+}
+
+/// Render the notice shown when the command guard stopped a round.
+pub fn new_potter_command_blocked(
+    command: Vec<String>,
+    rule: String,
+) -> PrefixedWrappedHistoryCell {
+    let command =
+        shlex::try_join(command.iter().map(String::as_str)).unwrap_or_else(|_| command.join(" "));
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        "blocked ".red(),
+        command.into(),
+        format!("  ({rule}; the next round is told not to run it)").dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "✗ ".red(), "  ")
+}
+
+/// Render the notice shown when a round's context window crossed the `--context-checkpoint`
+/// threshold.
+pub fn new_potter_context_checkpoint(percent_left: i64) -> PrefixedWrappedHistoryCell {
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        format!("{percent_left}% context left").into(),
+        "  (saving progress before a fresh round)".dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
+}
+
+/// Render the notice shown when a round ran out of context window.
+pub fn new_potter_context_exhausted() -> PrefixedWrappedHistoryCell {
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        "context window exhausted".into(),
+        "  (continuing in a fresh round that does not count against --rounds)".dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "• ".dim(), "  ")
+}
+
+/// Render the notice shown when `--idle-timeout`, `--round-timeout` or the session budget killed a
+/// round.
+pub fn new_potter_round_timed_out(timeout: PotterRoundTimeout) -> PrefixedWrappedHistoryCell {
+    let details = match timeout {
+        PotterRoundTimeout::Idle { limit_secs } => format!(
+            "no events for {}",
+            crate::status_indicator_widget::fmt_elapsed_compact(limit_secs)
+        ),
+        PotterRoundTimeout::Round { limit_secs } => format!(
+            "round ran for {}",
+            crate::status_indicator_widget::fmt_elapsed_compact(limit_secs)
+        ),
+        PotterRoundTimeout::Budget => "session budget ran out".to_string(),
+    };
+    let text: Text<'static> = Line::from(vec![
+        Span::styled(
+            "CodexPotter: ",
+            Style::default()
+                .fg(secondary_color())
+                .add_modifier(Modifier::BOLD),
+        ),
+        format!("round timed out, {details}").red(),
+        "  (app-server killed)".dim(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "✗ ".red(), "  ")
+}
+
 /// Render a hint that points to the created project prompt file.
 pub fn new_potter_project_hint(user_prompt_file: PathBuf) -> PrefixedWrappedHistoryCell {
     let user_prompt_file = user_prompt_file.to_string_lossy().to_string();
@@ -50,6 +326,28 @@ pub fn new_potter_project_hint(user_prompt_file: PathBuf) -> PrefixedWrappedHist
     PrefixedWrappedHistoryCell::new(text, "  ↳ ".dim(), "    ")
 }
 
+/// Render the token usage summary shown at the end of every round.
+pub fn new_potter_round_usage(
+    duration: Duration,
+    token_usage: TokenUsage,
+    session_token_usage: TokenUsage,
+) -> PrefixedWrappedHistoryCell {
+    let elapsed = crate::status_indicator_widget::fmt_elapsed_compact(duration.as_secs());
+    let text: Text<'static> = Line::from(vec![
+        "Round: ".dim(),
+        format!("{} tokens", format_tokens_compact(token_usage.total_tokens)).into(),
+        format!(" in {elapsed} ({})", token_usage_breakdown(&token_usage)).dim(),
+        " · Session: ".dim(),
+        format!(
+            "{} tokens",
+            format_tokens_compact(session_token_usage.total_tokens)
+        )
+        .into(),
+    ])
+    .into();
+    PrefixedWrappedHistoryCell::new(text, "  ↳ ".dim(), "    ")
+}
+
 /// Render the final multi-round summary block shown on success.
 pub fn new_potter_session_succeeded(
     rounds: u32,
@@ -57,6 +355,7 @@ pub fn new_potter_session_succeeded(
     user_prompt_file: PathBuf,
     git_commit_start: String,
     git_commit_end: String,
+    round_token_usage: Vec<TokenUsage>,
 ) -> PotterSessionSucceededCell {
     PotterSessionSucceededCell {
         rounds,
@@ -64,6 +363,7 @@ pub fn new_potter_session_succeeded(
         user_prompt_file,
         git_commit_start,
         git_commit_end,
+        round_token_usage,
     }
 }
 
@@ -75,6 +375,7 @@ pub struct PotterSessionSucceededCell {
     user_prompt_file: PathBuf,
     git_commit_start: String,
     git_commit_end: String,
+    round_token_usage: Vec<TokenUsage>,
 }
 
 impl HistoryCell for PotterSessionSucceededCell {
@@ -117,6 +418,30 @@ impl HistoryCell for PotterSessionSucceededCell {
             ]));
         }
 
+        if !self.round_token_usage.is_empty() {
+            let mut total = TokenUsage::default();
+            for usage in &self.round_token_usage {
+                total.add_assign(usage);
+            }
+            let per_round = self
+                .round_token_usage
+                .iter()
+                .map(|usage| format_tokens_compact(usage.total_tokens))
+                .collect::<Vec<_>>()
+                .join(", ");
+
+            lines.push(Line::from(""));
+            lines.push(Line::from(vec![
+                "    Tokens:       ".into(),
+                format!("{} total", format_tokens_compact(total.total_tokens)).bold(),
+                format!(" ({})", token_usage_breakdown(&total)).dim(),
+            ]));
+            lines.push(Line::from(vec![
+                "                  ".into(),
+                format!("per round: {per_round}").dim(),
+            ]));
+        }
+
         lines.push(Line::from(""));
         lines.push(Line::from(vec![
             "    Iterate more: ".into(),
@@ -127,6 +452,16 @@ impl HistoryCell for PotterSessionSucceededCell {
     }
 }
 
+fn token_usage_breakdown(usage: &TokenUsage) -> String {
+    format!(
+        "input {}, cached {}, output {}, reasoning {}",
+        format_tokens_compact(usage.input_tokens),
+        format_tokens_compact(usage.cached_input_tokens),
+        format_tokens_compact(usage.output_tokens),
+        format_tokens_compact(usage.reasoning_output_tokens),
+    )
+}
+
 fn potter_session_succeeded_separator(width: u16) -> Line<'static> {
     let style = Style::default().fg(secondary_color());
     Line::from("─".repeat(width as usize)).style(style)
@@ -164,6 +499,75 @@ fn derive_resume_project_path(progress_file: &Path) -> Option<String> {
     Some(suffix.join("/"))
 }
 
+#[derive(Debug, Clone)]
+/// History cell showing the result of the verification command (`--verify`) after a round.
+pub struct PotterRoundVerifiedCell {
+    pub command: String,
+    pub success: bool,
+    pub exit_code: Option<i32>,
+    pub duration: Duration,
+    pub output_tail: String,
+}
+
+impl PotterRoundVerifiedCell {
+    /// Number of trailing output lines shown for a failed verification.
+    const MAX_OUTPUT_LINES: usize = 10;
+}
+
+impl HistoryCell for PotterRoundVerifiedCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let elapsed = crate::status_indicator_widget::fmt_elapsed_compact(self.duration.as_secs());
+        let status = if self.success {
+            "verification passed".green()
+        } else {
+            "verification failed".red()
+        };
+        let details = match self.exit_code {
+            Some(code) if !self.success => format!(" (exit {code}, {elapsed})"),
+            _ => format!(" ({elapsed})"),
+        };
+
+        let mut out = word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                status,
+                " · ".dim(),
+                self.command.clone().cyan(),
+                details.dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  ")),
+        );
+
+        if self.success {
+            return out;
+        }
+
+        let lines = self.output_tail.lines().collect::<Vec<_>>();
+        let tail = &lines[lines.len().saturating_sub(Self::MAX_OUTPUT_LINES)..];
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            tail.iter().map(|line| vec![line.to_string().dim()]),
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
+
 #[derive(Debug, Clone)]
 /// History cell shown while CodexPotter is retrying after a stream/network error.
 pub struct PotterStreamRecoveryRetryCell {
@@ -209,6 +613,67 @@ impl HistoryCell for PotterStreamRecoveryRetryCell {
     }
 }
 
+#[derive(Debug, Clone)]
+/// History cell shown while CodexPotter waits for the usage limit to reset, with a countdown.
+pub struct PotterUsageLimitWaitCell {
+    /// Unix timestamp (seconds) when the round continues.
+    pub resumes_at: i64,
+    pub error_message: String,
+}
+
+impl PotterUsageLimitWaitCell {
+    pub fn display_lines_at(&self, width: u16, now_unix: i64) -> Vec<Line<'static>> {
+        if width == 0 {
+            return Vec::new();
+        }
+
+        let potter_style = Style::default()
+            .fg(secondary_color())
+            .add_modifier(Modifier::BOLD);
+        let remaining = u64::try_from(self.resumes_at - now_unix).unwrap_or(0);
+        let resumes_at = chrono::DateTime::from_timestamp(self.resumes_at, 0)
+            .map(|at| {
+                at.with_timezone(&chrono::Local)
+                    .format("%Y-%m-%d %H:%M")
+                    .to_string()
+            })
+            .unwrap_or_default();
+
+        let mut out = word_wrap_lines(
+            [Line::from(vec![
+                Span::styled("CodexPotter", potter_style),
+                ": ".into(),
+                "usage limit reached, continuing in ".into(),
+                fmt_elapsed_compact(remaining).bold(),
+                format!(" (at {resumes_at})").dim(),
+            ])],
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from("• ".dim()))
+                .subsequent_indent(Line::from("  ")),
+        );
+
+        let error_message = capitalize_first(self.error_message.trim_start());
+
+        let prefix = "  └ ";
+        let prefix_width = UnicodeWidthStr::width(prefix);
+        out.extend(word_wrap_lines(
+            error_message.lines().map(|line| vec![line.dim()]),
+            RtOptions::new(width.max(1) as usize)
+                .initial_indent(Line::from(prefix.dim()))
+                .subsequent_indent(Line::from(Span::from(" ".repeat(prefix_width)).dim()))
+                .break_words(true),
+        ));
+
+        out
+    }
+}
+
+impl HistoryCell for PotterUsageLimitWaitCell {
+    fn display_lines(&self, width: u16) -> Vec<Line<'static>> {
+        self.display_lines_at(width, chrono::Utc::now().timestamp())
+    }
+}
+
 #[derive(Debug, Clone)]
 /// History cell shown when CodexPotter gives up retrying after stream/network errors.
 pub struct PotterStreamRecoveryUnrecoverableCell {
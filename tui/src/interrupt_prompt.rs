@@ -0,0 +1,98 @@
+use ratatui::text::Line;
+
+use crate::bottom_pane::ListSelectionView;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::tui::Tui;
+
+/// What the user chose after interrupting a turn (<kbd>Ctrl</kbd>+<kbd>C</kbd>).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum InterruptChoice {
+    /// Type a new instruction and continue the round in the same thread.
+    ContinueRound,
+    NextRound,
+    /// End the session (also Esc/Ctrl+C).
+    EndSession,
+}
+
+pub(crate) async fn prompt_interrupt_choice(tui: &mut Tui) -> anyhow::Result<InterruptChoice> {
+    let selected =
+        crate::action_picker_prompt::run_selection_view(tui, interrupt_choice_view()).await?;
+    Ok(match selected {
+        Some(0) => InterruptChoice::ContinueRound,
+        Some(1) => InterruptChoice::NextRound,
+        _ => InterruptChoice::EndSession,
+    })
+}
+
+fn interrupt_choice_view() -> ListSelectionView {
+    let items = vec![
+        SelectionItem {
+            name: "Continue this round".to_string(),
+            description: Some("Send the agent a new instruction".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Start the next round".to_string(),
+            description: Some("Leave this round and iterate on the progress file".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "End the session".to_string(),
+            description: Some("Stop here; resume picks up later".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+    ];
+
+    ListSelectionView::new(SelectionViewParams {
+        title: Some("Turn interrupted".to_string()),
+        footer_hint: Some(Line::from(
+            "Press enter to confirm, or esc to end the session.",
+        )),
+        items,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::renderable::Renderable;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    #[test]
+    fn interrupt_choice_view_lists_actions() {
+        let view = interrupt_choice_view();
+        let width = 80;
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, view.desired_height(width))).expect("terminal");
+        terminal
+            .draw(|frame| view.render(frame.area(), frame.buffer_mut()))
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        let rendered = (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n");
+
+        for expected in [
+            "Turn interrupted",
+            "Continue this round",
+            "Start the next round",
+            "End the session",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected:?} in:\n{rendered}"
+            );
+        }
+    }
+}
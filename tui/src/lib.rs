@@ -9,6 +9,7 @@ mod ansi_escape;
 mod app_event;
 mod app_event_sender;
 mod app_server_render;
+mod approval_prompt;
 mod bottom_pane;
 mod codex_config;
 mod color;
@@ -23,12 +24,14 @@ mod global_gitignore_prompt;
 mod history_cell;
 mod history_cell_potter;
 mod insert_history;
+mod interrupt_prompt;
 mod key_hint;
 mod markdown;
 mod markdown_render;
 mod markdown_stream;
 mod mention_codec;
 mod multi_agents;
+mod plan_approval_prompt;
 mod potter_tui;
 mod prompt_history_store;
 mod render;
@@ -49,16 +52,21 @@ mod ui_consts;
 mod update_action;
 mod update_prompt;
 mod updates;
+mod user_input_prompt;
 mod version;
 mod wrapping;
 
 #[cfg(test)]
 mod test_backend;
 
+pub use app_server_render::SessionViewExit;
+pub use app_server_render::SessionViewOptions;
+pub use codex_config::CodexModelOverrides;
 pub use exit::AppExitInfo;
 pub use exit::ExitReason;
 pub use global_gitignore_prompt::GlobalGitignorePromptOutcome;
 pub use global_gitignore_prompt::run_global_gitignore_prompt;
+pub use plan_approval_prompt::PlanApprovalChoice;
 pub use potter_tui::CodexPotterTui;
 pub use resume_picker_prompt::ResumePickerOutcome;
 pub use resume_picker_prompt::ResumePickerRow;
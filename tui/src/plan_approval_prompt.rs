@@ -0,0 +1,142 @@
+use ratatui::style::Stylize;
+use ratatui::text::Line;
+use ratatui::widgets::Paragraph;
+use ratatui::widgets::Wrap;
+
+use crate::bottom_pane::ListSelectionView;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::tui::Tui;
+
+/// What the user chose on the plan approval screen (`--approve-plan`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PlanApprovalChoice {
+    Approve,
+    /// Edit the plan in `$VISUAL`/`$EDITOR`, then review it again.
+    Edit,
+    /// Reject the plan (also Esc/Ctrl+C).
+    Reject,
+}
+
+pub async fn prompt_plan_approval(
+    tui: &mut Tui,
+    tasks: &[String],
+    notice: Option<String>,
+) -> anyhow::Result<PlanApprovalChoice> {
+    let selected =
+        crate::action_picker_prompt::run_selection_view(tui, plan_approval_view(tasks, notice))
+            .await?;
+    Ok(match selected {
+        Some(0) => PlanApprovalChoice::Approve,
+        Some(1) => PlanApprovalChoice::Edit,
+        _ => PlanApprovalChoice::Reject,
+    })
+}
+
+fn plan_approval_view(tasks: &[String], notice: Option<String>) -> ListSelectionView {
+    let mut lines = vec![
+        Line::from("Review the plan".bold()),
+        Line::from(
+            "Nothing has been changed yet; approved tasks become the progress file's Todo.".dim(),
+        ),
+        Line::from(""),
+    ];
+    if tasks.is_empty() {
+        lines.push(Line::from("(the plan has no tasks)".italic().dim()));
+    }
+    for (idx, task) in tasks.iter().enumerate() {
+        lines.push(Line::from(vec![
+            format!("{}. ", idx + 1).dim(),
+            task.clone().into(),
+        ]));
+    }
+
+    let items = vec![
+        SelectionItem {
+            name: "Approve plan".to_string(),
+            description: Some("Start the write-capable rounds".to_string()),
+            disabled_reason: tasks
+                .is_empty()
+                .then(|| "the plan has no tasks".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Edit plan".to_string(),
+            description: Some("Open the tasks in $VISUAL / $EDITOR".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Reject plan".to_string(),
+            description: Some("Stop the session; resume plans again".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+    ];
+
+    ListSelectionView::new(SelectionViewParams {
+        header: Box::new(Paragraph::new(lines).wrap(Wrap { trim: false })),
+        footer_note: notice.map(|notice| Line::from(notice.red())),
+        footer_hint: Some(Line::from("Press enter to confirm, or esc to reject.")),
+        items,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::renderable::Renderable;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn render(view: &ListSelectionView, width: u16) -> String {
+        let height = view.desired_height(width);
+        let mut terminal = Terminal::new(TestBackend::new(width, height)).expect("terminal");
+        terminal
+            .draw(|frame| view.render(frame.area(), frame.buffer_mut()))
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+                    .trim_end()
+                    .to_string()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    #[test]
+    fn plan_approval_view_lists_tasks_and_actions() {
+        let view = plan_approval_view(
+            &["Add the parser".to_string(), "Add tests".to_string()],
+            Some("Cannot open external editor: set $VISUAL or $EDITOR".to_string()),
+        );
+        let rendered = render(&view, 100);
+
+        for expected in [
+            "Review the plan",
+            "1. Add the parser",
+            "2. Add tests",
+            "Approve plan",
+            "Edit plan",
+            "Reject plan",
+            "Cannot open external editor",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected:?} in:\n{rendered}"
+            );
+        }
+    }
+
+    #[test]
+    fn plan_approval_view_without_tasks_says_so() {
+        let rendered = render(&plan_approval_view(&[], None), 100);
+        assert!(rendered.contains("(the plan has no tasks)"), "{rendered}");
+    }
+}
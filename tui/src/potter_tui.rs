@@ -17,7 +17,10 @@ pub struct CodexPotterTui {
     turns_rendered: bool,
     queued_user_prompts: VecDeque<String>,
     composer_draft: Option<crate::bottom_pane::ChatComposerDraft>,
+    /// The progress panel stays collapsed across rounds once the user collapsed it.
+    progress_panel_collapsed: bool,
     check_for_update_on_startup: bool,
+    codex_model_overrides: crate::CodexModelOverrides,
 }
 
 impl CodexPotterTui {
@@ -30,7 +33,9 @@ impl CodexPotterTui {
             turns_rendered: false,
             queued_user_prompts: VecDeque::new(),
             composer_draft: None,
+            progress_panel_collapsed: false,
             check_for_update_on_startup: true,
+            codex_model_overrides: crate::CodexModelOverrides::default(),
         })
     }
 
@@ -42,6 +47,12 @@ impl CodexPotterTui {
         self.check_for_update_on_startup = enabled;
     }
 
+    /// Model, profile, and reasoning effort passed on the command line, shown in the startup
+    /// banner on top of the Codex config layers.
+    pub fn set_codex_model_overrides(&mut self, overrides: crate::CodexModelOverrides) {
+        self.codex_model_overrides = overrides;
+    }
+
     /// Show the "update available" modal, if applicable.
     ///
     /// Returns `Some(action)` when the user chooses "Update now", so the caller can run the
@@ -100,6 +111,7 @@ impl CodexPotterTui {
             &mut self.tui,
             show_startup_banner,
             self.check_for_update_on_startup,
+            &self.codex_model_overrides,
             composer_draft,
         )
         .await
@@ -124,6 +136,39 @@ impl CodexPotterTui {
         result
     }
 
+    /// Show a proposed plan (`--approve-plan`) and let the user approve, edit, or reject it.
+    ///
+    /// `notice` is shown below the actions (e.g. why the editor could not be opened).
+    pub async fn prompt_plan_approval(
+        &mut self,
+        tasks: &[String],
+        notice: Option<String>,
+    ) -> anyhow::Result<crate::PlanApprovalChoice> {
+        let result =
+            crate::plan_approval_prompt::prompt_plan_approval(&mut self.tui, tasks, notice).await;
+
+        self.tui.pause_events();
+        tui::flush_terminal_input_buffer();
+        self.tui.resume_events();
+
+        result
+    }
+
+    /// Edit `seed` in the external editor (`$VISUAL`/`$EDITOR`) and return the saved text.
+    ///
+    /// Returns `Ok(None)` when no editor is configured.
+    pub async fn edit_in_external_editor(&mut self, seed: &str) -> anyhow::Result<Option<String>> {
+        let result =
+            crate::external_editor_integration::run_external_editor_with_seed(&mut self.tui, seed)
+                .await;
+
+        self.tui.pause_events();
+        tui::flush_terminal_input_buffer();
+        self.tui.resume_events();
+
+        result
+    }
+
     /// Prompt the user to select a resumable CodexPotter project to resume.
     ///
     /// `Esc` returns [`crate::ResumePickerOutcome::StartFresh`] (do not exit the app).
@@ -182,6 +227,39 @@ impl CodexPotterTui {
             backend,
             &mut queued,
             &mut composer_draft,
+            &mut self.progress_panel_collapsed,
+        )
+        .await;
+        self.queued_user_prompts = queued;
+        self.composer_draft = composer_draft;
+        self.turns_rendered = true;
+        result
+    }
+
+    /// Render the live transcript of one of several parallel sessions until the control plane
+    /// detaches the view, the user switches sessions (<kbd>PgUp</kbd>/<kbd>PgDn</kbd>), or the
+    /// user interrupts.
+    pub async fn render_session_view(
+        &mut self,
+        options: crate::SessionViewOptions,
+        codex_op_tx: UnboundedSender<Op>,
+        codex_event_rx: UnboundedReceiver<Event>,
+        fatal_exit_rx: UnboundedReceiver<String>,
+    ) -> anyhow::Result<crate::SessionViewExit> {
+        let mut queued = std::mem::take(&mut self.queued_user_prompts);
+        let mut composer_draft = self.composer_draft.take();
+        let backend = crate::app_server_render::RenderOnlyBackendChannels {
+            codex_op_tx,
+            codex_event_rx,
+            fatal_exit_rx,
+        };
+        let result = crate::app_server_render::run_session_view_with_tui(
+            &mut self.tui,
+            options,
+            backend,
+            &mut queued,
+            &mut composer_draft,
+            &mut self.progress_panel_collapsed,
         )
         .await;
         self.queued_user_prompts = queued;
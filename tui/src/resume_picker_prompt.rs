@@ -46,6 +46,8 @@ pub struct ResumePickerRow {
     pub updated_at: SystemTime,
     /// Git branch recorded in the project progress file front matter.
     pub git_branch: Option<String>,
+    /// Workflow recorded in the progress file front matter, when it is not the default.
+    pub workflow: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -613,6 +615,13 @@ fn render_list(buf: &mut Buffer, area: Rect, screen: &ResumePickerScreen, metric
         preview_width = preview_width.saturating_sub(max_updated_width + 2);
         preview_width = preview_width.saturating_sub(max_branch_width + 2);
 
+        let workflow_tag = row
+            .workflow
+            .as_deref()
+            .map(|workflow| format!("[{workflow}] "))
+            .unwrap_or_default();
+        preview_width = preview_width.saturating_sub(workflow_tag.chars().count());
+
         let preview = truncate_text(row.user_request.as_str(), preview_width);
         let line: Line = vec![
             marker,
@@ -622,6 +631,7 @@ fn render_list(buf: &mut Buffer, area: Rect, screen: &ResumePickerScreen, metric
             "  ".into(),
             branch_span,
             "  ".into(),
+            Span::from(workflow_tag).magenta(),
             preview.into(),
         ]
         .into();
@@ -672,6 +682,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -679,6 +690,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -686,6 +698,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                workflow: Some("research".to_string()),
             },
         ];
 
@@ -719,6 +732,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -726,6 +740,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -733,6 +748,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                workflow: None,
             },
         ];
 
@@ -765,6 +781,7 @@ mod tests {
                 created_at: now - Duration::from_secs(3 * 24 * 60 * 60),
                 updated_at: now - Duration::from_secs(42),
                 git_branch: None,
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/b"),
@@ -772,6 +789,7 @@ mod tests {
                 created_at: now - Duration::from_secs(24 * 60 * 60),
                 updated_at: now - Duration::from_secs(35 * 60),
                 git_branch: Some("feature/resume".to_string()),
+                workflow: None,
             },
             ResumePickerRow {
                 project_path: PathBuf::from("/tmp/c"),
@@ -779,6 +797,7 @@ mod tests {
                 created_at: now - Duration::from_secs(2 * 60 * 60),
                 updated_at: now - Duration::from_secs(2 * 60 * 60),
                 git_branch: Some("main".to_string()),
+                workflow: None,
             },
         ];
 
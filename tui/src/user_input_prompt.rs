@@ -0,0 +1,293 @@
+use std::collections::HashMap;
+
+use codex_protocol::protocol::PotterElicitationDecision;
+use codex_protocol::request_user_input::DECIDE_YOURSELF_ANSWER;
+use codex_protocol::request_user_input::RequestUserInputAnswer;
+use codex_protocol::request_user_input::RequestUserInputQuestion;
+use codex_protocol::request_user_input::RequestUserInputQuestionOption;
+use codex_protocol::request_user_input::RequestUserInputResponse;
+use ratatui::text::Line;
+
+use crate::bottom_pane::ListSelectionView;
+use crate::bottom_pane::SelectionItem;
+use crate::bottom_pane::SelectionViewParams;
+use crate::tui::Tui;
+
+/// A question prompt or MCP elicitation waiting for the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum UserInputPrompt {
+    Questions {
+        id: String,
+        questions: Vec<RequestUserInputQuestion>,
+    },
+    Elicitation {
+        id: String,
+        server_name: String,
+        message: String,
+    },
+}
+
+/// What the user picked for one question.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum QuestionChoice {
+    Option(usize),
+    TypeAnswer,
+    DecideYourself,
+}
+
+/// Ask the user each of `questions` in turn.
+///
+/// Esc/Ctrl+C, and a free-text answer left empty (or without an editor), let the agent decide.
+pub(crate) async fn prompt_questions(
+    tui: &mut Tui,
+    questions: &[RequestUserInputQuestion],
+) -> anyhow::Result<RequestUserInputResponse> {
+    let mut answers = HashMap::new();
+    for (idx, question) in questions.iter().enumerate() {
+        let selected = crate::action_picker_prompt::run_selection_view(
+            tui,
+            question_view(question, idx, questions.len()),
+        )
+        .await?;
+        let answer = match selected.map(|selected| question_choice(question, selected)) {
+            Some(QuestionChoice::Option(option)) => question
+                .options
+                .as_ref()
+                .and_then(|options| options.get(option))
+                .map(|option| option.label.clone()),
+            Some(QuestionChoice::TypeAnswer) => {
+                crate::external_editor_integration::run_external_editor_with_seed(tui, "")
+                    .await
+                    .ok()
+                    .flatten()
+                    .filter(|text| !text.trim().is_empty())
+            }
+            Some(QuestionChoice::DecideYourself) | None => None,
+        };
+        answers.insert(
+            question.id.clone(),
+            RequestUserInputAnswer {
+                answers: vec![answer.unwrap_or_else(|| DECIDE_YOURSELF_ANSWER.to_string())],
+            },
+        );
+    }
+    Ok(RequestUserInputResponse { answers })
+}
+
+/// Ask the user to accept, decline or cancel an elicitation; Esc/Ctrl+C cancel.
+pub(crate) async fn prompt_elicitation(
+    tui: &mut Tui,
+    server_name: &str,
+    message: &str,
+) -> anyhow::Result<PotterElicitationDecision> {
+    let selected = crate::action_picker_prompt::run_selection_view(
+        tui,
+        elicitation_view(server_name, message),
+    )
+    .await?;
+    Ok(match selected {
+        Some(0) => PotterElicitationDecision::Accept,
+        Some(1) => PotterElicitationDecision::Decline,
+        _ => PotterElicitationDecision::Cancel,
+    })
+}
+
+fn options(question: &RequestUserInputQuestion) -> &[RequestUserInputQuestionOption] {
+    question.options.as_deref().unwrap_or_default()
+}
+
+/// Questions without options, and questions that allow "other", take a typed answer.
+fn accepts_typed_answer(question: &RequestUserInputQuestion) -> bool {
+    question.is_other || options(question).is_empty()
+}
+
+fn question_choice(question: &RequestUserInputQuestion, selected: usize) -> QuestionChoice {
+    let option_count = options(question).len();
+    if selected < option_count {
+        QuestionChoice::Option(selected)
+    } else if selected == option_count && accepts_typed_answer(question) {
+        QuestionChoice::TypeAnswer
+    } else {
+        QuestionChoice::DecideYourself
+    }
+}
+
+fn question_view(
+    question: &RequestUserInputQuestion,
+    idx: usize,
+    total: usize,
+) -> ListSelectionView {
+    let mut items = options(question)
+        .iter()
+        .map(|option| SelectionItem {
+            name: option.label.clone(),
+            description: (!option.description.is_empty()).then(|| option.description.clone()),
+            dismiss_on_select: true,
+            ..Default::default()
+        })
+        .collect::<Vec<_>>();
+    if accepts_typed_answer(question) {
+        items.push(SelectionItem {
+            name: "Type an answer".to_string(),
+            description: Some("Write it in $VISUAL/$EDITOR".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        });
+    }
+    items.push(SelectionItem {
+        name: "Let the agent decide".to_string(),
+        description: Some("The agent makes the call and notes its assumption".to_string()),
+        dismiss_on_select: true,
+        ..Default::default()
+    });
+
+    let title = if total > 1 {
+        format!("{} ({}/{total})", question.header, idx + 1)
+    } else {
+        question.header.clone()
+    };
+    ListSelectionView::new(SelectionViewParams {
+        title: Some(title),
+        subtitle: Some(question.question.clone()),
+        footer_hint: Some(Line::from(
+            "Press enter to confirm, or esc to let the agent decide.",
+        )),
+        items,
+        ..Default::default()
+    })
+}
+
+fn elicitation_view(server_name: &str, message: &str) -> ListSelectionView {
+    let items = vec![
+        SelectionItem {
+            name: "Accept".to_string(),
+            description: Some(format!("Let {server_name} go ahead")),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Decline".to_string(),
+            description: Some("The server is told the request was declined".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+        SelectionItem {
+            name: "Cancel".to_string(),
+            description: Some("Dismiss the request without answering".to_string()),
+            dismiss_on_select: true,
+            ..Default::default()
+        },
+    ];
+
+    ListSelectionView::new(SelectionViewParams {
+        title: Some(format!("{server_name} asks")),
+        subtitle: Some(message.to_string()),
+        footer_hint: Some(Line::from("Press enter to confirm, or esc to cancel.")),
+        items,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::render::renderable::Renderable;
+    use pretty_assertions::assert_eq;
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+
+    fn render(view: ListSelectionView) -> String {
+        let width = 80;
+        let mut terminal =
+            Terminal::new(TestBackend::new(width, view.desired_height(width))).expect("terminal");
+        terminal
+            .draw(|frame| view.render(frame.area(), frame.buffer_mut()))
+            .expect("draw");
+        let buffer = terminal.backend().buffer();
+        (0..buffer.area.height)
+            .map(|y| {
+                (0..buffer.area.width)
+                    .map(|x| buffer[(x, y)].symbol())
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
+    }
+
+    fn question(is_other: bool) -> RequestUserInputQuestion {
+        RequestUserInputQuestion {
+            id: "scope".to_string(),
+            header: "Scope".to_string(),
+            question: "Which crate should change?".to_string(),
+            is_other,
+            is_secret: false,
+            options: Some(vec![
+                RequestUserInputQuestionOption {
+                    label: "cli".to_string(),
+                    description: "The binary".to_string(),
+                },
+                RequestUserInputQuestionOption {
+                    label: "tui".to_string(),
+                    description: String::new(),
+                },
+            ]),
+        }
+    }
+
+    #[test]
+    fn question_view_shows_question_and_options() {
+        let rendered = render(question_view(&question(true), 0, 2));
+        for expected in [
+            "Scope (1/2)",
+            "Which crate should change?",
+            "cli",
+            "The binary",
+            "tui",
+            "Type an answer",
+            "Let the agent decide",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected:?} in:\n{rendered}"
+            );
+        }
+    }
+
+    #[test]
+    fn question_choice_maps_rows() {
+        let closed = question(false);
+        assert_eq!(question_choice(&closed, 1), QuestionChoice::Option(1));
+        assert_eq!(question_choice(&closed, 2), QuestionChoice::DecideYourself);
+
+        let open = question(true);
+        assert_eq!(question_choice(&open, 2), QuestionChoice::TypeAnswer);
+        assert_eq!(question_choice(&open, 3), QuestionChoice::DecideYourself);
+
+        let free_text = RequestUserInputQuestion {
+            options: None,
+            ..question(false)
+        };
+        assert_eq!(question_choice(&free_text, 0), QuestionChoice::TypeAnswer);
+        assert_eq!(
+            question_choice(&free_text, 1),
+            QuestionChoice::DecideYourself
+        );
+    }
+
+    #[test]
+    fn elicitation_view_shows_server_and_choices() {
+        let rendered = render(elicitation_view("github", "Sign in to continue?"));
+        for expected in [
+            "github asks",
+            "Sign in to continue?",
+            "Accept",
+            "Decline",
+            "Cancel",
+        ] {
+            assert!(
+                rendered.contains(expected),
+                "missing {expected:?} in:\n{rendered}"
+            );
+        }
+    }
+}